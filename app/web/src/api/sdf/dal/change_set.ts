@@ -26,6 +26,7 @@ export interface ChangeSet {
   reviewedByUserId?: UserId;
   reviewedByUser?: string;
   reviewedAt?: IsoDateString;
+  rollbackOnFailure?: boolean;
   updatedAt?: IsoDateString;
   abandonRequestedAt?: IsoDateString;
   abandonRequestedByUserId?: UserId;
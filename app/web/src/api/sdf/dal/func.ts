@@ -90,6 +90,7 @@ export const isCustomizableFuncKind = (f: FuncKind) =>
 export enum FuncArgumentKind {
   Array = "array",
   Boolean = "boolean",
+  Float = "float",
   Integer = "integer",
   Json = "json",
   Object = "object",
@@ -111,6 +112,7 @@ export enum FuncBackendKind {
   Array = "Array",
   Boolean = "Boolean",
   Diff = "Diff",
+  Float = "Float",
   Identity = "Identity",
   Integer = "Integer",
   JsAction = "JsAction",
@@ -132,6 +134,7 @@ export interface FuncSummary {
   displayName: string | null;
   description: string | null;
   isLocked: boolean;
+  timeoutSecs?: number | null;
   arguments: FuncArgument[];
   backendKind: FuncBackendKind;
   bindings: FuncBinding[];
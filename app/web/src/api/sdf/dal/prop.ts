@@ -3,6 +3,7 @@ export type PropId = string;
 export enum PropKind {
   Array = "array",
   Boolean = "boolean",
+  Float = "float",
   Integer = "integer",
   Json = "json",
   Object = "object",
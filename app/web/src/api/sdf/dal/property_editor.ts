@@ -9,6 +9,7 @@ import { LabelList } from "@/api/sdf/dal/label_list";
 export enum PropertyEditorPropKind {
   Array = "array",
   Boolean = "boolean",
+  Float = "float",
   Integer = "integer",
   Object = "object",
   String = "string",
@@ -8,6 +8,7 @@ export interface AdminWorkspace {
   name: string;
   defaultChangeSetId: string;
   componentConcurrencyLimit?: number;
+  valueConcurrencyLimit?: number;
   snapshotVersion: string;
 }
 
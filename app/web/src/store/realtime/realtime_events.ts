@@ -178,6 +178,13 @@ export type WsEventPayloadMap = {
     componentId: ComponentId;
     changeSetId: string;
   };
+  ComponentDriftUpdated: {
+    componentId: ComponentId;
+    componentName: string;
+    status: "drifted" | "inSync" | "unknown";
+    driftedProps: { path: string; modeled: unknown; actual: unknown }[];
+    lastSynced: string | null;
+  };
   ComponentUpdated: {
     component: RawComponent;
     changeSetId: string;
@@ -25,6 +25,14 @@ export interface DependentValuesUpdateStatusUpdate {
   timestamp: Date;
 }
 
+export interface DependentValuesUpdateProgressStatusUpdate {
+  kind: "dependentValueUpdateProgress";
+  total: number;
+  computed: number;
+  skipped: number;
+  timestamp: Date;
+}
+
 export interface RebaseStatusUpdate {
   kind: "rebase";
   status: StatusMessageState;
@@ -33,6 +41,7 @@ export interface RebaseStatusUpdate {
 
 export type StatusUpdate =
   | DependentValuesUpdateStatusUpdate
+  | DependentValuesUpdateProgressStatusUpdate
   | RebaseStatusUpdate;
 
 export type GlobalUpdateStatus = {
@@ -79,6 +88,7 @@ export type Conflict = string;
 export interface StatusStoreState {
   activeComponents: Record<ComponentId, DependentValuesUpdateStatusUpdate>;
   dvuRootsCount: number;
+  dvuProgress?: DependentValuesUpdateProgressStatusUpdate;
   rebaseStatus: RebaseStatus;
 }
 
@@ -131,8 +141,12 @@ export const useStatusStore = (forceChangeSetId?: ChangeSetId) => {
               isUpdating: isUpdatingDvus || isRebasing,
             };
           },
-          globalStatusMessage(): string {
+          globalStatusMessage(state): string {
             if (this.globalStatus.isUpdating) {
+              if (state.dvuProgress && state.dvuProgress.total > 0) {
+                const { computed, total } = state.dvuProgress;
+                return `Updating the model (${computed}/${total} values computed)`;
+              }
               return "Updating the model";
             }
             return "Model is up to date";
@@ -202,6 +216,7 @@ export const useStatusStore = (forceChangeSetId?: ChangeSetId) => {
                 if (!this.globalStatus.isUpdating) {
                   // if we're done updating, clear the old data
                   this.activeComponents = {};
+                  this.dvuProgress = undefined;
                   this.rebaseStatus = {
                     rebaseStart: undefined,
                     rebaseFinished: undefined,
@@ -219,6 +234,8 @@ export const useStatusStore = (forceChangeSetId?: ChangeSetId) => {
                       delete this.activeComponents[update.componentId];
                     }
                   }
+                } else if (update.kind === "dependentValueUpdateProgress") {
+                  this.dvuProgress = update;
                 } else if (update.kind === "rebase") {
                   if (update.status === "statusStarted") {
                     if (!this.rebaseStatus.rebaseStart) {
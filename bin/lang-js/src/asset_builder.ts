@@ -491,6 +491,7 @@ export class SiPropValueFromDefinitionBuilder implements ISiPropValueFromDefinit
 export type PropDefinitionKind =
   | "array"
   | "boolean"
+  | "float"
   | "integer"
   | "map"
   | "object"
@@ -729,7 +730,7 @@ export class PropBuilder implements IPropBuilder {
   /**
    * The type of the prop
    *
-   * @param kind {PropDefinitionKind} [array | boolean | integer | map | object | string]
+   * @param kind {PropDefinitionKind} [array | boolean | float | integer | map | object | string]
    *
    * @returns this
    *
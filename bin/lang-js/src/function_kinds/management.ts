@@ -47,6 +47,8 @@ export interface ManagementOperations {
       properties?: object;
       geometry?: { [key: string]: Geometry };
       parent?: string;
+      unparent?: boolean;
+      removeFromViews?: string[];
       connect: {
         add?: ManagmentConnect[],
         remove?: ManagmentConnect[],
@@ -59,6 +61,8 @@ export interface ManagementOperations {
       remove?: string[],
     }
   };
+  delete?: string[];
+  erase?: string[];
 }
 
 export interface ManagementFuncResultSuccess extends ResultSuccess {
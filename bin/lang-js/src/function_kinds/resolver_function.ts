@@ -20,6 +20,7 @@ export interface ResolverComponent {
 export enum FuncBackendResponseType {
   Array = "Array",
   Boolean = "Boolean",
+  Float = "Float",
   Identity = "Identity",
   Integer = "Integer",
   Map = "Map",
@@ -70,6 +71,10 @@ const isBoolean = (value: unknown): TypeCheckResult => (_.isBoolean(value)
   ? { valid: true }
   : { valid: false, message: "Return type must be a boolean." });
 
+const isFloat = (value: unknown): TypeCheckResult => (_.isFinite(value)
+  ? { valid: true }
+  : { valid: false, message: `Return type must be a number.` });
+
 const isInteger = (value: unknown): TypeCheckResult => (_.isInteger(value)
   ? { valid: true }
   : { valid: false, message: `Return type must be an integer.` });
@@ -156,6 +161,7 @@ const typeChecks: {
 } = {
   [FuncBackendResponseType.Array]: isArray,
   [FuncBackendResponseType.Boolean]: isBoolean,
+  [FuncBackendResponseType.Float]: isFloat,
   [FuncBackendResponseType.Integer]: isInteger,
   [FuncBackendResponseType.Object]: isObject,
   [FuncBackendResponseType.String]: isString,
@@ -168,6 +174,7 @@ const typeChecks: {
 const nullables: { [key in FuncBackendResponseType]?: boolean } = {
   [FuncBackendResponseType.Array]: true,
   [FuncBackendResponseType.Boolean]: true,
+  [FuncBackendResponseType.Float]: true,
   [FuncBackendResponseType.Integer]: true,
   [FuncBackendResponseType.Json]: true,
   [FuncBackendResponseType.Map]: true,
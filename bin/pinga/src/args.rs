@@ -119,6 +119,10 @@ pub(crate) struct Args {
     #[arg(long)]
     pub(crate) concurrency: Option<u32>,
 
+    /// The number of concurrent jobs that can be processed per workspace [default: unlimited]
+    #[arg(long)]
+    pub(crate) workspace_concurrency: Option<u32>,
+
     /// The path at which the layer db cache is created/used on disk [e.g. /banana/]
     #[arg(long)]
     pub(crate) layer_db_disk_path: Option<String>,
@@ -218,6 +222,9 @@ impl TryFrom<Args> for Config {
             if let Some(concurrency) = args.concurrency {
                 config_map.set("concurrency_limit", i64::from(concurrency));
             }
+            if let Some(concurrency) = args.workspace_concurrency {
+                config_map.set("workspace_concurrency_limit", i64::from(concurrency));
+            }
             if let Some(layer_cache_disk_path) = args.layer_db_disk_path {
                 config_map.set("layer_db_config.disk_path", layer_cache_disk_path);
             }
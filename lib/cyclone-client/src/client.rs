@@ -764,6 +764,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
         };
 
         // Start the protocol
@@ -865,6 +866,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
         };
 
         // Start the protocol
@@ -939,6 +941,7 @@ mod tests {
             validation_format: r#"{"type":"number","flags":{"presence":"required"},"rules":[{"name":"integer"},{"name":"min","args":{"limit":33}},{"name":"max","args":{"limit":33}}]}"#.to_string(),
             code_base64: "".to_string(),
             before: vec![],
+            timeout_secs: None,
         };
         let mut progress = client
             .prepare_execution(CycloneRequest::from_parts(req, Default::default()))
@@ -1007,6 +1010,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
         };
 
         // Start the protocol
@@ -1093,6 +1097,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
         };
 
         // Start the protocol
@@ -1181,6 +1186,7 @@ mod tests {
                     return new AssetBuilder().build();
                 }"#,
             ),
+            timeout_secs: None,
         };
 
         // Start the protocol
@@ -1258,6 +1264,7 @@ mod tests {
                     return new AssetBuilder().build();
                 }"#,
             ),
+            timeout_secs: None,
         };
 
         // Start the protocol
@@ -1343,6 +1350,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
         };
 
         // Start the protocol
@@ -1428,6 +1436,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
         };
 
         // Start the protocol
@@ -12,6 +12,9 @@ pub struct ActionRunRequest {
     pub code_base64: String,
     pub args: serde_json::Value,
     pub before: Vec<BeforeFunction>,
+    /// Overrides the function timeout configured for the executing server, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
 }
 
 #[remain::sorted]
@@ -42,6 +45,10 @@ impl CycloneRequestable for ActionRunRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+
     fn websocket_path(&self) -> &str {
         "/execute/command"
     }
@@ -16,6 +16,9 @@ pub struct ManagementRequest {
     pub this_component: ComponentViewWithGeometry,
     pub components: HashMap<String, ComponentViewWithGeometry>,
     pub before: Vec<BeforeFunction>,
+    /// Overrides the function timeout configured for the executing server, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
 }
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -42,6 +45,10 @@ impl CycloneRequestable for ManagementRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+
     fn websocket_path(&self) -> &str {
         "/execute/management"
     }
@@ -29,6 +29,10 @@ where
         self.request.websocket_path()
     }
 
+    pub fn timeout_secs(&self) -> Option<u64> {
+        self.request.timeout_secs()
+    }
+
     pub fn into_parts(self) -> (R, SensitiveStrings) {
         (self.request, self.sensitive_strings.into())
     }
@@ -38,6 +42,10 @@ pub trait CycloneRequestable {
     type Response;
 
     fn execution_id(&self) -> &str;
+    /// The function timeout requested by the caller, if it differs from the server default.
+    fn timeout_secs(&self) -> Option<u64> {
+        None
+    }
     fn websocket_path(&self) -> &str;
     fn inc_run_metric(&self);
     fn dec_run_metric(&self);
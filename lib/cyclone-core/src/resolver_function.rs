@@ -15,6 +15,9 @@ pub struct ResolverFunctionRequest {
     pub response_type: ResolverFunctionResponseType,
     pub code_base64: String,
     pub before: Vec<BeforeFunction>,
+    /// Overrides the function timeout configured for the executing server, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
@@ -33,6 +36,7 @@ pub enum ResolverFunctionResponseType {
     Array,
     Boolean,
     CodeGeneration,
+    Float,
     Identity,
     Integer,
     Json,
@@ -62,6 +66,10 @@ impl CycloneRequestable for ResolverFunctionRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+
     fn websocket_path(&self) -> &str {
         "/execute/resolver"
     }
@@ -10,6 +10,9 @@ pub struct SchemaVariantDefinitionRequest {
     pub execution_id: String,
     pub handler: String,
     pub code_base64: String,
+    /// Overrides the function timeout configured for the executing server, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -29,6 +32,10 @@ impl CycloneRequestable for SchemaVariantDefinitionRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+
     fn websocket_path(&self) -> &str {
         "/execute/schema_variant_definition"
     }
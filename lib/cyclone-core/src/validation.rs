@@ -12,6 +12,9 @@ pub struct ValidationRequest {
     pub value: Option<serde_json::Value>,
     pub validation_format: String,
     pub before: Vec<BeforeFunction>,
+    /// Overrides the function timeout configured for the executing server, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -28,6 +31,10 @@ impl CycloneRequestable for ValidationRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+
     fn websocket_path(&self) -> &str {
         "/execute/validation"
     }
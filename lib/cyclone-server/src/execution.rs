@@ -33,6 +33,9 @@ use crate::WebSocketMessage;
 
 const TX_TIMEOUT_SECS: Duration = Duration::from_secs(5);
 const DEFAULT_LANG_SERVER_PROCESS_TIMEOUT: Duration = Duration::from_secs(32 * 60);
+/// How much longer than a per-request function timeout the lang server process may live, giving
+/// the lang server a chance to report its own timeout before the process is shut down.
+const LANG_SERVER_PROCESS_TIMEOUT_GRACE: Duration = Duration::from_secs(60);
 
 pub fn new<Request, LangServerSuccess, Success>(
     lang_server_path: impl Into<PathBuf>,
@@ -127,6 +130,19 @@ where
         let cyclone_request = Self::read_request(ws).await?;
         let (request, sensitive_strings) = cyclone_request.into_parts();
 
+        // A timeout on the request overrides the server's configured function timeout, and may
+        // extend the process timeout so that long running functions are not cut short
+        let function_timeout = match request.timeout_secs() {
+            Some(timeout) => Some(timeout.to_string()),
+            None => self.lang_server_function_timeout.map(|t| t.to_string()),
+        };
+        let process_timeout = match request.timeout_secs() {
+            Some(timeout) => self
+                .lang_server_process_timeout
+                .max(Duration::from_secs(timeout) + LANG_SERVER_PROCESS_TIMEOUT_GRACE),
+            None => self.lang_server_process_timeout,
+        };
+
         // Spawn lang server as a child process with handles on all i/o descriptors
         let mut command = Command::new(&self.lang_server_path);
         command
@@ -135,8 +151,8 @@ where
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
-        if let Some(timeout) = self.lang_server_function_timeout {
-            command.arg("--timeout").arg(timeout.to_string());
+        if let Some(timeout) = function_timeout {
+            command.arg("--timeout").arg(timeout);
         }
         if self.lang_server_debugging {
             command.env("SI_LANG_JS_LOG", "*");
@@ -173,7 +189,7 @@ where
             stderr,
             sensitive_strings: Arc::new(sensitive_strings),
             success_marker: self.success_marker,
-            lang_server_process_timeout: self.lang_server_process_timeout,
+            lang_server_process_timeout: process_timeout,
         })
     }
 
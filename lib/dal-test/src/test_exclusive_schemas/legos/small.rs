@@ -333,6 +333,32 @@ pub(crate) async fn migrate_test_exclusive_schema_small_odd_lego(
     let deeply_nested_children =
         build_management_func(deeply_nested_children_code, "test:deeplyNestedChildren")?;
 
+    let delete_managed_code = r#"
+    async function main({ components }: Input): Promise<Output> {
+        const toDelete = [];
+        const toErase = [];
+
+        for (let [id, component] of Object.entries(components)) {
+            const name = component.properties?.si?.name ?? "unknown";
+            if (name.startsWith("erase")) {
+                toErase.push(id);
+            } else {
+                toDelete.push(id);
+            }
+        }
+
+        return {
+            status: "ok",
+            ops: {
+                delete: toDelete,
+                erase: toErase,
+            },
+        }
+    }
+    "#;
+    let delete_managed_func =
+        build_management_func(delete_managed_code, "test:deleteManagedSmallLego")?;
+
     let fn_name = "test:deleteActionSmallLego";
     let delete_action_func = build_action_func(delete_action_code, fn_name)?;
 
@@ -466,6 +492,15 @@ pub(crate) async fn migrate_test_exclusive_schema_small_odd_lego(
                         .func_unique_id(&create_and_connect_to_self_as_children_func.unique_id)
                         .build()?,
                 )
+                .management_func(
+                    ManagementFuncSpec::builder()
+                        .name("Delete Managed")
+                        .managed_schemas(Some(HashSet::from([
+                            SCHEMA_ID_SMALL_EVEN_LEGO.to_string()
+                        ])))
+                        .func_unique_id(&delete_managed_func.unique_id)
+                        .build()?,
+                )
                 .build()?,
         )
         .build()?;
@@ -486,6 +521,7 @@ pub(crate) async fn migrate_test_exclusive_schema_small_odd_lego(
         .func(create_and_connect_to_self_func)
         .func(create_and_connect_to_self_as_children_func)
         .func(deeply_nested_children)
+        .func(delete_managed_func)
         .schema(small_lego_schema)
         .build()?;
 
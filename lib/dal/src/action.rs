@@ -3,6 +3,7 @@ use std::collections::{HashSet, VecDeque};
 use petgraph::prelude::*;
 use postgres_types::{FromSql, ToSql};
 use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
 use si_events::ulid::Ulid;
 use si_layer_cache::LayerDbError;
 use strum::{AsRefStr, Display, EnumDiscriminants, EnumIter, EnumString};
@@ -23,12 +24,14 @@ use crate::{
         category_node_weight::CategoryNodeKind, ActionNodeWeight, NodeWeight, NodeWeightError,
     },
     AttributeValue, ChangeSetError, ChangeSetId, ComponentError, ComponentId, DalContext,
-    EdgeWeightKind, EdgeWeightKindDiscriminants, HelperError, TransactionsError,
-    WorkspaceSnapshotError, WsEvent, WsEventError, WsEventResult, WsPayload,
+    EdgeWeightKind, EdgeWeightKindDiscriminants, HelperError, SchemaVariantError,
+    TransactionsError, WorkspaceSnapshotError, WsEvent, WsEventError, WsEventResult, WsPayload,
 };
 
 pub mod dependency_graph;
 pub mod prototype;
+pub mod refresh_schedule;
+pub mod rollback;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -51,8 +54,12 @@ pub enum ActionError {
     LayerDb(#[from] LayerDbError),
     #[error("Node Weight error: {0}")]
     NodeWeight(#[from] NodeWeightError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
     #[error("prototype not found for action: {0}")]
     PrototypeNotFoundForAction(ActionId),
+    #[error("schema variant error: {0}")]
+    SchemaVariant(#[from] SchemaVariantError),
     #[error("Transactions error: {0}")]
     Transactions(#[from] TransactionsError),
     #[error("Unable to determine kind for action: {0}")]
@@ -337,11 +344,27 @@ impl Action {
         ctx: &DalContext,
         action_prototype_id: ActionPrototypeId,
         maybe_component_id: Option<ComponentId>,
+    ) -> ActionResult<Self> {
+        Self::new_with_originating_change_set_id(
+            ctx,
+            action_prototype_id,
+            maybe_component_id,
+            ctx.change_set_id(),
+        )
+        .await
+    }
+
+    /// Creates an [`Action`] that is attributed to the given [`ChangeSet`](crate::ChangeSet)
+    /// rather than the current one, so it belongs to that change set's batch.
+    pub(crate) async fn new_with_originating_change_set_id(
+        ctx: &DalContext,
+        action_prototype_id: ActionPrototypeId,
+        maybe_component_id: Option<ComponentId>,
+        originating_change_set_id: ChangeSetId,
     ) -> ActionResult<Self> {
         let new_id: ActionId = ctx.workspace_snapshot()?.generate_ulid().await?.into();
         let lineage_id = ctx.workspace_snapshot()?.generate_ulid().await?;
 
-        let originating_change_set_id = ctx.change_set_id();
         let node_weight =
             NodeWeight::new_action(originating_change_set_id, new_id.into(), lineage_id);
         ctx.workspace_snapshot()?
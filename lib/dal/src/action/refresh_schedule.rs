@@ -0,0 +1,519 @@
+//! Recurring `Refresh` [`Actions`](Action) for a [`Component`] or for every [`Component`] of a
+//! [`SchemaVariant`].
+//!
+//! Schedules are workspace settings kept in Postgres rather than in the graph: they aren't
+//! change set specific, as refreshes only ever run against HEAD. Pinga periodically claims the
+//! schedules that are due (see [`RefreshSchedule::run_due`]) and enqueues the `Refresh` actions
+//! on HEAD, which are then dispatched through the usual
+//! [`ActionJob`](crate::job::definition::ActionJob) path.
+
+use chrono::{DateTime, Duration, Utc};
+use croner::{errors::CronError, Cron};
+use serde::{Deserialize, Serialize};
+use si_data_pg::{PgError, PgRow};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use super::{prototype::ActionKind, Action, ActionError, ActionId};
+use crate::{
+    pk, AccessBuilder, Component, ComponentError, ComponentId, DalContext, DalContextBuilder,
+    HistoryActor, SchemaVariant, SchemaVariantError, SchemaVariantId, Tenancy, TransactionsError,
+    WorkspacePk,
+};
+
+/// Schedules are claimed at most once per tick of the scheduler, so shorter intervals would not
+/// be honored anyway.
+pub const MIN_INTERVAL_SECONDS: u32 = 60;
+
+/// How many due schedules a single call to [`RefreshSchedule::run_due`] claims.
+const CLAIM_BATCH_SIZE: i64 = 100;
+
+const CLAIM_DUE_QUERY: &str = "
+    SELECT * FROM refresh_schedules
+    WHERE next_run_at <= $1
+    ORDER BY next_run_at
+    LIMIT $2
+    FOR UPDATE SKIP LOCKED
+";
+
+pk!(RefreshScheduleId);
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum RefreshScheduleError {
+    #[error("action error: {0}")]
+    Action(#[from] ActionError),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("component not found: {0}")]
+    ComponentNotFound(ComponentId),
+    #[error("cron expression never matches: {0}")]
+    CronNeverMatches(String),
+    #[error("invalid cron expression {0}: {1}")]
+    CronParse(String, #[source] CronError),
+    #[error("refresh interval must be at least {MIN_INTERVAL_SECONDS} seconds, found {0}")]
+    IntervalTooShort(u32),
+    #[error("refresh schedule row has neither a valid target nor a valid cadence: {0}")]
+    InvalidRow(RefreshScheduleId),
+    #[error("no workspace in tenancy")]
+    NoWorkspace,
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("schema variant error: {0}")]
+    SchemaVariant(#[from] SchemaVariantError),
+    #[error("schema variant not found: {0}")]
+    SchemaVariantNotFound(SchemaVariantId),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type RefreshScheduleResult<T> = Result<T, RefreshScheduleError>;
+
+/// What a [`RefreshSchedule`] refreshes.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum RefreshScheduleTarget {
+    Component {
+        component_id: ComponentId,
+    },
+    /// Every [`Component`] using the [`SchemaVariant`] at the time the schedule runs.
+    SchemaVariant {
+        schema_variant_id: SchemaVariantId,
+    },
+}
+
+/// How often a [`RefreshSchedule`] runs.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum RefreshCadence {
+    Interval {
+        seconds: u32,
+    },
+    /// A standard five field cron expression (`minute hour day-of-month month day-of-week`),
+    /// evaluated in UTC. As in cron, when both day fields are restricted a time matches if
+    /// _either_ of them does.
+    Cron {
+        expression: String,
+    },
+}
+
+impl RefreshCadence {
+    /// Returns when a schedule with this cadence next runs, after having run at `after`.
+    pub fn next_run_after(&self, after: DateTime<Utc>) -> RefreshScheduleResult<DateTime<Utc>> {
+        match self {
+            Self::Interval { seconds } => {
+                if *seconds < MIN_INTERVAL_SECONDS {
+                    return Err(RefreshScheduleError::IntervalTooShort(*seconds));
+                }
+                Ok(after + Duration::seconds(i64::from(*seconds)))
+            }
+            Self::Cron { expression } => Cron::new(expression)
+                .parse()
+                .map_err(|err| RefreshScheduleError::CronParse(expression.to_owned(), err))?
+                .find_next_occurrence(&after, false)
+                .map_err(|_| RefreshScheduleError::CronNeverMatches(expression.to_owned())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshSchedule {
+    pub id: RefreshScheduleId,
+    pub workspace_id: WorkspacePk,
+    pub target: RefreshScheduleTarget,
+    pub cadence: RefreshCadence,
+    pub next_run_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<PgRow> for RefreshSchedule {
+    type Error = RefreshScheduleError;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        let id: RefreshScheduleId = row.try_get("id")?;
+        let component_id: Option<ComponentId> = row.try_get("component_id")?;
+        let schema_variant_id: Option<SchemaVariantId> = row.try_get("schema_variant_id")?;
+        let interval_seconds: Option<i64> = row.try_get("interval_seconds")?;
+        let cron_expression: Option<String> = row.try_get("cron_expression")?;
+
+        let target = match (component_id, schema_variant_id) {
+            (Some(component_id), None) => RefreshScheduleTarget::Component { component_id },
+            (None, Some(schema_variant_id)) => {
+                RefreshScheduleTarget::SchemaVariant { schema_variant_id }
+            }
+            _ => return Err(RefreshScheduleError::InvalidRow(id)),
+        };
+        let cadence = match (interval_seconds, cron_expression) {
+            (Some(seconds), None) => RefreshCadence::Interval {
+                seconds: u32::try_from(seconds)
+                    .map_err(|_| RefreshScheduleError::InvalidRow(id))?,
+            },
+            (None, Some(expression)) => RefreshCadence::Cron { expression },
+            _ => return Err(RefreshScheduleError::InvalidRow(id)),
+        };
+
+        Ok(Self {
+            id,
+            workspace_id: row.try_get("workspace_id")?,
+            target,
+            cadence,
+            next_run_at: row.try_get("next_run_at")?,
+            last_run_at: row.try_get("last_run_at")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+impl RefreshSchedule {
+    #[instrument(name = "refresh_schedule.new", level = "info", skip(ctx))]
+    pub async fn new(
+        ctx: &DalContext,
+        target: RefreshScheduleTarget,
+        cadence: RefreshCadence,
+    ) -> RefreshScheduleResult<Self> {
+        let workspace_id = ctx
+            .tenancy()
+            .workspace_pk_opt()
+            .ok_or(RefreshScheduleError::NoWorkspace)?;
+        let next_run_at = cadence.next_run_after(Utc::now())?;
+
+        match target {
+            RefreshScheduleTarget::Component { component_id } => {
+                if Component::try_get_by_id(ctx, component_id).await?.is_none() {
+                    return Err(RefreshScheduleError::ComponentNotFound(component_id));
+                }
+            }
+            RefreshScheduleTarget::SchemaVariant { schema_variant_id } => {
+                if SchemaVariant::get_by_id(ctx, schema_variant_id)
+                    .await?
+                    .is_none()
+                {
+                    return Err(RefreshScheduleError::SchemaVariantNotFound(
+                        schema_variant_id,
+                    ));
+                }
+            }
+        }
+
+        let (component_id, schema_variant_id) = match target {
+            RefreshScheduleTarget::Component { component_id } => (Some(component_id), None),
+            RefreshScheduleTarget::SchemaVariant { schema_variant_id } => {
+                (None, Some(schema_variant_id))
+            }
+        };
+        let (interval_seconds, cron_expression) = match &cadence {
+            RefreshCadence::Interval { seconds } => (Some(i64::from(*seconds)), None),
+            RefreshCadence::Cron { expression } => (None, Some(expression.as_str())),
+        };
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "INSERT INTO refresh_schedules (workspace_id, component_id, schema_variant_id, interval_seconds, cron_expression, next_run_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+                &[
+                    &workspace_id,
+                    &component_id,
+                    &schema_variant_id,
+                    &interval_seconds,
+                    &cron_expression,
+                    &next_run_at,
+                ],
+            )
+            .await?;
+
+        Self::try_from(row)
+    }
+
+    /// Lists the schedules of the current workspace.
+    pub async fn list(ctx: &DalContext) -> RefreshScheduleResult<Vec<Self>> {
+        let workspace_id = ctx
+            .tenancy()
+            .workspace_pk_opt()
+            .ok_or(RefreshScheduleError::NoWorkspace)?;
+
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * FROM refresh_schedules WHERE workspace_id = $1 ORDER BY created_at",
+                &[&workspace_id],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    /// Removes a schedule of the current workspace, returning whether it existed.
+    pub async fn remove(ctx: &DalContext, id: RefreshScheduleId) -> RefreshScheduleResult<bool> {
+        let workspace_id = ctx
+            .tenancy()
+            .workspace_pk_opt()
+            .ok_or(RefreshScheduleError::NoWorkspace)?;
+
+        let deleted = ctx
+            .txns()
+            .await?
+            .pg()
+            .execute(
+                "DELETE FROM refresh_schedules WHERE id = $1 AND workspace_id = $2",
+                &[&id, &workspace_id],
+            )
+            .await?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Claims the schedules, across all workspaces, that are due as of `now` and moves them on to
+    /// their next run. Rows are locked with `SKIP LOCKED`, so concurrent schedulers never claim
+    /// the same schedule; the claim takes effect when `ctx` is committed.
+    pub async fn claim_due(
+        ctx: &DalContext,
+        now: DateTime<Utc>,
+    ) -> RefreshScheduleResult<Vec<Self>> {
+        let txns = ctx.txns().await?;
+        let rows = txns
+            .pg()
+            .query(CLAIM_DUE_QUERY, &[&now, &CLAIM_BATCH_SIZE])
+            .await?;
+
+        let mut claimed = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut schedule = Self::try_from(row)?;
+            let next_run_at = schedule.cadence.next_run_after(now)?;
+            txns.pg()
+                .query_none(
+                    "UPDATE refresh_schedules SET next_run_at = $2, last_run_at = $3, updated_at = CLOCK_TIMESTAMP() WHERE id = $1",
+                    &[&schedule.id, &next_run_at, &now],
+                )
+                .await?;
+            schedule.next_run_at = next_run_at;
+            schedule.last_run_at = Some(now);
+            claimed.push(schedule);
+        }
+
+        Ok(claimed)
+    }
+
+    /// Enqueues a `Refresh` action for every targeted [`Component`] that has a resource and
+    /// doesn't already have one queued. `ctx` must be for HEAD of the schedule's workspace.
+    pub async fn enqueue_refresh_actions(
+        &self,
+        ctx: &DalContext,
+    ) -> RefreshScheduleResult<Vec<ActionId>> {
+        let component_ids = match self.target {
+            RefreshScheduleTarget::Component { component_id } => vec![component_id],
+            RefreshScheduleTarget::SchemaVariant { schema_variant_id } => {
+                SchemaVariant::list_component_ids(ctx, schema_variant_id).await?
+            }
+        };
+
+        let mut action_ids = Vec::new();
+        for component_id in component_ids {
+            let Some(component) = Component::try_get_by_id(ctx, component_id).await? else {
+                continue;
+            };
+            if component.to_delete() || component.resource(ctx).await?.is_none() {
+                continue;
+            }
+
+            let schema_variant_id = Component::schema_variant_id(ctx, component_id).await?;
+            for prototype_id in SchemaVariant::find_action_prototypes_by_kind(
+                ctx,
+                schema_variant_id,
+                ActionKind::Refresh,
+            )
+            .await?
+            {
+                if Action::find_equivalent(ctx, prototype_id, Some(component_id))
+                    .await?
+                    .is_some()
+                {
+                    continue;
+                }
+                action_ids.push(
+                    Action::new(ctx, prototype_id, Some(component_id))
+                        .await?
+                        .id(),
+                );
+            }
+        }
+
+        Ok(action_ids)
+    }
+
+    /// Removes the schedule if the [`Component`] or [`SchemaVariant`] it targets no longer exists
+    /// in `ctx`, returning whether it did. Without this a schedule would keep firing, to no
+    /// effect, forever.
+    pub async fn remove_if_target_gone(&self, ctx: &DalContext) -> RefreshScheduleResult<bool> {
+        let target_exists = match self.target {
+            RefreshScheduleTarget::Component { component_id } => {
+                Component::try_get_by_id(ctx, component_id).await?.is_some()
+            }
+            RefreshScheduleTarget::SchemaVariant { schema_variant_id } => {
+                SchemaVariant::get_by_id(ctx, schema_variant_id)
+                    .await?
+                    .is_some()
+            }
+        };
+        if target_exists {
+            return Ok(false);
+        }
+
+        info!(
+            si.workspace.id = %self.workspace_id,
+            si.refresh_schedule.id = %self.id,
+            target = ?self.target,
+            "removing refresh schedule whose target no longer exists"
+        );
+        Self::remove(ctx, self.id).await
+    }
+
+    /// Claims every due schedule and enqueues its `Refresh` actions on HEAD of its workspace.
+    /// A schedule that fails is logged and skipped, it runs again at its next run. Returns how
+    /// many schedules ran.
+    #[instrument(name = "refresh_schedule.run_due", level = "info", skip_all)]
+    pub async fn run_due(ctx_builder: &DalContextBuilder) -> RefreshScheduleResult<usize> {
+        let ctx = ctx_builder.build_default().await?;
+        let claimed = Self::claim_due(&ctx, Utc::now()).await?;
+        ctx.commit_no_rebase().await?;
+
+        for schedule in &claimed {
+            if let Err(err) = schedule.run(ctx_builder).await {
+                error!(
+                    si.error.message = ?err,
+                    si.workspace.id = %schedule.workspace_id,
+                    si.refresh_schedule.id = %schedule.id,
+                    "unable to run refresh schedule"
+                );
+            }
+        }
+
+        Ok(claimed.len())
+    }
+
+    async fn run(&self, ctx_builder: &DalContextBuilder) -> RefreshScheduleResult<()> {
+        let ctx = ctx_builder
+            .build_head(AccessBuilder::new(
+                Tenancy::new(self.workspace_id),
+                HistoryActor::SystemInit,
+            ))
+            .await?;
+
+        if self.remove_if_target_gone(&ctx).await? {
+            ctx.commit_no_rebase().await?;
+            return Ok(());
+        }
+
+        let action_ids = self.enqueue_refresh_actions(&ctx).await?;
+        if !action_ids.is_empty() {
+            // The rebaser dispatches the new actions once they're in HEAD.
+            ctx.commit().await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s)
+            .expect("invalid timestamp")
+            .with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> RefreshScheduleResult<DateTime<Utc>> {
+        RefreshCadence::Cron {
+            expression: expression.to_owned(),
+        }
+        .next_run_after(at(after))
+    }
+
+    #[test]
+    fn interval() {
+        let cadence = RefreshCadence::Interval { seconds: 120 };
+        assert_eq!(
+            at("2024-01-01T10:02:00Z"),
+            cadence
+                .next_run_after(at("2024-01-01T10:00:00Z"))
+                .expect("valid interval")
+        );
+
+        assert!(matches!(
+            RefreshCadence::Interval { seconds: 59 }.next_run_after(Utc::now()),
+            Err(RefreshScheduleError::IntervalTooShort(59))
+        ));
+    }
+
+    #[test]
+    fn cron_runs_strictly_after() {
+        assert_eq!(
+            at("2024-01-01T10:15:00Z"),
+            next("*/15 * * * *", "2024-01-01T10:00:00Z").expect("valid cron")
+        );
+        assert_eq!(
+            at("2024-01-01T11:00:00Z"),
+            next("*/15 * * * *", "2024-01-01T10:59:30Z").expect("valid cron")
+        );
+        assert_eq!(
+            at("2025-01-01T00:00:00Z"),
+            next("0 0 1 1 *", "2024-06-15T12:00:00Z").expect("valid cron")
+        );
+    }
+
+    #[test]
+    fn cron_day_fields() {
+        // 2024-01-01 is a Monday, so the 5th is a Friday and the 7th a Sunday
+        assert_eq!(
+            at("2024-01-02T09:00:00Z"),
+            next("0 9 * * 1-5", "2024-01-01T09:00:00Z").expect("valid cron")
+        );
+        assert_eq!(
+            at("2024-01-07T09:00:00Z"),
+            next("0 9 * * 7", "2024-01-01T00:00:00Z").expect("valid cron")
+        );
+        assert_eq!(
+            at("2024-01-07T09:00:00Z"),
+            next("0 9 * * 0", "2024-01-01T00:00:00Z").expect("valid cron")
+        );
+        // When both day fields are restricted either one matching is enough
+        assert_eq!(
+            at("2024-01-03T00:00:00Z"),
+            next("0 0 3 * 5", "2024-01-01T00:00:00Z").expect("valid cron")
+        );
+        assert_eq!(
+            at("2024-01-05T00:00:00Z"),
+            next("0 0 3 * 5", "2024-01-03T00:00:00Z").expect("valid cron")
+        );
+    }
+
+    #[test]
+    fn cron_rejects_invalid_expressions() {
+        // Only the standard five fields are accepted, a leading seconds field is not
+        for invalid in [
+            "* * * *",
+            "0 * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "a * * * *",
+        ] {
+            assert!(
+                matches!(
+                    next(invalid, "2024-01-01T00:00:00Z"),
+                    Err(RefreshScheduleError::CronParse(..))
+                ),
+                "{invalid}"
+            );
+        }
+        assert!(next("0 0 31 2 *", "2024-01-01T00:00:00Z").is_err());
+    }
+}
@@ -0,0 +1,246 @@
+//! Rollback-on-failure for batches of [`Actions`](Action).
+//!
+//! Applying a [`ChangeSet`] enqueues a batch of [`Actions`](Action) on HEAD, each recording the
+//! change set as its originating change set. When that change set has opted in with
+//! [`ChangeSet::set_rollback_on_failure`], the first failure in the batch compensates for it:
+//!
+//! * the batch's `Queued` and `OnHold` actions that depend on the failed action are removed, as
+//!   they would only build on top of the failure. Actions that do not depend on it are left to
+//!   run,
+//! * a `Destroy` action is enqueued for every resource a `Create` in the batch succeeded in
+//!   creating, most recent first. Successful `Create` actions are recorded as they finish, see
+//!   [`Action::record_created_by_batch`]. The
+//!   [`ActionDependencyGraph`](super::dependency_graph::ActionDependencyGraph) orders `Destroy`
+//!   actions against the data flow, so they run in reverse topological order.
+//!
+//! A `Create` that was already running when the batch was rolled back, and succeeds afterwards,
+//! is compensated for as soon as it is recorded.
+//!
+//! The compensating `Destroy` actions are attributed to the same originating change set, so the
+//! func run history of the batch records the whole saga: the successful creates, the failure
+//! and the destroys that undid them.
+
+use std::collections::HashSet;
+
+use telemetry::prelude::*;
+
+use super::{
+    dependency_graph::ActionDependencyGraph,
+    prototype::{ActionKind, ActionPrototype},
+    Action, ActionId, ActionResult, ActionState,
+};
+use crate::{ChangeSet, ChangeSetId, Component, ComponentId, DalContext, SchemaVariant};
+
+impl Action {
+    /// Rolls back the batch the failed [`Action`] belongs to, if its originating [`ChangeSet`]
+    /// opted into rollback-on-failure. Returns the compensating `Destroy` actions that were
+    /// enqueued.
+    ///
+    /// Failed `Destroy` actions never trigger a rollback, so a failing compensation does not
+    /// start another one.
+    #[instrument(name = "action.rollback_batch", level = "info", skip(ctx))]
+    pub async fn rollback_batch_for_failed_action(
+        ctx: &DalContext,
+        failed_action_id: ActionId,
+    ) -> ActionResult<Vec<ActionId>> {
+        let failed_action = Self::get_by_id(ctx, failed_action_id).await?;
+        let batch_change_set_id = failed_action.originating_changeset_id();
+
+        let rollback_on_failure = ChangeSet::find(ctx, batch_change_set_id)
+            .await?
+            .is_some_and(|change_set| change_set.rollback_on_failure);
+        if !rollback_on_failure {
+            return Ok(Vec::new());
+        }
+
+        let failed_prototype_id = Self::prototype_id(ctx, failed_action_id).await?;
+        if ActionPrototype::get_by_id(ctx, failed_prototype_id)
+            .await?
+            .kind
+            == ActionKind::Destroy
+        {
+            return Ok(Vec::new());
+        }
+
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "INSERT INTO action_batch_rollbacks (change_set_id) VALUES ($1)
+                    ON CONFLICT (change_set_id) DO NOTHING",
+                &[&batch_change_set_id],
+            )
+            .await?;
+
+        let created_component_ids =
+            Self::component_ids_created_by_batch(ctx, batch_change_set_id).await?;
+        let compensated: HashSet<ComponentId> = created_component_ids.iter().copied().collect();
+
+        let dependency_graph = ActionDependencyGraph::for_workspace(ctx).await?;
+        for action_id in dependency_graph.get_all_dependencies(failed_action_id) {
+            let action = Self::get_by_id(ctx, action_id).await?;
+            if action.originating_changeset_id() != batch_change_set_id
+                || !matches!(action.state(), ActionState::Queued | ActionState::OnHold)
+            {
+                continue;
+            }
+
+            // Keep compensations enqueued by an earlier failure in the same batch.
+            let prototype_id = Self::prototype_id(ctx, action_id).await?;
+            let kind = ActionPrototype::get_by_id(ctx, prototype_id).await?.kind;
+            let component_id = Self::component_id(ctx, action_id).await?;
+            if kind == ActionKind::Destroy
+                && component_id.is_some_and(|component_id| compensated.contains(&component_id))
+            {
+                continue;
+            }
+
+            Self::remove_by_id(ctx, action_id).await?;
+        }
+
+        let mut compensating_action_ids = Vec::new();
+        for component_id in created_component_ids {
+            compensating_action_ids
+                .extend(Self::compensate_create(ctx, component_id, batch_change_set_id).await?);
+        }
+
+        info!(
+            si.change_set.id = %batch_change_set_id,
+            si.action.id = %failed_action_id,
+            compensating_actions = compensating_action_ids.len(),
+            "rolled back action batch"
+        );
+
+        Ok(compensating_action_ids)
+    }
+
+    /// Records that the `Create` [`Action`] succeeded in creating the component's resource, if
+    /// its batch opted into rollback-on-failure. Must be called before the action is removed.
+    ///
+    /// If the batch has already been rolled back, the create was in flight when it was, so the
+    /// resource is compensated for right away.
+    ///
+    /// Opting in after a batch has started only covers the creates that finish afterwards.
+    #[instrument(name = "action.record_created_by_batch", level = "debug", skip(ctx))]
+    pub async fn record_created_by_batch(
+        ctx: &DalContext,
+        action_id: ActionId,
+        component_id: ComponentId,
+    ) -> ActionResult<()> {
+        let batch_change_set_id = Self::get_by_id(ctx, action_id)
+            .await?
+            .originating_changeset_id();
+
+        let rollback_on_failure = ChangeSet::find(ctx, batch_change_set_id)
+            .await?
+            .is_some_and(|change_set| change_set.rollback_on_failure);
+        if !rollback_on_failure {
+            return Ok(());
+        }
+
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "INSERT INTO action_batch_created_components (change_set_id, component_id)
+                    VALUES ($1, $2)",
+                &[&batch_change_set_id, &component_id],
+            )
+            .await?;
+
+        let rolled_back = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT change_set_id FROM action_batch_rollbacks WHERE change_set_id = $1",
+                &[&batch_change_set_id],
+            )
+            .await?
+            .is_some();
+        if rolled_back {
+            let compensating_action_ids =
+                Self::compensate_create(ctx, component_id, batch_change_set_id).await?;
+            info!(
+                si.change_set.id = %batch_change_set_id,
+                si.action.id = %action_id,
+                compensating_actions = compensating_action_ids.len(),
+                "compensated create that finished after its batch was rolled back"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Enqueues the `Destroy` actions that undo the resource a `Create` in the batch created,
+    /// unless they are already enqueued.
+    async fn compensate_create(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        batch_change_set_id: ChangeSetId,
+    ) -> ActionResult<Vec<ActionId>> {
+        let Some(component) = Component::try_get_by_id(ctx, component_id).await? else {
+            return Ok(Vec::new());
+        };
+        if component.resource(ctx).await?.is_none() {
+            return Ok(Vec::new());
+        }
+
+        let mut compensating_action_ids = Vec::new();
+        let schema_variant_id = Component::schema_variant_id(ctx, component_id).await?;
+        for prototype_id in SchemaVariant::find_action_prototypes_by_kind(
+            ctx,
+            schema_variant_id,
+            ActionKind::Destroy,
+        )
+        .await?
+        {
+            if Self::find_equivalent(ctx, prototype_id, Some(component_id))
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            let action = Self::new_with_originating_change_set_id(
+                ctx,
+                prototype_id,
+                Some(component_id),
+                batch_change_set_id,
+            )
+            .await?;
+            compensating_action_ids.push(action.id());
+        }
+
+        Ok(compensating_action_ids)
+    }
+
+    /// Components whose `Create` action from the given batch succeeded, most recently created
+    /// first.
+    async fn component_ids_created_by_batch(
+        ctx: &DalContext,
+        batch_change_set_id: ChangeSetId,
+    ) -> ActionResult<Vec<ComponentId>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT component_id FROM action_batch_created_components
+                    WHERE change_set_id = $1
+                    ORDER BY created_at DESC",
+                &[&batch_change_set_id],
+            )
+            .await?;
+
+        let mut seen = HashSet::new();
+        let mut component_ids = Vec::with_capacity(rows.len());
+        for row in rows {
+            let component_id: ComponentId = row.try_get("component_id")?;
+            if seen.insert(component_id) {
+                component_ids.push(component_id);
+            }
+        }
+
+        Ok(component_ids)
+    }
+}
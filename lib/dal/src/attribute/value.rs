@@ -90,6 +90,7 @@ pub use is_for::ValueIsFor;
 pub mod debug;
 pub mod dependent_value_graph;
 pub mod is_for;
+pub mod provenance;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -998,7 +999,7 @@ impl AttributeValue {
             .into())
     }
 
-    async fn element_prop_id_for_id(
+    pub async fn element_prop_id_for_id(
         ctx: &DalContext,
         parent_attribute_value_id: AttributeValueId,
     ) -> AttributeValueResult<PropId> {
@@ -2371,7 +2372,11 @@ impl AttributeValue {
     ) -> AttributeValueResult<Vec<AttributeValueId>> {
         let prop = Self::prop(ctx, id).await?;
         match prop.kind {
-            PropKind::Boolean | PropKind::Integer | PropKind::Json | PropKind::String => Ok(vec![]),
+            PropKind::Boolean
+            | PropKind::Float
+            | PropKind::Integer
+            | PropKind::Json
+            | PropKind::String => Ok(vec![]),
             PropKind::Array | PropKind::Map => {
                 Self::get_child_av_ids_from_ordering_node(ctx, id).await
             }
@@ -67,6 +67,15 @@ impl DependentValueGraph {
         Ok(dependent_value_graph)
     }
 
+    /// An empty graph, for tests that build up their dependencies by hand.
+    #[cfg(test)]
+    pub(crate) fn empty() -> Self {
+        Self {
+            inner: DependencyGraph::new(),
+            values_that_need_to_execute_from_prototype_function: HashSet::new(),
+        }
+    }
+
     /// Parse the set of initial ids in order to construct the list of [`values`](WorkQueueValue).
     async fn parse_initial_ids(
         &mut self,
@@ -0,0 +1,311 @@
+//! Answers "why is this value what it is?" for an [`AttributeValue`].
+//!
+//! The provenance of a value is the func of its prototype and, for each argument of that func,
+//! where the argument's value came from: another prop on the same component, an input socket
+//! fed by an explicit connection or by an inferred frame connection, a secret or a static
+//! value. Every upstream [`AttributeValue`] is expanded the same way until the sources are
+//! manual values, defaults, secrets or static values, giving the full upstream lineage.
+//!
+//! Values whose own prototype does not compute them, but sit below a value that is set by a
+//! function (e.g. a field of an object produced by a qualification), record that ancestor as
+//! their controlling ancestor, which is expanded in turn.
+
+use std::collections::HashSet;
+
+use async_recursion::async_recursion;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_events::FuncRunId;
+use strum::Display;
+use telemetry::prelude::*;
+
+use super::{AttributeValue, AttributeValueError, AttributeValueResult, ValueIsFor};
+use crate::attribute::prototype::argument::{
+    static_value::StaticArgumentValue, value_source::ValueSource, AttributePrototypeArgument,
+};
+use crate::component::socket::ComponentInputSocket;
+use crate::func::argument::FuncArgument;
+use crate::func::intrinsics::IntrinsicFunc;
+use crate::{
+    AttributePrototype, AttributeValueId, Component, ComponentId, DalContext, Func, FuncId,
+    InputSocketId, OutputSocketId, PropId, SecretId,
+};
+
+/// Where the value of a [`ProvenanceNode`] ultimately comes from.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Deserialize, Display, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ValueOrigin {
+    /// Set by a non-dynamic func defined on the schema variant.
+    Default,
+    /// Computed by a dynamic func from its arguments.
+    Function,
+    /// Set on the component by a user (or a management function).
+    Manual,
+    /// No value has been set.
+    Unset,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceNode {
+    pub attribute_value_id: AttributeValueId,
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub path: Option<String>,
+    pub value: Option<Value>,
+    pub origin: ValueOrigin,
+    pub func_id: FuncId,
+    pub func_name: String,
+    /// The most recent run of any func for this value in the workspace, if there has been one.
+    pub last_func_run_id: Option<FuncRunId>,
+    pub controlling_ancestor: Option<Box<ProvenanceNode>>,
+    pub arguments: Vec<ProvenanceArgument>,
+    /// The value was already expanded elsewhere in the tree, so its arguments are omitted here.
+    pub already_expanded: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceArgument {
+    pub name: String,
+    /// Whether data flows from the source, which it does not when the source component is
+    /// marked for deletion and the destination component is not.
+    pub is_used: bool,
+    pub source: ProvenanceSource,
+}
+
+#[remain::sorted]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ProvenanceSource {
+    /// An output socket matched to the input socket by a frame.
+    #[serde(rename_all = "camelCase")]
+    InferredConnection {
+        output_socket_id: OutputSocketId,
+        upstream: Box<ProvenanceNode>,
+    },
+    /// An input socket of the same component.
+    #[serde(rename_all = "camelCase")]
+    InputSocket {
+        input_socket_id: InputSocketId,
+        upstream: Box<ProvenanceNode>,
+    },
+    /// An output socket, connected explicitly to the input socket.
+    #[serde(rename_all = "camelCase")]
+    OutputSocket {
+        output_socket_id: OutputSocketId,
+        upstream: Box<ProvenanceNode>,
+    },
+    /// A prop of the same component.
+    #[serde(rename_all = "camelCase")]
+    Prop {
+        prop_id: PropId,
+        upstream: Box<ProvenanceNode>,
+    },
+    /// A secret, whose value is never included.
+    #[serde(rename_all = "camelCase")]
+    Secret { secret_id: SecretId },
+    #[serde(rename_all = "camelCase")]
+    StaticValue { value: Value },
+}
+
+impl AttributeValue {
+    /// Builds the full upstream provenance tree of an [`AttributeValue`].
+    #[instrument(name = "attribute_value.provenance", level = "debug", skip(ctx))]
+    pub async fn provenance(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+    ) -> AttributeValueResult<ProvenanceNode> {
+        let mut expanded = HashSet::new();
+        provenance_node(ctx, attribute_value_id, &mut expanded).await
+    }
+}
+
+#[async_recursion]
+async fn provenance_node(
+    ctx: &DalContext,
+    attribute_value_id: AttributeValueId,
+    expanded: &mut HashSet<AttributeValueId>,
+) -> AttributeValueResult<ProvenanceNode> {
+    let component_id = AttributeValue::component_id(ctx, attribute_value_id).await?;
+    let component_name = Component::get_by_id(ctx, component_id)
+        .await?
+        .name(ctx)
+        .await?;
+    let prototype_id = AttributeValue::prototype_id(ctx, attribute_value_id).await?;
+    let func_id = AttributePrototype::func_id(ctx, prototype_id).await?;
+    let func = Func::get_by_id_or_error(ctx, func_id).await?;
+    let is_component_specific = AttributeValue::component_prototype_id(ctx, attribute_value_id)
+        .await?
+        .is_some();
+
+    let origin = if func.name == IntrinsicFunc::Unset.name() {
+        ValueOrigin::Unset
+    } else if func.is_dynamic() {
+        ValueOrigin::Function
+    } else if is_component_specific {
+        ValueOrigin::Manual
+    } else {
+        ValueOrigin::Default
+    };
+
+    let last_func_run_id = ctx
+        .layer_db()
+        .func_run()
+        .get_last_run_for_attribute_value_id(
+            ctx.events_tenancy().workspace_pk,
+            attribute_value_id.into(),
+        )
+        .await?
+        .map(|func_run| func_run.id());
+
+    let mut node = ProvenanceNode {
+        attribute_value_id,
+        component_id,
+        component_name,
+        path: AttributeValue::get_path_for_id(ctx, attribute_value_id).await?,
+        value: AttributeValue::get_by_id(ctx, attribute_value_id)
+            .await?
+            .view(ctx)
+            .await?,
+        origin,
+        func_id,
+        func_name: func.name,
+        last_func_run_id,
+        controlling_ancestor: None,
+        arguments: Vec::new(),
+        already_expanded: false,
+    };
+
+    // Frames can connect components in both directions, so the same value may be reached more
+    // than once (and, through a cycle, from itself).
+    if !expanded.insert(attribute_value_id) {
+        node.already_expanded = true;
+        return Ok(node);
+    }
+
+    if origin != ValueOrigin::Function {
+        let mut parent_id =
+            AttributeValue::parent_attribute_value_id(ctx, attribute_value_id).await?;
+        while let Some(ancestor_id) = parent_id {
+            if AttributeValue::is_set_by_dependent_function(ctx, ancestor_id).await? {
+                node.controlling_ancestor =
+                    Some(Box::new(provenance_node(ctx, ancestor_id, expanded).await?));
+                break;
+            }
+            parent_id = AttributeValue::parent_attribute_value_id(ctx, ancestor_id).await?;
+        }
+        return Ok(node);
+    }
+
+    for apa_id in AttributePrototypeArgument::list_ids_for_prototype(ctx, prototype_id).await? {
+        let apa = AttributePrototypeArgument::get_by_id(ctx, apa_id).await?;
+        // Arguments for explicit connections are shared by the prototype of every component
+        // with the same socket, so only follow the ones targeting this component.
+        if apa
+            .targets()
+            .is_some_and(|targets| targets.destination_component_id != component_id)
+        {
+            continue;
+        }
+        let source_component_id = apa
+            .targets()
+            .map(|targets| targets.source_component_id)
+            .unwrap_or(component_id);
+        let is_used =
+            Component::should_data_flow_between_components(ctx, component_id, source_component_id)
+                .await?;
+
+        let func_argument_id =
+            AttributePrototypeArgument::func_argument_id_by_id(ctx, apa_id).await?;
+        let name = FuncArgument::get_name_by_id(ctx, func_argument_id).await?;
+
+        let value_source = AttributePrototypeArgument::value_source_by_id(ctx, apa_id)
+            .await?
+            .ok_or(AttributeValueError::AttributePrototypeArgumentMissingValueSource(apa_id))?;
+        let sources = match value_source {
+            ValueSource::Secret(secret_id) => vec![ProvenanceSource::Secret { secret_id }],
+            ValueSource::StaticArgumentValue(static_argument_value_id) => {
+                vec![ProvenanceSource::StaticValue {
+                    value: StaticArgumentValue::get_by_id(ctx, static_argument_value_id)
+                        .await?
+                        .value,
+                }]
+            }
+            ValueSource::InputSocket(_) | ValueSource::OutputSocket(_) | ValueSource::Prop(_) => {
+                let mut sources = Vec::new();
+                for upstream_id in value_source
+                    .attribute_values_for_component_id(ctx, source_component_id)
+                    .await?
+                {
+                    let upstream = Box::new(provenance_node(ctx, upstream_id, expanded).await?);
+                    sources.push(match value_source {
+                        ValueSource::InputSocket(input_socket_id) => {
+                            ProvenanceSource::InputSocket {
+                                input_socket_id,
+                                upstream,
+                            }
+                        }
+                        ValueSource::OutputSocket(output_socket_id) => {
+                            ProvenanceSource::OutputSocket {
+                                output_socket_id,
+                                upstream,
+                            }
+                        }
+                        _ => ProvenanceSource::Prop {
+                            prop_id: AttributeValue::prop_id(ctx, upstream_id).await?,
+                            upstream,
+                        },
+                    });
+                }
+                sources
+            }
+        };
+
+        node.arguments
+            .extend(sources.into_iter().map(|source| ProvenanceArgument {
+                name: name.clone(),
+                is_used,
+                source,
+            }));
+    }
+
+    if let ValueIsFor::InputSocket(input_socket_id) =
+        AttributeValue::is_for(ctx, attribute_value_id).await?
+    {
+        if let Some(component_input_socket) =
+            ComponentInputSocket::get_by_ids(ctx, component_id, input_socket_id).await?
+        {
+            // Inferred connections feed the single argument of the input socket's func.
+            let name = FuncArgument::list_for_func(ctx, func_id)
+                .await?
+                .pop()
+                .map(|func_argument| func_argument.name)
+                .unwrap_or_default();
+            for output_match in
+                ComponentInputSocket::find_inferred_connections(ctx, component_input_socket).await?
+            {
+                let is_used = Component::should_data_flow_between_components(
+                    ctx,
+                    component_id,
+                    output_match.component_id,
+                )
+                .await?;
+                let upstream = Box::new(
+                    provenance_node(ctx, output_match.attribute_value_id, expanded).await?,
+                );
+                node.arguments.push(ProvenanceArgument {
+                    name: name.clone(),
+                    is_used,
+                    source: ProvenanceSource::InferredConnection {
+                        output_socket_id: output_match.output_socket_id,
+                        upstream,
+                    },
+                });
+            }
+        }
+    }
+
+    Ok(node)
+}
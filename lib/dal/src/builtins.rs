@@ -14,7 +14,7 @@ use crate::module::ModuleError;
 use crate::pkg::PkgError;
 use crate::{
     action::prototype::ActionPrototypeError, AttributeValueId, PropId, SchemaVariantError,
-    SchemaVariantId, StandardModelError, TransactionsError,
+    SchemaVariantId, StandardModelError, TransactionsError, WorkspaceError,
 };
 
 pub mod func;
@@ -69,6 +69,8 @@ pub enum BuiltinsError {
     StandardModel(#[from] StandardModelError),
     #[error("error creating new transactions")]
     Transactions(#[from] TransactionsError),
+    #[error("workspace error: {0}")]
+    Workspace(#[from] Box<WorkspaceError>),
 }
 
 pub type BuiltinsResult<T> = Result<T, BuiltinsError>;
@@ -2,7 +2,8 @@ use si_pkg::SiPkg;
 
 use crate::module::Module;
 use crate::{
-    func::intrinsics::IntrinsicFunc, pkg::import_pkg_from_pkg, BuiltinsResult, DalContext,
+    func::intrinsics::IntrinsicFunc, pkg::import_pkg_from_pkg, AccessBuilder, BuiltinsResult,
+    DalContext, DalContextBuilder, HistoryActor, Tenancy, Workspace,
 };
 use telemetry::prelude::*;
 
@@ -54,3 +55,37 @@ pub async fn migrate_intrinsics_no_commit(ctx: &DalContext) -> BuiltinsResult<()
     import_pkg_from_pkg(ctx, &intrinsics_pkg, None).await?;
     Ok(())
 }
+
+/// Installs the intrinsics added since a workspace was created into the HEAD change set of every
+/// user workspace. Open change sets pick them up when the rebaser replays HEAD's changes onto
+/// them.
+#[instrument(skip_all)]
+pub async fn install_missing_intrinsics(ctx_builder: &DalContextBuilder) -> BuiltinsResult<()> {
+    let ctx = ctx_builder.build_default().await?;
+    let workspaces = Workspace::list_all_user_workspaces(&ctx)
+        .await
+        .map_err(Box::new)?;
+
+    for workspace in workspaces {
+        let head_ctx = ctx_builder
+            .build_head(AccessBuilder::new(
+                Tenancy::new(*workspace.pk()),
+                HistoryActor::SystemInit,
+            ))
+            .await?;
+
+        let installed = IntrinsicFunc::install_missing(&head_ctx).await?;
+        if installed.is_empty() {
+            continue;
+        }
+
+        info!(
+            si.workspace.id = %workspace.pk(),
+            ?installed,
+            "installed missing intrinsics"
+        );
+        head_ctx.commit().await?;
+    }
+
+    Ok(())
+}
@@ -14,7 +14,7 @@ use tokio::time;
 
 use crate::billing_publish::BillingPublishError;
 use crate::slow_rt::SlowRuntimeError;
-use crate::workspace_snapshot::graph::RebaseBatch;
+use crate::workspace_snapshot::graph::{RebaseBatch, WorkspaceSnapshotGraphDiscriminants};
 use crate::{
     action::{ActionError, ActionId},
     id, ChangeSetStatus, ComponentError, DalContext, HistoryActor, HistoryEvent, HistoryEventError,
@@ -26,17 +26,31 @@ use crate::{
     WorkspaceError,
 };
 
+pub mod approval_policy;
+pub mod cherry_pick;
+pub mod diff;
 pub mod event;
+pub mod fork;
+pub mod merge_preview;
+pub mod restore;
 pub mod status;
 pub mod view;
 
+use self::approval_policy::{ApprovalPolicy, ApprovalPolicyEvaluation, ChangeSetApproval};
+
 const FIND_ANCESTORS_QUERY: &str = include_str!("queries/change_set/find_ancestors.sql");
 
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum ChangeSetError {
+    #[error("approval policy error: {0}")]
+    ApprovalPolicy(#[from] Box<approval_policy::ApprovalPolicyError>),
+    #[error("change set {0} does not satisfy the approval policy of its workspace")]
+    ApprovalPolicyNotSatisfied(ChangeSetId),
     #[error("billing publish error: {0}")]
     BillingPublish(#[from] Box<BillingPublishError>),
+    #[error("cannot fork change set {0} with status {1}")]
+    CannotForkChangeSet(ChangeSetId, ChangeSetStatus),
     #[error("change set not approved for apply. Current state: {0}")]
     ChangeSetNotApprovedForApply(ChangeSetStatus),
     #[error("change set with id {0} not found")]
@@ -55,6 +69,8 @@ pub enum ChangeSetError {
     HistoryEvent(#[from] HistoryEventError),
     #[error("invalid user actor pk")]
     InvalidActor(UserPk),
+    #[error("no applied change set found for restore point: {0:?}")]
+    InvalidRestorePoint(restore::RestorePoint),
     #[error("invalid user system init")]
     InvalidUserSystemInit,
     #[error("tokio join error: {0}")]
@@ -77,6 +93,15 @@ pub enum ChangeSetError {
     Pg(#[from] PgError),
     #[error("rebaser client error: {0}")]
     RebaserClient(#[from] rebaser_client::ClientError),
+    #[error("snapshot {0} to restore no longer exists")]
+    RestoreSnapshotMissing(WorkspaceSnapshotAddress),
+    #[error("snapshot {0} to restore has not been migrated to the current graph version ({1})")]
+    RestoreSnapshotNeedsMigration(
+        WorkspaceSnapshotAddress,
+        WorkspaceSnapshotGraphDiscriminants,
+    ),
+    #[error("change set {0} has no recorded base snapshot to restore")]
+    RestoreSnapshotNotRecorded(ChangeSetId),
     #[error("schema error: {0}")]
     Schema(#[from] Box<SchemaError>),
     #[error("schema variant error: {0}")]
@@ -109,6 +134,12 @@ impl From<WorkspaceError> for ChangeSetError {
     }
 }
 
+impl From<approval_policy::ApprovalPolicyError> for ChangeSetError {
+    fn from(value: approval_policy::ApprovalPolicyError) -> Self {
+        Self::ApprovalPolicy(Box::new(value))
+    }
+}
+
 impl From<WsEventError> for ChangeSetError {
     fn from(value: WsEventError) -> Self {
         Self::WsEvent(Box::new(value))
@@ -173,10 +204,22 @@ pub struct ChangeSet {
     pub base_change_set_id: Option<ChangeSetId>,
     pub workspace_snapshot_address: WorkspaceSnapshotAddress,
     pub workspace_id: Option<WorkspacePk>,
+    /// The user who created the change set, if it was created by a user.
+    pub created_by_user_id: Option<UserPk>,
     pub merge_requested_by_user_id: Option<UserPk>,
     pub merge_requested_at: Option<DateTime<Utc>>,
     pub reviewed_by_user_id: Option<UserPk>,
     pub reviewed_at: Option<DateTime<Utc>>,
+    /// Whether a failed [`Action`](crate::Action) enqueued by applying this change set rolls
+    /// back the rest of its batch. See [`crate::action::rollback`].
+    pub rollback_on_failure: bool,
+    /// The snapshot of the base change set this change set last caught up with: the base as of
+    /// the fork, then as of each batch of HEAD updates replayed onto it. Changes made to the base
+    /// since are concurrent with the ones made here. See [`merge_preview`].
+    pub merge_base_snapshot_address: Option<WorkspaceSnapshotAddress>,
+    /// The snapshot of the base change set right after this change set was applied to it. See
+    /// [`restore`].
+    pub applied_base_snapshot_address: Option<WorkspaceSnapshotAddress>,
 }
 
 impl TryFrom<PgRow> for ChangeSet {
@@ -194,10 +237,14 @@ impl TryFrom<PgRow> for ChangeSet {
             base_change_set_id: value.try_get("base_change_set_id")?,
             workspace_snapshot_address: value.try_get("workspace_snapshot_address")?,
             workspace_id: value.try_get("workspace_id")?,
+            created_by_user_id: value.try_get("created_by_user_id")?,
             merge_requested_by_user_id: value.try_get("merge_requested_by_user_id")?,
             merge_requested_at: value.try_get("merge_requested_at")?,
             reviewed_by_user_id: value.try_get("reviewed_by_user_id")?,
             reviewed_at: value.try_get("reviewed_at")?,
+            rollback_on_failure: value.try_get("rollback_on_failure")?,
+            merge_base_snapshot_address: value.try_get("merge_base_snapshot_address")?,
+            applied_base_snapshot_address: value.try_get("applied_base_snapshot_address")?,
         })
     }
 }
@@ -222,15 +269,26 @@ impl ChangeSet {
         // completely disjoint changesets.
         let workspace_snapshot_address = workspace_snapshot.write(ctx).await.map_err(Box::new)?;
 
+        let merge_base_snapshot_address = match base_change_set_id {
+            Some(base_change_set_id) => Some(
+                Self::find(ctx, base_change_set_id)
+                    .await?
+                    .ok_or(ChangeSetError::ChangeSetNotFound(base_change_set_id))?
+                    .workspace_snapshot_address,
+            ),
+            None => None,
+        };
+
         let workspace_id = ctx.tenancy().workspace_pk_opt();
+        let created_by_user_id = Self::extract_userid_from_context(ctx).await;
         let name = name.as_ref();
         let row = ctx
             .txns()
             .await?
             .pg()
             .query_one(
-                "INSERT INTO change_set_pointers (id, name, base_change_set_id, status, workspace_id, workspace_snapshot_address) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
-                &[&change_set_id, &name, &base_change_set_id, &ChangeSetStatus::Open.to_string(), &workspace_id, &workspace_snapshot_address],
+                "INSERT INTO change_set_pointers (id, name, base_change_set_id, status, workspace_id, workspace_snapshot_address, merge_base_snapshot_address, created_by_user_id) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING *",
+                &[&change_set_id, &name, &base_change_set_id, &ChangeSetStatus::Open.to_string(), &workspace_id, &workspace_snapshot_address, &merge_base_snapshot_address, &created_by_user_id],
             )
             .await?;
         let change_set = Self::try_from(row)?;
@@ -314,6 +372,7 @@ impl ChangeSet {
             reviewed_by_user_id: self.reviewed_by_user_id.map(|id| id.into()),
             reviewed_by_user,
             reviewed_at: self.reviewed_at,
+            rollback_on_failure: self.rollback_on_failure,
         };
 
         Ok(change_set)
@@ -394,6 +453,71 @@ impl ChangeSet {
         Ok(())
     }
 
+    /// Opts the batch of [`Actions`](crate::Action) enqueued by applying this change set in or
+    /// out of rollback-on-failure. This does not bump `updated_at`, as that orders the history
+    /// of applied change sets.
+    pub async fn set_rollback_on_failure(
+        &mut self,
+        ctx: &DalContext,
+        rollback_on_failure: bool,
+    ) -> ChangeSetResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "UPDATE change_set_pointers SET rollback_on_failure = $2 WHERE id = $1",
+                &[&self.id, &rollback_on_failure],
+            )
+            .await?;
+
+        self.rollback_on_failure = rollback_on_failure;
+
+        Ok(())
+    }
+
+    /// Records that this change set has caught up with its base change set as of the snapshot at
+    /// the given address. Like [`Self::set_rollback_on_failure`], this does not bump
+    /// `updated_at`.
+    pub async fn update_merge_base(
+        &mut self,
+        ctx: &DalContext,
+        merge_base_snapshot_address: WorkspaceSnapshotAddress,
+    ) -> ChangeSetResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "UPDATE change_set_pointers SET merge_base_snapshot_address = $2 WHERE id = $1",
+                &[&self.id, &merge_base_snapshot_address],
+            )
+            .await?;
+
+        self.merge_base_snapshot_address = Some(merge_base_snapshot_address);
+
+        Ok(())
+    }
+
+    /// Records the snapshot of the base change set right after this change set was applied to
+    /// it. Like [`Self::set_rollback_on_failure`], this does not bump `updated_at`.
+    async fn update_applied_base_snapshot(
+        &mut self,
+        ctx: &DalContext,
+        applied_base_snapshot_address: WorkspaceSnapshotAddress,
+    ) -> ChangeSetResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "UPDATE change_set_pointers SET applied_base_snapshot_address = $2 WHERE id = $1",
+                &[&self.id, &applied_base_snapshot_address],
+            )
+            .await?;
+
+        self.applied_base_snapshot_address = Some(applied_base_snapshot_address);
+
+        Ok(())
+    }
+
     pub async fn request_change_set_approval(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
         let user_pk = Self::extract_userid_from_context_or_error(ctx).await?;
         let status = ChangeSetStatus::NeedsApproval;
@@ -406,12 +530,13 @@ impl ChangeSet {
             )
             .await?;
 
+        self.merge_requested_by_user_id = Some(user_pk);
         self.status = status;
 
         Ok(())
     }
 
-    /// Set the status to Open, and clear any reviewed/merge requested info
+    /// Set the status to Open, and clear any reviewed/merge requested info and approvals
     pub async fn reopen_change_set(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
         let status = ChangeSetStatus::Open;
         ctx.txns()
@@ -428,6 +553,7 @@ impl ChangeSet {
                 &[&self.id, &status.to_string()],
             )
             .await?;
+        ChangeSetApproval::clear_for_change_set(ctx, self.id).await?;
 
         self.status = status;
 
@@ -435,17 +561,29 @@ impl ChangeSet {
     }
 
     /// First, transitions the status of the [`ChangeSet`] to [`ChangeSetStatus::NeedsApproval`]
-    /// then [`ChangeSetStatus::Approved`]. Next, checks if DVU Roots still exist. Finally,
-    /// lock every [`SchemaVariant`] and [`Func`] that is currently unlocked
+    /// then records the approval of the current user. Next, checks if DVU Roots still exist.
+    /// Finally, lock every [`SchemaVariant`] and [`Func`] that is currently unlocked
+    ///
+    /// Forcing does not get around the [`ApprovalPolicy`] of the workspace: the approval of the
+    /// current user counts like any other, and the policy has to be satisfied.
     pub async fn prepare_for_force_apply(ctx: &DalContext) -> ChangeSetResult<()> {
-        // first change the status to approved and who did it
         let mut change_set = ChangeSet::find(ctx, ctx.change_set_id())
             .await?
             .ok_or(TransactionsError::ChangeSetNotFound(ctx.change_set_id()))?;
 
-        change_set.request_change_set_approval(ctx).await?;
+        // first change the status and who did it, unless a policy applies and the approval was
+        // already requested, in which case taking over the request would let the current user
+        // get around the self approval rules
+        if change_set.merge_requested_by_user_id.is_none()
+            || ApprovalPolicy::get(ctx).await?.is_none()
+        {
+            change_set.request_change_set_approval(ctx).await?;
+        }
         // then approve it
         change_set.approve_change_set_for_apply(ctx).await?;
+        if change_set.status != ChangeSetStatus::Approved {
+            return Err(ChangeSetError::ApprovalPolicyNotSatisfied(change_set.id));
+        }
         // then do the rest
         Self::prepare_for_apply(ctx).await
     }
@@ -483,6 +621,8 @@ impl ChangeSet {
             ));
         }
 
+        Self::ensure_approval_policy_satisfied(ctx, change_set.id).await?;
+
         // Lock all unlocked variants
         for schema_id in Schema::list_ids(ctx).await.map_err(Box::new)? {
             let schema = Schema::get_by_id_or_error(ctx, schema_id)
@@ -515,9 +655,32 @@ impl ChangeSet {
         Ok(())
     }
 
+    /// Returns an error unless the [`ChangeSet`] satisfies the [`ApprovalPolicy`] of its
+    /// workspace. Every way of applying a change set has to go through this before locking
+    /// anything, since locking changes the snapshot that approvals were given for.
+    pub async fn ensure_approval_policy_satisfied(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+    ) -> ChangeSetResult<()> {
+        if !ApprovalPolicyEvaluation::for_change_set(ctx, change_set_id)
+            .await?
+            .satisfied
+        {
+            return Err(ChangeSetError::ApprovalPolicyNotSatisfied(change_set_id));
+        }
+        Ok(())
+    }
+
+    /// Records the approval of the current user. The [`ChangeSet`] only becomes
+    /// [`ChangeSetStatus::Approved`] once it satisfies the [`ApprovalPolicy`] of its workspace,
+    /// if there is one.
     pub async fn approve_change_set_for_apply(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
         let user_pk = Self::extract_userid_from_context_or_error(ctx).await?;
-        let status = ChangeSetStatus::Approved;
+        let status = if ApprovalPolicy::record_approval(ctx, self, user_pk).await? {
+            ChangeSetStatus::Approved
+        } else {
+            ChangeSetStatus::NeedsApproval
+        };
         ctx.txns()
             .await?
             .pg()
@@ -532,6 +695,8 @@ impl ChangeSet {
         Ok(())
     }
 
+    /// Rejects the [`ChangeSet`] and clears its approvals, so they have to be given again once
+    /// it is reopened.
     pub async fn reject_change_set_for_apply(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
         let user_pk = Self::extract_userid_from_context_or_error(ctx).await?;
         let status = ChangeSetStatus::Rejected;
@@ -543,6 +708,7 @@ impl ChangeSet {
                 &[&self.id, &user_pk, &status.to_string()],
             )
             .await?;
+        ChangeSetApproval::clear_for_change_set(ctx, self.id).await?;
 
         self.status = status;
 
@@ -788,6 +954,15 @@ impl ChangeSet {
                 })??;
         }
 
+        // The rebaser has moved the base by now. Another change set applied to the base in the
+        // meantime would be included here too, which is what the base looked like as of this
+        // apply anyway.
+        let base_change_set = Self::find(ctx, base_change_set_id)
+            .await?
+            .ok_or(ChangeSetError::ChangeSetNotFound(base_change_set_id))?;
+        self.update_applied_base_snapshot(ctx, base_change_set.workspace_snapshot_address)
+            .await?;
+
         self.update_status(ctx, ChangeSetStatus::Applied).await?;
         let user = Self::extract_userid_from_context(ctx).await;
         WsEvent::change_set_applied(ctx, self.id, base_change_set_id, user)
@@ -954,13 +1129,19 @@ impl ChangeSet {
         ctx: &DalContext,
         workspace_snapshot_address: &WorkspaceSnapshotAddress,
     ) -> ChangeSetResult<bool> {
+        // Change sets that can still be applied need their merge base for merge previews.
         let row = ctx
             .txns()
             .await?
             .pg()
             .query_one(
-                "SELECT count(id) AS count FROM change_set_pointers WHERE workspace_snapshot_address = $1",
-                &[&workspace_snapshot_address],
+                "SELECT count(id) AS count FROM change_set_pointers WHERE workspace_snapshot_address = $1 OR (merge_base_snapshot_address = $1 AND status NOT IN ($2, $3, $4))",
+                &[
+                    &workspace_snapshot_address,
+                    &ChangeSetStatus::Abandoned.to_string(),
+                    &ChangeSetStatus::Applied.to_string(),
+                    &ChangeSetStatus::Failed.to_string(),
+                ],
             )
             .await?;
 
@@ -0,0 +1,491 @@
+//! Declarative, per-workspace rules for who has to approve a [`ChangeSet`] before it can be
+//! applied.
+//!
+//! Without an [`ApprovalPolicy`], a single approval from anyone allowed to approve (see the
+//! `permissions` crate) is enough. With one, the approvals recorded for a change set are
+//! evaluated against the policy (see [`ApprovalPolicyEvaluation`]) and
+//! [`ChangeSet::prepare_for_apply`] refuses to go on until it is satisfied.
+//!
+//! Every approval is recorded along with the snapshot of the change set the approver saw, so
+//! that a policy can discard approvals given before the change set last changed.
+
+use std::collections::{BTreeSet, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::{PgError, PgRow};
+use si_events::WorkspaceSnapshotAddress;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use super::diff::{SnapshotDiff, SnapshotDiffError};
+use super::{ChangeSet, ChangeSetError, ChangeSetId};
+use crate::{
+    Component, ComponentError, ComponentId, DalContext, TransactionsError, UserPk,
+    WorkspaceSnapshot, WorkspaceSnapshotError,
+};
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ApprovalPolicyError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] Box<ChangeSetError>),
+    #[error("change set not found: {0}")]
+    ChangeSetNotFound(ChangeSetId),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("user {1} requested the approval of change set {0} and cannot approve it")]
+    SelfApprovalNotAllowed(ChangeSetId, UserPk),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("snapshot diff error: {0}")]
+    SnapshotDiff(#[from] SnapshotDiffError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("approval requirement refers to unknown approval group: {0}")]
+    UnknownApprovalGroup(String),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+impl From<ChangeSetError> for ApprovalPolicyError {
+    fn from(value: ChangeSetError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+pub type ApprovalPolicyResult<T> = Result<T, ApprovalPolicyError>;
+
+/// A named set of users whose approvals can be required by a [`GroupApprovalRequirement`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalGroup {
+    pub name: String,
+    pub members: Vec<UserPk>,
+}
+
+/// Requires approvals from members of an [`ApprovalGroup`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupApprovalRequirement {
+    pub group: String,
+    pub required_approvals: u32,
+    /// The requirement only applies to change sets that add, remove or modify a [`Component`]
+    /// of one of these schemas. It applies to every change set if empty.
+    #[serde(default)]
+    pub schema_names: Vec<String>,
+}
+
+/// The approvals a [`ChangeSet`] needs before it can be applied, for every change set of a
+/// workspace.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ApprovalPolicy {
+    /// How many distinct users need to approve, regardless of the groups they belong to.
+    pub required_approvals: u32,
+    pub groups: Vec<ApprovalGroup>,
+    pub group_requirements: Vec<GroupApprovalRequirement>,
+    /// Whether the user who created a change set, or who requested its approval, can approve it.
+    pub allow_self_approval: bool,
+    /// Whether approvals stop counting once the change set changes, including when changes
+    /// applied to its base are replayed onto it.
+    pub invalidate_on_change: bool,
+}
+
+impl Default for ApprovalPolicy {
+    fn default() -> Self {
+        Self {
+            required_approvals: 1,
+            groups: vec![],
+            group_requirements: vec![],
+            allow_self_approval: true,
+            invalidate_on_change: false,
+        }
+    }
+}
+
+impl ApprovalPolicy {
+    /// Returns the policy of the workspace of the context, if it has one.
+    #[instrument(name = "change_set.approval_policy.get", level = "debug", skip(ctx))]
+    pub async fn get(ctx: &DalContext) -> ApprovalPolicyResult<Option<Self>> {
+        let Some(workspace_id) = ctx.tenancy().workspace_pk_opt() else {
+            return Ok(None);
+        };
+
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT policy FROM change_set_approval_policies WHERE workspace_id = $1",
+                &[&workspace_id],
+            )
+            .await?;
+
+        match maybe_row {
+            Some(row) => {
+                let policy: serde_json::Value = row.try_get("policy")?;
+                Ok(Some(serde_json::from_value(policy)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Sets (or, with `None`, removes) the policy of the workspace of the context.
+    #[instrument(name = "change_set.approval_policy.set", level = "info", skip(ctx))]
+    pub async fn set(ctx: &DalContext, policy: Option<Self>) -> ApprovalPolicyResult<()> {
+        let workspace_id = ctx.workspace_pk()?;
+
+        match policy {
+            Some(policy) => {
+                policy.validate()?;
+                ctx.txns()
+                    .await?
+                    .pg()
+                    .query_none(
+                        "INSERT INTO change_set_approval_policies (workspace_id, policy) VALUES ($1, $2)
+                        ON CONFLICT (workspace_id) DO UPDATE SET policy = $2, updated_at = CLOCK_TIMESTAMP()",
+                        &[&workspace_id, &serde_json::to_value(&policy)?],
+                    )
+                    .await?;
+            }
+            None => {
+                ctx.txns()
+                    .await?
+                    .pg()
+                    .query_none(
+                        "DELETE FROM change_set_approval_policies WHERE workspace_id = $1",
+                        &[&workspace_id],
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> ApprovalPolicyResult<()> {
+        for requirement in &self.group_requirements {
+            if self.group(&requirement.group).is_none() {
+                return Err(ApprovalPolicyError::UnknownApprovalGroup(
+                    requirement.group.to_owned(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn group(&self, name: &str) -> Option<&ApprovalGroup> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    /// Records the approval of the [`ChangeSet`] by the given user, replacing any previous
+    /// approval of theirs. Returns whether the change set is now approved.
+    pub async fn record_approval(
+        ctx: &DalContext,
+        change_set: &ChangeSet,
+        user_id: UserPk,
+    ) -> ApprovalPolicyResult<bool> {
+        let policy = Self::get(ctx).await?;
+        if let Some(policy) = &policy {
+            if !policy.allow_self_approval && is_self_approval(change_set, user_id) {
+                return Err(ApprovalPolicyError::SelfApprovalNotAllowed(
+                    change_set.id,
+                    user_id,
+                ));
+            }
+        }
+
+        ChangeSetApproval::upsert(
+            ctx,
+            change_set.id,
+            user_id,
+            change_set.workspace_snapshot_address,
+        )
+        .await?;
+
+        match policy {
+            Some(policy) => Ok(
+                ApprovalPolicyEvaluation::evaluate(ctx, change_set.id, policy)
+                    .await?
+                    .satisfied,
+            ),
+            None => Ok(true),
+        }
+    }
+}
+
+/// The approval of a [`ChangeSet`] by a user.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetApproval {
+    pub change_set_id: ChangeSetId,
+    pub user_id: UserPk,
+    /// The snapshot of the change set at the time of the approval.
+    pub workspace_snapshot_address: WorkspaceSnapshotAddress,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<PgRow> for ChangeSetApproval {
+    type Error = ApprovalPolicyError;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            change_set_id: row.try_get("change_set_id")?,
+            user_id: row.try_get("user_id")?,
+            workspace_snapshot_address: row.try_get("workspace_snapshot_address")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl ChangeSetApproval {
+    async fn upsert(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+        user_id: UserPk,
+        workspace_snapshot_address: WorkspaceSnapshotAddress,
+    ) -> ApprovalPolicyResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "INSERT INTO change_set_approvals (change_set_id, user_id, workspace_snapshot_address) VALUES ($1, $2, $3)
+                ON CONFLICT (change_set_id, user_id) DO UPDATE SET workspace_snapshot_address = $3, created_at = CLOCK_TIMESTAMP()",
+                &[&change_set_id, &user_id, &workspace_snapshot_address],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn list_for_change_set(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+    ) -> ApprovalPolicyResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * FROM change_set_approvals WHERE change_set_id = $1 ORDER BY created_at",
+                &[&change_set_id],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    /// Removes every approval of the [`ChangeSet`], e.g. when it is reopened.
+    pub async fn clear_for_change_set(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+    ) -> ApprovalPolicyResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "DELETE FROM change_set_approvals WHERE change_set_id = $1",
+                &[&change_set_id],
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Which approvals an [`ApprovalRequirementStatus`] counts.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ApprovalRequirementKind {
+    /// Approvals from any user.
+    Any,
+    /// Approvals from members of the group.
+    Group { group: String },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequirementStatus {
+    pub requirement: ApprovalRequirementKind,
+    pub required_approvals: u32,
+    pub approved_by: Vec<UserPk>,
+    pub satisfied: bool,
+}
+
+/// Where a [`ChangeSet`] stands with regard to the [`ApprovalPolicy`] of its workspace.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalPolicyEvaluation {
+    pub change_set_id: ChangeSetId,
+    pub policy: Option<ApprovalPolicy>,
+    /// The requirements that apply to the change set. Group requirements limited to schemas the
+    /// change set doesn't touch are left out.
+    pub requirements: Vec<ApprovalRequirementStatus>,
+    /// Users whose approvals don't count, because they were given before the change set last
+    /// changed or because the change set is their own.
+    pub discarded_approvals: Vec<UserPk>,
+    pub satisfied: bool,
+}
+
+impl ApprovalPolicyEvaluation {
+    /// Evaluates the approvals of the [`ChangeSet`] against the policy of its workspace. A change
+    /// set is always satisfied if the workspace has no policy.
+    #[instrument(
+        name = "change_set.approval_policy.for_change_set",
+        level = "info",
+        skip(ctx)
+    )]
+    pub async fn for_change_set(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+    ) -> ApprovalPolicyResult<Self> {
+        match ApprovalPolicy::get(ctx).await? {
+            Some(policy) => Self::evaluate(ctx, change_set_id, policy).await,
+            None => Ok(Self {
+                change_set_id,
+                policy: None,
+                requirements: vec![],
+                discarded_approvals: vec![],
+                satisfied: true,
+            }),
+        }
+    }
+
+    async fn evaluate(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+        policy: ApprovalPolicy,
+    ) -> ApprovalPolicyResult<Self> {
+        let change_set = ChangeSet::find(ctx, change_set_id)
+            .await?
+            .ok_or(ApprovalPolicyError::ChangeSetNotFound(change_set_id))?;
+
+        let mut approved_by = Vec::new();
+        let mut discarded_approvals = Vec::new();
+        for approval in ChangeSetApproval::list_for_change_set(ctx, change_set_id).await? {
+            let stale = policy.invalidate_on_change
+                && approval.workspace_snapshot_address != change_set.workspace_snapshot_address;
+            let self_approval =
+                !policy.allow_self_approval && is_self_approval(&change_set, approval.user_id);
+            if stale || self_approval {
+                discarded_approvals.push(approval.user_id);
+            } else {
+                approved_by.push(approval.user_id);
+            }
+        }
+
+        let mut requirements = vec![ApprovalRequirementStatus {
+            requirement: ApprovalRequirementKind::Any,
+            required_approvals: policy.required_approvals,
+            satisfied: approved_by.len() >= policy.required_approvals as usize,
+            approved_by: approved_by.clone(),
+        }];
+
+        let touched_schema_names = if policy
+            .group_requirements
+            .iter()
+            .any(|requirement| !requirement.schema_names.is_empty())
+        {
+            touched_schema_names(ctx, &change_set).await?
+        } else {
+            BTreeSet::new()
+        };
+        for requirement in &policy.group_requirements {
+            if !requirement.schema_names.is_empty()
+                && !requirement
+                    .schema_names
+                    .iter()
+                    .any(|schema_name| touched_schema_names.contains(schema_name))
+            {
+                continue;
+            }
+
+            let members: HashSet<UserPk> = policy
+                .group(&requirement.group)
+                .map(|group| group.members.iter().copied().collect())
+                .unwrap_or_default();
+            let group_approved_by: Vec<UserPk> = approved_by
+                .iter()
+                .filter(|user_id| members.contains(user_id))
+                .copied()
+                .collect();
+            requirements.push(ApprovalRequirementStatus {
+                requirement: ApprovalRequirementKind::Group {
+                    group: requirement.group.to_owned(),
+                },
+                required_approvals: requirement.required_approvals,
+                satisfied: group_approved_by.len() >= requirement.required_approvals as usize,
+                approved_by: group_approved_by,
+            });
+        }
+
+        let satisfied = requirements.iter().all(|requirement| requirement.satisfied);
+
+        Ok(Self {
+            change_set_id,
+            policy: Some(policy),
+            requirements,
+            discarded_approvals,
+            satisfied,
+        })
+    }
+}
+
+/// Whether an approval by the given user is an approval of their own work. Both the author of the
+/// change set and whoever requested its approval count, so that requesting the approval from a
+/// second account doesn't get around the rule.
+fn is_self_approval(change_set: &ChangeSet, user_id: UserPk) -> bool {
+    change_set.created_by_user_id == Some(user_id)
+        || change_set.merge_requested_by_user_id == Some(user_id)
+}
+
+/// Returns the names of the schemas of the [`Components`](Component) the change set adds,
+/// removes, modifies or connects, compared to its base change set.
+async fn touched_schema_names(
+    ctx: &DalContext,
+    change_set: &ChangeSet,
+) -> ApprovalPolicyResult<BTreeSet<String>> {
+    let base_address = SnapshotDiff::base_workspace_snapshot_address(ctx, change_set).await?;
+    let diff =
+        SnapshotDiff::between(ctx, base_address, change_set.workspace_snapshot_address).await?;
+
+    let mut component_ids: BTreeSet<ComponentId> = BTreeSet::new();
+    component_ids.extend(diff.components.iter().map(|change| change.component_id));
+    component_ids.extend(
+        diff.attribute_values
+            .iter()
+            .map(|change| change.component_id),
+    );
+    for connection in &diff.connections {
+        component_ids.insert(connection.from_component_id);
+        component_ids.insert(connection.to_component_id);
+    }
+
+    let mut change_set_ctx = ctx.clone();
+    change_set_ctx.set_workspace_snapshot(
+        WorkspaceSnapshot::find(ctx, change_set.workspace_snapshot_address).await?,
+    );
+    let mut base_ctx = ctx.clone();
+    base_ctx.set_workspace_snapshot(WorkspaceSnapshot::find(ctx, base_address).await?);
+
+    let mut schema_names = BTreeSet::new();
+    for component_id in component_ids {
+        // Removed components only exist in the base.
+        let snapshot_ctx = if Component::try_get_by_id(&change_set_ctx, component_id)
+            .await?
+            .is_some()
+        {
+            &change_set_ctx
+        } else {
+            &base_ctx
+        };
+        let schema = Component::schema_for_component_id(snapshot_ctx, component_id).await?;
+        schema_names.insert(schema.name().to_owned());
+    }
+
+    Ok(schema_names)
+}
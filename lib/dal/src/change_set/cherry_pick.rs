@@ -0,0 +1,181 @@
+//! Replaying the work of one [`ChangeSet`] on top of another.
+//!
+//! Cherry-picking takes the updates that turn the source change set's merge base (or, without
+//! one, its base change set) into the source, and performs them on the target change set the
+//! same way the rebaser performs the updates of an applied change set. Updates that have no
+//! effect on the target, such as a value edited on a component the target deleted, are reported
+//! back rather than failing the cherry-pick.
+//!
+//! The source's dependent value roots are not carried over. Instead, every value the cherry-pick
+//! changes on the target is enqueued for a dependent values update, so that values on the target
+//! that depend on them are recomputed.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use si_events::ulid::Ulid;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use super::merge_preview::{
+    describe, is_dependent_value_root, is_effective, owner, MergePreviewError, MergeUpdateKind,
+};
+use super::{ChangeSet, ChangeSetError, ChangeSetId};
+use crate::attribute::value::AttributeValueError;
+use crate::workspace_snapshot::node_weight::NodeWeightDiscriminants;
+use crate::{
+    AttributeValue, ComponentId, DalContext, EdgeWeightKindDiscriminants, TransactionsError,
+    WorkspaceSnapshot, WorkspaceSnapshotError,
+};
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum CherryPickError {
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] AttributeValueError),
+    #[error("cannot cherry-pick change set {0} onto itself")]
+    CherryPickOntoSelf(ChangeSetId),
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] Box<ChangeSetError>),
+    #[error("change set not found: {0}")]
+    ChangeSetNotFound(ChangeSetId),
+    #[error("merge preview error: {0}")]
+    MergePreview(#[from] MergePreviewError),
+    #[error("change set {0} does not have a base change set")]
+    NoBaseChangeSet(ChangeSetId),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+impl From<ChangeSetError> for CherryPickError {
+    fn from(value: ChangeSetError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+pub type CherryPickResult<T> = Result<T, CherryPickError>;
+
+/// An update of the source change set that has no effect on the target change set.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnappliedUpdate {
+    pub kind: MergeUpdateKind,
+    pub node_id: Ulid,
+    pub node_kind: NodeWeightDiscriminants,
+    pub destination_id: Option<Ulid>,
+    pub edge_kind: Option<EdgeWeightKindDiscriminants>,
+    /// The component the update belongs to in the source change set, if any.
+    pub component_id: Option<ComponentId>,
+    pub path: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CherryPickReport {
+    pub source_change_set_id: ChangeSetId,
+    pub target_change_set_id: ChangeSetId,
+    pub applied: usize,
+    pub unapplied: Vec<UnappliedUpdate>,
+}
+
+impl ChangeSet {
+    /// Performs the updates made in the source [`ChangeSet`] on the workspace snapshot of the
+    /// context. Nothing is persisted until the context is committed.
+    #[instrument(name = "change_set.cherry_pick", level = "info", skip(ctx))]
+    pub async fn cherry_pick(
+        ctx: &DalContext,
+        source_change_set_id: ChangeSetId,
+    ) -> CherryPickResult<CherryPickReport> {
+        let target_change_set_id = ctx.change_set_id();
+        if source_change_set_id == target_change_set_id {
+            return Err(CherryPickError::CherryPickOntoSelf(source_change_set_id));
+        }
+
+        let source = Self::find(ctx, source_change_set_id)
+            .await?
+            .ok_or(CherryPickError::ChangeSetNotFound(source_change_set_id))?;
+        let source_base_address = match source.merge_base_snapshot_address {
+            Some(merge_base_snapshot_address) => merge_base_snapshot_address,
+            None => {
+                let base_change_set_id = source
+                    .base_change_set_id
+                    .ok_or(CherryPickError::NoBaseChangeSet(source_change_set_id))?;
+                Self::find(ctx, base_change_set_id)
+                    .await?
+                    .ok_or(CherryPickError::ChangeSetNotFound(base_change_set_id))?
+                    .workspace_snapshot_address
+            }
+        };
+
+        let mut source_ctx = ctx.clone();
+        source_ctx.set_workspace_snapshot(
+            WorkspaceSnapshot::find(ctx, source.workspace_snapshot_address).await?,
+        );
+        let source_snapshot = source_ctx.workspace_snapshot()?;
+        let source_base_snapshot = WorkspaceSnapshot::find(ctx, source_base_address).await?;
+
+        let updates: Vec<_> = source_base_snapshot
+            .detect_updates(&source_snapshot)
+            .await?
+            .into_iter()
+            .filter(|update| !is_dependent_value_root(update))
+            .collect();
+
+        let target_snapshot = ctx.workspace_snapshot()?;
+        let corrected_updates = target_snapshot
+            .correct_transforms(updates.clone(), true)
+            .await?;
+        target_snapshot.perform_updates(&corrected_updates).await?;
+
+        let mut changed_attribute_value_ids = HashSet::new();
+        let mut report = CherryPickReport {
+            source_change_set_id,
+            target_change_set_id,
+            applied: 0,
+            unapplied: Vec::new(),
+        };
+        for update in updates {
+            if is_effective(&target_snapshot, &update).await? {
+                report.applied += 1;
+                if let Some((_, Some(attribute_value_id))) = owner(ctx, &update).await? {
+                    changed_attribute_value_ids.insert(attribute_value_id);
+                }
+                continue;
+            }
+
+            let (kind, node_id, node_kind, destination_id, edge_kind) = describe(&update);
+            let (component_id, path) = match owner(&source_ctx, &update).await? {
+                Some((component_id, Some(attribute_value_id))) => (
+                    Some(component_id),
+                    AttributeValue::get_path_for_id(&source_ctx, attribute_value_id).await?,
+                ),
+                Some((component_id, None)) => (Some(component_id), None),
+                None => (None, None),
+            };
+            report.unapplied.push(UnappliedUpdate {
+                kind,
+                node_id,
+                node_kind,
+                destination_id,
+                edge_kind,
+                component_id,
+                path,
+            });
+        }
+
+        if !changed_attribute_value_ids.is_empty() {
+            ctx.add_dependent_values_and_enqueue(changed_attribute_value_ids.into_iter().collect())
+                .await?;
+        }
+
+        info!(
+            applied = report.applied,
+            unapplied = report.unapplied.len(),
+            "cherry-picked change set"
+        );
+
+        Ok(report)
+    }
+}
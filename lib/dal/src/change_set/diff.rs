@@ -0,0 +1,542 @@
+//! This module contains [`SnapshotDiff`], a structured, human-oriented description of what
+//! changed between two workspace snapshots.
+//!
+//! [`WorkspaceSnapshot::detect_updates`] produces the low level list of graph operations needed
+//! by the rebaser. A [`SnapshotDiff`] instead answers the questions a reviewer asks: which
+//! [`Components`](Component) were added, removed or renamed, which values changed (and from what
+//! to what), which connections were made or broken and which [`Funcs`](Func) and
+//! [`SchemaVariants`](SchemaVariant) were touched.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_events::{merkle_tree_hash::MerkleTreeHash, ulid::Ulid, WorkspaceSnapshotAddress};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    ChangeSet, ChangeSetError, ChangeSetId, Component, ComponentError, ComponentId, DalContext,
+    Func, FuncError, FuncId, InputSocketId, OutputSocketId, Schema, SchemaError, SchemaVariant,
+    SchemaVariantError, SchemaVariantId, TransactionsError, WorkspaceSnapshot,
+    WorkspaceSnapshotError,
+};
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum SnapshotDiffError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] Box<ChangeSetError>),
+    #[error("change set not found: {0}")]
+    ChangeSetNotFound(ChangeSetId),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("func error: {0}")]
+    Func(#[from] FuncError),
+    #[error("schema error: {0}")]
+    Schema(#[from] SchemaError),
+    #[error("schema variant error: {0}")]
+    SchemaVariant(#[from] SchemaVariantError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+impl From<ChangeSetError> for SnapshotDiffError {
+    fn from(value: ChangeSetError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+pub type SnapshotDiffResult<T> = Result<T, SnapshotDiffError>;
+
+/// How an item differs between the "before" and "after" snapshots.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffKind {
+    Added,
+    Modified,
+    Removed,
+}
+
+/// A [`Component`] that was added, removed or renamed.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentChange {
+    pub component_id: ComponentId,
+    /// [`DiffKind::Modified`] means the component was renamed.
+    pub kind: DiffKind,
+    /// The name in the "after" snapshot, or the last known name for removed components.
+    pub name: String,
+    /// The name in the "before" snapshot, only set for renamed components.
+    pub old_name: Option<String>,
+}
+
+/// A single value that differs at a prop path within a [`Component`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeValueChange {
+    pub component_id: ComponentId,
+    /// The path to the value, e.g. `/root/domain/image` or `/root/domain/ports/0`.
+    pub path: String,
+    pub kind: DiffKind,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// A connection between two [`Components`](Component) that was made or broken.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionChange {
+    pub kind: DiffKind,
+    pub from_component_id: ComponentId,
+    pub from_output_socket_id: OutputSocketId,
+    pub to_component_id: ComponentId,
+    pub to_input_socket_id: InputSocketId,
+}
+
+/// A [`Func`] that was added, removed or modified (code, metadata or arguments).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncChange {
+    pub func_id: FuncId,
+    pub kind: DiffKind,
+    pub name: String,
+}
+
+/// A [`SchemaVariant`] that was added, removed or modified (props, sockets, funcs, etc.).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaVariantChange {
+    pub schema_variant_id: SchemaVariantId,
+    pub kind: DiffKind,
+    pub display_name: String,
+    pub version: String,
+}
+
+/// The semantic difference between two workspace snapshots.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    pub before: WorkspaceSnapshotAddress,
+    pub after: WorkspaceSnapshotAddress,
+    pub components: Vec<ComponentChange>,
+    pub attribute_values: Vec<AttributeValueChange>,
+    pub connections: Vec<ConnectionChange>,
+    pub funcs: Vec<FuncChange>,
+    pub schema_variants: Vec<SchemaVariantChange>,
+}
+
+impl SnapshotDiff {
+    /// Computes the diff between a [`ChangeSet`] and the point it last caught up with its base
+    /// change set (or HEAD, if it has no base), so that only the change set's own work shows up.
+    #[instrument(name = "change_set.diff.for_change_set", level = "info", skip(ctx))]
+    pub async fn for_change_set(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+    ) -> SnapshotDiffResult<Self> {
+        let change_set = ChangeSet::find(ctx, change_set_id)
+            .await?
+            .ok_or(SnapshotDiffError::ChangeSetNotFound(change_set_id))?;
+
+        Self::between(
+            ctx,
+            Self::base_workspace_snapshot_address(ctx, &change_set).await?,
+            change_set.workspace_snapshot_address,
+        )
+        .await
+    }
+
+    /// Returns the snapshot address the work of the given [`ChangeSet`] is measured against: its
+    /// merge base. Diffing against the current snapshot of its base change set instead would
+    /// show everything the base changed since as reverted.
+    ///
+    /// Change sets without a recorded merge base fall back to the current snapshot of their base
+    /// change set, or HEAD if they have no base.
+    pub async fn base_workspace_snapshot_address(
+        ctx: &DalContext,
+        change_set: &ChangeSet,
+    ) -> SnapshotDiffResult<WorkspaceSnapshotAddress> {
+        if let Some(merge_base_snapshot_address) = change_set.merge_base_snapshot_address {
+            return Ok(merge_base_snapshot_address);
+        }
+
+        let base_change_set_id = match change_set.base_change_set_id {
+            Some(base_change_set_id) => base_change_set_id,
+            None => ctx.get_workspace_default_change_set_id().await?,
+        };
+        let base_change_set = ChangeSet::find(ctx, base_change_set_id)
+            .await?
+            .ok_or(SnapshotDiffError::ChangeSetNotFound(base_change_set_id))?;
+
+        Ok(base_change_set.workspace_snapshot_address)
+    }
+
+    /// Computes the diff between the snapshots at the two given addresses.
+    #[instrument(name = "change_set.diff.between", level = "info", skip(ctx))]
+    pub async fn between(
+        ctx: &DalContext,
+        before: WorkspaceSnapshotAddress,
+        after: WorkspaceSnapshotAddress,
+    ) -> SnapshotDiffResult<Self> {
+        let mut diff = Self {
+            before,
+            after,
+            components: vec![],
+            attribute_values: vec![],
+            connections: vec![],
+            funcs: vec![],
+            schema_variants: vec![],
+        };
+        if before == after {
+            return Ok(diff);
+        }
+
+        let before_ctx = ctx_for_snapshot(ctx, before).await?;
+        let after_ctx = ctx_for_snapshot(ctx, after).await?;
+
+        diff.diff_components(&before_ctx, &after_ctx).await?;
+        diff.diff_connections(&before_ctx, &after_ctx).await?;
+        diff.diff_funcs(&before_ctx, &after_ctx).await?;
+        diff.diff_schema_variants(&before_ctx, &after_ctx).await?;
+
+        Ok(diff)
+    }
+
+    /// Returns true if nothing a reviewer would care about differs between the snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+            && self.attribute_values.is_empty()
+            && self.connections.is_empty()
+            && self.funcs.is_empty()
+            && self.schema_variants.is_empty()
+    }
+
+    async fn diff_components(
+        &mut self,
+        before_ctx: &DalContext,
+        after_ctx: &DalContext,
+    ) -> SnapshotDiffResult<()> {
+        let before_ids: HashSet<ComponentId> =
+            Component::list_ids(before_ctx).await?.into_iter().collect();
+        let after_ids: HashSet<ComponentId> =
+            Component::list_ids(after_ctx).await?.into_iter().collect();
+
+        let mut component_ids: Vec<ComponentId> = before_ids.union(&after_ids).copied().collect();
+        component_ids.sort();
+
+        for component_id in component_ids {
+            match (
+                before_ids.contains(&component_id),
+                after_ids.contains(&component_id),
+            ) {
+                (false, true) => {
+                    self.components.push(ComponentChange {
+                        component_id,
+                        kind: DiffKind::Added,
+                        name: Component::name_by_id(after_ctx, component_id).await?,
+                        old_name: None,
+                    });
+                    self.diff_component_values(component_id, None, Some(after_ctx))
+                        .await?;
+                }
+                (true, false) => {
+                    self.components.push(ComponentChange {
+                        component_id,
+                        kind: DiffKind::Removed,
+                        name: Component::name_by_id(before_ctx, component_id).await?,
+                        old_name: None,
+                    });
+                    self.diff_component_values(component_id, Some(before_ctx), None)
+                        .await?;
+                }
+                _ => {
+                    // Every attribute value of a component lives beneath it in the graph, so an
+                    // unchanged merkle tree hash means none of its values changed.
+                    if merkle_tree_hash(before_ctx, component_id).await?
+                        == merkle_tree_hash(after_ctx, component_id).await?
+                    {
+                        continue;
+                    }
+
+                    let old_name = Component::name_by_id(before_ctx, component_id).await?;
+                    let name = Component::name_by_id(after_ctx, component_id).await?;
+                    if old_name != name {
+                        self.components.push(ComponentChange {
+                            component_id,
+                            kind: DiffKind::Modified,
+                            name,
+                            old_name: Some(old_name),
+                        });
+                    }
+                    self.diff_component_values(component_id, Some(before_ctx), Some(after_ctx))
+                        .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn diff_component_values(
+        &mut self,
+        component_id: ComponentId,
+        before_ctx: Option<&DalContext>,
+        after_ctx: Option<&DalContext>,
+    ) -> SnapshotDiffResult<()> {
+        let before_values = match before_ctx {
+            Some(ctx) => flattened_component_view(ctx, component_id).await?,
+            None => BTreeMap::new(),
+        };
+        let after_values = match after_ctx {
+            Some(ctx) => flattened_component_view(ctx, component_id).await?,
+            None => BTreeMap::new(),
+        };
+
+        let mut paths: Vec<&String> = before_values.keys().chain(after_values.keys()).collect();
+        paths.sort();
+        paths.dedup();
+
+        for path in paths {
+            let before = before_values.get(path);
+            let after = after_values.get(path);
+            let kind = match (before, after) {
+                (None, Some(_)) => DiffKind::Added,
+                (Some(_), None) => DiffKind::Removed,
+                (Some(before), Some(after)) if before != after => DiffKind::Modified,
+                _ => continue,
+            };
+
+            self.attribute_values.push(AttributeValueChange {
+                component_id,
+                path: path.to_owned(),
+                kind,
+                before: before.cloned(),
+                after: after.cloned(),
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn diff_connections(
+        &mut self,
+        before_ctx: &DalContext,
+        after_ctx: &DalContext,
+    ) -> SnapshotDiffResult<()> {
+        let before_connections = connections(before_ctx).await?;
+        let after_connections = connections(after_ctx).await?;
+
+        for (connection, kind) in after_connections
+            .difference(&before_connections)
+            .map(|connection| (connection, DiffKind::Added))
+            .chain(
+                before_connections
+                    .difference(&after_connections)
+                    .map(|connection| (connection, DiffKind::Removed)),
+            )
+        {
+            let (from_component_id, from_output_socket_id, to_component_id, to_input_socket_id) =
+                *connection;
+            self.connections.push(ConnectionChange {
+                kind,
+                from_component_id,
+                from_output_socket_id,
+                to_component_id,
+                to_input_socket_id,
+            });
+        }
+        self.connections.sort_by_key(|connection| {
+            (
+                connection.to_component_id,
+                connection.to_input_socket_id,
+                connection.from_component_id,
+                connection.from_output_socket_id,
+            )
+        });
+
+        Ok(())
+    }
+
+    async fn diff_funcs(
+        &mut self,
+        before_ctx: &DalContext,
+        after_ctx: &DalContext,
+    ) -> SnapshotDiffResult<()> {
+        let before_funcs: HashMap<FuncId, Func> = Func::list_all(before_ctx)
+            .await?
+            .into_iter()
+            .map(|func| (func.id, func))
+            .collect();
+        let after_funcs: HashMap<FuncId, Func> = Func::list_all(after_ctx)
+            .await?
+            .into_iter()
+            .map(|func| (func.id, func))
+            .collect();
+
+        let mut func_ids: Vec<FuncId> = before_funcs
+            .keys()
+            .chain(after_funcs.keys())
+            .copied()
+            .collect();
+        func_ids.sort();
+        func_ids.dedup();
+
+        for func_id in func_ids {
+            let (kind, func) = match (before_funcs.get(&func_id), after_funcs.get(&func_id)) {
+                (None, Some(func)) => (DiffKind::Added, func),
+                (Some(func), None) => (DiffKind::Removed, func),
+                (Some(_), Some(func)) => {
+                    // The merkle tree hash covers the func's content as well as its arguments.
+                    if merkle_tree_hash(before_ctx, func_id).await?
+                        == merkle_tree_hash(after_ctx, func_id).await?
+                    {
+                        continue;
+                    }
+                    (DiffKind::Modified, func)
+                }
+                (None, None) => continue,
+            };
+
+            self.funcs.push(FuncChange {
+                func_id,
+                kind,
+                name: func.name.to_owned(),
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn diff_schema_variants(
+        &mut self,
+        before_ctx: &DalContext,
+        after_ctx: &DalContext,
+    ) -> SnapshotDiffResult<()> {
+        let before_variants = schema_variants(before_ctx).await?;
+        let after_variants = schema_variants(after_ctx).await?;
+
+        let mut schema_variant_ids: Vec<SchemaVariantId> = before_variants
+            .keys()
+            .chain(after_variants.keys())
+            .copied()
+            .collect();
+        schema_variant_ids.sort();
+        schema_variant_ids.dedup();
+
+        for schema_variant_id in schema_variant_ids {
+            let (kind, variant) = match (
+                before_variants.get(&schema_variant_id),
+                after_variants.get(&schema_variant_id),
+            ) {
+                (None, Some(variant)) => (DiffKind::Added, variant),
+                (Some(variant), None) => (DiffKind::Removed, variant),
+                (Some(_), Some(variant)) => {
+                    if merkle_tree_hash(before_ctx, schema_variant_id).await?
+                        == merkle_tree_hash(after_ctx, schema_variant_id).await?
+                    {
+                        continue;
+                    }
+                    (DiffKind::Modified, variant)
+                }
+                (None, None) => continue,
+            };
+
+            self.schema_variants.push(SchemaVariantChange {
+                schema_variant_id,
+                kind,
+                display_name: variant.display_name().to_owned(),
+                version: variant.version().to_owned(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+async fn ctx_for_snapshot(
+    ctx: &DalContext,
+    address: WorkspaceSnapshotAddress,
+) -> SnapshotDiffResult<DalContext> {
+    let mut snapshot_ctx = ctx.clone();
+    snapshot_ctx.set_workspace_snapshot(WorkspaceSnapshot::find(ctx, address).await?);
+    Ok(snapshot_ctx)
+}
+
+async fn merkle_tree_hash(
+    ctx: &DalContext,
+    id: impl Into<Ulid>,
+) -> SnapshotDiffResult<MerkleTreeHash> {
+    Ok(ctx
+        .workspace_snapshot()?
+        .get_node_weight_by_id(id)
+        .await?
+        .merkle_tree_hash())
+}
+
+async fn connections(
+    ctx: &DalContext,
+) -> SnapshotDiffResult<HashSet<(ComponentId, OutputSocketId, ComponentId, InputSocketId)>> {
+    let mut connections = HashSet::new();
+    for component_id in Component::list_ids(ctx).await? {
+        for connection in Component::incoming_connections_for_id(ctx, component_id).await? {
+            connections.insert((
+                connection.from_component_id,
+                connection.from_output_socket_id,
+                connection.to_component_id,
+                connection.to_input_socket_id,
+            ));
+        }
+    }
+
+    Ok(connections)
+}
+
+async fn schema_variants(
+    ctx: &DalContext,
+) -> SnapshotDiffResult<HashMap<SchemaVariantId, SchemaVariant>> {
+    let mut variants = HashMap::new();
+    for schema_id in Schema::list_ids(ctx).await? {
+        for variant in SchemaVariant::list_for_schema(ctx, schema_id).await? {
+            variants.insert(variant.id(), variant);
+        }
+    }
+
+    Ok(variants)
+}
+
+/// Returns every leaf value of the component's view keyed by its path from `/root`.
+async fn flattened_component_view(
+    ctx: &DalContext,
+    component_id: ComponentId,
+) -> SnapshotDiffResult<BTreeMap<String, Value>> {
+    let mut values = BTreeMap::new();
+    if let Some(view) = Component::get_by_id(ctx, component_id)
+        .await?
+        .view(ctx)
+        .await?
+    {
+        flatten_value("/root".to_owned(), view, &mut values);
+    }
+
+    Ok(values)
+}
+
+fn flatten_value(path: String, value: Value, values: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                flatten_value(format!("{path}/{key}"), value, values);
+            }
+        }
+        Value::Array(array) if !array.is_empty() => {
+            for (index, value) in array.into_iter().enumerate() {
+                flatten_value(format!("{path}/{index}"), value, values);
+            }
+        }
+        leaf => {
+            values.insert(path, leaf);
+        }
+    }
+}
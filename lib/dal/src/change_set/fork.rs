@@ -0,0 +1,84 @@
+//! Forking a [`ChangeSet`] from another open change set, rather than from HEAD.
+//!
+//! The new change set starts from the source's current snapshot. It is either stacked on the
+//! source ([`ForkBase::Source`]), so that applying it merges into the source, or based on HEAD
+//! ([`ForkBase::Head`]), so that a change set can serve as a template of work applied to HEAD
+//! directly.
+
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+use super::{ChangeSet, ChangeSetError, ChangeSetId, ChangeSetResult};
+use crate::{ChangeSetStatus, DalContext, WsEvent};
+
+/// The change set a forked [`ChangeSet`] is applied to.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ForkBase {
+    /// HEAD, like any change set forked from HEAD.
+    Head,
+    /// The change set the fork was made from.
+    #[default]
+    Source,
+}
+
+impl ChangeSet {
+    /// Creates a new [`ChangeSet`] whose graph is the current snapshot of the source change set.
+    #[instrument(name = "change_set.fork", level = "info", skip(ctx, name))]
+    pub async fn fork(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        source_change_set_id: ChangeSetId,
+        base: ForkBase,
+    ) -> ChangeSetResult<Self> {
+        let source = Self::find(ctx, source_change_set_id)
+            .await?
+            .ok_or(ChangeSetError::ChangeSetNotFound(source_change_set_id))?;
+        if matches!(
+            source.status,
+            ChangeSetStatus::Abandoned | ChangeSetStatus::Applied | ChangeSetStatus::Failed
+        ) {
+            return Err(ChangeSetError::CannotForkChangeSet(
+                source_change_set_id,
+                source.status,
+            ));
+        }
+
+        let change_set = match base {
+            ForkBase::Head => {
+                let mut change_set =
+                    Self::fork_head_from_snapshot(ctx, name, source.workspace_snapshot_address)
+                        .await?;
+                // A source based on HEAD has seen exactly as much of HEAD as the fork has. A
+                // source stacked on another change set has a merge base in that change set, so
+                // the fork keeps HEAD's current snapshot as its merge base instead.
+                let head_change_set_id = ctx.get_workspace_default_change_set_id().await?;
+                if source.base_change_set_id == Some(head_change_set_id) {
+                    if let Some(merge_base_snapshot_address) = source.merge_base_snapshot_address {
+                        change_set
+                            .update_merge_base(ctx, merge_base_snapshot_address)
+                            .await?;
+                    }
+                }
+                change_set
+            }
+            ForkBase::Source => {
+                let change_set = Self::new(
+                    ctx,
+                    name,
+                    Some(source_change_set_id),
+                    source.workspace_snapshot_address,
+                )
+                .await?;
+                WsEvent::change_set_created(ctx, change_set.id)
+                    .await?
+                    .publish_on_commit(ctx)
+                    .await?;
+                change_set
+            }
+        };
+
+        Ok(change_set)
+    }
+}
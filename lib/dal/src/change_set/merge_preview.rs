@@ -0,0 +1,506 @@
+//! This module contains [`MergePreview`], which describes what applying a [`ChangeSet`] to its
+//! base change set would do once the base has moved on.
+//!
+//! The updates that would be applied are computed exactly as an apply computes them, then
+//! rebased in memory onto the current base (HEAD, usually) and classified:
+//!
+//! - [`UpdateClassification::Clean`]: the update lands and nothing changed concurrently.
+//! - [`UpdateClassification::Overwrite`]: the update lands, but replaces (or reverts) something
+//!   changed on the base since the change set last caught up with it, such as a value edited or
+//!   a component deleted on HEAD.
+//! - [`UpdateClassification::Dropped`]: the update does not survive the rebase, for example an
+//!   edit to something that no longer exists on the base.
+//!
+//! Concurrent changes are detected against the change set's merge base (see
+//! [`ChangeSet::merge_base_snapshot_address`]). Change sets created before merge bases were
+//! recorded have none, in which case no update is classified as an overwrite.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use si_events::{ulid::Ulid, ContentHash, WorkspaceSnapshotAddress};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::attribute::value::AttributeValueError;
+use crate::workspace_snapshot::graph::detect_updates::Update;
+use crate::workspace_snapshot::node_weight::{NodeWeight, NodeWeightDiscriminants};
+use crate::{
+    AttributeValue, AttributeValueId, ChangeSet, ChangeSetError, ChangeSetId, Component,
+    ComponentError, ComponentId, DalContext, EdgeWeightKindDiscriminants, TransactionsError,
+    WorkspaceSnapshot, WorkspaceSnapshotError,
+};
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum MergePreviewError {
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] AttributeValueError),
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] Box<ChangeSetError>),
+    #[error("change set not found: {0}")]
+    ChangeSetNotFound(ChangeSetId),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("change set {0} does not have a base change set")]
+    NoBaseChangeSet(ChangeSetId),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+impl From<ChangeSetError> for MergePreviewError {
+    fn from(value: ChangeSetError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+pub type MergePreviewResult<T> = Result<T, MergePreviewError>;
+
+/// What would become of an update if the change set were applied now.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum UpdateClassification {
+    Clean,
+    Dropped,
+    Overwrite,
+}
+
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeUpdateKind {
+    NewEdge,
+    NewNode,
+    RemoveEdge,
+    ReplaceNode,
+}
+
+/// A single graph update that applying the change set would perform.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewedUpdate {
+    pub kind: MergeUpdateKind,
+    pub classification: UpdateClassification,
+    /// The node added or replaced, or the source of the edge.
+    pub node_id: Ulid,
+    pub node_kind: NodeWeightDiscriminants,
+    /// The destination of the edge, for edge updates.
+    pub destination_id: Option<Ulid>,
+    pub edge_kind: Option<EdgeWeightKindDiscriminants>,
+    /// The path of the attribute value the update belongs to, if any, e.g. `/root/si/name`.
+    pub path: Option<String>,
+}
+
+/// The updates that belong to a single [`Component`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentMergePreview {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    /// The component was deleted on the base after the change set last caught up with it.
+    pub deleted_on_base: bool,
+    pub updates: Vec<PreviewedUpdate>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MergePreview {
+    pub change_set_id: ChangeSetId,
+    pub base_change_set_id: ChangeSetId,
+    pub base_snapshot_address: WorkspaceSnapshotAddress,
+    /// `None` if the change set predates merge bases, in which case overwrites can't be detected.
+    pub merge_base_snapshot_address: Option<WorkspaceSnapshotAddress>,
+    pub clean: usize,
+    pub overwrites: usize,
+    pub dropped: usize,
+    pub components: Vec<ComponentMergePreview>,
+    /// Updates that don't belong to a component, such as changes to funcs, schema variants or
+    /// views.
+    pub other_updates: Vec<PreviewedUpdate>,
+}
+
+impl MergePreview {
+    /// Previews applying the [`ChangeSet`] to the current state of its base change set.
+    #[instrument(
+        name = "change_set.merge_preview.for_change_set",
+        level = "info",
+        skip(ctx)
+    )]
+    pub async fn for_change_set(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+    ) -> MergePreviewResult<Self> {
+        let change_set = ChangeSet::find(ctx, change_set_id)
+            .await?
+            .ok_or(MergePreviewError::ChangeSetNotFound(change_set_id))?;
+        let base_change_set_id = change_set
+            .base_change_set_id
+            .ok_or(MergePreviewError::NoBaseChangeSet(change_set_id))?;
+        let base_change_set = ChangeSet::find(ctx, base_change_set_id)
+            .await?
+            .ok_or(MergePreviewError::ChangeSetNotFound(base_change_set_id))?;
+
+        let change_set_ctx = ctx_for_snapshot(ctx, change_set.workspace_snapshot_address).await?;
+        let base_ctx = ctx_for_snapshot(ctx, base_change_set.workspace_snapshot_address).await?;
+        let merge_base_ctx = match change_set.merge_base_snapshot_address {
+            Some(address) => Some(ctx_for_snapshot(ctx, address).await?),
+            None => None,
+        };
+
+        let change_set_snapshot = change_set_ctx.workspace_snapshot()?;
+        let base_snapshot = base_ctx.workspace_snapshot()?;
+
+        // Rebase in memory, the same way the rebaser does when the change set is applied.
+        let updates = base_snapshot.detect_updates(&change_set_snapshot).await?;
+        let corrected_updates = base_snapshot
+            .correct_transforms(updates.clone(), true)
+            .await?;
+        let rebased_snapshot =
+            WorkspaceSnapshot::find(ctx, base_change_set.workspace_snapshot_address).await?;
+        rebased_snapshot.perform_updates(&corrected_updates).await?;
+
+        let mut preview = Self {
+            change_set_id,
+            base_change_set_id,
+            base_snapshot_address: base_change_set.workspace_snapshot_address,
+            merge_base_snapshot_address: change_set.merge_base_snapshot_address,
+            clean: 0,
+            overwrites: 0,
+            dropped: 0,
+            components: Vec::new(),
+            other_updates: Vec::new(),
+        };
+        let mut components: BTreeMap<ComponentId, ComponentMergePreview> = BTreeMap::new();
+
+        for update in updates {
+            // Dependent value roots are bookkeeping for the next dependent values update, not
+            // changes anyone made.
+            if is_dependent_value_root(&update) {
+                continue;
+            }
+
+            let classification = if !corrected_updates.contains(&update)
+                || !is_effective(&rebased_snapshot, &update).await?
+            {
+                UpdateClassification::Dropped
+            } else if let Some(merge_base_ctx) = &merge_base_ctx {
+                if is_concurrent(
+                    &merge_base_ctx.workspace_snapshot()?,
+                    &base_snapshot,
+                    &update,
+                )
+                .await?
+                {
+                    UpdateClassification::Overwrite
+                } else {
+                    UpdateClassification::Clean
+                }
+            } else {
+                UpdateClassification::Clean
+            };
+            match classification {
+                UpdateClassification::Clean => preview.clean += 1,
+                UpdateClassification::Dropped => preview.dropped += 1,
+                UpdateClassification::Overwrite => preview.overwrites += 1,
+            }
+
+            // Nodes removed by the change set are only found on the base.
+            let owner = match owner(&change_set_ctx, &update).await? {
+                Some(owner) => Some((owner, &change_set_ctx)),
+                None => owner(&base_ctx, &update)
+                    .await?
+                    .map(|owner| (owner, &base_ctx)),
+            };
+
+            let (kind, node_id, node_kind, destination_id, edge_kind) = describe(&update);
+            let mut previewed_update = PreviewedUpdate {
+                kind,
+                classification,
+                node_id,
+                node_kind,
+                destination_id,
+                edge_kind,
+                path: None,
+            };
+
+            let Some(((component_id, attribute_value_id), owner_ctx)) = owner else {
+                preview.other_updates.push(previewed_update);
+                continue;
+            };
+            if let Some(attribute_value_id) = attribute_value_id {
+                previewed_update.path =
+                    AttributeValue::get_path_for_id(owner_ctx, attribute_value_id).await?;
+            }
+
+            if !components.contains_key(&component_id) {
+                let deleted_on_base = match &merge_base_ctx {
+                    Some(merge_base_ctx) => {
+                        merge_base_ctx
+                            .workspace_snapshot()?
+                            .get_node_index_by_id_opt(component_id)
+                            .await
+                            .is_some()
+                            && base_snapshot
+                                .get_node_index_by_id_opt(component_id)
+                                .await
+                                .is_none()
+                    }
+                    None => false,
+                };
+                components.insert(
+                    component_id,
+                    ComponentMergePreview {
+                        component_id,
+                        component_name: Component::name_by_id(owner_ctx, component_id).await?,
+                        deleted_on_base,
+                        updates: Vec::new(),
+                    },
+                );
+            }
+            if let Some(component) = components.get_mut(&component_id) {
+                component.updates.push(previewed_update);
+            }
+        }
+
+        preview.components = components.into_values().collect();
+
+        Ok(preview)
+    }
+
+    /// Whether applying would replace changes made to the base since the change set last caught
+    /// up with it.
+    pub fn has_unresolved_overwrites(&self) -> bool {
+        self.overwrites > 0
+    }
+}
+
+async fn ctx_for_snapshot(
+    ctx: &DalContext,
+    address: WorkspaceSnapshotAddress,
+) -> MergePreviewResult<DalContext> {
+    let mut snapshot_ctx = ctx.clone();
+    snapshot_ctx.set_workspace_snapshot(WorkspaceSnapshot::find(ctx, address).await?);
+    Ok(snapshot_ctx)
+}
+
+pub(super) fn is_dependent_value_root(update: &Update) -> bool {
+    let kind = match update {
+        Update::NewNode { node_weight } | Update::ReplaceNode { node_weight } => {
+            NodeWeightDiscriminants::from(node_weight)
+        }
+        Update::NewEdge { destination, .. } | Update::RemoveEdge { destination, .. } => {
+            destination.node_weight_kind
+        }
+    };
+    matches!(
+        kind,
+        NodeWeightDiscriminants::DependentValueRoot
+            | NodeWeightDiscriminants::FinishedDependentValueRoot
+    )
+}
+
+pub(super) fn describe(
+    update: &Update,
+) -> (
+    MergeUpdateKind,
+    Ulid,
+    NodeWeightDiscriminants,
+    Option<Ulid>,
+    Option<EdgeWeightKindDiscriminants>,
+) {
+    match update {
+        Update::NewNode { node_weight } => (
+            MergeUpdateKind::NewNode,
+            node_weight.id(),
+            node_weight.into(),
+            None,
+            None,
+        ),
+        Update::ReplaceNode { node_weight } => (
+            MergeUpdateKind::ReplaceNode,
+            node_weight.id(),
+            node_weight.into(),
+            None,
+            None,
+        ),
+        Update::NewEdge {
+            source,
+            destination,
+            edge_weight,
+        } => (
+            MergeUpdateKind::NewEdge,
+            source.id.into(),
+            source.node_weight_kind,
+            Some(destination.id.into()),
+            Some(edge_weight.kind().into()),
+        ),
+        Update::RemoveEdge {
+            source,
+            destination,
+            edge_kind,
+        } => (
+            MergeUpdateKind::RemoveEdge,
+            source.id.into(),
+            source.node_weight_kind,
+            Some(destination.id.into()),
+            Some(*edge_kind),
+        ),
+    }
+}
+
+async fn node_hash(
+    snapshot: &WorkspaceSnapshot,
+    id: Ulid,
+) -> MergePreviewResult<Option<ContentHash>> {
+    Ok(match snapshot.get_node_index_by_id_opt(id).await {
+        Some(node_index) => Some(snapshot.get_node_weight(node_index).await?.node_hash()),
+        None => None,
+    })
+}
+
+async fn has_edge(
+    snapshot: &WorkspaceSnapshot,
+    source: Ulid,
+    destination: Ulid,
+    edge_kind: EdgeWeightKindDiscriminants,
+) -> bool {
+    snapshot
+        .find_edge(source, destination, edge_kind)
+        .await
+        .is_some()
+}
+
+/// Whether the update is reflected in the rebased snapshot.
+pub(super) async fn is_effective(
+    rebased: &WorkspaceSnapshot,
+    update: &Update,
+) -> MergePreviewResult<bool> {
+    Ok(match update {
+        Update::NewNode { node_weight } | Update::ReplaceNode { node_weight } => {
+            node_hash(rebased, node_weight.id()).await? == Some(node_weight.node_hash())
+        }
+        Update::NewEdge {
+            source,
+            destination,
+            edge_weight,
+        } => {
+            has_edge(
+                rebased,
+                source.id.into(),
+                destination.id.into(),
+                edge_weight.kind().into(),
+            )
+            .await
+        }
+        Update::RemoveEdge {
+            source,
+            destination,
+            edge_kind,
+        } => !has_edge(rebased, source.id.into(), destination.id.into(), *edge_kind).await,
+    })
+}
+
+/// Whether the base changed what the update touches since the merge base, so that applying the
+/// update replaces (or reverts) that change.
+async fn is_concurrent(
+    merge_base: &WorkspaceSnapshot,
+    base: &WorkspaceSnapshot,
+    update: &Update,
+) -> MergePreviewResult<bool> {
+    Ok(match update {
+        Update::ReplaceNode { node_weight } => {
+            node_hash(merge_base, node_weight.id()).await?
+                != node_hash(base, node_weight.id()).await?
+        }
+        // Adding back a node the base removed.
+        Update::NewNode { node_weight } => {
+            merge_base
+                .get_node_index_by_id_opt(node_weight.id())
+                .await
+                .is_some()
+                && base
+                    .get_node_index_by_id_opt(node_weight.id())
+                    .await
+                    .is_none()
+        }
+        // Adding back an edge the base removed.
+        Update::NewEdge {
+            source,
+            destination,
+            edge_weight,
+        } => {
+            let edge_kind = edge_weight.kind().into();
+            has_edge(
+                merge_base,
+                source.id.into(),
+                destination.id.into(),
+                edge_kind,
+            )
+            .await
+                && !has_edge(base, source.id.into(), destination.id.into(), edge_kind).await
+        }
+        // Removing an edge the base added.
+        Update::RemoveEdge {
+            source,
+            destination,
+            edge_kind,
+        } => {
+            !has_edge(
+                merge_base,
+                source.id.into(),
+                destination.id.into(),
+                *edge_kind,
+            )
+            .await
+                && has_edge(base, source.id.into(), destination.id.into(), *edge_kind).await
+        }
+    })
+}
+
+/// Finds the [`Component`] (and the attribute value, if any) the update belongs to, trying the
+/// source of an edge before its destination.
+pub(super) async fn owner(
+    ctx: &DalContext,
+    update: &Update,
+) -> MergePreviewResult<Option<(ComponentId, Option<AttributeValueId>)>> {
+    let node_ids: Vec<Ulid> = match update {
+        Update::NewNode { node_weight } | Update::ReplaceNode { node_weight } => {
+            vec![node_weight.id()]
+        }
+        Update::NewEdge {
+            source,
+            destination,
+            ..
+        }
+        | Update::RemoveEdge {
+            source,
+            destination,
+            ..
+        } => vec![source.id.into(), destination.id.into()],
+    };
+
+    let snapshot = ctx.workspace_snapshot()?;
+    for node_id in node_ids {
+        let Some(node_index) = snapshot.get_node_index_by_id_opt(node_id).await else {
+            continue;
+        };
+        let node_weight = snapshot.get_node_weight(node_index).await?;
+        if let NodeWeight::Component(_) = node_weight {
+            return Ok(Some((node_id.into(), None)));
+        }
+        // Nodes shared by several values (or belonging to none) are not attributed to a
+        // component.
+        let Ok(Some(attribute_value_id)) =
+            snapshot.associated_attribute_value_id(node_weight).await
+        else {
+            continue;
+        };
+        let component_id = AttributeValue::component_id(ctx, attribute_value_id).await?;
+        return Ok(Some((component_id, Some(attribute_value_id))));
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,158 @@
+//! Point-in-time restore of HEAD.
+//!
+//! Every applied [`ChangeSet`] records the address of HEAD's snapshot right after it was applied,
+//! so the chain of applied change sets is a history of HEAD. Restoring creates a new, open change
+//! set (based on HEAD) whose graph is the historical snapshot. Applying it through the normal
+//! approval flow calculates the updates from HEAD to the historical graph, which reverts HEAD to
+//! that state: nodes added since are removed, nodes removed since are added back and modified
+//! nodes are replaced.
+//!
+//! Snapshots of applied change sets are only kept for the garbage collection retention window
+//! (see [`crate::garbage_collection`]), so only that window can be restored.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_events::WorkspaceSnapshotAddress;
+use telemetry::prelude::*;
+
+use super::{ChangeSet, ChangeSetError, ChangeSetId, ChangeSetResult};
+use crate::{
+    workspace_snapshot::graph::WorkspaceSnapshotGraphDiscriminants, ChangeSetStatus, DalContext,
+    WorkspaceSnapshotGraph, WsEvent,
+};
+
+const FIND_LAST_APPLIED_TO_BEFORE_QUERY: &str = "
+    SELECT * FROM change_set_pointers
+    WHERE workspace_id = $1
+        AND base_change_set_id = $2
+        AND status = $3
+        AND updated_at <= $4
+    ORDER BY updated_at DESC
+    LIMIT 1
+";
+
+/// Identifies the historical state of HEAD to restore.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum RestorePoint {
+    /// HEAD as it was right after the given change set was applied.
+    ChangeSet { change_set_id: ChangeSetId },
+    /// HEAD as it was at the given time, i.e. after the last change set applied to HEAD at or
+    /// before it.
+    Timestamp { as_of: DateTime<Utc> },
+}
+
+impl ChangeSet {
+    /// Returns the applied [`ChangeSet`] whose snapshot is HEAD as of the given [`RestorePoint`].
+    pub async fn find_restore_point(
+        ctx: &DalContext,
+        restore_point: RestorePoint,
+    ) -> ChangeSetResult<Self> {
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk_opt()
+            .ok_or(ChangeSetError::NoTenancySet)?;
+        let head_change_set_id = ctx.get_workspace_default_change_set_id().await?;
+
+        let applied_change_set = match restore_point {
+            RestorePoint::ChangeSet { change_set_id } => {
+                let change_set = Self::find(ctx, change_set_id)
+                    .await?
+                    .ok_or(ChangeSetError::ChangeSetNotFound(change_set_id))?;
+                if change_set.status != ChangeSetStatus::Applied
+                    || change_set.base_change_set_id != Some(head_change_set_id)
+                {
+                    return Err(ChangeSetError::InvalidRestorePoint(restore_point));
+                }
+                change_set
+            }
+            RestorePoint::Timestamp { as_of } => {
+                let row = ctx
+                    .txns()
+                    .await?
+                    .pg()
+                    .query_opt(
+                        FIND_LAST_APPLIED_TO_BEFORE_QUERY,
+                        &[
+                            &workspace_pk,
+                            &head_change_set_id,
+                            &ChangeSetStatus::Applied.to_string(),
+                            &as_of,
+                        ],
+                    )
+                    .await?
+                    .ok_or(ChangeSetError::InvalidRestorePoint(restore_point))?;
+                Self::try_from(row)?
+            }
+        };
+
+        Ok(applied_change_set)
+    }
+
+    /// Creates a new [`ChangeSet`], based on HEAD, whose graph is HEAD as of the given
+    /// [`RestorePoint`]. Applying it reverts HEAD to that state. Without a name, the new change
+    /// set is named after the restored one.
+    #[instrument(name = "change_set.fork_head_as_of", level = "info", skip(ctx, name))]
+    pub async fn fork_head_as_of(
+        ctx: &DalContext,
+        name: Option<String>,
+        restore_point: RestorePoint,
+    ) -> ChangeSetResult<Self> {
+        let applied_change_set = Self::find_restore_point(ctx, restore_point).await?;
+        let applied_base_snapshot_address =
+            applied_change_set.applied_base_snapshot_address.ok_or(
+                ChangeSetError::RestoreSnapshotNotRecorded(applied_change_set.id),
+            )?;
+        let name = name.unwrap_or_else(|| format!("Restore to {}", applied_change_set.name));
+
+        Self::fork_head_from_snapshot(ctx, name, applied_base_snapshot_address).await
+    }
+
+    /// Creates a new [`ChangeSet`], based on HEAD, whose graph is the snapshot at the given
+    /// address. Applying it turns HEAD into that snapshot.
+    ///
+    /// The snapshot may be old, so it has to still exist and be on the current graph version.
+    #[instrument(
+        name = "change_set.fork_head_from_snapshot",
+        level = "info",
+        skip(ctx, name)
+    )]
+    pub async fn fork_head_from_snapshot(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        workspace_snapshot_address: WorkspaceSnapshotAddress,
+    ) -> ChangeSetResult<Self> {
+        let graph = ctx
+            .layer_db()
+            .workspace_snapshot()
+            .read(&workspace_snapshot_address)
+            .await?
+            .ok_or(ChangeSetError::RestoreSnapshotMissing(
+                workspace_snapshot_address,
+            ))?;
+        let version = WorkspaceSnapshotGraphDiscriminants::from(graph.as_ref());
+        if version != WorkspaceSnapshotGraph::current_discriminant() {
+            return Err(ChangeSetError::RestoreSnapshotNeedsMigration(
+                workspace_snapshot_address,
+                version,
+            ));
+        }
+
+        let head_change_set_id = ctx.get_workspace_default_change_set_id().await?;
+
+        let change_set = Self::new(
+            ctx,
+            name,
+            Some(head_change_set_id),
+            workspace_snapshot_address,
+        )
+        .await?;
+
+        WsEvent::change_set_created(ctx, change_set.id)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(change_set)
+    }
+}
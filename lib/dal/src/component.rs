@@ -69,9 +69,12 @@ use crate::{
     WsEventError, WsEventResult, WsPayload,
 };
 
+pub mod bulk_update;
 pub mod code;
 pub mod debug;
+pub mod delete;
 pub mod diff;
+pub mod drift;
 pub mod frame;
 pub mod inferred_connection_graph;
 pub mod properties;
@@ -0,0 +1,418 @@
+//! Apply the same attribute changes to many [`Components`](Component) at once.
+//!
+//! Each [`AttributePatch`] addresses a value by a JSON pointer from the root prop, e.g.
+//! `/root/domain/region` or `/root/domain/tags/Name`. Map entries are addressed by key and
+//! created if missing, array elements are addressed by index and `-` appends a new element, as
+//! in RFC 6902.
+//!
+//! Every target is resolved and every new value validated before anything is written, so a
+//! patch that fails for one component is applied to none of them. Since
+//! [`AttributeValue::update`] only enqueues one dependent values update per change set, the
+//! whole batch is processed by a single DVU when the context is committed.
+
+use std::collections::HashMap;
+
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use telemetry::prelude::*;
+
+use crate::attribute::value::AttributeValueError;
+use crate::component::ComponentResult;
+use crate::validation::{ValidationOutput, ValidationStatus};
+use crate::{
+    AttributeValue, AttributeValueId, Component, ComponentId, DalContext, Prop, PropKind,
+    SchemaVariant, SchemaVariantId,
+};
+
+const ROOT_SEGMENT: &str = "root";
+const APPEND_SEGMENT: &str = "-";
+const SET_BY_FUNCTION: &str = "value is set by a function and cannot be edited";
+const MAX_CONCURRENT_VALIDATIONS: usize = 16;
+
+/// The [`Components`](Component) a bulk update applies to.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum BulkUpdateTargets {
+    /// An explicit list of components.
+    #[serde(rename_all = "camelCase")]
+    Components { component_ids: Vec<ComponentId> },
+    /// Every component of the schema variant.
+    #[serde(rename_all = "camelCase")]
+    SchemaVariant { schema_variant_id: SchemaVariantId },
+}
+
+/// Sets, or unsets when `value` is `None`, the value at a JSON pointer.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributePatch {
+    pub path: String,
+    pub value: Option<Value>,
+}
+
+/// Why a patch could not be applied to a component.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateFailure {
+    pub component_id: ComponentId,
+    pub path: String,
+    pub message: String,
+}
+
+/// A value written by a bulk update.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateChange {
+    pub component_id: ComponentId,
+    pub attribute_value_id: AttributeValueId,
+    pub path: String,
+    pub before_value: Option<Value>,
+    pub after_value: Option<Value>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateReport {
+    pub changes: Vec<BulkUpdateChange>,
+    pub failures: Vec<BulkUpdateFailure>,
+}
+
+impl BulkUpdateReport {
+    /// Whether the patches were applied. Nothing is applied if anything failed.
+    pub fn applied(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The components that had at least one value written, in the order they were updated.
+    pub fn component_ids(&self) -> Vec<ComponentId> {
+        let mut component_ids: Vec<ComponentId> = Vec::new();
+        for change in &self.changes {
+            if !component_ids.contains(&change.component_id) {
+                component_ids.push(change.component_id);
+            }
+        }
+        component_ids
+    }
+}
+
+/// Where a patch lands for one component.
+#[derive(Clone, Debug)]
+enum ResolvedTarget {
+    /// An existing value, overwritten in place.
+    Existing(AttributeValueId),
+    /// A new element of a map (with a key) or an array (without one).
+    Insert {
+        parent_attribute_value_id: AttributeValueId,
+        key: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug)]
+struct ResolvedPatch {
+    component_id: ComponentId,
+    path: String,
+    target: ResolvedTarget,
+    value: Option<Value>,
+}
+
+impl Component {
+    /// Applies every patch to every targeted [`Component`]. If any patch can't be resolved or
+    /// its value fails validation for any component, nothing is written and the failures are
+    /// returned in the report.
+    #[instrument(
+        name = "component.bulk_update_attributes",
+        level = "info",
+        skip(ctx, patches),
+        fields(patch_count = patches.len())
+    )]
+    pub async fn bulk_update_attributes(
+        ctx: &DalContext,
+        targets: BulkUpdateTargets,
+        patches: Vec<AttributePatch>,
+    ) -> ComponentResult<BulkUpdateReport> {
+        let component_ids = match targets {
+            BulkUpdateTargets::Components { component_ids } => component_ids,
+            BulkUpdateTargets::SchemaVariant { schema_variant_id } => {
+                SchemaVariant::list_component_ids(ctx, schema_variant_id).await?
+            }
+        };
+
+        let mut report = BulkUpdateReport::default();
+        let mut resolved_patches = Vec::new();
+
+        for &component_id in &component_ids {
+            for patch in &patches {
+                match resolve_patch(ctx, component_id, patch).await? {
+                    Ok(resolved) => resolved_patches.push(resolved),
+                    Err(message) => report.failures.push(BulkUpdateFailure {
+                        component_id,
+                        path: patch.path.clone(),
+                        message,
+                    }),
+                }
+            }
+        }
+
+        for (index, message) in validate(ctx, &resolved_patches).await? {
+            let resolved = &resolved_patches[index];
+            report.failures.push(BulkUpdateFailure {
+                component_id: resolved.component_id,
+                path: resolved.path.clone(),
+                message,
+            });
+        }
+
+        if !report.failures.is_empty() {
+            return Ok(report);
+        }
+
+        for resolved in resolved_patches {
+            let (attribute_value_id, before_value) = match resolved.target {
+                ResolvedTarget::Existing(attribute_value_id) => {
+                    let before_value = AttributeValue::get_by_id(ctx, attribute_value_id)
+                        .await?
+                        .value(ctx)
+                        .await?;
+                    AttributeValue::update(ctx, attribute_value_id, resolved.value.clone()).await?;
+                    (attribute_value_id, before_value)
+                }
+                ResolvedTarget::Insert {
+                    parent_attribute_value_id,
+                    key,
+                } => {
+                    let attribute_value_id = AttributeValue::insert(
+                        ctx,
+                        parent_attribute_value_id,
+                        resolved.value.clone(),
+                        key,
+                    )
+                    .await?;
+                    (attribute_value_id, None)
+                }
+            };
+
+            report.changes.push(BulkUpdateChange {
+                component_id: resolved.component_id,
+                attribute_value_id,
+                path: resolved.path,
+                before_value,
+                after_value: resolved.value,
+            });
+        }
+
+        info!(
+            components = component_ids.len(),
+            changes = report.changes.len(),
+            "applied bulk attribute update"
+        );
+
+        Ok(report)
+    }
+}
+
+/// Walks the path from the component's root value. The inner error is a user facing reason the
+/// path doesn't resolve for this component.
+async fn resolve_patch(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    patch: &AttributePatch,
+) -> ComponentResult<Result<ResolvedPatch, String>> {
+    let segments: Vec<String> = match patch.path.strip_prefix('/') {
+        Some(path) => path.split('/').map(unescape_segment).collect(),
+        None => return Ok(Err("path must be a JSON pointer".to_owned())),
+    };
+    let Some((first, rest)) = segments.split_first() else {
+        return Ok(Err("path must not be empty".to_owned()));
+    };
+    if first != ROOT_SEGMENT || rest.is_empty() {
+        return Ok(Err("path must address a value below /root".to_owned()));
+    }
+
+    let mut attribute_value_id = Component::root_attribute_value_id(ctx, component_id).await?;
+    for (index, segment) in rest.iter().enumerate() {
+        // Values below one set by a function are controlled by it as well.
+        if AttributeValue::is_set_by_dependent_function(ctx, attribute_value_id).await? {
+            return Ok(Err(SET_BY_FUNCTION.to_owned()));
+        }
+
+        let is_last = index == rest.len() - 1;
+        let prop = AttributeValue::prop(ctx, attribute_value_id).await?;
+
+        let child = match prop.kind {
+            PropKind::Object => {
+                let mut found = None;
+                for child_id in
+                    AttributeValue::get_child_av_ids_in_order(ctx, attribute_value_id).await?
+                {
+                    if AttributeValue::prop(ctx, child_id).await?.name == *segment {
+                        found = Some(child_id);
+                        break;
+                    }
+                }
+                found
+            }
+            PropKind::Map => {
+                let child = AttributeValue::map_children(ctx, attribute_value_id)
+                    .await?
+                    .get(segment)
+                    .copied();
+                if child.is_none() && is_last {
+                    return Ok(Ok(ResolvedPatch {
+                        component_id,
+                        path: patch.path.clone(),
+                        target: ResolvedTarget::Insert {
+                            parent_attribute_value_id: attribute_value_id,
+                            key: Some(segment.clone()),
+                        },
+                        value: patch.value.clone(),
+                    }));
+                }
+                child
+            }
+            PropKind::Array => {
+                if segment == APPEND_SEGMENT && is_last {
+                    return Ok(Ok(ResolvedPatch {
+                        component_id,
+                        path: patch.path.clone(),
+                        target: ResolvedTarget::Insert {
+                            parent_attribute_value_id: attribute_value_id,
+                            key: None,
+                        },
+                        value: patch.value.clone(),
+                    }));
+                }
+                match segment.parse::<usize>() {
+                    Ok(element_index) => {
+                        AttributeValue::get_child_av_ids_in_order(ctx, attribute_value_id)
+                            .await?
+                            .get(element_index)
+                            .copied()
+                    }
+                    Err(_) => None,
+                }
+            }
+            _ => None,
+        };
+
+        match child {
+            Some(child_id) => attribute_value_id = child_id,
+            None => return Ok(Err(format!("no value found at {}", patch.path))),
+        }
+    }
+
+    if AttributeValue::is_set_by_dependent_function(ctx, attribute_value_id).await? {
+        return Ok(Err(SET_BY_FUNCTION.to_owned()));
+    }
+
+    Ok(Ok(ResolvedPatch {
+        component_id,
+        path: patch.path.clone(),
+        target: ResolvedTarget::Existing(attribute_value_id),
+        value: patch.value.clone(),
+    }))
+}
+
+/// A distinct format and value to validate, and the patches that share it.
+struct PendingValidation {
+    attribute_value_id: AttributeValueId,
+    validation_format: String,
+    value: Option<Value>,
+    patch_indices: Vec<usize>,
+}
+
+/// Runs the validation of each targeted prop against its new value, without writing anything.
+/// Patches with the same format and value share one run, and distinct runs go out concurrently.
+/// Returns the index of each invalid patch with the reason, in patch order.
+async fn validate(
+    ctx: &DalContext,
+    resolved_patches: &[ResolvedPatch],
+) -> ComponentResult<Vec<(usize, String)>> {
+    let mut pending: HashMap<(String, String), PendingValidation> = HashMap::new();
+    for (index, resolved) in resolved_patches.iter().enumerate() {
+        let Some((attribute_value_id, validation_format)) =
+            validation_format(ctx, &resolved.target).await?
+        else {
+            continue;
+        };
+        let key = (
+            validation_format.clone(),
+            serde_json::to_string(&resolved.value)?,
+        );
+        pending
+            .entry(key)
+            .or_insert_with(|| PendingValidation {
+                attribute_value_id,
+                validation_format,
+                value: resolved.value.clone(),
+                patch_indices: Vec::new(),
+            })
+            .patch_indices
+            .push(index);
+    }
+
+    let outputs: Vec<_> = stream::iter(pending.into_values())
+        .map(|pending| async move {
+            let output = ValidationOutput::compute_for_format(
+                ctx,
+                pending.attribute_value_id,
+                pending.value,
+                pending.validation_format,
+            )
+            .await;
+            (pending.patch_indices, output)
+        })
+        .buffer_unordered(MAX_CONCURRENT_VALIDATIONS)
+        .collect()
+        .await;
+
+    let mut failures = Vec::new();
+    for (patch_indices, output) in outputs {
+        let output = output.map_err(AttributeValueError::from)?;
+        let message = match output.status {
+            ValidationStatus::Error | ValidationStatus::Failure => output
+                .message
+                .unwrap_or_else(|| "value failed validation".to_owned()),
+            ValidationStatus::Pending | ValidationStatus::Success => continue,
+        };
+        failures.extend(
+            patch_indices
+                .into_iter()
+                .map(|index| (index, message.clone())),
+        );
+    }
+    failures.sort_by_key(|(index, _)| *index);
+
+    Ok(failures)
+}
+
+/// The validation format of the prop a patch targets, with the [`AttributeValue`] to attribute
+/// the run to.
+async fn validation_format(
+    ctx: &DalContext,
+    target: &ResolvedTarget,
+) -> ComponentResult<Option<(AttributeValueId, String)>> {
+    Ok(match target {
+        ResolvedTarget::Existing(attribute_value_id) => {
+            ValidationOutput::get_format_for_attribute_value_id(ctx, *attribute_value_id)
+                .await
+                .map_err(AttributeValueError::from)?
+                .map(|validation_format| (*attribute_value_id, validation_format))
+        }
+        ResolvedTarget::Insert {
+            parent_attribute_value_id,
+            ..
+        } => {
+            let element_prop_id =
+                AttributeValue::element_prop_id_for_id(ctx, *parent_attribute_value_id).await?;
+            Prop::get_by_id(ctx, element_prop_id)
+                .await?
+                .validation_format
+                .map(|validation_format| (*parent_attribute_value_id, validation_format))
+        }
+    })
+}
+
+/// Undoes JSON pointer escaping (RFC 6901).
+fn unescape_segment(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
@@ -0,0 +1,148 @@
+//! Delete or erase [`Components`](Component) the way the diagram does.
+//!
+//! Deleting a component that has a resource only marks it for deletion, while erasing always
+//! removes it from the graph. Either way, an audit log is written and the [`WsEvents`](WsEvent)
+//! the frontend needs to redraw the component and its connections are published on commit.
+
+use std::collections::HashMap;
+
+use si_events::audit_log::AuditLogKind;
+
+use crate::change_status::ChangeStatus;
+use crate::component::ComponentResult;
+use crate::diagram::SummaryDiagramEdge;
+use crate::{Component, ComponentId, DalContext, WsEvent};
+
+/// Deletes (or, if `force_erase` is set, erases) the given [`Components`](Component) and returns
+/// whether each of them still exists, i.e. was only marked for deletion.
+///
+/// Components that are already gone (e.g. removed along with an earlier component in the list)
+/// are skipped.
+pub async fn delete_components(
+    ctx: &DalContext,
+    component_ids: &[ComponentId],
+    force_erase: bool,
+) -> ComponentResult<HashMap<ComponentId, bool>> {
+    let mut components = HashMap::new();
+    if component_ids.is_empty() {
+        return Ok(components);
+    }
+
+    let components_existing_on_head =
+        Component::exists_on_head(ctx, component_ids.to_vec()).await?;
+    let base_change_set_ctx = if components_existing_on_head.is_empty() {
+        None
+    } else {
+        Some(ctx.clone_with_base().await?)
+    };
+
+    let mut socket_map = HashMap::new();
+    for &component_id in component_ids {
+        let Some(component) = Component::try_get_by_id(ctx, component_id).await? else {
+            continue;
+        };
+        let component_name = component.name(ctx).await?;
+        let component_schema_variant = component.schema_variant(ctx).await?;
+
+        // Capture the connections before the component goes away, so they can be redrawn as
+        // deleted.
+        let mut deleted_edges = vec![];
+        for incoming in component.incoming_connections(ctx).await? {
+            deleted_edges.push(SummaryDiagramEdge {
+                from_component_id: incoming.from_component_id,
+                from_socket_id: incoming.from_output_socket_id,
+                to_component_id: incoming.to_component_id,
+                to_socket_id: incoming.to_input_socket_id,
+                change_status: ChangeStatus::Deleted,
+                created_info: serde_json::to_value(incoming.created_info)?,
+                deleted_info: serde_json::to_value(incoming.deleted_info)?,
+                to_delete: true,
+                from_base_change_set: false,
+            });
+        }
+        for outgoing in component.outgoing_connections(ctx).await? {
+            deleted_edges.push(SummaryDiagramEdge {
+                from_component_id: outgoing.from_component_id,
+                from_socket_id: outgoing.from_output_socket_id,
+                to_component_id: outgoing.to_component_id,
+                to_socket_id: outgoing.to_input_socket_id,
+                change_status: ChangeStatus::Deleted,
+                created_info: serde_json::to_value(outgoing.created_info)?,
+                deleted_info: serde_json::to_value(outgoing.deleted_info)?,
+                to_delete: true,
+                from_base_change_set: false,
+            });
+        }
+
+        let component_still_exists = if force_erase {
+            Component::remove(ctx, component_id).await?;
+            false
+        } else {
+            component.delete(ctx).await?.is_some()
+        };
+        ctx.workspace_snapshot()?.cleanup().await?;
+
+        ctx.write_audit_log(
+            AuditLogKind::DeleteComponent {
+                component_id: component_id.into(),
+                name: component_name.to_owned(),
+                schema_variant_id: component_schema_variant.id().into(),
+                schema_variant_name: component_schema_variant.display_name().to_string(),
+            },
+            component_name,
+        )
+        .await?;
+
+        components.insert(component_id, component_still_exists);
+
+        let deleted_payload = match &base_change_set_ctx {
+            // to_delete=True
+            _ if component_still_exists => Some(
+                Component::get_by_id(ctx, component_id)
+                    .await?
+                    .into_frontend_type_for_default_view(
+                        ctx,
+                        ChangeStatus::Deleted,
+                        &mut socket_map,
+                    )
+                    .await?,
+            ),
+            Some(base_change_set_ctx) if components_existing_on_head.contains(&component_id) => {
+                Some(
+                    Component::get_by_id(base_change_set_ctx, component_id)
+                        .await?
+                        .into_frontend_type_for_default_view(
+                            base_change_set_ctx,
+                            ChangeStatus::Deleted,
+                            &mut socket_map,
+                        )
+                        .await?,
+                )
+            }
+            _ => None,
+        };
+        match deleted_payload {
+            Some(payload) => {
+                WsEvent::component_updated(ctx, payload)
+                    .await?
+                    .publish_on_commit(ctx)
+                    .await?;
+            }
+            None => {
+                WsEvent::component_deleted(ctx, component_id)
+                    .await?
+                    .publish_on_commit(ctx)
+                    .await?;
+            }
+        }
+
+        for edge in deleted_edges {
+            WsEvent::connection_upserted(ctx, edge.into())
+                .await?
+                .publish_on_commit(ctx)
+                .await?;
+        }
+    }
+
+    Ok(components)
+}
@@ -0,0 +1,420 @@
+//! Drift detection between the modeled `/root/domain` of a [`Component`] and its resource.
+//!
+//! After an action or refresh, `/root/resource_value` holds the resource payload mapped back onto
+//! the schema by the variant's `resource_payload_to_value` function. Only the props that map to
+//! each other are compared: those at the same path under both `/root/domain` and
+//! `/root/resource_value`, with the same shape. A domain prop has drifted when the resource
+//! reports a different value for it. Props the resource says nothing about are not considered
+//! drifted, as resources rarely echo every input back.
+//!
+//! Variants without a `resource_payload_to_value` function leave `/root/resource_value` empty,
+//! in which case the raw resource payload is compared against the domain's own props instead.
+
+use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+
+use async_recursion::async_recursion;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::Display;
+use telemetry::prelude::*;
+
+use crate::component::ComponentResult;
+use crate::prop::{PropPath, PropResult};
+use crate::{
+    AttributeValue, Component, ComponentId, DalContext, Prop, PropId, PropKind, SchemaVariantId,
+    WsEvent, WsEventResult, WsPayload,
+};
+
+const DOMAIN_PATH: &str = "/root/domain";
+const DOMAIN_PROP_PATH: [&str; 2] = ["root", "domain"];
+const RESOURCE_VALUE_PROP_PATH: [&str; 2] = ["root", "resource_value"];
+
+/// The drift status of a [`Component`], analogous to a qualification status.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Deserialize, Display, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DriftStatus {
+    /// At least one domain prop disagrees with the resource.
+    Drifted,
+    /// Every domain prop the resource reports on agrees with it.
+    InSync,
+    /// The component has no resource to compare against.
+    Unknown,
+}
+
+/// A domain prop whose modeled value disagrees with the resource.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PropDrift {
+    /// The path of the prop, e.g. `/root/domain/region`.
+    pub path: String,
+    pub modeled: Value,
+    pub actual: Value,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDrift {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub status: DriftStatus,
+    pub drifted_props: Vec<PropDrift>,
+    /// When the resource was last synced, if there is one.
+    pub last_synced: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftSummary {
+    pub total: i64,
+    pub in_sync: i64,
+    pub drifted: i64,
+    pub unknown: i64,
+    pub components: Vec<ComponentDrift>,
+}
+
+impl Component {
+    /// Compares the [`Component`]'s domain against its resource.
+    #[instrument(name = "component.drift", level = "debug", skip(ctx))]
+    pub async fn drift(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<ComponentDrift> {
+        Self::drift_with_mappings(ctx, component_id, &mut DriftMappings::default()).await
+    }
+
+    async fn drift_with_mappings(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        mappings: &mut DriftMappings,
+    ) -> ComponentResult<ComponentDrift> {
+        if let Some(drift) = Self::resource_drift_with_mappings(ctx, component_id, mappings).await?
+        {
+            return Ok(drift);
+        }
+
+        Ok(ComponentDrift {
+            component_id,
+            component_name: Self::name_by_id(ctx, component_id).await?,
+            status: DriftStatus::Unknown,
+            drifted_props: Vec::new(),
+            last_synced: None,
+        })
+    }
+
+    /// Compares the [`Component`]'s domain against its resource, or returns `None` without
+    /// looking at the domain if the component has no resource.
+    #[instrument(name = "component.resource_drift", level = "debug", skip(ctx))]
+    pub async fn resource_drift(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Option<ComponentDrift>> {
+        Self::resource_drift_with_mappings(ctx, component_id, &mut DriftMappings::default()).await
+    }
+
+    async fn resource_drift_with_mappings(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        mappings: &mut DriftMappings,
+    ) -> ComponentResult<Option<ComponentDrift>> {
+        let Some(resource) = Self::resource_by_id(ctx, component_id).await? else {
+            return Ok(None);
+        };
+        let component_name = Self::name_by_id(ctx, component_id).await?;
+
+        let schema_variant_id = Self::schema_variant_id(ctx, component_id).await?;
+        let mappings = mappings.for_variant(ctx, schema_variant_id).await?;
+        let domain = prop_view(ctx, component_id, mappings.domain_prop_id).await?;
+
+        let resource_value = match mappings.resource_value_prop_id {
+            Some(resource_value_prop_id) => {
+                prop_view(ctx, component_id, resource_value_prop_id).await?
+            }
+            None => Value::Null,
+        };
+        let (actual, mapped) = if is_empty(&resource_value) {
+            (
+                resource.payload.clone().unwrap_or(Value::Null),
+                &mappings.onto_domain,
+            )
+        } else {
+            (resource_value, &mappings.onto_resource_value)
+        };
+
+        let mut drifted_props = Vec::new();
+        if let Some(mapped) = mapped {
+            mapped.collect_drift(DOMAIN_PATH.to_owned(), &domain, &actual, &mut drifted_props);
+        }
+
+        Ok(Some(ComponentDrift {
+            component_id,
+            component_name,
+            status: if drifted_props.is_empty() {
+                DriftStatus::InSync
+            } else {
+                DriftStatus::Drifted
+            },
+            drifted_props,
+            last_synced: Some(resource.last_synced),
+        }))
+    }
+}
+
+impl DriftSummary {
+    /// Computes the drift of every [`Component`] in the workspace snapshot of the context.
+    #[instrument(name = "component.drift.get_summary", level = "info", skip(ctx))]
+    pub async fn get_summary(ctx: &DalContext) -> ComponentResult<Self> {
+        let mut summary = Self {
+            total: 0,
+            in_sync: 0,
+            drifted: 0,
+            unknown: 0,
+            components: Vec::new(),
+        };
+
+        let mut mappings = DriftMappings::default();
+        for component_id in Component::list_ids(ctx).await? {
+            let drift = Component::drift_with_mappings(ctx, component_id, &mut mappings).await?;
+            match drift.status {
+                DriftStatus::Drifted => summary.drifted += 1,
+                DriftStatus::InSync => summary.in_sync += 1,
+                DriftStatus::Unknown => summary.unknown += 1,
+            }
+            summary.total += 1;
+            summary.components.push(drift);
+        }
+
+        Ok(summary)
+    }
+}
+
+impl WsEvent {
+    pub async fn component_drift_updated(
+        ctx: &DalContext,
+        drift: ComponentDrift,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::ComponentDriftUpdated(drift)).await
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+/// Resources commonly report numbers and booleans as strings (and vice versa), which isn't
+/// drift.
+fn is_equivalent(modeled: &Value, actual: &Value) -> bool {
+    match (modeled, actual) {
+        (Value::String(modeled), Value::Number(_) | Value::Bool(_)) => {
+            *modeled == actual.to_string()
+        }
+        (Value::Number(_) | Value::Bool(_), Value::String(actual)) => {
+            modeled.to_string() == *actual
+        }
+        _ => modeled == actual,
+    }
+}
+
+async fn prop_view(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    prop_id: PropId,
+) -> ComponentResult<Value> {
+    let value_id = Component::attribute_value_for_prop_id(ctx, component_id, prop_id).await?;
+    Ok(AttributeValue::get_by_id(ctx, value_id)
+        .await?
+        .view(ctx)
+        .await?
+        .unwrap_or(Value::Null))
+}
+
+/// The prop mappings of the schema variants seen so far, so that a drift pass builds them once
+/// per variant rather than once per [`Component`].
+#[derive(Debug, Default)]
+struct DriftMappings(HashMap<SchemaVariantId, VariantMappings>);
+
+impl DriftMappings {
+    async fn for_variant(
+        &mut self,
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+    ) -> PropResult<&VariantMappings> {
+        Ok(match self.0.entry(schema_variant_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                entry.insert(VariantMappings::new(ctx, schema_variant_id).await?)
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+struct VariantMappings {
+    domain_prop_id: PropId,
+    resource_value_prop_id: Option<PropId>,
+    /// The domain mapped onto `/root/resource_value`.
+    onto_resource_value: Option<MappedProp>,
+    /// The domain mapped onto itself, to compare against the raw resource payload.
+    onto_domain: Option<MappedProp>,
+}
+
+impl VariantMappings {
+    async fn new(ctx: &DalContext, schema_variant_id: SchemaVariantId) -> PropResult<Self> {
+        let domain_prop_id =
+            Prop::find_prop_id_by_path(ctx, schema_variant_id, &PropPath::new(DOMAIN_PROP_PATH))
+                .await?;
+        let resource_value_prop_id = Prop::find_prop_id_by_path_opt(
+            ctx,
+            schema_variant_id,
+            &PropPath::new(RESOURCE_VALUE_PROP_PATH),
+        )
+        .await?;
+        let onto_resource_value = match resource_value_prop_id {
+            Some(resource_value_prop_id) => {
+                MappedProp::new(ctx, domain_prop_id, resource_value_prop_id).await?
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            domain_prop_id,
+            resource_value_prop_id,
+            onto_resource_value,
+            onto_domain: MappedProp::new(ctx, domain_prop_id, domain_prop_id).await?,
+        })
+    }
+}
+
+/// The part of a domain prop's tree that maps onto the prop being compared against it.
+#[derive(Debug)]
+enum MappedProp {
+    Leaf,
+    Object(BTreeMap<String, MappedProp>),
+    /// An array or map, whose elements map onto each other.
+    Collection(Box<MappedProp>),
+}
+
+impl MappedProp {
+    /// Maps the domain prop onto the other prop, or returns `None` if none of their props map
+    /// to each other.
+    #[async_recursion]
+    async fn new(
+        ctx: &DalContext,
+        domain_prop_id: PropId,
+        other_prop_id: PropId,
+    ) -> PropResult<Option<Self>> {
+        let domain_kind = Prop::get_by_id(ctx, domain_prop_id).await?.kind;
+        let other_kind = Prop::get_by_id(ctx, other_prop_id).await?.kind;
+
+        Ok(match (domain_kind, other_kind) {
+            (PropKind::Object, PropKind::Object) => {
+                let other_children: HashMap<String, PropId> =
+                    Prop::direct_child_props_ordered(ctx, other_prop_id)
+                        .await?
+                        .into_iter()
+                        .map(|prop| (prop.name, prop.id))
+                        .collect();
+
+                let mut children = BTreeMap::new();
+                for child in Prop::direct_child_props_ordered(ctx, domain_prop_id).await? {
+                    let Some(other_child_id) = other_children.get(&child.name) else {
+                        continue;
+                    };
+                    if let Some(mapped) = Self::new(ctx, child.id, *other_child_id).await? {
+                        children.insert(child.name, mapped);
+                    }
+                }
+                (!children.is_empty()).then_some(Self::Object(children))
+            }
+            (PropKind::Array, PropKind::Array) | (PropKind::Map, PropKind::Map) => {
+                let element_prop_id = Prop::direct_child_prop_ids_ordered(ctx, domain_prop_id)
+                    .await?
+                    .first()
+                    .copied();
+                let other_element_prop_id = Prop::direct_child_prop_ids_ordered(ctx, other_prop_id)
+                    .await?
+                    .first()
+                    .copied();
+                match (element_prop_id, other_element_prop_id) {
+                    (Some(element_prop_id), Some(other_element_prop_id)) => {
+                        Self::new(ctx, element_prop_id, other_element_prop_id)
+                            .await?
+                            .map(|mapped| Self::Collection(Box::new(mapped)))
+                    }
+                    _ => None,
+                }
+            }
+            (PropKind::Array | PropKind::Map | PropKind::Object, _)
+            | (_, PropKind::Array | PropKind::Map | PropKind::Object) => None,
+            _ => Some(Self::Leaf),
+        })
+    }
+
+    /// Collects the props whose modeled value disagrees with the actual one, skipping values
+    /// that aren't modeled, that the resource doesn't report, or whose shapes don't match.
+    fn collect_drift(
+        &self,
+        path: String,
+        modeled: &Value,
+        actual: &Value,
+        drifted_props: &mut Vec<PropDrift>,
+    ) {
+        if modeled.is_null() {
+            return;
+        }
+
+        match (self, modeled, actual) {
+            (Self::Leaf, modeled, actual) => {
+                let same_shape = modeled.is_object() == actual.is_object()
+                    && modeled.is_array() == actual.is_array();
+                if same_shape && !is_equivalent(modeled, actual) {
+                    drifted_props.push(PropDrift {
+                        path,
+                        modeled: modeled.clone(),
+                        actual: actual.clone(),
+                    });
+                }
+            }
+            (Self::Object(children), Value::Object(modeled), Value::Object(actual)) => {
+                for (name, child) in children {
+                    if let (Some(modeled), Some(actual)) = (modeled.get(name), actual.get(name)) {
+                        child.collect_drift(
+                            format!("{path}/{name}"),
+                            modeled,
+                            actual,
+                            drifted_props,
+                        );
+                    }
+                }
+            }
+            (Self::Collection(element), Value::Array(modeled), Value::Array(actual)) => {
+                for (index, (modeled, actual)) in modeled.iter().zip(actual).enumerate() {
+                    element.collect_drift(
+                        format!("{path}/{index}"),
+                        modeled,
+                        actual,
+                        drifted_props,
+                    );
+                }
+            }
+            (Self::Collection(element), Value::Object(modeled), Value::Object(actual)) => {
+                for (key, modeled) in modeled {
+                    if let Some(actual) = actual.get(key) {
+                        element.collect_drift(
+                            format!("{path}/{key}"),
+                            modeled,
+                            actual,
+                            drifted_props,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
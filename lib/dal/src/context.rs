@@ -40,7 +40,7 @@ use crate::{audit_logging, slow_rt, EncryptedSecret, Workspace, WorkspaceError};
 use crate::{
     change_set::{ChangeSet, ChangeSetId},
     job::{
-        definition::ActionJob,
+        definition::{ActionJob, FuncTestCasesJob},
         processor::{JobQueueProcessor, JobQueueProcessorError},
         producer::{BlockingJobError, BlockingJobResult, JobProducer},
         queue::JobQueue,
@@ -464,6 +464,29 @@ impl DalContext {
             .map_err(Into::into)
     }
 
+    /// Replays updates produced by `from_snapshot_address` of another change set, which becomes the
+    /// merge base of the change set once they have been performed.
+    pub async fn run_async_replay_from_change_set(
+        &self,
+        workspace_pk: WorkspacePk,
+        change_set_id: ChangeSetId,
+        updates_address: RebaseBatchAddress,
+        from_change_set_id: ChangeSetId,
+        from_snapshot_address: WorkspaceSnapshotAddress,
+    ) -> TransactionsResult<RequestId> {
+        self.rebaser()
+            .replay_updates_from_change_set(
+                workspace_pk.into(),
+                change_set_id.into(),
+                updates_address,
+                from_change_set_id.into(),
+                from_snapshot_address,
+                self.event_session_id,
+            )
+            .await
+            .map_err(Into::into)
+    }
+
     pub async fn run_rebase_from_change_set_with_reply(
         &self,
         workspace_pk: WorkspacePk,
@@ -811,6 +834,14 @@ impl DalContext {
         Ok(())
     }
 
+    pub async fn enqueue_func_test_cases(
+        &self,
+        job: Box<FuncTestCasesJob>,
+    ) -> TransactionsResult<()> {
+        self.txns().await?.job_queue.enqueue_job(job).await;
+        Ok(())
+    }
+
     /// Add the node ids to the workspace snapshot graph and enqueue a dependent values update.
     /// This update will only be run on commit if blocking_commit is used. If commit is used, the
     /// DVU debouncer will run the job. Note that the DVU debouncer might still pick up the job
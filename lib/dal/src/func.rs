@@ -18,7 +18,7 @@ use ulid::Ulid as CoreUlid;
 use crate::change_set::ChangeSetError;
 use crate::func::argument::FuncArgumentId;
 use crate::func::intrinsics::IntrinsicFunc;
-use crate::layer_db_types::{FuncContent, FuncContentV2};
+use crate::layer_db_types::{FuncContent, FuncContentV3};
 use crate::workspace_snapshot::edge_weight::{EdgeWeightKind, EdgeWeightKindDiscriminants};
 use crate::workspace_snapshot::graph::WorkspaceSnapshotGraphError;
 use crate::workspace_snapshot::node_weight::category_node_weight::CategoryNodeKind;
@@ -39,6 +39,8 @@ pub mod intrinsics;
 mod kind;
 pub mod resource_payload_to_value;
 pub mod runner;
+pub mod test_case;
+pub mod version;
 pub use kind::FuncKind;
 
 #[remain::sorted]
@@ -64,8 +66,12 @@ pub enum FuncError {
     FuncLocked(FuncId),
     #[error("func name already in use {0}")]
     FuncNameInUse(String),
+    #[error("func test case error: {0}")]
+    FuncTestCase(#[from] Box<test_case::FuncTestCaseError>),
     #[error("func to be deleted has bindings: {0}")]
     FuncToBeDeletedHasBindings(FuncId),
+    #[error("func version error: {0}")]
+    FuncVersion(#[from] Box<version::FuncVersionError>),
     #[error("helper error: {0}")]
     Helper(#[from] HelperError),
     #[error("cannot find intrinsic func {0}")]
@@ -96,7 +102,7 @@ pub type FuncResult<T> = Result<T, FuncError>;
 
 impl From<Func> for FuncContent {
     fn from(value: Func) -> Self {
-        Self::V2(FuncContentV2 {
+        Self::V3(FuncContentV3 {
             timestamp: value.timestamp,
             display_name: value.display_name,
             description: value.description,
@@ -109,6 +115,7 @@ impl From<Func> for FuncContent {
             code_base64: value.code_base64,
             code_blake3: value.code_blake3,
             is_locked: value.is_locked,
+            timeout_secs: value.timeout_secs,
         })
     }
 }
@@ -166,10 +173,12 @@ pub struct Func {
     pub code_base64: Option<String>,
     pub code_blake3: ContentHash,
     pub is_locked: bool,
+    /// Overrides the default execution timeout of the function executor, in seconds.
+    pub timeout_secs: Option<u64>,
 }
 
 impl Func {
-    pub fn assemble(node_weight: &FuncNodeWeight, content: FuncContentV2) -> Self {
+    pub fn assemble(node_weight: &FuncNodeWeight, content: FuncContentV3) -> Self {
         Self {
             id: node_weight.id().into(),
             name: node_weight.name().to_owned(),
@@ -187,6 +196,7 @@ impl Func {
             code_base64: content.code_base64,
             code_blake3: content.code_blake3,
             is_locked: content.is_locked,
+            timeout_secs: content.timeout_secs,
         }
     }
 
@@ -238,7 +248,7 @@ impl Func {
             ContentHash::new("".as_bytes())
         };
 
-        let content = FuncContentV2 {
+        let content = FuncContentV3 {
             timestamp,
             display_name: display_name.map(Into::into),
             description: description.map(Into::into),
@@ -251,10 +261,11 @@ impl Func {
             code_base64,
             code_blake3,
             is_locked: false,
+            timeout_secs: None,
         };
 
         let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(FuncContent::V2(content.clone()).into()),
+            Arc::new(FuncContent::V3(content.clone()).into()),
             None,
             ctx.events_tenancy(),
             ctx.events_actor(),
@@ -283,12 +294,22 @@ impl Func {
         Ok(Self::assemble(&func_node_weight, content))
     }
 
+    /// Locks the func, recording a new [`FuncVersion`](version::FuncVersion) in its history if
+    /// it is not builtin.
     pub async fn lock(self, ctx: &DalContext) -> FuncResult<Func> {
-        self.modify(ctx, |func| {
-            func.is_locked = true;
-            Ok(())
-        })
-        .await
+        let func = self
+            .modify(ctx, |func| {
+                func.is_locked = true;
+                Ok(())
+            })
+            .await?;
+        if !func.builtin {
+            version::FuncVersion::release_for_locked_func(ctx, &func)
+                .await
+                .map_err(Box::new)?;
+        }
+
+        Ok(func)
     }
 
     pub fn metadata_view(&self) -> FuncMetadataView {
@@ -352,7 +373,7 @@ impl Func {
         )?;
 
         // migrate if necessary!
-        let inner: FuncContentV2 = content.extract();
+        let inner: FuncContentV3 = content.extract();
 
         Ok(Self::assemble(func_node_weight, inner))
     }
@@ -409,6 +430,7 @@ impl Func {
             Some(intrinsic) => match intrinsic {
                 IntrinsicFunc::SetArray
                 | IntrinsicFunc::SetBoolean
+                | IntrinsicFunc::SetFloat
                 | IntrinsicFunc::SetInteger
                 | IntrinsicFunc::SetJson
                 | IntrinsicFunc::SetMap
@@ -671,7 +693,8 @@ impl Func {
     }
 
     /// Creates an exact clone of the current func that is not locked, including recreating all
-    /// [`FuncArgument`]s
+    /// [`FuncArgument`]s and [`FuncTestCases`](test_case::FuncTestCase), and carrying over its
+    /// [`FuncVersion`](version::FuncVersion) history
     pub async fn create_unlocked_func_copy(&self, ctx: &DalContext) -> FuncResult<Self> {
         let new_func = Self::new(
             ctx,
@@ -687,6 +710,7 @@ impl Func {
             self.code_base64.clone(),
         )
         .await?;
+        let new_func = new_func.copy_timeout_from(ctx, self).await?;
 
         for arg in FuncArgument::list_for_func(ctx, self.id)
             .await
@@ -700,6 +724,12 @@ impl Func {
         FuncArgument::list_for_func(ctx, new_func.id)
             .await
             .map_err(Box::new)?;
+        test_case::FuncTestCase::copy_all(ctx, self.id, new_func.id)
+            .await
+            .map_err(Box::new)?;
+        version::FuncVersion::copy_all(ctx, self.id, new_func.id)
+            .await
+            .map_err(Box::new)?;
         Ok(new_func)
     }
 
@@ -726,10 +756,24 @@ impl Func {
             self.code_base64.clone(),
         )
         .await?;
+        let duplicated_func = duplicated_func.copy_timeout_from(ctx, self).await?;
 
         Ok(duplicated_func)
     }
 
+    /// Carries the execution timeout of `source` over to a freshly created copy of it.
+    async fn copy_timeout_from(self, ctx: &DalContext, source: &Func) -> FuncResult<Self> {
+        if source.timeout_secs == self.timeout_secs {
+            return Ok(self);
+        }
+        let timeout_secs = source.timeout_secs;
+        self.modify(ctx, |func| {
+            func.timeout_secs = timeout_secs;
+            Ok(())
+        })
+        .await
+    }
+
     pub async fn into_frontend_type(&self, ctx: &DalContext) -> FuncResult<FuncSummary> {
         let bindings: Vec<FuncBinding> = FuncBinding::for_func_id(ctx, self.id)
             .await
@@ -770,6 +814,7 @@ impl Func {
             display_name: self.display_name.clone(),
             description: self.description.clone(),
             is_locked: self.is_locked,
+            timeout_secs: self.timeout_secs,
             bindings,
             arguments,
             types: Some(types),
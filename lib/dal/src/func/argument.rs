@@ -63,7 +63,8 @@ pub enum FuncArgumentError {
 
 type FuncArgumentResult<T> = Result<T, FuncArgumentError>;
 
-#[remain::sorted]
+// NOTE: do not add "remain::sorted" for postcard de/ser. We need the order to be retained, so new
+// variants must go at the end.
 #[derive(
     Deserialize,
     Serialize,
@@ -88,6 +89,7 @@ pub enum FuncArgumentKind {
     Map,
     Object,
     String,
+    Float,
 }
 
 impl From<PropKind> for FuncArgumentKind {
@@ -96,6 +98,7 @@ impl From<PropKind> for FuncArgumentKind {
             PropKind::Json => FuncArgumentKind::Json,
             PropKind::Array => FuncArgumentKind::Array,
             PropKind::Boolean => FuncArgumentKind::Boolean,
+            PropKind::Float => FuncArgumentKind::Float,
             PropKind::Integer => FuncArgumentKind::Integer,
             PropKind::Object => FuncArgumentKind::Object,
             PropKind::String => FuncArgumentKind::String,
@@ -111,6 +114,7 @@ impl From<PkgFuncArgumentKind> for FuncArgumentKind {
             PkgFuncArgumentKind::Json => FuncArgumentKind::Json,
             PkgFuncArgumentKind::Array => FuncArgumentKind::Array,
             PkgFuncArgumentKind::Boolean => FuncArgumentKind::Boolean,
+            PkgFuncArgumentKind::Float => FuncArgumentKind::Float,
             PkgFuncArgumentKind::Integer => FuncArgumentKind::Integer,
             PkgFuncArgumentKind::Map => FuncArgumentKind::Map,
             PkgFuncArgumentKind::Object => FuncArgumentKind::Object,
@@ -125,6 +129,7 @@ impl From<FuncArgumentKind> for PkgFuncArgumentKind {
             FuncArgumentKind::Any => PkgFuncArgumentKind::Any,
             FuncArgumentKind::Array => PkgFuncArgumentKind::Array,
             FuncArgumentKind::Boolean => PkgFuncArgumentKind::Boolean,
+            FuncArgumentKind::Float => PkgFuncArgumentKind::Float,
             FuncArgumentKind::Integer => PkgFuncArgumentKind::Integer,
             FuncArgumentKind::Map => PkgFuncArgumentKind::Map,
             FuncArgumentKind::Object => PkgFuncArgumentKind::Object,
@@ -140,6 +145,7 @@ impl From<si_frontend_types::FuncArgumentKind> for FuncArgumentKind {
             si_frontend_types::FuncArgumentKind::Any => FuncArgumentKind::Any,
             si_frontend_types::FuncArgumentKind::Array => FuncArgumentKind::Array,
             si_frontend_types::FuncArgumentKind::Boolean => FuncArgumentKind::Boolean,
+            si_frontend_types::FuncArgumentKind::Float => FuncArgumentKind::Float,
             si_frontend_types::FuncArgumentKind::Integer => FuncArgumentKind::Integer,
             si_frontend_types::FuncArgumentKind::Json => FuncArgumentKind::Json,
             si_frontend_types::FuncArgumentKind::Map => FuncArgumentKind::Map,
@@ -169,6 +175,7 @@ impl From<FuncArgumentKind> for si_frontend_types::FuncArgumentKind {
             FuncArgumentKind::Any => si_frontend_types::FuncArgumentKind::Any,
             FuncArgumentKind::Array => si_frontend_types::FuncArgumentKind::Array,
             FuncArgumentKind::Boolean => si_frontend_types::FuncArgumentKind::Boolean,
+            FuncArgumentKind::Float => si_frontend_types::FuncArgumentKind::Float,
             FuncArgumentKind::Integer => si_frontend_types::FuncArgumentKind::Integer,
             FuncArgumentKind::Json => si_frontend_types::FuncArgumentKind::Json,
             FuncArgumentKind::Map => si_frontend_types::FuncArgumentKind::Map,
@@ -42,6 +42,7 @@ use crate::attribute::prototype::argument::{
 use crate::attribute::prototype::AttributePrototypeError;
 use crate::attribute::value::AttributeValueError;
 use crate::func::argument::{FuncArgument, FuncArgumentError, FuncArgumentId, FuncArgumentKind};
+use crate::func::version::{FuncSemVer, FuncVersion, FuncVersionBump, FuncVersionError};
 use crate::func::FuncKind;
 use crate::prop::PropError;
 use crate::schema::variant::authoring::{VariantAuthoringClient, VariantAuthoringError};
@@ -98,8 +99,12 @@ pub enum FuncAuthoringError {
     FuncRunner(#[from] FuncRunnerError),
     #[error("func runner has failed to send a value and exited")]
     FuncRunnerSend,
+    #[error("func version error: {0}")]
+    FuncVersion(#[from] FuncVersionError),
     #[error("invalid func kind for creation: {0}")]
     InvalidFuncKindForCreation(FuncKind),
+    #[error("invalid func timeout ({0}s): must be between 1 and {MAX_FUNC_TIMEOUT_SECS} seconds")]
+    InvalidFuncTimeout(u64),
     #[error("layerdb error: {0}")]
     LayerDb(#[from] LayerDbError),
     #[error("no input location given for attribute prototype id ({0}) and func argument id ({1})")]
@@ -132,6 +137,9 @@ pub enum FuncAuthoringError {
 
 type FuncAuthoringResult<T> = Result<T, FuncAuthoringError>;
 
+/// The longest execution timeout that can be set on a [`Func`].
+pub const MAX_FUNC_TIMEOUT_SECS: u64 = 60 * 60;
+
 /// This unit struct is the primary interface for the [`Func`](crate::Func) authoring experience.
 #[derive(Debug)]
 pub struct FuncAuthoringClient;
@@ -257,7 +265,7 @@ impl FuncAuthoringClient {
 
         let is_intrinsic = func.is_intrinsic();
         let (func_run_id, result_channel) =
-            FuncRunner::run_test(ctx, func, args, component_id).await?;
+            FuncRunner::run_test(ctx, func, args, Some(component_id)).await?;
 
         let func_run_value = result_channel
             .await
@@ -554,6 +562,51 @@ impl FuncAuthoringClient {
         Ok(())
     }
 
+    /// Restores the code of an earlier [`FuncVersion`] of the given [`FuncId`]. If the [`Func`]
+    /// is locked, the code is restored into a new unlocked copy (see
+    /// [`Self::create_unlocked_func_copy`]), otherwise it replaces the current code. In both
+    /// cases, the draft for the next version records the restore.
+    #[instrument(
+        level = "info",
+        name = "func.authoring.restore_func_version",
+        skip(ctx)
+    )]
+    pub async fn restore_func_version(
+        ctx: &DalContext,
+        func_id: FuncId,
+        version: FuncSemVer,
+    ) -> FuncAuthoringResult<Func> {
+        let func_version = FuncVersion::get_for_func_or_error(ctx, func_id, version).await?;
+        let func = Func::get_by_id_or_error(ctx, func_id).await?;
+        let func = if func.is_locked {
+            Self::create_unlocked_func_copy(ctx, func_id, None).await?
+        } else {
+            func
+        };
+
+        let handler = func_version.handler.clone();
+        Func::modify_by_id(ctx, func.id, |func| {
+            func.handler.clone_from(&handler);
+            Ok(())
+        })
+        .await?;
+        Self::save_code(
+            ctx,
+            func.id,
+            func_version.code_plaintext()?.unwrap_or_default(),
+        )
+        .await?;
+        FuncVersion::set_draft(
+            ctx,
+            func.id,
+            FuncVersionBump::default(),
+            Some(format!("Restore version {version}")),
+        )
+        .await?;
+
+        Ok(Func::get_by_id_or_error(ctx, func.id).await?)
+    }
+
     /// Save metadata about the [`FuncId`]
     /// Returns an error if the [`Func`] is currently locked
     #[instrument(level = "info", name = "func.authoring.update_func", skip(ctx))]
@@ -574,6 +627,30 @@ impl FuncAuthoringClient {
         Ok(updated_func)
     }
 
+    /// Sets how long executions of a [`Func`] may run before they are killed. A timeout of `None`
+    /// uses the default timeout of the function executor.
+    #[instrument(name = "func.authoring.update_func_timeout", level = "info", skip(ctx))]
+    pub async fn update_func_timeout(
+        ctx: &DalContext,
+        func_id: FuncId,
+        timeout_secs: Option<u64>,
+    ) -> FuncAuthoringResult<Func> {
+        if let Some(timeout_secs) = timeout_secs {
+            if timeout_secs == 0 || timeout_secs > MAX_FUNC_TIMEOUT_SECS {
+                return Err(FuncAuthoringError::InvalidFuncTimeout(timeout_secs));
+            }
+        }
+
+        let func = Func::get_by_id_or_error(ctx, func_id).await?;
+        func.error_if_locked()?;
+        let updated_func = Func::modify_by_id(ctx, func.id, |func| {
+            func.timeout_secs = timeout_secs;
+            Ok(())
+        })
+        .await?;
+        Ok(updated_func)
+    }
+
     /// Compiles types corresponding to "lang-js".
     pub fn compile_langjs_types() -> &'static str {
         ts_types::compile_langjs_types()
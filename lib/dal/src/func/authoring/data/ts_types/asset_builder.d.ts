@@ -303,7 +303,7 @@ declare class SiPropValueFromDefinitionBuilder implements ISiPropValueFromDefini
     setKind(kind: SiPropValueFromDefinitionKind): this;
     setValueFrom(valueFrom: ValueFrom): this;
 }
-type PropDefinitionKind = "array" | "boolean" | "integer" | "map" | "object" | "string";
+type PropDefinitionKind = "array" | "boolean" | "float" | "integer" | "map" | "object" | "string";
 interface PropDefinition {
     name: string;
     kind: PropDefinitionKind;
@@ -459,7 +459,7 @@ declare class PropBuilder implements IPropBuilder {
     /**
      * The type of the prop
      *
-     * @param kind {PropDefinitionKind} [array | boolean | integer | map | object | string]
+     * @param kind {PropDefinitionKind} [array | boolean | float | integer | map | object | string]
      *
      * @returns this
      *
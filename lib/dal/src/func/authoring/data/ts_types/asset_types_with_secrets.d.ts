@@ -374,6 +374,7 @@ declare class SiPropValueFromDefinitionBuilder implements ISiPropValueFromDefini
 type PropDefinitionKind =
   "array"
   | "boolean"
+  | "float"
   | "integer"
   | "map"
   | "object"
@@ -561,7 +562,7 @@ declare class PropBuilder implements IPropBuilder {
   /**
    * The type of the prop
    *
-   * @param {string} kind [array | boolean | integer | map | object | string]
+   * @param {string} kind [array | boolean | float | integer | map | object | string]
    *
    * @returns this
    *
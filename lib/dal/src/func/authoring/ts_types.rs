@@ -26,7 +26,9 @@ pub(crate) fn compile_return_types(
     match response_type {
         FuncBackendResponseType::Boolean => "type Output = boolean | null;",
         FuncBackendResponseType::String => "type Output = string | null;",
-        FuncBackendResponseType::Integer => "type Output = number | null;",
+        FuncBackendResponseType::Integer | FuncBackendResponseType::Float => {
+            "type Output = number | null;"
+        }
         FuncBackendResponseType::Qualification => {
             "type Output = {
   result: 'success' | 'warning' | 'failure';
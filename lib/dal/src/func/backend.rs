@@ -19,6 +19,7 @@ use crate::{Func, FuncId, PropKind};
 pub mod array;
 pub mod boolean;
 pub mod diff;
+pub mod float;
 pub mod identity;
 pub mod integer;
 pub mod js_action;
@@ -99,6 +100,7 @@ pub enum FuncBackendKind {
     Unset,
     Validation,
     Management,
+    Float,
 }
 
 impl From<FuncBackendKind> for si_events::FuncBackendKind {
@@ -124,6 +126,7 @@ impl From<FuncBackendKind> for si_events::FuncBackendKind {
             FuncBackendKind::Unset => si_events::FuncBackendKind::Unset,
             FuncBackendKind::Validation => si_events::FuncBackendKind::Validation,
             FuncBackendKind::Management => si_events::FuncBackendKind::Management,
+            FuncBackendKind::Float => si_events::FuncBackendKind::Float,
         }
     }
 }
@@ -151,6 +154,7 @@ impl From<si_events::FuncBackendKind> for FuncBackendKind {
             si_events::FuncBackendKind::Unset => FuncBackendKind::Unset,
             si_events::FuncBackendKind::Validation => FuncBackendKind::Validation,
             si_events::FuncBackendKind::Management => FuncBackendKind::Management,
+            si_events::FuncBackendKind::Float => FuncBackendKind::Float,
         }
     }
 }
@@ -190,6 +194,7 @@ pub enum FuncBackendResponseType {
     Validation,
     Void,
     Management,
+    Float,
 }
 
 impl From<FuncBackendResponseType> for si_events::FuncBackendResponseType {
@@ -220,6 +225,7 @@ impl From<FuncBackendResponseType> for si_events::FuncBackendResponseType {
             FuncBackendResponseType::Validation => si_events::FuncBackendResponseType::Validation,
             FuncBackendResponseType::Void => si_events::FuncBackendResponseType::Void,
             FuncBackendResponseType::Management => si_events::FuncBackendResponseType::Management,
+            FuncBackendResponseType::Float => si_events::FuncBackendResponseType::Float,
         }
     }
 }
@@ -252,6 +258,7 @@ impl From<si_events::FuncBackendResponseType> for FuncBackendResponseType {
             si_events::FuncBackendResponseType::Validation => FuncBackendResponseType::Validation,
             si_events::FuncBackendResponseType::Void => FuncBackendResponseType::Void,
             si_events::FuncBackendResponseType::Management => FuncBackendResponseType::Management,
+            si_events::FuncBackendResponseType::Float => FuncBackendResponseType::Float,
         }
     }
 }
@@ -273,6 +280,7 @@ impl From<ResolverFunctionResponseType> for FuncBackendResponseType {
             ResolverFunctionResponseType::Json => FuncBackendResponseType::Json,
             ResolverFunctionResponseType::Void => FuncBackendResponseType::Void,
             ResolverFunctionResponseType::Management => FuncBackendResponseType::Management,
+            ResolverFunctionResponseType::Float => FuncBackendResponseType::Float,
         }
     }
 }
@@ -309,6 +317,7 @@ impl TryFrom<FuncBackendResponseType> for ResolverFunctionResponseType {
             }
             FuncBackendResponseType::Void => ResolverFunctionResponseType::Void,
             FuncBackendResponseType::Management => ResolverFunctionResponseType::Management,
+            FuncBackendResponseType::Float => ResolverFunctionResponseType::Float,
         };
         Ok(value)
     }
@@ -323,6 +332,7 @@ pub struct FuncDispatchContext {
     pub func_run_id: FuncRunId,
     pub workspace_id: WorkspaceId,
     pub change_set_id: ChangeSetId,
+    pub timeout_secs: Option<u64>,
 }
 
 impl FuncDispatchContext {
@@ -340,11 +350,19 @@ impl FuncDispatchContext {
                 func_run_id,
                 workspace_id,
                 change_set_id,
+                timeout_secs: None,
             },
             rx,
         )
     }
 
+    /// Sets the timeout to request from the function executor, falling back to its default when
+    /// `None`.
+    pub fn with_timeout_secs(mut self, timeout_secs: Option<u64>) -> Self {
+        self.timeout_secs = timeout_secs;
+        self
+    }
+
     pub fn into_inner(
         self,
     ) -> (
@@ -397,7 +415,13 @@ pub trait FuncDispatch: std::fmt::Debug {
             .handler
             .as_deref()
             .ok_or_else(|| FuncBackendError::DispatchMissingHandler(func.id))?;
-        let value = Self::new(context, code_base64, handler, args, before);
+        let value = Self::new(
+            context.with_timeout_secs(func.timeout_secs),
+            code_base64,
+            handler,
+            args,
+            before,
+        );
         Ok(value)
     }
 
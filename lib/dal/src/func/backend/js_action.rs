@@ -38,6 +38,7 @@ impl FuncDispatch for FuncBackendJsAction {
             code_base64: code_base64.into(),
             args: args.0,
             before,
+            timeout_secs: context.timeout_secs,
         };
 
         Box::new(Self { context, request })
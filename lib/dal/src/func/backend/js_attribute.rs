@@ -39,6 +39,7 @@ impl FuncDispatch for FuncBackendJsAttribute {
             response_type: args.response_type,
             code_base64: code_base64.into(),
             before,
+            timeout_secs: context.timeout_secs,
         };
 
         Box::new(Self { context, request })
@@ -59,6 +60,7 @@ impl FuncDispatch for FuncBackendJsAttribute {
                 ResolverFunctionResponseType::Action
                 | ResolverFunctionResponseType::Array
                 | ResolverFunctionResponseType::Boolean
+                | ResolverFunctionResponseType::Float
                 | ResolverFunctionResponseType::Integer
                 | ResolverFunctionResponseType::Identity
                 | ResolverFunctionResponseType::Map
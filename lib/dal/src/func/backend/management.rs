@@ -44,6 +44,7 @@ impl FuncDispatch for FuncBackendManagement {
             components: args.components,
             current_view: args.current_view,
             before,
+            timeout_secs: context.timeout_secs,
         };
 
         Box::new(Self { context, request })
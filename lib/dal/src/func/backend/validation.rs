@@ -34,6 +34,7 @@ impl FuncDispatch for FuncBackendValidation {
             handler: "".to_string(),
             code_base64: "".to_string(),
             before: vec![],
+            timeout_secs: context.timeout_secs,
         };
 
         Box::new(Self { context, request })
@@ -896,6 +896,7 @@ async fn validate_intrinsic_inputs(
         }
         IntrinsicFunc::SetArray
         | IntrinsicFunc::SetBoolean
+        | IntrinsicFunc::SetFloat
         | IntrinsicFunc::SetInteger
         | IntrinsicFunc::SetJson
         | IntrinsicFunc::SetMap
@@ -164,11 +164,15 @@ type Output = {{
             remove?: {{ from: string, to: {{ component: string; socket: string; }} }}[],
         }},
         parent?: string,
+        unparent?: boolean,
+        removeFromViews?: string[],
     }} }},
     actions?: {{ [key: string]: {{
       add?: ("create" | "update" | "refresh" | "delete" | string)[];
       remove?: ("create" | "update" | "refresh" | "delete" | string)[];
-    }} }}
+    }} }},
+    delete?: string[],
+    erase?: string[],
   }},
   message?: string | null;
 }};
@@ -1,12 +1,13 @@
 use chrono::DateTime;
 use si_pkg::{
     FuncArgumentKind, FuncArgumentSpec, FuncSpec, FuncSpecBackendKind, FuncSpecBackendResponseType,
-    FuncSpecData, PkgSpec,
+    FuncSpecData, PkgSpec, PkgSpecBuilder, SiPkg,
 };
 use strum::{AsRefStr, Display, EnumIter, EnumString, IntoEnumIterator};
 
 use crate::func::{FuncError, FuncResult};
-use crate::PropKind;
+use crate::pkg::import::{import_func, import_func_arguments, ThingMap};
+use crate::{DalContext, Func, FuncId, PropKind};
 
 #[remain::sorted]
 #[derive(AsRefStr, Display, EnumIter, EnumString, Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,6 +15,7 @@ pub enum IntrinsicFunc {
     Identity,
     SetArray,
     SetBoolean,
+    SetFloat,
     SetInteger,
     SetJson,
     SetMap,
@@ -24,7 +26,7 @@ pub enum IntrinsicFunc {
 }
 
 impl IntrinsicFunc {
-    pub fn pkg_spec() -> FuncResult<PkgSpec> {
+    fn pkg_spec_builder() -> FuncResult<PkgSpecBuilder> {
         let mut builder = PkgSpec::builder();
         builder.name("si-intrinsic-funcs");
         builder.version("2023-05-24");
@@ -32,6 +34,11 @@ impl IntrinsicFunc {
             "Wed, 24 May 2023 00:00:00 PST",
         )?);
         builder.created_by("System Initiative");
+        Ok(builder)
+    }
+
+    pub fn pkg_spec() -> FuncResult<PkgSpec> {
+        let mut builder = Self::pkg_spec_builder()?;
         for intrinsic in IntrinsicFunc::iter() {
             builder.func(intrinsic.to_spec()?);
         }
@@ -41,6 +48,48 @@ impl IntrinsicFunc {
             .map_err(|e| FuncError::IntrinsicSpecCreation(e.to_string()))
     }
 
+    /// Installs the intrinsics missing from the workspace and returns the ones it installed.
+    ///
+    /// Intrinsics are installed when a workspace is created, so workspaces created before an
+    /// intrinsic was added (e.g. [`SetFloat`](Self::SetFloat)) need this as an upgrade step.
+    pub async fn install_missing(ctx: &DalContext) -> FuncResult<Vec<Self>> {
+        let mut installed = Vec::new();
+        for intrinsic in Self::iter() {
+            if Func::find_id_by_name(ctx, intrinsic.name())
+                .await?
+                .is_none()
+            {
+                intrinsic.install(ctx).await?;
+                installed.push(intrinsic);
+            }
+        }
+
+        Ok(installed)
+    }
+
+    async fn install(self, ctx: &DalContext) -> FuncResult<FuncId> {
+        let mut builder = Self::pkg_spec_builder()?;
+        builder.func(self.to_spec()?);
+        let pkg_spec = builder
+            .build()
+            .map_err(|e| FuncError::IntrinsicSpecCreation(e.to_string()))?;
+        let pkg = SiPkg::load_from_spec(pkg_spec)?;
+        let func_spec = pkg
+            .funcs()?
+            .pop()
+            .ok_or(FuncError::IntrinsicFuncNotFound(self.name().to_owned()))?;
+
+        let mut thing_map = ThingMap::new();
+        let func = import_func(ctx, &func_spec, None, &mut thing_map, false)
+            .await
+            .map_err(Box::new)?;
+        import_func_arguments(ctx, func.id, &func_spec.arguments()?, &mut thing_map)
+            .await
+            .map_err(Box::new)?;
+
+        Ok(func.id)
+    }
+
     pub fn to_spec(&self) -> FuncResult<FuncSpec> {
         let mut builder = FuncSpec::builder();
         builder.name(self.name());
@@ -94,6 +143,19 @@ impl IntrinsicFunc {
                         .map_err(|e| FuncError::IntrinsicSpecCreation(e.to_string()))?,
                 );
             }
+            Self::SetFloat => {
+                builder
+                    .unique_id("895b6a286c1d84bcb28b0f34f49f5388dfccd5e94e2ed0455f1487fe957018a5");
+                data_builder.backend_kind(FuncSpecBackendKind::Float);
+                data_builder.response_type(FuncSpecBackendResponseType::Float);
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("value")
+                        .kind(FuncArgumentKind::Float)
+                        .build()
+                        .map_err(|e| FuncError::IntrinsicSpecCreation(e.to_string()))?,
+                );
+            }
             Self::SetInteger => {
                 builder
                     .unique_id("7d384b237852f20b8dec2fbd2e644ffc6bde901d7dc937bd77f50a0d57e642a9");
@@ -196,6 +258,7 @@ impl IntrinsicFunc {
             Self::Identity => "si:identity",
             Self::SetArray => "si:setArray",
             Self::SetBoolean => "si:setBoolean",
+            Self::SetFloat => "si:setFloat",
             Self::SetInteger => "si:setInteger",
             Self::SetMap => "si:setMap",
             Self::SetObject => "si:setObject",
@@ -211,6 +274,7 @@ impl IntrinsicFunc {
             "si:identity" => Self::Identity,
             "si:setArray" => Self::SetArray,
             "si:setBoolean" => Self::SetBoolean,
+            "si:setFloat" => Self::SetFloat,
             "si:setInteger" => Self::SetInteger,
             "si:setMap" => Self::SetMap,
             "si:setObject" => Self::SetObject,
@@ -230,6 +294,7 @@ impl From<PropKind> for IntrinsicFunc {
         match value {
             PropKind::Array => IntrinsicFunc::SetArray,
             PropKind::Boolean => IntrinsicFunc::SetBoolean,
+            PropKind::Float => IntrinsicFunc::SetFloat,
             PropKind::Integer => IntrinsicFunc::SetInteger,
             PropKind::Json => IntrinsicFunc::SetJson,
             PropKind::Map => IntrinsicFunc::SetMap,
@@ -73,6 +73,7 @@ impl FuncKind {
             | FuncBackendKind::Json
             | FuncBackendKind::Boolean
             | FuncBackendKind::Diff
+            | FuncBackendKind::Float
             | FuncBackendKind::Identity
             | FuncBackendKind::Integer
             | FuncBackendKind::Map
@@ -7,7 +7,8 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use si_events::{
     ActionId, ActionResultState, CasValue, ContentHash, EncryptedSecretKey, FuncRun,
-    FuncRunBuilder, FuncRunBuilderError, FuncRunId, FuncRunLog, FuncRunLogId, FuncRunValue,
+    FuncRunBuilder, FuncRunBuilderError, FuncRunId, FuncRunLog, FuncRunLogId, FuncRunState,
+    FuncRunValue,
 };
 use si_layer_cache::LayerDbError;
 use telemetry::prelude::*;
@@ -47,6 +48,7 @@ use super::backend::{
     array::FuncBackendArray,
     boolean::FuncBackendBoolean,
     diff::FuncBackendDiff,
+    float::FuncBackendFloat,
     identity::FuncBackendIdentity,
     integer::FuncBackendInteger,
     js_action::FuncBackendJsAction,
@@ -97,6 +99,10 @@ pub enum FuncRunnerError {
     FuncIntrinsicValidationMissing,
     #[error("func run builder error: {0}")]
     FuncRunBuilder(#[from] FuncRunBuilderError),
+    #[error("func run ({0}) cannot be cancelled in state: {1}")]
+    FuncRunNotCancellable(FuncRunId, FuncRunState),
+    #[error("func run not found: {0}")]
+    FuncRunNotFound(FuncRunId),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     #[error("invalid resolver function type: {0}")]
@@ -188,7 +194,7 @@ impl FuncRunner {
         ctx: &DalContext,
         func: Func,
         args: serde_json::Value,
-        component_id: ComponentId,
+        component_id: Option<ComponentId>,
     ) -> FuncRunnerResult<(FuncRunId, FuncRunnerValueChannel)> {
         let span = current_span_for_instrument_at!("debug");
 
@@ -207,7 +213,7 @@ impl FuncRunner {
             ctx: &DalContext,
             func: Func,
             args: serde_json::Value,
-            component_id: ComponentId,
+            component_id: Option<ComponentId>,
             span: &Span,
         ) -> FuncRunnerResult<FuncRunner> {
             let function_args: CasValue = args.clone().into();
@@ -217,9 +223,14 @@ impl FuncRunner {
                 ctx.events_tenancy(),
                 ctx.events_actor(),
             )?;
-            let before = FuncRunner::before_funcs(ctx, component_id).await?;
+            // Without a component there are no secrets to authenticate with, so no "before"
+            // funcs are run.
+            let before = match component_id {
+                Some(component_id) => FuncRunner::before_funcs(ctx, component_id).await?,
+                None => vec![],
+            };
 
-            let component_id = component_id.into();
+            let component_id: Option<si_events::ComponentId> = component_id.map(Into::into);
 
             let func_run_create_time = Utc::now();
             let func_run_inner = FuncRunBuilder::default()
@@ -235,7 +246,7 @@ impl FuncRunner {
                 .function_args_cas_address(function_args_cas_address)
                 .function_code_cas_address(func.code_blake3)
                 .attribute_value_id(None)
-                .component_id(Some(component_id))
+                .component_id(component_id)
                 .created_at(func_run_create_time)
                 .updated_at(func_run_create_time)
                 .build()?;
@@ -258,7 +269,9 @@ impl FuncRunner {
                     "si.change_set.id",
                     func_run_inner.change_set_id().array_to_str(&mut id_buf),
                 );
-                span.record("si.component.id", component_id.array_to_str(&mut id_buf));
+                if let Some(component_id) = component_id {
+                    span.record("si.component.id", component_id.array_to_str(&mut id_buf));
+                }
                 span.record(
                     "si.workspace.id",
                     func_run_inner.workspace_pk().array_to_str(&mut id_buf),
@@ -1211,6 +1224,56 @@ impl FuncRunner {
             return Err(FuncRunnerError::DoNotHavePermissionToKillExecution);
         }
 
+        Self::kill_execution_inner(ctx, func_run_id).await
+    }
+
+    /// Cancels a [`FuncRun`] on behalf of a member of the workspace it belongs to.
+    ///
+    /// Unlike [`Self::kill_execution`], this only acts on func runs of the current workspace that
+    /// have not finished yet. If the func run belongs to an action, the action job receives the
+    /// killed result and marks the action (and anything waiting on it) as failed.
+    #[instrument(
+        name = "func_runner.cancel_execution",
+        level = "info",
+        skip(ctx),
+        fields(job.id = Empty, si.func_run.id = Empty)
+    )]
+    pub async fn cancel_execution(
+        ctx: &DalContext,
+        func_run_id: FuncRunId,
+    ) -> FuncRunnerResult<()> {
+        let span = current_span_for_instrument_at!("info");
+
+        if !span.is_disabled() {
+            let mut id_buf = FuncRunId::array_to_str_buf();
+
+            let id = func_run_id.array_to_str(&mut id_buf);
+            span.record("job.id", &id);
+            span.record("si.func_run.id", &id);
+        }
+
+        let func_run = ctx
+            .layer_db()
+            .func_run()
+            .read(func_run_id)
+            .await?
+            .ok_or(FuncRunnerError::FuncRunNotFound(func_run_id))?;
+        if func_run.workspace_pk() != ctx.events_tenancy().workspace_pk {
+            return Err(FuncRunnerError::FuncRunNotFound(func_run_id));
+        }
+
+        match func_run.state() {
+            FuncRunState::Created | FuncRunState::Dispatched | FuncRunState::Running => {}
+            state => return Err(FuncRunnerError::FuncRunNotCancellable(func_run_id, state)),
+        }
+
+        Self::kill_execution_inner(ctx, func_run_id).await
+    }
+
+    async fn kill_execution_inner(
+        ctx: &DalContext,
+        func_run_id: FuncRunId,
+    ) -> FuncRunnerResult<()> {
         let result = ctx
             .veritech()
             .kill_execution(&KillExecutionRequest {
@@ -1561,16 +1624,8 @@ impl FuncRunnerLogsTask {
                 timestamp: item.timestamp,
             });
 
-            WsEvent::func_run_log_updated(
-                &self.ctx,
-                func_run_log.func_run_id(),
-                func_run_log.id(),
-                self.action_id,
-            )
-            .await?
-            .publish_immediately(&self.ctx)
-            .await?;
-
+            // Write before announcing the update so that observers reading the log when they
+            // receive the event always see the new line
             self.ctx
                 .layer_db()
                 .func_run_log()
@@ -1581,6 +1636,16 @@ impl FuncRunnerLogsTask {
                     self.ctx.events_actor(),
                 )
                 .await?;
+
+            WsEvent::func_run_log_updated(
+                &self.ctx,
+                func_run_log.func_run_id(),
+                func_run_log.id(),
+                self.action_id,
+            )
+            .await?
+            .publish_immediately(&self.ctx)
+            .await?;
         }
 
         // Now that all `OutputStream` messages have been received, we will never
@@ -1602,6 +1667,16 @@ impl FuncRunnerLogsTask {
             )
             .await?;
 
+        WsEvent::func_run_log_updated(
+            &self.ctx,
+            func_run_log.func_run_id(),
+            func_run_log.id(),
+            self.action_id,
+        )
+        .await?
+        .publish_immediately(&self.ctx)
+        .await?;
+
         Ok(())
     }
 }
@@ -1714,6 +1789,7 @@ impl FuncRunnerExecutionTask {
             FuncBackendKind::Identity => FuncBackendIdentity::create_and_execute(&self.args).await,
             FuncBackendKind::Diff => FuncBackendDiff::create_and_execute(&self.args).await,
             FuncBackendKind::Integer => FuncBackendInteger::create_and_execute(&self.args).await,
+            FuncBackendKind::Float => FuncBackendFloat::create_and_execute(&self.args).await,
             FuncBackendKind::Map => FuncBackendMap::create_and_execute(&self.args).await,
             FuncBackendKind::Object => FuncBackendObject::create_and_execute(&self.args).await,
             FuncBackendKind::String => FuncBackendString::create_and_execute(&self.args).await,
@@ -1808,7 +1884,12 @@ impl FuncRunnerExecutionTask {
                 backend,
             }) => {
                 let mut next_state_inner = Arc::unwrap_or_clone(running_state_func_run.clone());
-                next_state_inner.set_state_to_failure();
+                // A killed execution is reported as a failure, but should not be recorded as one
+                if kind == FunctionResultFailureErrorKind::KilledExecution {
+                    next_state_inner.set_state_to_killed();
+                } else {
+                    next_state_inner.set_state_to_failure();
+                }
                 let next_state = Arc::new(next_state_inner);
                 if !self.func.is_intrinsic() {
                     self.ctx
@@ -0,0 +1,539 @@
+//! Declarative test cases attached to a [`Func`].
+//!
+//! A [`FuncTestCase`] pairs a [`FuncTestCaseFixture`] (the mocked inputs for a run) with a
+//! [`FuncTestCaseExpectation`] (what the output should look like). Test cases live in the graph as
+//! content nodes hanging off of their func:
+//!
+//! [`Func`] -- [`EdgeWeightKind::FuncTestCase`] --> [`FuncTestCase`]
+//!
+//! Runs never decrypt real secrets: the func is executed without a component, so any secrets it
+//! needs must be mocked in the fixture.
+
+use serde::{Deserialize, Serialize};
+use si_events::{CasValue, FuncRunId};
+use si_layer_cache::LayerDbError;
+use std::sync::Arc;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::layer_db_types::{FuncTestCaseContent, FuncTestCaseContentV1};
+use crate::workspace_snapshot::content_address::{ContentAddress, ContentAddressDiscriminants};
+use crate::workspace_snapshot::edge_weight::{
+    EdgeWeight, EdgeWeightKind, EdgeWeightKindDiscriminants,
+};
+use crate::workspace_snapshot::node_weight::{NodeWeight, NodeWeightError};
+use crate::workspace_snapshot::WorkspaceSnapshotError;
+use crate::{
+    id, DalContext, Func, FuncError, FuncId, Timestamp, TransactionsError, WsEvent, WsEventResult,
+    WsPayload,
+};
+
+use super::runner::{FuncRunner, FuncRunnerError};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FuncTestCaseError {
+    #[error("func error: {0}")]
+    Func(#[from] Box<FuncError>),
+    #[error("func not found for test case: {0}")]
+    FuncNotFoundForTestCase(FuncTestCaseId),
+    #[error("func runner error: {0}")]
+    FuncRunner(#[from] Box<FuncRunnerError>),
+    #[error("fixture args must be an object when mocking the component, resource or secrets")]
+    InvalidFixtureArgs,
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+    #[error("test case name \"{1}\" already in use for func {0}")]
+    NameInUse(FuncId, String),
+    #[error("node weight error: {0}")]
+    NodeWeight(#[from] NodeWeightError),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+pub type FuncTestCaseResult<T> = Result<T, FuncTestCaseError>;
+
+id!(FuncTestCaseId);
+
+/// The mocked inputs for a [`FuncTestCase`] run.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTestCaseFixture {
+    /// The arguments passed to the func.
+    #[serde(default)]
+    pub args: serde_json::Value,
+    /// A mocked component view, passed to the func as `properties`.
+    #[serde(default)]
+    pub component: Option<serde_json::Value>,
+    /// A mocked resource, passed to the func as `properties.resource`.
+    #[serde(default)]
+    pub resource: Option<serde_json::Value>,
+    /// Mocked secrets, passed to the func as `properties.secrets`.
+    #[serde(default)]
+    pub secrets: Option<serde_json::Value>,
+}
+
+impl FuncTestCaseFixture {
+    /// Assembles the arguments the func is run with, layering the mocked component, resource and
+    /// secrets on top of the raw args.
+    pub fn to_args(&self) -> FuncTestCaseResult<serde_json::Value> {
+        if self.component.is_none() && self.resource.is_none() && self.secrets.is_none() {
+            return Ok(self.args.clone());
+        }
+
+        let mut args = match &self.args {
+            serde_json::Value::Null => serde_json::Map::new(),
+            serde_json::Value::Object(args) => args.clone(),
+            _ => return Err(FuncTestCaseError::InvalidFixtureArgs),
+        };
+
+        let properties = args
+            .entry("properties")
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(component) = &self.component {
+            *properties = component.clone();
+        }
+        let properties = properties
+            .as_object_mut()
+            .ok_or(FuncTestCaseError::InvalidFixtureArgs)?;
+        if let Some(resource) = &self.resource {
+            properties.insert("resource".to_string(), resource.clone());
+        }
+        if let Some(secrets) = &self.secrets {
+            properties.insert("secrets".to_string(), secrets.clone());
+        }
+
+        Ok(serde_json::Value::Object(args))
+    }
+}
+
+/// A single check made against part of a func's output.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "value")]
+pub enum FuncTestCaseCheck {
+    /// Nothing exists at the pointer.
+    Absent,
+    /// The value at the pointer equals the given value.
+    Equals(serde_json::Value),
+    /// Something exists at the pointer.
+    Exists,
+    /// The value at the pointer does not equal the given value.
+    NotEquals(serde_json::Value),
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTestCaseAssertion {
+    /// A JSON pointer (e.g. `/payload/name`) into the output. An empty pointer is the whole output.
+    #[serde(default)]
+    pub pointer: String,
+    pub check: FuncTestCaseCheck,
+}
+
+impl FuncTestCaseAssertion {
+    fn failure(&self, output: &serde_json::Value) -> Option<String> {
+        let found = output.pointer(&self.pointer);
+        let pointer = if self.pointer.is_empty() {
+            "output"
+        } else {
+            self.pointer.as_str()
+        };
+
+        match (&self.check, found) {
+            (FuncTestCaseCheck::Absent, Some(found)) => {
+                Some(format!("expected nothing at {pointer}, found {found}"))
+            }
+            (FuncTestCaseCheck::Equals(expected), Some(found)) if found != expected => {
+                Some(format!("expected {expected} at {pointer}, found {found}"))
+            }
+            (FuncTestCaseCheck::Equals(expected), None) => {
+                Some(format!("expected {expected} at {pointer}, found nothing"))
+            }
+            (FuncTestCaseCheck::Exists, None) => {
+                Some(format!("expected a value at {pointer}, found nothing"))
+            }
+            (FuncTestCaseCheck::NotEquals(unexpected), Some(found)) if found == unexpected => {
+                Some(format!("expected anything but {unexpected} at {pointer}"))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// What a [`FuncTestCase`] expects the func to return.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "value")]
+pub enum FuncTestCaseExpectation {
+    /// The output must equal this value exactly.
+    Output(serde_json::Value),
+    /// Every assertion must hold for the output.
+    Assertions(Vec<FuncTestCaseAssertion>),
+}
+
+impl FuncTestCaseExpectation {
+    /// Returns a message for every way the output fails to meet the expectation.
+    pub fn failures(&self, output: &serde_json::Value) -> Vec<String> {
+        match self {
+            Self::Output(expected) if expected != output => {
+                vec![format!("expected output {expected}, found {output}")]
+            }
+            Self::Output(_) => vec![],
+            Self::Assertions(assertions) => assertions
+                .iter()
+                .filter_map(|assertion| assertion.failure(output))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTestCase {
+    pub id: FuncTestCaseId,
+    pub name: String,
+    pub description: Option<String>,
+    pub fixture: FuncTestCaseFixture,
+    pub expectation: FuncTestCaseExpectation,
+    #[serde(flatten)]
+    pub timestamp: Timestamp,
+}
+
+/// The outcome of running a single [`FuncTestCase`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTestCaseRunResult {
+    pub test_case_id: FuncTestCaseId,
+    pub name: String,
+    pub func_run_id: Option<FuncRunId>,
+    pub passed: bool,
+    pub output: Option<serde_json::Value>,
+    pub failures: Vec<String>,
+}
+
+impl FuncTestCase {
+    fn assemble(id: FuncTestCaseId, content: FuncTestCaseContentV1) -> FuncTestCaseResult<Self> {
+        Ok(Self {
+            id,
+            name: content.name,
+            description: content.description,
+            fixture: serde_json::from_value(content.fixture.into())?,
+            expectation: serde_json::from_value(content.expectation.into())?,
+            timestamp: content.timestamp,
+        })
+    }
+
+    fn content(&self) -> FuncTestCaseResult<FuncTestCaseContent> {
+        Ok(FuncTestCaseContent::V1(FuncTestCaseContentV1 {
+            timestamp: self.timestamp,
+            name: self.name.clone(),
+            description: self.description.clone(),
+            fixture: CasValue::from(serde_json::to_value(&self.fixture)?),
+            expectation: CasValue::from(serde_json::to_value(&self.expectation)?),
+        }))
+    }
+
+    #[instrument(name = "func.test_case.new", level = "debug", skip_all)]
+    pub async fn new(
+        ctx: &DalContext,
+        func_id: FuncId,
+        name: impl Into<String>,
+        description: Option<String>,
+        fixture: FuncTestCaseFixture,
+        expectation: FuncTestCaseExpectation,
+    ) -> FuncTestCaseResult<Self> {
+        let name = name.into();
+        if Self::find_by_name_for_func(ctx, func_id, &name)
+            .await?
+            .is_some()
+        {
+            return Err(FuncTestCaseError::NameInUse(func_id, name));
+        }
+
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let id: FuncTestCaseId = workspace_snapshot.generate_ulid().await?.into();
+        let lineage_id = workspace_snapshot.generate_ulid().await?;
+
+        let test_case = Self {
+            id,
+            name,
+            description,
+            fixture,
+            expectation,
+            timestamp: Timestamp::now(),
+        };
+
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(test_case.content()?.into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        workspace_snapshot
+            .add_or_replace_node(NodeWeight::new_content(
+                id,
+                lineage_id,
+                ContentAddress::FuncTestCase(hash),
+            ))
+            .await?;
+        workspace_snapshot
+            .add_edge(func_id, EdgeWeight::new(EdgeWeightKind::FuncTestCase), id)
+            .await?;
+
+        Ok(test_case)
+    }
+
+    pub async fn get_by_id_or_error(
+        ctx: &DalContext,
+        id: FuncTestCaseId,
+    ) -> FuncTestCaseResult<Self> {
+        let node_weight = ctx
+            .workspace_snapshot()?
+            .get_node_weight_by_id(id)
+            .await?
+            .get_content_node_weight_of_kind(ContentAddressDiscriminants::FuncTestCase)?;
+
+        let content: FuncTestCaseContent = ctx
+            .layer_db()
+            .cas()
+            .try_read_as(&node_weight.content_hash())
+            .await?
+            .ok_or(WorkspaceSnapshotError::MissingContentFromStore(id.into()))?;
+
+        Self::assemble(id, content.extract())
+    }
+
+    pub async fn list_ids_for_func(
+        ctx: &DalContext,
+        func_id: FuncId,
+    ) -> FuncTestCaseResult<Vec<FuncTestCaseId>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+
+        let mut ids = vec![];
+        for idx in workspace_snapshot
+            .outgoing_targets_for_edge_weight_kind(
+                func_id,
+                EdgeWeightKindDiscriminants::FuncTestCase,
+            )
+            .await?
+        {
+            ids.push(workspace_snapshot.get_node_weight(idx).await?.id().into());
+        }
+
+        Ok(ids)
+    }
+
+    /// Lists the [`FuncTestCases`](FuncTestCase) for a [`Func`], ordered by name.
+    pub async fn list_for_func(ctx: &DalContext, func_id: FuncId) -> FuncTestCaseResult<Vec<Self>> {
+        let mut test_cases = vec![];
+        for id in Self::list_ids_for_func(ctx, func_id).await? {
+            test_cases.push(Self::get_by_id_or_error(ctx, id).await?);
+        }
+        test_cases.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(test_cases)
+    }
+
+    pub async fn find_by_name_for_func(
+        ctx: &DalContext,
+        func_id: FuncId,
+        name: &str,
+    ) -> FuncTestCaseResult<Option<Self>> {
+        Ok(Self::list_for_func(ctx, func_id)
+            .await?
+            .into_iter()
+            .find(|test_case| test_case.name == name))
+    }
+
+    pub async fn func_id_for_test_case(
+        ctx: &DalContext,
+        id: FuncTestCaseId,
+    ) -> FuncTestCaseResult<FuncId> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let idx = workspace_snapshot
+            .incoming_sources_for_edge_weight_kind(id, EdgeWeightKindDiscriminants::FuncTestCase)
+            .await?
+            .pop()
+            .ok_or(FuncTestCaseError::FuncNotFoundForTestCase(id))?;
+
+        Ok(workspace_snapshot.get_node_weight(idx).await?.id().into())
+    }
+
+    pub async fn modify_by_id<L>(
+        ctx: &DalContext,
+        id: FuncTestCaseId,
+        lambda: L,
+    ) -> FuncTestCaseResult<Self>
+    where
+        L: FnOnce(&mut Self) -> FuncTestCaseResult<()>,
+    {
+        let test_case = Self::get_by_id_or_error(ctx, id).await?;
+        let mut updated = test_case.clone();
+        lambda(&mut updated)?;
+
+        if updated == test_case {
+            return Ok(test_case);
+        }
+
+        if updated.name != test_case.name {
+            let func_id = Self::func_id_for_test_case(ctx, id).await?;
+            if Self::find_by_name_for_func(ctx, func_id, &updated.name)
+                .await?
+                .is_some()
+            {
+                return Err(FuncTestCaseError::NameInUse(func_id, updated.name));
+            }
+        }
+
+        updated.timestamp.updated_at = Timestamp::now().updated_at;
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(updated.content()?.into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+        ctx.workspace_snapshot()?
+            .update_content(id.into(), hash)
+            .await?;
+
+        Ok(updated)
+    }
+
+    pub async fn remove(ctx: &DalContext, id: FuncTestCaseId) -> FuncTestCaseResult<()> {
+        ctx.workspace_snapshot()?.remove_node_by_id(id).await?;
+        Ok(())
+    }
+
+    /// Copies every test case on one [`Func`] to another, e.g. when creating an unlocked copy.
+    pub async fn copy_all(
+        ctx: &DalContext,
+        from_func_id: FuncId,
+        to_func_id: FuncId,
+    ) -> FuncTestCaseResult<()> {
+        for test_case in Self::list_for_func(ctx, from_func_id).await? {
+            Self::new(
+                ctx,
+                to_func_id,
+                test_case.name,
+                test_case.description,
+                test_case.fixture,
+                test_case.expectation,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs the test case against the current code of its [`Func`]. Failures of the func itself
+    /// (e.g. a thrown exception) fail the test case rather than returning an error.
+    #[instrument(name = "func.test_case.run", level = "info", skip(ctx))]
+    pub async fn run(
+        ctx: &DalContext,
+        id: FuncTestCaseId,
+    ) -> FuncTestCaseResult<FuncTestCaseRunResult> {
+        let test_case = Self::get_by_id_or_error(ctx, id).await?;
+        let func_id = Self::func_id_for_test_case(ctx, id).await?;
+        let func = Func::get_by_id_or_error(ctx, func_id)
+            .await
+            .map_err(Box::new)?;
+
+        let mut result = FuncTestCaseRunResult {
+            test_case_id: id,
+            name: test_case.name.clone(),
+            func_run_id: None,
+            passed: false,
+            output: None,
+            failures: vec![],
+        };
+
+        let (func_run_id, result_channel) =
+            FuncRunner::run_test(ctx, func, test_case.fixture.to_args()?, None)
+                .await
+                .map_err(Box::new)?;
+        result.func_run_id = Some(func_run_id);
+
+        match result_channel.await {
+            Ok(Ok(func_run_value)) => {
+                let output = func_run_value
+                    .value()
+                    .cloned()
+                    .unwrap_or(serde_json::Value::Null);
+                result.failures = test_case.expectation.failures(&output);
+                result.passed = result.failures.is_empty();
+                result.output = Some(output);
+            }
+            Ok(Err(err)) => result.failures.push(err.to_string()),
+            Err(_) => result
+                .failures
+                .push("func run went away before a value could be sent down the channel".into()),
+        }
+
+        Ok(result)
+    }
+
+    /// Runs every test case for the [`Func`], ordered by name.
+    #[instrument(name = "func.test_case.run_all_for_func", level = "info", skip(ctx))]
+    pub async fn run_all_for_func(
+        ctx: &DalContext,
+        func_id: FuncId,
+    ) -> FuncTestCaseResult<Vec<FuncTestCaseRunResult>> {
+        let mut results = vec![];
+        for test_case in Self::list_for_func(ctx, func_id).await? {
+            results.push(Self::run(ctx, test_case.id).await?);
+        }
+
+        Ok(results)
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTestCasesRunPayload {
+    func_id: FuncId,
+    results: Vec<FuncTestCaseRunResult>,
+    passed: bool,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTestCasesRunFailedPayload {
+    func_id: FuncId,
+    error: String,
+}
+
+impl WsEvent {
+    pub async fn func_test_cases_run(
+        ctx: &DalContext,
+        func_id: FuncId,
+        results: Vec<FuncTestCaseRunResult>,
+    ) -> WsEventResult<Self> {
+        let passed = results.iter().all(|result| result.passed);
+        WsEvent::new(
+            ctx,
+            WsPayload::FuncTestCasesRun(FuncTestCasesRunPayload {
+                func_id,
+                results,
+                passed,
+            }),
+        )
+        .await
+    }
+
+    pub async fn func_test_cases_run_failed(
+        ctx: &DalContext,
+        func_id: FuncId,
+        error: String,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::FuncTestCasesRunFailed(FuncTestCasesRunFailedPayload { func_id, error }),
+        )
+        .await
+    }
+}
@@ -0,0 +1,544 @@
+//! Version history for [`Funcs`](Func).
+//!
+//! Every time a [`Func`] is locked, a [`FuncVersion`] capturing its code is appended to its
+//! history. The history lives in the graph as content nodes hanging off of the func, and is
+//! carried along to unlocked copies so that each func knows every version that came before it:
+//!
+//! [`Func`] -- [`EdgeWeightKind::FuncVersion`] --> [`FuncVersion`]
+//!
+//! While a func is unlocked, authors can stage a [`FuncVersionDraft`] describing how the next
+//! version should be bumped and what changed. The draft is consumed when the func is locked.
+
+use base64::{engine::general_purpose, Engine};
+use serde::{Deserialize, Serialize};
+use si_layer_cache::LayerDbError;
+use std::fmt;
+use std::str::FromStr;
+use std::string::FromUtf8Error;
+use std::sync::Arc;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::code_view::{CodeLanguage, CodeView};
+use crate::layer_db_types::{FuncVersionContent, FuncVersionContentV1};
+use crate::workspace_snapshot::content_address::{ContentAddress, ContentAddressDiscriminants};
+use crate::workspace_snapshot::edge_weight::{
+    EdgeWeight, EdgeWeightKind, EdgeWeightKindDiscriminants,
+};
+use crate::workspace_snapshot::node_weight::{NodeWeight, NodeWeightError};
+use crate::workspace_snapshot::WorkspaceSnapshotError;
+use crate::{
+    id, DalContext, Func, FuncError, FuncId, HistoryActor, Timestamp, TransactionsError, UserPk,
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FuncVersionError {
+    #[error("base64 decode error: {0}")]
+    Base64Decode(#[from] base64::DecodeError),
+    #[error("func error: {0}")]
+    Func(#[from] Box<FuncError>),
+    #[error("cannot stage a version draft for locked func: {0}")]
+    FuncLocked(FuncId),
+    #[error("invalid func version \"{0}\": expected MAJOR.MINOR.PATCH")]
+    InvalidVersion(String),
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+    #[error("node weight error: {0}")]
+    NodeWeight(#[from] NodeWeightError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("utf8 error: {0}")]
+    Utf8(#[from] FromUtf8Error),
+    #[error("version {1} not found for func {0}")]
+    VersionNotFound(FuncId, FuncSemVer),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+pub type FuncVersionResult<T> = Result<T, FuncVersionError>;
+
+id!(FuncVersionId);
+
+/// A `MAJOR.MINOR.PATCH` version for a [`Func`]. Serialized as a string.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct FuncSemVer {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl FuncSemVer {
+    /// The version given to the first locked version of a func without any history.
+    pub const INITIAL: Self = Self {
+        major: 1,
+        minor: 0,
+        patch: 0,
+    };
+
+    pub fn bump(self, bump: FuncVersionBump) -> Self {
+        match bump {
+            FuncVersionBump::Major => Self {
+                major: self.major + 1,
+                minor: 0,
+                patch: 0,
+            },
+            FuncVersionBump::Minor => Self {
+                minor: self.minor + 1,
+                patch: 0,
+                ..self
+            },
+            FuncVersionBump::Patch => Self {
+                patch: self.patch + 1,
+                ..self
+            },
+        }
+    }
+}
+
+impl fmt::Display for FuncSemVer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for FuncSemVer {
+    type Err = FuncVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || FuncVersionError::InvalidVersion(s.to_owned());
+        let mut parts = s.trim().trim_start_matches('v').split('.');
+        let mut next = || -> FuncVersionResult<u64> {
+            parts
+                .next()
+                .and_then(|part| part.parse().ok())
+                .ok_or_else(invalid)
+        };
+        let version = Self {
+            major: next()?,
+            minor: next()?,
+            patch: next()?,
+        };
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(version)
+    }
+}
+
+impl TryFrom<String> for FuncSemVer {
+    type Error = FuncVersionError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<FuncSemVer> for String {
+    fn from(value: FuncSemVer) -> Self {
+        value.to_string()
+    }
+}
+
+/// Which part of the [`FuncSemVer`] to increment when the next version is released.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FuncVersionBump {
+    Major,
+    Minor,
+    #[default]
+    Patch,
+}
+
+/// A released version of a [`Func`], captured when the func was locked.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncVersion {
+    pub id: FuncVersionId,
+    pub version: FuncSemVer,
+    /// The locked func whose code this version captured. This is [`None`] for versions imported
+    /// from a module.
+    pub source_func_id: Option<FuncId>,
+    pub message: Option<String>,
+    pub author: Option<UserPk>,
+    pub handler: Option<String>,
+    pub code_base64: Option<String>,
+    #[serde(flatten)]
+    pub timestamp: Timestamp,
+}
+
+/// The staged changelog for the next version of an unlocked [`Func`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncVersionDraft {
+    pub id: FuncVersionId,
+    pub bump: FuncVersionBump,
+    pub message: Option<String>,
+}
+
+/// The code changes between two versions of a [`Func`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncVersionDiff {
+    pub from: FuncSemVer,
+    /// [`None`] when diffing against the func's current code.
+    pub to: Option<FuncSemVer>,
+    pub diff: CodeView,
+}
+
+/// The stored form of both released versions and drafts; a draft has no version yet.
+enum FuncVersionEntry {
+    Draft(FuncVersionDraft),
+    Released(FuncVersion),
+}
+
+impl FuncVersion {
+    pub fn code_plaintext(&self) -> FuncVersionResult<Option<String>> {
+        Ok(match &self.code_base64 {
+            Some(base64_code) => Some(String::from_utf8(
+                general_purpose::STANDARD_NO_PAD.decode(base64_code)?,
+            )?),
+            None => None,
+        })
+    }
+
+    /// Lists the released versions of the [`Func`], oldest first.
+    pub async fn list_for_func(ctx: &DalContext, func_id: FuncId) -> FuncVersionResult<Vec<Self>> {
+        let mut versions: Vec<Self> = list_entries(ctx, func_id)
+            .await?
+            .into_iter()
+            .filter_map(|entry| match entry {
+                FuncVersionEntry::Released(version) => Some(version),
+                FuncVersionEntry::Draft(_) => None,
+            })
+            .collect();
+        versions.sort_by_key(|version| version.version);
+
+        Ok(versions)
+    }
+
+    pub async fn latest_for_func(
+        ctx: &DalContext,
+        func_id: FuncId,
+    ) -> FuncVersionResult<Option<Self>> {
+        Ok(Self::list_for_func(ctx, func_id).await?.pop())
+    }
+
+    pub async fn get_for_func_or_error(
+        ctx: &DalContext,
+        func_id: FuncId,
+        version: FuncSemVer,
+    ) -> FuncVersionResult<Self> {
+        Self::list_for_func(ctx, func_id)
+            .await?
+            .into_iter()
+            .find(|found| found.version == version)
+            .ok_or(FuncVersionError::VersionNotFound(func_id, version))
+    }
+
+    pub async fn get_draft(
+        ctx: &DalContext,
+        func_id: FuncId,
+    ) -> FuncVersionResult<Option<FuncVersionDraft>> {
+        Ok(list_entries(ctx, func_id)
+            .await?
+            .into_iter()
+            .find_map(|entry| match entry {
+                FuncVersionEntry::Draft(draft) => Some(draft),
+                FuncVersionEntry::Released(_) => None,
+            }))
+    }
+
+    /// Stages the changelog for the next version of an unlocked [`Func`], replacing any existing
+    /// draft.
+    #[instrument(name = "func.version.set_draft", level = "debug", skip(ctx))]
+    pub async fn set_draft(
+        ctx: &DalContext,
+        func_id: FuncId,
+        bump: FuncVersionBump,
+        message: Option<String>,
+    ) -> FuncVersionResult<FuncVersionDraft> {
+        let func = Func::get_by_id_or_error(ctx, func_id)
+            .await
+            .map_err(Box::new)?;
+        if func.is_locked {
+            return Err(FuncVersionError::FuncLocked(func_id));
+        }
+
+        if let Some(existing) = Self::get_draft(ctx, func_id).await? {
+            ctx.workspace_snapshot()?
+                .remove_node_by_id(existing.id)
+                .await?;
+        }
+
+        let content = FuncVersionContentV1 {
+            timestamp: Timestamp::now(),
+            version: None,
+            bump,
+            source_func_id: None,
+            message: message.clone(),
+            author: None,
+            handler: None,
+            code_base64: None,
+        };
+        let id = add_entry(ctx, func_id, content).await?;
+
+        Ok(FuncVersionDraft { id, bump, message })
+    }
+
+    /// Records a new version for a [`Func`] that was just locked, consuming its draft. Nothing is
+    /// recorded if there is no draft and the code matches the latest version.
+    #[instrument(
+        name = "func.version.release_for_locked_func",
+        level = "debug",
+        skip_all
+    )]
+    pub async fn release_for_locked_func(
+        ctx: &DalContext,
+        func: &Func,
+    ) -> FuncVersionResult<Option<Self>> {
+        let draft = Self::get_draft(ctx, func.id).await?;
+        let latest = Self::latest_for_func(ctx, func.id).await?;
+
+        if let Some(draft) = &draft {
+            ctx.workspace_snapshot()?
+                .remove_node_by_id(draft.id)
+                .await?;
+        }
+
+        let version = match (&latest, &draft) {
+            (Some(latest), None)
+                if latest.code_base64 == func.code_base64 && latest.handler == func.handler =>
+            {
+                return Ok(None);
+            }
+            (Some(latest), draft) => latest
+                .version
+                .bump(draft.as_ref().map(|draft| draft.bump).unwrap_or_default()),
+            (None, _) => FuncSemVer::INITIAL,
+        };
+
+        let author = match ctx.history_actor() {
+            HistoryActor::User(user_pk) => Some(*user_pk),
+            HistoryActor::SystemInit => None,
+        };
+        let content = FuncVersionContentV1 {
+            timestamp: Timestamp::now(),
+            version: Some(version),
+            bump: draft.as_ref().map(|draft| draft.bump).unwrap_or_default(),
+            source_func_id: Some(func.id),
+            message: draft.and_then(|draft| draft.message),
+            author,
+            handler: func.handler.clone(),
+            code_base64: func.code_base64.clone(),
+        };
+        let id = add_entry(ctx, func.id, content.clone()).await?;
+
+        Ok(Some(Self {
+            id,
+            version,
+            source_func_id: content.source_func_id,
+            message: content.message,
+            author: content.author,
+            handler: content.handler,
+            code_base64: content.code_base64,
+            timestamp: content.timestamp,
+        }))
+    }
+
+    /// Adds an already released version to the history of a [`Func`], e.g. when importing a
+    /// module. Versions that are already in the history are skipped.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn import(
+        ctx: &DalContext,
+        func_id: FuncId,
+        version: FuncSemVer,
+        message: Option<String>,
+        author: Option<UserPk>,
+        handler: Option<String>,
+        code_base64: Option<String>,
+        timestamp: Timestamp,
+    ) -> FuncVersionResult<()> {
+        if Self::list_for_func(ctx, func_id)
+            .await?
+            .iter()
+            .any(|existing| existing.version == version)
+        {
+            return Ok(());
+        }
+
+        add_entry(
+            ctx,
+            func_id,
+            FuncVersionContentV1 {
+                timestamp,
+                version: Some(version),
+                bump: FuncVersionBump::default(),
+                source_func_id: None,
+                message,
+                author,
+                handler,
+                code_base64,
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Copies the released history of one [`Func`] to another, e.g. when creating an unlocked
+    /// copy. The content is shared, so only new nodes are created.
+    pub async fn copy_all(
+        ctx: &DalContext,
+        from_func_id: FuncId,
+        to_func_id: FuncId,
+    ) -> FuncVersionResult<()> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        for version in Self::list_for_func(ctx, from_func_id).await? {
+            let content_hash = workspace_snapshot
+                .get_node_weight_by_id(version.id)
+                .await?
+                .get_content_node_weight_of_kind(ContentAddressDiscriminants::FuncVersion)?
+                .content_hash();
+
+            let id = workspace_snapshot.generate_ulid().await?;
+            let lineage_id = workspace_snapshot.generate_ulid().await?;
+            workspace_snapshot
+                .add_or_replace_node(NodeWeight::new_content(
+                    id,
+                    lineage_id,
+                    ContentAddress::FuncVersion(content_hash),
+                ))
+                .await?;
+            workspace_snapshot
+                .add_edge(to_func_id, EdgeWeight::new(EdgeWeightKind::FuncVersion), id)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Diffs the code of two versions of a [`Func`]. When `to` is [`None`], the diff is against
+    /// the func's current code.
+    pub async fn diff(
+        ctx: &DalContext,
+        func_id: FuncId,
+        from: FuncSemVer,
+        to: Option<FuncSemVer>,
+    ) -> FuncVersionResult<FuncVersionDiff> {
+        let from_code = Self::get_for_func_or_error(ctx, func_id, from)
+            .await?
+            .code_plaintext()?
+            .unwrap_or_default();
+        let to_code = match to {
+            Some(to) => Self::get_for_func_or_error(ctx, func_id, to)
+                .await?
+                .code_plaintext()?,
+            None => Func::get_by_id_or_error(ctx, func_id)
+                .await
+                .map_err(Box::new)?
+                .code_plaintext()
+                .map_err(Box::new)?,
+        }
+        .unwrap_or_default();
+
+        let lines: Vec<String> = diff::lines(&from_code, &to_code)
+            .into_iter()
+            .map(|line| match line {
+                diff::Result::Left(left) => format!("-{left}"),
+                diff::Result::Both(unchanged, _) => format!(" {unchanged}"),
+                diff::Result::Right(right) => format!("+{right}"),
+            })
+            .collect();
+
+        Ok(FuncVersionDiff {
+            from,
+            to,
+            diff: CodeView::assemble(CodeLanguage::Diff, Some(lines.join("\n")), None, None),
+        })
+    }
+}
+
+fn assemble(id: FuncVersionId, content: FuncVersionContentV1) -> FuncVersionEntry {
+    match content.version {
+        Some(version) => FuncVersionEntry::Released(FuncVersion {
+            id,
+            version,
+            source_func_id: content.source_func_id,
+            message: content.message,
+            author: content.author,
+            handler: content.handler,
+            code_base64: content.code_base64,
+            timestamp: content.timestamp,
+        }),
+        None => FuncVersionEntry::Draft(FuncVersionDraft {
+            id,
+            bump: content.bump,
+            message: content.message,
+        }),
+    }
+}
+
+async fn list_entries(
+    ctx: &DalContext,
+    func_id: FuncId,
+) -> FuncVersionResult<Vec<FuncVersionEntry>> {
+    let workspace_snapshot = ctx.workspace_snapshot()?;
+
+    let mut entries = vec![];
+    for idx in workspace_snapshot
+        .outgoing_targets_for_edge_weight_kind(func_id, EdgeWeightKindDiscriminants::FuncVersion)
+        .await?
+    {
+        let node_weight = workspace_snapshot
+            .get_node_weight(idx)
+            .await?
+            .get_content_node_weight_of_kind(ContentAddressDiscriminants::FuncVersion)?;
+        let id = node_weight.id();
+
+        let content: FuncVersionContent = ctx
+            .layer_db()
+            .cas()
+            .try_read_as(&node_weight.content_hash())
+            .await?
+            .ok_or(WorkspaceSnapshotError::MissingContentFromStore(id))?;
+
+        entries.push(assemble(id.into(), content.extract()));
+    }
+
+    Ok(entries)
+}
+
+async fn add_entry(
+    ctx: &DalContext,
+    func_id: FuncId,
+    content: FuncVersionContentV1,
+) -> FuncVersionResult<FuncVersionId> {
+    let (hash, _) = ctx.layer_db().cas().write(
+        Arc::new(FuncVersionContent::V1(content).into()),
+        None,
+        ctx.events_tenancy(),
+        ctx.events_actor(),
+    )?;
+
+    let workspace_snapshot = ctx.workspace_snapshot()?;
+    let id = workspace_snapshot.generate_ulid().await?;
+    let lineage_id = workspace_snapshot.generate_ulid().await?;
+    workspace_snapshot
+        .add_or_replace_node(NodeWeight::new_content(
+            id,
+            lineage_id,
+            ContentAddress::FuncVersion(hash),
+        ))
+        .await?;
+    workspace_snapshot
+        .add_edge(func_id, EdgeWeight::new(EdgeWeightKind::FuncVersion), id)
+        .await?;
+
+    Ok(id.into())
+}
@@ -0,0 +1,181 @@
+//! This module contains the "mark" phase of garbage collection for the layer db.
+//!
+//! Every change set that is still in use (or was applied or abandoned within the retention
+//! window) is a root, as is the merge base of every change set still in use and the restore point
+//! of every change set applied within the window (see [`crate::change_set::restore`]). The
+//! snapshot for each root is walked to collect the content hashes it references, along with the
+//! hashes nested inside that content (such as the code blob of a func). The result is handed to
+//! [`LayerDb::sweep`](si_layer_cache::LayerDb::sweep), which deletes everything older than the
+//! retention window that was not marked.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use si_data_pg::PgError;
+use si_events::{ContentHash, WorkspaceSnapshotAddress};
+use si_layer_cache::{
+    db::gc::{GcOptions, GcReport, GcRoots},
+    LayerDbError,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    layer_db_types::FuncContent,
+    workspace_snapshot::{graph::WorkspaceSnapshotGraphDiscriminants, node_weight::NodeWeight},
+    ChangeSetStatus, DalContext, TransactionsError, WorkspaceSnapshotGraph,
+};
+
+/// How many content values are read at a time when looking for nested content hashes.
+const NESTED_CONTENT_BATCH_SIZE: usize = 1000;
+
+const LIST_ROOT_ADDRESSES_QUERY: &str = "
+    SELECT workspace_snapshot_address
+    FROM change_set_pointers
+    WHERE status NOT IN ($1, $2, $3) OR updated_at >= $4
+    UNION
+    SELECT merge_base_snapshot_address AS workspace_snapshot_address
+    FROM change_set_pointers
+    WHERE status NOT IN ($1, $2, $3) AND merge_base_snapshot_address IS NOT NULL
+    UNION
+    SELECT applied_base_snapshot_address AS workspace_snapshot_address
+    FROM change_set_pointers
+    WHERE status = $2 AND updated_at >= $4 AND applied_base_snapshot_address IS NOT NULL
+";
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum GarbageCollectionError {
+    #[error("chrono out of range error: {0}")]
+    ChronoOutOfRange(#[from] chrono::OutOfRangeError),
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace snapshot {0} has not been migrated to the current graph version ({1}); refusing to collect garbage")]
+    UnmigratedSnapshot(
+        WorkspaceSnapshotAddress,
+        WorkspaceSnapshotGraphDiscriminants,
+    ),
+}
+
+pub type GarbageCollectionResult<T> = Result<T, GarbageCollectionError>;
+
+/// Collects unreachable workspace snapshots and content from the layer db.
+///
+/// With [`GcOptions::dry_run`] set, nothing is deleted and the returned report describes what
+/// would have been collected.
+#[instrument(
+    name = "garbage_collection.collect",
+    level = "info",
+    skip_all,
+    fields(
+        si.layer_db.gc.dry_run = options.dry_run,
+        si.layer_db.gc.roots = Empty,
+    ),
+)]
+pub async fn collect(ctx: &DalContext, options: GcOptions) -> GarbageCollectionResult<GcReport> {
+    let span = current_span_for_instrument_at!("info");
+
+    let roots = mark(ctx, options).await?;
+    span.record(
+        "si.layer_db.gc.roots",
+        roots.workspace_snapshot_addresses.len(),
+    );
+
+    Ok(ctx
+        .layer_db()
+        .sweep(&roots, options, ctx.events_tenancy(), ctx.events_actor())
+        .await?)
+}
+
+/// Walks every root snapshot and returns the set of reachable snapshot addresses and content
+/// hashes.
+#[instrument(name = "garbage_collection.mark", level = "info", skip_all)]
+pub async fn mark(ctx: &DalContext, options: GcOptions) -> GarbageCollectionResult<GcRoots> {
+    let cutoff = Utc::now() - chrono::Duration::from_std(options.retention)?;
+
+    let mut root_addresses: HashSet<WorkspaceSnapshotAddress> = HashSet::new();
+    let rows = ctx
+        .txns()
+        .await?
+        .pg()
+        .query(
+            LIST_ROOT_ADDRESSES_QUERY,
+            &[
+                &ChangeSetStatus::Abandoned.to_string(),
+                &ChangeSetStatus::Applied.to_string(),
+                &ChangeSetStatus::Failed.to_string(),
+                &cutoff,
+            ],
+        )
+        .await?;
+    for row in rows {
+        root_addresses.insert(row.try_get("workspace_snapshot_address")?);
+    }
+
+    // Snapshots written recently may not have a change set pointing to them yet (for example,
+    // when a rebase is in flight), so they are roots as well.
+    root_addresses.extend(
+        ctx.layer_db()
+            .recent_workspace_snapshot_addresses(options.retention)
+            .await?,
+    );
+
+    let mut roots = GcRoots::new();
+    let mut func_content_hashes = HashSet::new();
+    for address in root_addresses {
+        roots.mark_workspace_snapshot_address(address);
+
+        // Read straight from the layer db rather than going through `WorkspaceSnapshot::find`,
+        // which waits for the snapshot to show up in memory.
+        let Some(graph) = ctx.layer_db().workspace_snapshot().read(&address).await? else {
+            warn!(
+                si.workspace_snapshot.address = %address,
+                "root workspace snapshot is missing; skipping",
+            );
+            continue;
+        };
+
+        // Walking an unmigrated graph is not possible, and skipping it could collect content
+        // that it still references.
+        let version = WorkspaceSnapshotGraphDiscriminants::from(graph.as_ref());
+        if version != WorkspaceSnapshotGraph::current_discriminant() {
+            return Err(GarbageCollectionError::UnmigratedSnapshot(address, version));
+        }
+
+        for (node_weight, _) in graph.nodes() {
+            if let NodeWeight::Func(func_node_weight) = node_weight {
+                func_content_hashes.insert(func_node_weight.content_hash());
+            }
+            roots.mark_content_hashes(node_weight.content_store_hashes());
+        }
+    }
+
+    mark_nested_content_hashes(ctx, &mut roots, func_content_hashes).await?;
+
+    Ok(roots)
+}
+
+/// Marks the content hashes embedded inside content rather than referenced by the graph. Today
+/// that is only the code blob of every func, which [`FuncContent`] refers to by hash.
+async fn mark_nested_content_hashes(
+    ctx: &DalContext,
+    roots: &mut GcRoots,
+    func_content_hashes: HashSet<ContentHash>,
+) -> GarbageCollectionResult<()> {
+    let func_content_hashes: Vec<ContentHash> = func_content_hashes.into_iter().collect();
+    for batch in func_content_hashes.chunks(NESTED_CONTENT_BATCH_SIZE) {
+        let contents: HashMap<ContentHash, FuncContent> =
+            ctx.layer_db().cas().try_read_many_as(batch).await?;
+        roots.mark_content_hashes(
+            contents
+                .into_values()
+                .map(|content| content.extract().code_blake3),
+        );
+    }
+
+    Ok(())
+}
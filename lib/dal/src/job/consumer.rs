@@ -13,6 +13,7 @@ use tokio::task::JoinError;
 
 use crate::billing_publish::BillingPublishError;
 use crate::diagram::DiagramError;
+use crate::func::test_case::FuncTestCaseError;
 use crate::prop::PropError;
 use crate::validation::ValidationError;
 use crate::FuncError;
@@ -52,6 +53,8 @@ pub enum JobConsumerError {
     Diagram(#[from] DiagramError),
     #[error("func error: {0}")]
     Func(#[from] FuncError),
+    #[error("func test case error: {0}")]
+    FuncTestCase(#[from] FuncTestCaseError),
     #[error("Invalid job arguments. Expected: {0} Actual: {1:?}")]
     InvalidArguments(String, Vec<Value>),
     #[error("std io error: {0}")]
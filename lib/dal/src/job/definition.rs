@@ -1,9 +1,11 @@
 mod action;
 pub mod compute_validation;
 pub mod dependent_values_update;
+mod func_test_cases;
 
 pub use action::ActionJob;
 pub use dependent_values_update::DependentValuesUpdate;
+pub use func_test_cases::FuncTestCasesJob;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 pub enum AttributeValueBasedJobIdentifier {
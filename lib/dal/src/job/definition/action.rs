@@ -241,6 +241,11 @@ async fn process_execution(
         if run_result.status == ResourceStatus::Ok {
             success = true;
 
+            // Let the batch destroy what this created if a later action in it fails.
+            if prototype.kind == ActionKind::Create {
+                Action::record_created_by_batch(ctx, action_id, component_id).await?;
+            }
+
             // Remove `ActionId` from graph as the execution succeeded
             Action::remove_by_id(ctx, action_id).await?;
 
@@ -300,6 +305,10 @@ async fn process_execution(
         Action::set_state(ctx, action_id, ActionState::Failed).await?;
     }
 
+    if !success {
+        Action::rollback_batch_for_failed_action(ctx, action_id).await?;
+    }
+
     WsEvent::action_list_updated(ctx)
         .await?
         .publish_on_commit(ctx)
@@ -344,6 +353,8 @@ async fn process_failed_action(ctx: &DalContext, action_id: ActionId) -> JobCons
         )
         .await?;
 
+    Action::rollback_batch_for_failed_action(ctx, action_id).await?;
+
     ctx.commit().await?;
     Ok(())
 }
@@ -3,17 +3,19 @@ use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     convert::TryFrom,
     sync::Arc,
+    time::Duration,
 };
 use telemetry_utils::metric;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use si_events::FuncRunValue;
+use si_events::{FuncRunValue, WorkspaceSnapshotAddress};
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::{
     sync::RwLock,
     task::{JoinError, JoinSet},
+    time::Instant,
 };
 use ulid::Ulid;
 
@@ -29,15 +31,23 @@ use crate::{
     prop::PropError,
     status::{StatusMessageState, StatusUpdate, StatusUpdateError},
     workspace_snapshot::DependentValueRoot,
-    AccessBuilder, AttributeValue, AttributeValueId, ComponentError, ComponentId, DalContext, Func,
-    TransactionsError, Visibility, WorkspacePk, WorkspaceSnapshotError, WsEvent, WsEventError,
+    AccessBuilder, AttributeValue, AttributeValueId, ChangeSet, ChangeSetError, Component,
+    ComponentError, ComponentId, DalContext, Func, TransactionsError, Visibility, WorkspacePk,
+    WorkspaceSnapshot, WorkspaceSnapshotError, WsEvent, WsEventError,
 };
 
+/// How often to look for dependent value roots added to the change set while the job runs.
+const SUPERSEDED_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// How often to send progress, so large updates don't flood the frontend.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum DependentValueUpdateError {
     #[error("attribute value error: {0}")]
     AttributeValue(#[from] AttributeValueError),
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
     #[error("component error: {0}")]
     Component(#[from] ComponentError),
     #[error("dependent values update audit log error: {0}")]
@@ -203,6 +213,10 @@ impl StatusUpdateTracker {
             )
     }
 
+    fn component_ids(&self) -> HashSet<ComponentId> {
+        self.components_by_value.values().copied().collect()
+    }
+
     fn finish_remaining(&self) -> Vec<StatusUpdate> {
         self.values_by_component
             .iter()
@@ -226,6 +240,149 @@ impl StatusUpdateTracker {
     }
 }
 
+/// Values that changes made to the change set since the job started will recompute anyway.
+///
+/// New dependent value roots land on the change set's snapshot rather than on the copy this job
+/// works on, and the next dependent values update computes everything they reach. Computing
+/// those values now would only have the result thrown away, so they are skipped instead.
+struct SupersededValues {
+    snapshot_address: Option<WorkspaceSnapshotAddress>,
+    known_roots: HashSet<Ulid>,
+    value_ids: HashSet<AttributeValueId>,
+    last_checked: Instant,
+}
+
+impl SupersededValues {
+    fn new(roots: &[DependentValueRoot]) -> Self {
+        Self {
+            snapshot_address: None,
+            known_roots: roots
+                .iter()
+                .map(|root| si_events::ulid::Ulid::from(*root).into())
+                .collect(),
+            value_ids: HashSet::new(),
+            last_checked: Instant::now(),
+        }
+    }
+
+    /// Blocks the value, and so everything that depends on it, if newer roots will recompute it.
+    /// Returns whether the value was skipped.
+    fn skip(&self, dependency_graph: &mut DependentValueGraph, value_id: AttributeValueId) -> bool {
+        if !self.value_ids.contains(&value_id) {
+            return false;
+        }
+        dependency_graph.cycle_on_self(value_id);
+        true
+    }
+
+    /// Records the given roots, returning the unfinished ones that haven't been seen before.
+    fn unseen_roots(&mut self, roots: Vec<DependentValueRoot>) -> Vec<DependentValueRoot> {
+        roots
+            .into_iter()
+            .filter(|root| {
+                matches!(root, DependentValueRoot::Unfinished(_))
+                    && self
+                        .known_roots
+                        .insert(si_events::ulid::Ulid::from(*root).into())
+            })
+            .collect()
+    }
+
+    async fn refresh(&mut self, ctx: &DalContext) -> DependentValueUpdateResult<()> {
+        if self.last_checked.elapsed() < SUPERSEDED_CHECK_INTERVAL {
+            return Ok(());
+        }
+        self.last_checked = Instant::now();
+
+        let Some(change_set) = ChangeSet::find(ctx, ctx.change_set_id()).await? else {
+            return Ok(());
+        };
+        if self.snapshot_address == Some(change_set.workspace_snapshot_address) {
+            return Ok(());
+        }
+        self.snapshot_address = Some(change_set.workspace_snapshot_address);
+
+        let latest_snapshot =
+            WorkspaceSnapshot::find(ctx, change_set.workspace_snapshot_address).await?;
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let mut new_roots = Vec::new();
+        for root in self.unseen_roots(latest_snapshot.get_dependent_value_roots().await?) {
+            // Values that don't exist in this job's snapshot can't be in its graph.
+            if workspace_snapshot
+                .get_node_index_by_id_opt(root)
+                .await
+                .is_some()
+            {
+                new_roots.push(root);
+            }
+        }
+        if new_roots.is_empty() {
+            return Ok(());
+        }
+
+        let superseded_graph = DependentValueGraph::new(ctx, new_roots).await?;
+        let superseded_value_ids = superseded_graph.all_value_ids();
+        debug!(
+            superseded = superseded_value_ids.len(),
+            "new dependent value roots arrived during dependent values update"
+        );
+        self.value_ids.extend(superseded_value_ids);
+
+        Ok(())
+    }
+}
+
+struct ProgressReporter {
+    total: usize,
+    computed: usize,
+    skipped: usize,
+    last_sent: Option<Instant>,
+}
+
+impl ProgressReporter {
+    fn new(total: usize) -> Self {
+        Self {
+            total,
+            computed: 0,
+            skipped: 0,
+            last_sent: None,
+        }
+    }
+
+    /// The current progress, unless progress was sent within the last [`PROGRESS_INTERVAL`].
+    fn throttled_update(&mut self) -> Option<StatusUpdate> {
+        if self
+            .last_sent
+            .is_some_and(|last_sent| last_sent.elapsed() < PROGRESS_INTERVAL)
+        {
+            return None;
+        }
+        Some(self.update())
+    }
+
+    fn update(&mut self) -> StatusUpdate {
+        self.last_sent = Some(Instant::now());
+        StatusUpdate::new_dvu_progress(self.total, self.computed, self.skipped)
+    }
+
+    async fn send_throttled(&mut self, ctx: &DalContext) {
+        if let Some(status_update) = self.throttled_update() {
+            send_progress(ctx, status_update).await;
+        }
+    }
+
+    async fn send(&mut self, ctx: &DalContext) {
+        let status_update = self.update();
+        send_progress(ctx, status_update).await;
+    }
+}
+
+async fn send_progress(ctx: &DalContext, status_update: StatusUpdate) {
+    if let Err(err) = send_status_update(ctx, status_update).await {
+        error!(si.error.message = ?err, "status update progress event send failed");
+    }
+}
+
 impl DependentValuesUpdate {
     async fn inner_run(
         &self,
@@ -256,8 +413,11 @@ impl DependentValuesUpdate {
             finished_values.clear();
         }
 
-        let concurrency_limit = ctx.get_workspace().await?.component_concurrency_limit() as usize;
+        let workspace = ctx.get_workspace().await?;
+        let concurrency_limit = workspace.component_concurrency_limit() as usize;
+        let value_concurrency_limit = workspace.value_concurrency_limit() as usize;
 
+        let mut superseded = SupersededValues::new(&roots);
         let mut dependency_graph = DependentValueGraph::new(ctx, roots).await?;
 
         debug!(
@@ -277,6 +437,9 @@ impl DependentValuesUpdate {
         let all_value_ids = dependency_graph.all_value_ids();
         metric!(counter.dvu.values_to_run = all_value_ids.len());
 
+        let mut progress = ProgressReporter::new(all_value_ids.len());
+        progress.send(ctx).await;
+
         let mut tracker = StatusUpdateTracker::new_for_values(ctx, all_value_ids).await?;
 
         let mut spawned_ids = HashSet::new();
@@ -300,12 +463,26 @@ impl DependentValuesUpdate {
                     break;
                 }
             } else {
+                superseded.refresh(ctx).await?;
+
                 for attribute_value_id in &independent_value_ids {
                     let attribute_value_id = *attribute_value_id;
                     let parent_span = span.clone();
                     if !spawned_ids.contains(&attribute_value_id)
                         && !would_start_ids.contains(&attribute_value_id)
                     {
+                        // The remaining independent values are spawned as running ones finish.
+                        if task_id_to_av_id.len() >= value_concurrency_limit {
+                            break;
+                        }
+
+                        // Blocking the value also blocks everything that depends on it, all of
+                        // which the newer roots reach as well.
+                        if superseded.skip(&mut dependency_graph, attribute_value_id) {
+                            progress.skipped += 1;
+                            continue;
+                        }
+
                         let id = Ulid::new();
 
                         if tracker.would_start_component(attribute_value_id)
@@ -346,6 +523,7 @@ impl DependentValuesUpdate {
 
                 metric!(counter.dvu.values_to_run = -1);
                 metric!(counter.dvu.function_execution = -1);
+                progress.computed += 1;
 
                 if let Some(finished_value_id) = task_id_to_av_id.remove(&task_id) {
                     match execution_result {
@@ -433,9 +611,12 @@ impl DependentValuesUpdate {
                 }
             }
 
+            progress.send_throttled(ctx).await;
             independent_value_ids = dependency_graph.independent_values().into_iter().collect();
         }
 
+        progress.send(ctx).await;
+
         let snap = ctx.workspace_snapshot()?;
         let mut added_unfinished = false;
         for value_id in &independent_value_ids {
@@ -467,6 +648,16 @@ impl DependentValuesUpdate {
 
         debug!("DependentValuesUpdate took: {:?}", start.elapsed());
 
+        // Resource values may have been recomputed, so let the frontend know how the resources
+        // on HEAD now compare against their domains.
+        if ctx.change_set_id() == workspace.default_change_set_id() {
+            for component_id in tracker.component_ids() {
+                if let Err(err) = send_drift_update(ctx, component_id).await {
+                    error!(si.error.message = ?err, %component_id, "drift update event send failed");
+                }
+            }
+        }
+
         ctx.commit().await?;
         metric!(counter.dvu_concurrency_count = -1);
         Ok(JobCompletionState::Done)
@@ -559,6 +750,22 @@ async fn send_status_update(
     Ok(())
 }
 
+async fn send_drift_update(
+    ctx: &DalContext,
+    component_id: ComponentId,
+) -> DependentValueUpdateResult<()> {
+    // Components without a resource have nothing to drift from.
+    let Some(drift) = Component::resource_drift(ctx, component_id).await? else {
+        return Ok(());
+    };
+    WsEvent::component_drift_updated(ctx, drift)
+        .await?
+        .publish_on_commit(ctx)
+        .await?;
+
+    Ok(())
+}
+
 impl TryFrom<JobInfo> for DependentValuesUpdate {
     type Error = JobConsumerError;
 
@@ -718,3 +925,95 @@ pub mod audit_log {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unseen_roots_are_new_unfinished_roots() {
+        let known = AttributeValueId::generate();
+        let finished = AttributeValueId::generate();
+        let unseen = AttributeValueId::generate();
+        let mut superseded = SupersededValues::new(&[DependentValueRoot::Unfinished(known.into())]);
+
+        let roots = vec![
+            DependentValueRoot::Unfinished(known.into()),
+            DependentValueRoot::Finished(finished.into()),
+            DependentValueRoot::Unfinished(unseen.into()),
+        ];
+        assert_eq!(
+            vec![DependentValueRoot::Unfinished(unseen.into())],
+            superseded.unseen_roots(roots.clone())
+        );
+        // Roots are only reported the first time they are seen.
+        assert!(superseded.unseen_roots(roots).is_empty());
+    }
+
+    #[test]
+    fn superseded_values_and_their_dependents_are_skipped() {
+        let superseded_value = AttributeValueId::generate();
+        let dependent_of_superseded = AttributeValueId::generate();
+        let other_value = AttributeValueId::generate();
+        let dependent_of_other = AttributeValueId::generate();
+
+        let mut dependency_graph = DependentValueGraph::empty();
+        dependency_graph.value_depends_on(dependent_of_superseded, superseded_value);
+        dependency_graph.value_depends_on(dependent_of_other, other_value);
+
+        let mut superseded = SupersededValues::new(&[]);
+        superseded.value_ids.insert(superseded_value);
+
+        assert!(superseded.skip(&mut dependency_graph, superseded_value));
+        assert!(!superseded.skip(&mut dependency_graph, other_value));
+        assert_eq!(vec![other_value], dependency_graph.independent_values());
+
+        dependency_graph.remove_value(other_value);
+        assert_eq!(
+            vec![dependent_of_other],
+            dependency_graph.independent_values()
+        );
+        dependency_graph.remove_value(dependent_of_other);
+
+        // Neither the superseded value nor anything depending on it ever becomes independent.
+        assert!(dependency_graph.independent_values().is_empty());
+        assert!(dependency_graph.contains_value(dependent_of_superseded));
+    }
+
+    fn progress(status_update: Option<StatusUpdate>) -> Option<(usize, usize, usize)> {
+        status_update.map(|status_update| match status_update {
+            StatusUpdate::DependentValueUpdateProgress {
+                total,
+                computed,
+                skipped,
+                ..
+            } => (total, computed, skipped),
+            other => panic!("unexpected status update: {other:?}"),
+        })
+    }
+
+    #[test]
+    fn progress_is_throttled() {
+        let mut reporter = ProgressReporter::new(10);
+        assert_eq!(Some((10, 0, 0)), progress(Some(reporter.update())));
+
+        reporter.computed += 1;
+        assert_eq!(None, progress(reporter.throttled_update()));
+
+        // Once the interval has passed, the latest counts go out.
+        reporter.skipped += 2;
+        reporter.last_sent = Instant::now().checked_sub(PROGRESS_INTERVAL);
+        assert_eq!(Some((10, 1, 2)), progress(reporter.throttled_update()));
+        assert_eq!(None, progress(reporter.throttled_update()));
+
+        // Unthrottled updates always go out.
+        reporter.computed += 7;
+        assert_eq!(Some((10, 8, 2)), progress(Some(reporter.update())));
+    }
+
+    #[test]
+    fn first_throttled_progress_is_sent() {
+        let mut reporter = ProgressReporter::new(3);
+        assert_eq!(Some((3, 0, 0)), progress(reporter.throttled_update()));
+    }
+}
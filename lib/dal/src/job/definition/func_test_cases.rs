@@ -0,0 +1,124 @@
+use std::convert::TryFrom;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+use crate::{
+    func::test_case::FuncTestCase,
+    job::{
+        consumer::{
+            JobCompletionState, JobConsumer, JobConsumerError, JobConsumerMetadata,
+            JobConsumerResult, JobInfo,
+        },
+        producer::{JobProducer, JobProducerResult},
+    },
+    AccessBuilder, DalContext, FuncId, Visibility, WsEvent,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FuncTestCasesJobArgs {
+    func_id: FuncId,
+}
+
+impl From<FuncTestCasesJob> for FuncTestCasesJobArgs {
+    fn from(value: FuncTestCasesJob) -> Self {
+        Self {
+            func_id: value.func_id,
+        }
+    }
+}
+
+/// Runs every test case attached to a func and reports the results over the websocket.
+#[derive(Clone, Debug, Serialize)]
+pub struct FuncTestCasesJob {
+    func_id: FuncId,
+    access_builder: AccessBuilder,
+    visibility: Visibility,
+    job: Option<JobInfo>,
+}
+
+impl FuncTestCasesJob {
+    pub fn new(ctx: &DalContext, func_id: FuncId) -> Box<Self> {
+        let access_builder = AccessBuilder::from(ctx.clone());
+        let visibility = *ctx.visibility();
+
+        Box::new(Self {
+            func_id,
+            access_builder,
+            visibility,
+            job: None,
+        })
+    }
+}
+
+impl JobProducer for FuncTestCasesJob {
+    fn arg(&self) -> JobProducerResult<serde_json::Value> {
+        Ok(serde_json::to_value(FuncTestCasesJobArgs::from(
+            self.clone(),
+        ))?)
+    }
+}
+
+impl JobConsumerMetadata for FuncTestCasesJob {
+    fn type_name(&self) -> String {
+        "FuncTestCasesJob".to_string()
+    }
+
+    fn access_builder(&self) -> AccessBuilder {
+        self.access_builder
+    }
+
+    fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+}
+
+#[async_trait]
+impl JobConsumer for FuncTestCasesJob {
+    #[instrument(
+        name = "func_test_cases_job.run",
+        skip_all,
+        level = "info",
+        fields(
+            si.func.id = %self.func_id,
+            job = ?self.job,
+        )
+    )]
+    async fn run(&self, ctx: &mut DalContext) -> JobConsumerResult<JobCompletionState> {
+        match FuncTestCase::run_all_for_func(ctx, self.func_id).await {
+            Ok(results) => {
+                WsEvent::func_test_cases_run(ctx, self.func_id, results)
+                    .await?
+                    .publish_immediately(ctx)
+                    .await?;
+            }
+            Err(err) => {
+                // Let the author know the run didn't complete, then surface the error so it is
+                // logged alongside the job.
+                WsEvent::func_test_cases_run_failed(ctx, self.func_id, err.to_string())
+                    .await?
+                    .publish_immediately(ctx)
+                    .await?;
+                return Err(err.into());
+            }
+        }
+
+        Ok(JobCompletionState::Done)
+    }
+}
+
+impl TryFrom<JobInfo> for FuncTestCasesJob {
+    type Error = JobConsumerError;
+
+    fn try_from(job: JobInfo) -> Result<Self, Self::Error> {
+        let args = FuncTestCasesJobArgs::deserialize(&job.arg)?;
+
+        Ok(Self {
+            func_id: args.func_id,
+            access_builder: job.access_builder,
+            visibility: job.visibility,
+            job: Some(job),
+        })
+    }
+}
@@ -8,6 +8,7 @@ use strum::EnumDiscriminants;
 use thiserror::Error;
 
 use crate::action::prototype::ActionKind;
+use crate::func::version::{FuncSemVer, FuncVersionBump};
 use crate::validation::ValidationStatus;
 use crate::{
     action::ActionCompletionStatus, func::argument::FuncArgumentKind, prop::WidgetOptions,
@@ -60,6 +61,8 @@ pub enum ContentTypes {
     ManagementPrototype(ManagementPrototypeContent),
     Geometry(GeometryContent),
     View(ViewContent),
+    FuncTestCase(FuncTestCaseContent),
+    FuncVersion(FuncVersionContent),
 }
 
 macro_rules! impl_into_content_types {
@@ -117,6 +120,8 @@ impl_into_content_types!(Validation);
 impl_into_content_types!(ManagementPrototype);
 impl_into_content_types!(Geometry);
 impl_into_content_types!(View);
+impl_into_content_types!(FuncTestCase);
+impl_into_content_types!(FuncVersion);
 
 // Here we've broken the Foo, FooContent convention so we need to implement
 // these traits manually
@@ -267,6 +272,54 @@ pub struct ViewContentV1 {
     pub name: String,
 }
 
+#[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
+pub enum FuncTestCaseContent {
+    V1(FuncTestCaseContentV1),
+}
+
+impl FuncTestCaseContent {
+    pub fn extract(self) -> FuncTestCaseContentV1 {
+        let FuncTestCaseContent::V1(content) = self;
+        content
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FuncTestCaseContentV1 {
+    pub timestamp: Timestamp,
+    pub name: String,
+    pub description: Option<String>,
+    /// A serialized [`FuncTestCaseFixture`](crate::func::test_case::FuncTestCaseFixture).
+    pub fixture: CasValue,
+    /// A serialized [`FuncTestCaseExpectation`](crate::func::test_case::FuncTestCaseExpectation).
+    pub expectation: CasValue,
+}
+
+#[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
+pub enum FuncVersionContent {
+    V1(FuncVersionContentV1),
+}
+
+impl FuncVersionContent {
+    pub fn extract(self) -> FuncVersionContentV1 {
+        let FuncVersionContent::V1(content) = self;
+        content
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FuncVersionContentV1 {
+    pub timestamp: Timestamp,
+    /// [`None`] for the draft of the next version of an unlocked func.
+    pub version: Option<FuncSemVer>,
+    pub bump: FuncVersionBump,
+    pub source_func_id: Option<FuncId>,
+    pub message: Option<String>,
+    pub author: Option<UserPk>,
+    pub handler: Option<String>,
+    pub code_base64: Option<String>,
+}
+
 #[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
 pub enum GeometryContent {
     V1(GeometryContentV1),
@@ -292,6 +345,7 @@ pub struct GeometryContentV1 {
 pub enum FuncContent {
     V1(FuncContentV1),
     V2(FuncContentV2),
+    V3(FuncContentV3),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -326,10 +380,30 @@ pub struct FuncContentV2 {
     pub is_locked: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FuncContentV3 {
+    pub timestamp: Timestamp,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub hidden: bool,
+    pub builtin: bool,
+    pub backend_response_type: FuncBackendResponseType,
+    pub backend_kind: FuncBackendKind,
+    pub handler: Option<String>,
+    pub code_base64: Option<String>,
+    /// A hash of the code above
+    pub code_blake3: ContentHash,
+    pub is_locked: bool,
+    /// How long an execution of the func may run before it is killed, if it should differ from
+    /// the default timeout of the function executor
+    pub timeout_secs: Option<u64>,
+}
+
 impl FuncContent {
-    pub fn extract(self) -> FuncContentV2 {
+    pub fn extract(self) -> FuncContentV3 {
         match self {
-            FuncContent::V1(v1) => FuncContentV2 {
+            FuncContent::V1(v1) => FuncContentV3 {
                 timestamp: v1.timestamp,
                 hidden: v1.hidden,
                 display_name: v1.display_name,
@@ -342,8 +416,24 @@ impl FuncContent {
                 handler: v1.handler,
                 code_base64: v1.code_base64,
                 code_blake3: v1.code_blake3,
+                timeout_secs: None,
+            },
+            FuncContent::V2(v2) => FuncContentV3 {
+                timestamp: v2.timestamp,
+                display_name: v2.display_name,
+                description: v2.description,
+                link: v2.link,
+                hidden: v2.hidden,
+                builtin: v2.builtin,
+                backend_response_type: v2.backend_response_type,
+                backend_kind: v2.backend_kind,
+                handler: v2.handler,
+                code_base64: v2.code_base64,
+                code_blake3: v2.code_blake3,
+                is_locked: v2.is_locked,
+                timeout_secs: None,
             },
-            FuncContent::V2(v2) => v2,
+            FuncContent::V3(v3) => v3,
         }
     }
 }
@@ -36,6 +36,7 @@ pub mod dependency_graph;
 pub mod diagram;
 pub mod feature_flags;
 pub mod func;
+pub mod garbage_collection;
 pub mod history_event;
 pub mod input_sources;
 pub mod jetstream_streams;
@@ -1,15 +1,18 @@
 use std::collections::{hash_map, HashMap, HashSet, VecDeque};
 
+use plan::PlannedPropertyChange;
 use prototype::ManagementPrototypeExecution;
 use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
 use thiserror::Error;
 
 use veritech_client::{ManagementFuncStatus, ManagementResultSuccess};
 
+use crate::component::delete::delete_components;
 use crate::component::frame::{Frame, FrameError};
 use crate::dependency_graph::DependencyGraph;
 use crate::diagram::geometry::Geometry;
-use crate::diagram::view::{View, ViewId};
+use crate::diagram::view::{View, ViewComponentsUpdateList, ViewId};
 use crate::diagram::{DiagramError, SummaryDiagramManagementEdge};
 use crate::{
     action::{
@@ -26,12 +29,14 @@ use crate::{
     history_event::HistoryEventMetadata,
     prop::{PropError, PropPath},
     socket::{input::InputSocketError, output::OutputSocketError},
-    ActorView, AttributeValue, Component, ComponentError, ComponentId, ComponentType, DalContext,
-    Func, FuncError, InputSocket, InputSocketId, OutputSocket, OutputSocketId, Prop, PropKind,
-    Schema, SchemaError, SchemaId, SchemaVariantId, StandardModelError, WsEvent, WsEventError,
+    ActorView, AttributeValue, AttributeValueId, Component, ComponentError, ComponentId,
+    ComponentType, DalContext, Func, FuncError, InputSocket, InputSocketId, OutputSocket,
+    OutputSocketId, Prop, PropKind, Schema, SchemaError, SchemaId, SchemaVariantId,
+    StandardModelError, WsEvent, WsEventError,
 };
 use crate::{EdgeWeightKind, WorkspaceSnapshotError};
 
+pub mod plan;
 pub mod prototype;
 
 #[derive(Debug, Error)]
@@ -72,14 +77,24 @@ pub enum ManagementError {
     OutputSocket(#[from] OutputSocketError),
     #[error("Cannot connect component {0} to component {1} because component {0} does not have an output socket with name {2}")]
     OutputSocketDoesNotExist(ComponentId, ComponentId, String),
+    #[error("cannot add action {1} because component {0} does not have an action with that name or kind")]
+    PlaceholderDoesNotHaveAction(String, String),
+    #[error("component {0} does not have an input socket with name {1}")]
+    PlaceholderInputSocketDoesNotExist(String, String),
+    #[error("component {0} does not have an output socket with name {1}")]
+    PlaceholderOutputSocketDoesNotExist(String, String),
     #[error("prop error: {0}")]
     Prop(#[from] PropError),
+    #[error("value for {0} is not a valid {1}")]
+    PropertyKindMismatch(String, PropKind),
     #[error("schema error: {0}")]
     Schema(#[from] SchemaError),
     #[error("Cannot create component for Schema {0}, this schema does not exist or is not managed by this component")]
     SchemaDoesNotExist(String),
     #[error("standard model error: {0}")]
     StandardModel(#[from] StandardModelError),
+    #[error("view not found: {0}")]
+    ViewNotFound(String),
     #[error("workspace snapshot error: {0}")]
     WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
     #[error("ws event error: {0}")]
@@ -190,6 +205,11 @@ pub struct ManagementUpdateOperation {
     geometry: Option<HashMap<String, ManagementGeometry>>,
     connect: Option<ManagementUpdateConnections>,
     parent: Option<String>,
+    /// Removes the parent of the component, unless a new `parent` is also set.
+    unparent: Option<bool>,
+    /// The names of the views to erase the component from. A component cannot be erased from
+    /// the last view it is in.
+    remove_from_views: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -220,6 +240,11 @@ pub struct ManagementOperations {
     create: Option<HashMap<String, ManagementCreateOperation>>,
     update: Option<HashMap<String, ManagementUpdateOperation>>,
     actions: Option<HashMap<String, ManagementActionOperation>>,
+    /// Components to delete, with the same semantics as deleting them in the diagram: components
+    /// with resources are marked for deletion, the rest are removed.
+    delete: Option<Vec<String>>,
+    /// Components to erase from the graph, whether or not they have resources.
+    erase: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -454,7 +479,7 @@ impl<'a> ManagementOperator<'a> {
 
         let mut views = HashMap::new();
         for view in View::list(ctx).await? {
-            views.insert(view.name().to_owned(), view_id);
+            views.insert(view.name().to_owned(), view.id());
         }
 
         Ok(Self {
@@ -905,6 +930,12 @@ impl<'a> ManagementOperator<'a> {
                     child_component_id: component_id,
                     parent: new_parent.to_owned(),
                 }));
+            } else if operation.unparent == Some(true) {
+                Frame::orphan_child(self.ctx, component_id).await?;
+            }
+
+            if let Some(view_names) = &operation.remove_from_views {
+                self.remove_from_views(component_id, view_names).await?;
             }
 
             self.updated_components.insert(component_id);
@@ -930,6 +961,60 @@ impl<'a> ManagementOperator<'a> {
         Ok(())
     }
 
+    async fn remove_from_views(
+        &self,
+        component_id: ComponentId,
+        view_names: &[String],
+    ) -> ManagementResult<()> {
+        let mut updated_components: ViewComponentsUpdateList = HashMap::new();
+        for view_name in view_names {
+            let view_id = self
+                .views
+                .get(view_name)
+                .copied()
+                .ok_or_else(|| ManagementError::ViewNotFound(view_name.to_owned()))?;
+            let Some(geometry) =
+                Geometry::try_get_by_component_and_view(self.ctx, component_id, view_id).await?
+            else {
+                continue;
+            };
+
+            Geometry::remove(self.ctx, geometry.id()).await?;
+
+            updated_components
+                .entry(view_id)
+                .or_default()
+                .removed
+                .insert(component_id);
+        }
+
+        if !updated_components.is_empty() {
+            WsEvent::view_components_update(self.ctx, updated_components)
+                .await?
+                .publish_on_commit(self.ctx)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes and erases components the same way deleting them from the diagram does.
+    async fn deletes(&self) -> ManagementResult<()> {
+        let mut to_delete = vec![];
+        for placeholder in self.operations.delete.iter().flatten() {
+            to_delete.push(self.get_real_component_id(placeholder).await?);
+        }
+        let mut to_erase = vec![];
+        for placeholder in self.operations.erase.iter().flatten() {
+            to_erase.push(self.get_real_component_id(placeholder).await?);
+        }
+
+        delete_components(self.ctx, &to_delete, false).await?;
+        delete_components(self.ctx, &to_erase, true).await?;
+
+        Ok(())
+    }
+
     // Using the dep graph to ensure we send ws events for components in parent
     // to child order, so that parents exist in the frontend before their
     // children / parents are rendered as frames before their children report
@@ -1055,6 +1140,7 @@ impl<'a> ManagementOperator<'a> {
         }
 
         self.actions().await?;
+        self.deletes().await?;
 
         Ok(())
     }
@@ -1218,13 +1304,71 @@ const IGNORE_PATHS: [&[&str]; 6] = [
 
 const ROOT_SI_TYPE_PATH: &[&str] = &["root", "si", "type"];
 
-async fn update_component(
+/// A change to a component's properties found by [`walk_properties`], along with how
+/// [`update_component`] makes it.
+struct PropertyChange {
+    planned: PlannedPropertyChange,
+    /// `None` when the component does not exist yet, which only happens when planning.
+    write: Option<PropertyWrite>,
+}
+
+enum PropertyWrite {
+    SetType(ComponentId, ComponentType),
+    Update(AttributeValueId),
+    Insert {
+        map_attribute_value_id: AttributeValueId,
+        key: String,
+    },
+    Remove(AttributeValueId),
+}
+
+#[derive(Default)]
+struct PropertyWalk {
+    changes: Vec<PropertyChange>,
+    /// Paths that are skipped, because they do not exist on the schema, are set by a function
+    /// or cannot be set by management funcs.
+    ignored_paths: Vec<String>,
+    /// Values that are the wrong kind for their prop and cannot be coerced to it. Planning
+    /// reports these as errors, while operating only warns about them for now, so that existing
+    /// management funcs keep working: scalars are written as given and objects, maps and arrays
+    /// are skipped, as they always have been.
+    kind_mismatches: Vec<ManagementError>,
+}
+
+impl PropertyWalk {
+    fn change(
+        &mut self,
+        path: String,
+        current: Option<serde_json::Value>,
+        proposed: Option<serde_json::Value>,
+        write: Option<PropertyWrite>,
+    ) {
+        self.changes.push(PropertyChange {
+            planned: PlannedPropertyChange {
+                path,
+                current,
+                proposed,
+            },
+            write,
+        });
+    }
+}
+
+/// Walks the properties a management operation sets on a component and works out the changes
+/// they amount to, without making them. [`update_component`] applies the result and
+/// [`ManagementOperator::plan`] reports it, so both always agree.
+///
+/// Without a `component_id` (a component that would be created) there are no current values,
+/// and without a `variant_id` (a schema that is not installed yet) the properties cannot be
+/// validated, so every leaf is taken as given.
+async fn walk_properties(
     ctx: &DalContext,
-    component_id: ComponentId,
+    variant_id: Option<SchemaVariantId>,
+    component_id: Option<ComponentId>,
     properties: &serde_json::Value,
     extra_ignore_paths: &[&[&str]],
-) -> ManagementResult<()> {
-    let variant_id = Component::schema_variant_id(ctx, component_id).await?;
+) -> ManagementResult<PropertyWalk> {
+    let mut walk = PropertyWalk::default();
 
     // walk the properties serde_json::Value object without recursion
     let mut work_queue = VecDeque::new();
@@ -1232,142 +1376,287 @@ async fn update_component(
 
     while let Some((path, current_val)) = work_queue.pop_front() {
         let path_as_refs: Vec<_> = path.iter().map(|part| part.as_str()).collect();
-        if IGNORE_PATHS.contains(&path_as_refs.as_slice())
-            || extra_ignore_paths.contains(&path_as_refs.as_slice())
-        {
+        let path_string = format!("/{}", path.join("/"));
+        if IGNORE_PATHS.contains(&path_as_refs.as_slice()) {
+            walk.ignored_paths.push(path_string);
+            continue;
+        }
+        if extra_ignore_paths.contains(&path_as_refs.as_slice()) {
             continue;
         }
 
+        let Some(variant_id) = variant_id else {
+            match current_val {
+                serde_json::Value::Object(obj) if !obj.is_empty() => {
+                    for (key, value) in obj {
+                        let mut new_path = path.clone();
+                        new_path.push(key.to_owned());
+                        work_queue.push_back((new_path, value));
+                    }
+                }
+                _ => walk.change(path_string, None, Some(current_val.to_owned()), None),
+            }
+            continue;
+        };
+
         let Some(prop_id) =
             Prop::find_prop_id_by_path_opt(ctx, variant_id, &PropPath::new(path.as_slice()))
                 .await?
         else {
+            walk.ignored_paths.push(path_string);
             continue;
         };
 
-        let path_attribute_value_id =
-            Component::attribute_value_for_prop_id(ctx, component_id, prop_id).await?;
-
-        if AttributeValue::is_set_by_dependent_function(ctx, path_attribute_value_id).await? {
-            continue;
-        }
+        let path_attribute_value_id = match component_id {
+            Some(component_id) => {
+                let attribute_value_id =
+                    Component::attribute_value_for_prop_id(ctx, component_id, prop_id).await?;
+                if AttributeValue::is_set_by_dependent_function(ctx, attribute_value_id).await? {
+                    walk.ignored_paths.push(path_string);
+                    continue;
+                }
+                Some(attribute_value_id)
+            }
+            None => None,
+        };
+        let view = match path_attribute_value_id {
+            Some(attribute_value_id) => {
+                AttributeValue::get_by_id(ctx, attribute_value_id)
+                    .await?
+                    .view(ctx)
+                    .await?
+            }
+            None => None,
+        };
 
         // component type has to be special cased
         if path_as_refs.as_slice() == ROOT_SI_TYPE_PATH {
             let Ok(new_type) = serde_json::from_value::<ComponentType>(current_val.to_owned())
             else {
-                // error here?
+                walk.ignored_paths.push(path_string);
                 continue;
             };
-            Component::set_type_by_id(ctx, component_id, new_type).await?;
-
+            walk.change(
+                path_string,
+                view,
+                Some(current_val.to_owned()),
+                component_id.map(|component_id| PropertyWrite::SetType(component_id, new_type)),
+            );
             continue;
         }
 
         if let serde_json::Value::Null = current_val {
-            AttributeValue::update(ctx, path_attribute_value_id, Some(current_val.to_owned()))
-                .await?;
+            walk.change(
+                path_string,
+                view,
+                Some(current_val.to_owned()),
+                path_attribute_value_id.map(PropertyWrite::Update),
+            );
             continue;
         }
 
         let prop = Prop::get_by_id(ctx, prop_id).await?;
-
-        match prop.kind {
-            PropKind::String | PropKind::Boolean | PropKind::Integer | PropKind::Json => {
-                // todo: type check!
-                let view = AttributeValue::get_by_id(ctx, path_attribute_value_id)
-                    .await?
-                    .view(ctx)
-                    .await?;
-                if Some(current_val) != view.as_ref() {
-                    AttributeValue::update(
-                        ctx,
-                        path_attribute_value_id,
-                        Some(current_val.to_owned()),
-                    )
-                    .await?;
+        if !value_matches_kind(current_val, prop.kind) {
+            if let Some(coerced) = coerce_scalar(current_val, prop.kind) {
+                if Some(&coerced) != view.as_ref() {
+                    walk.change(
+                        path_string,
+                        view,
+                        Some(coerced),
+                        path_attribute_value_id.map(PropertyWrite::Update),
+                    );
                 }
+                continue;
             }
-            PropKind::Object => {
-                let serde_json::Value::Object(obj) = current_val else {
-                    continue;
-                };
 
+            walk.kind_mismatches
+                .push(ManagementError::PropertyKindMismatch(
+                    path_string.clone(),
+                    prop.kind,
+                ));
+            if matches!(
+                prop.kind,
+                PropKind::Object | PropKind::Map | PropKind::Array
+            ) {
+                continue;
+            }
+        }
+
+        match (prop.kind, current_val) {
+            (PropKind::Object, serde_json::Value::Object(obj)) => {
                 for (key, value) in obj {
                     let mut new_path = path.clone();
                     new_path.push(key.to_owned());
                     work_queue.push_back((new_path, value));
                 }
             }
-            PropKind::Map => {
-                let serde_json::Value::Object(map) = current_val else {
-                    continue;
+            (PropKind::Map, serde_json::Value::Object(map)) => {
+                let map_children = match path_attribute_value_id {
+                    Some(attribute_value_id) => {
+                        AttributeValue::map_children(ctx, attribute_value_id).await?
+                    }
+                    None => HashMap::new(),
                 };
 
-                let map_children =
-                    AttributeValue::map_children(ctx, path_attribute_value_id).await?;
-
                 // Remove any children that are not in the new map
                 for (key, child_id) in &map_children {
-                    if !map.contains_key(key) {
-                        if AttributeValue::is_set_by_dependent_function(ctx, *child_id).await? {
-                            continue;
-                        }
-
-                        AttributeValue::remove_by_id(ctx, *child_id).await?;
+                    if map.contains_key(key) {
+                        continue;
+                    }
+                    let child_path = format!("{path_string}/{key}");
+                    if AttributeValue::is_set_by_dependent_function(ctx, *child_id).await? {
+                        walk.ignored_paths.push(child_path);
+                        continue;
                     }
+                    let child_view = AttributeValue::get_by_id(ctx, *child_id)
+                        .await?
+                        .view(ctx)
+                        .await?;
+                    walk.change(
+                        child_path,
+                        child_view,
+                        None,
+                        Some(PropertyWrite::Remove(*child_id)),
+                    );
                 }
 
                 // We do not descend below a map. Instead we update the *entire*
                 // child tree of each map key
                 for (key, value) in map {
+                    let child_path = format!("{path_string}/{key}");
                     match map_children.get(key) {
                         Some(child_id) => {
                             if AttributeValue::is_set_by_dependent_function(ctx, *child_id).await? {
+                                walk.ignored_paths.push(child_path);
                                 continue;
                             }
-                            let view = AttributeValue::get_by_id(ctx, *child_id)
+                            let child_view = AttributeValue::get_by_id(ctx, *child_id)
                                 .await?
                                 .view(ctx)
                                 .await?;
-                            if Some(value) != view.as_ref() {
-                                AttributeValue::update(ctx, *child_id, Some(value.to_owned()))
-                                    .await?;
+                            if Some(value) != child_view.as_ref() {
+                                walk.change(
+                                    child_path,
+                                    child_view,
+                                    Some(value.to_owned()),
+                                    Some(PropertyWrite::Update(*child_id)),
+                                );
                             }
                         }
-                        None => {
-                            AttributeValue::insert(
-                                ctx,
-                                path_attribute_value_id,
-                                Some(value.to_owned()),
-                                Some(key.to_owned()),
-                            )
-                            .await?;
-                        }
+                        None => walk.change(
+                            child_path,
+                            None,
+                            Some(value.to_owned()),
+                            path_attribute_value_id.map(|map_attribute_value_id| {
+                                PropertyWrite::Insert {
+                                    map_attribute_value_id,
+                                    key: key.to_owned(),
+                                }
+                            }),
+                        ),
                     }
                 }
             }
-            PropKind::Array => {
-                if matches!(current_val, serde_json::Value::Array(_)) {
-                    let view = AttributeValue::get_by_id(ctx, path_attribute_value_id)
-                        .await?
-                        .view(ctx)
-                        .await?;
-
-                    if Some(current_val) != view.as_ref() {
-                        // Just update the entire array whole cloth
-                        AttributeValue::update(
-                            ctx,
-                            path_attribute_value_id,
-                            Some(current_val.to_owned()),
-                        )
-                        .await?;
-                    }
+            // Scalars, and arrays, which are updated whole cloth
+            _ => {
+                if Some(current_val) != view.as_ref() {
+                    walk.change(
+                        path_string,
+                        view,
+                        Some(current_val.to_owned()),
+                        path_attribute_value_id.map(PropertyWrite::Update),
+                    );
                 }
             }
         }
     }
 
+    Ok(walk)
+}
+
+fn value_matches_kind(value: &serde_json::Value, kind: PropKind) -> bool {
+    match kind {
+        PropKind::Array => value.is_array(),
+        PropKind::Boolean => value.is_boolean(),
+        PropKind::Float => value.is_number(),
+        PropKind::Integer => value.is_i64() || value.is_u64(),
+        PropKind::Json => true,
+        PropKind::Map | PropKind::Object => value.is_object(),
+        PropKind::String => value.is_string(),
+    }
+}
+
+/// Converts a scalar to the kind of its prop when it has an obvious equivalent, such as `"80"`
+/// for an integer or `true` for a string.
+fn coerce_scalar(value: &serde_json::Value, kind: PropKind) -> Option<serde_json::Value> {
+    match (kind, value) {
+        (PropKind::Boolean, serde_json::Value::String(string)) => {
+            string.parse::<bool>().ok().map(Into::into)
+        }
+        (PropKind::Float, serde_json::Value::String(string)) => string
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Into::into),
+        (PropKind::Integer, serde_json::Value::String(string)) => string
+            .parse::<serde_json::Number>()
+            .ok()
+            .filter(|number| number.is_i64() || number.is_u64())
+            .map(Into::into),
+        (PropKind::String, serde_json::Value::Number(number)) => Some(number.to_string().into()),
+        (PropKind::String, serde_json::Value::Bool(boolean)) => Some(boolean.to_string().into()),
+        _ => None,
+    }
+}
+
+async fn update_component(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    properties: &serde_json::Value,
+    extra_ignore_paths: &[&[&str]],
+) -> ManagementResult<()> {
+    let variant_id = Component::schema_variant_id(ctx, component_id).await?;
+
+    let walk = walk_properties(
+        ctx,
+        Some(variant_id),
+        Some(component_id),
+        properties,
+        extra_ignore_paths,
+    )
+    .await?;
+    for mismatch in walk.kind_mismatches {
+        warn!(
+            si.component.id = %component_id,
+            si.error.message = %mismatch,
+            "management func set a property to a value of the wrong kind",
+        );
+    }
+
+    for PropertyChange { planned, write } in walk.changes {
+        let Some(write) = write else {
+            continue;
+        };
+        match write {
+            PropertyWrite::SetType(component_id, new_type) => {
+                Component::set_type_by_id(ctx, component_id, new_type).await?;
+            }
+            PropertyWrite::Update(attribute_value_id) => {
+                AttributeValue::update(ctx, attribute_value_id, planned.proposed).await?;
+            }
+            PropertyWrite::Insert {
+                map_attribute_value_id,
+                key,
+            } => {
+                AttributeValue::insert(ctx, map_attribute_value_id, planned.proposed, Some(key))
+                    .await?;
+            }
+            PropertyWrite::Remove(attribute_value_id) => {
+                AttributeValue::remove_by_id(ctx, attribute_value_id).await?;
+            }
+        }
+    }
+
     Ok(())
 }
 
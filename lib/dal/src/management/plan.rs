@@ -0,0 +1,595 @@
+//! Dry runs of [`ManagementOperations`](super::ManagementOperations).
+//!
+//! [`ManagementOperator::plan`] validates the operations returned by a management func against
+//! the schemas involved and resolves every placeholder, without touching the graph. The resulting
+//! [`ManagementPlan`] describes what [`ManagementOperator::operate`] would do: the components it
+//! would create, the property changes it would make, the connections and actions it would add or
+//! remove, and the components it would delete.
+//!
+//! Validation problems are collected into [`ManagementPlan::errors`] instead of failing the plan,
+//! so a management func author sees all of them at once.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{
+    identify_action, walk_properties, ManagementConnection, ManagementError, ManagementOperator,
+    ManagementResult, SELF_ID,
+};
+use crate::{
+    action::prototype::{ActionKind, ActionPrototype},
+    Component, ComponentId, DalContext, Func, Schema, SchemaId, SchemaVariantId,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PlannedChange {
+    Add,
+    Remove,
+}
+
+/// A change to the value at `path`. A `proposed` value of `None` removes a map entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedPropertyChange {
+    pub path: String,
+    pub current: Option<Value>,
+    pub proposed: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedComponentCreate {
+    pub placeholder: String,
+    pub schema_id: SchemaId,
+    /// Whether the schema is installed in the workspace. Operating installs it, and properties
+    /// can only be validated against an installed schema.
+    pub schema_installed: bool,
+    pub properties: Vec<PlannedPropertyChange>,
+    pub parent: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedComponentUpdate {
+    pub placeholder: String,
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub properties: Vec<PlannedPropertyChange>,
+    /// The names of the views the component would be moved or resized in.
+    pub geometry_views: Vec<String>,
+    /// The names of the views the component would be erased from.
+    pub removed_from_views: Vec<String>,
+    pub parent: Option<String>,
+    pub unparent: bool,
+}
+
+/// A component that would be deleted or, if `erase` is set, erased from the graph.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedRemoval {
+    pub placeholder: String,
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub erase: bool,
+}
+
+/// A connection between two components. Component ids are `None` for components the plan
+/// creates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedConnection {
+    pub change: PlannedChange,
+    pub from_placeholder: String,
+    pub from_component_id: Option<ComponentId>,
+    pub from_socket: String,
+    pub to_placeholder: String,
+    pub to_component_id: Option<ComponentId>,
+    pub to_socket: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedAction {
+    pub change: PlannedChange,
+    pub placeholder: String,
+    pub component_id: Option<ComponentId>,
+    pub kind: ActionKind,
+    pub manual_func_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagementPlan {
+    pub creates: Vec<PlannedComponentCreate>,
+    pub updates: Vec<PlannedComponentUpdate>,
+    pub connections: Vec<PlannedConnection>,
+    pub actions: Vec<PlannedAction>,
+    pub removals: Vec<PlannedRemoval>,
+    /// Paths of properties that operating would skip, because they do not exist on the schema,
+    /// are set by a function or cannot be set by management funcs.
+    pub ignored_properties: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl ManagementPlan {
+    /// Whether [`ManagementOperator::operate`] is expected to succeed.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// A component the plan refers to, which either exists already or would be created.
+#[derive(Clone, Copy, Debug)]
+struct PlannedComponent {
+    component_id: Option<ComponentId>,
+    variant_id: Option<SchemaVariantId>,
+}
+
+impl ManagementOperator<'_> {
+    /// Builds the [`ManagementPlan`] for the operations without mutating the graph.
+    pub async fn plan(&mut self) -> ManagementResult<ManagementPlan> {
+        let mut plan = ManagementPlan::default();
+        let mut planned_components: HashMap<String, PlannedComponent> = HashMap::new();
+        let mut pending_connections = vec![];
+
+        let mut creates: Vec<_> = self
+            .operations
+            .create
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        creates.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (placeholder, operation) in creates {
+            if placeholder == SELF_ID {
+                plan.errors
+                    .push(ManagementError::CannotCreateComponentWithSelfPlaceholder.to_string());
+                continue;
+            }
+            if self.component_id_placeholders.contains_key(&placeholder) {
+                plan.errors
+                    .push(ManagementError::DuplicateComponentPlaceholder(placeholder).to_string());
+                continue;
+            }
+
+            let schema_id = match &operation.kind {
+                Some(kind) => match self.schema_map.get(kind) {
+                    Some(schema_id) => *schema_id,
+                    None => {
+                        plan.errors
+                            .push(ManagementError::SchemaDoesNotExist(kind.clone()).to_string());
+                        continue;
+                    }
+                },
+                None => self.manager_schema_id,
+            };
+            let variant_id = Schema::get_default_schema_variant_by_id(self.ctx, schema_id).await?;
+
+            let properties = match &operation.properties {
+                Some(properties) => {
+                    plan_property_changes(
+                        self.ctx,
+                        variant_id,
+                        None,
+                        properties,
+                        &[&["root", "si", "name"]],
+                        &mut plan,
+                    )
+                    .await?
+                }
+                None => vec![],
+            };
+
+            for connection in operation.connect.iter().flatten() {
+                pending_connections.push((
+                    PlannedChange::Add,
+                    placeholder.clone(),
+                    connection.clone(),
+                ));
+            }
+
+            planned_components.insert(
+                placeholder.clone(),
+                PlannedComponent {
+                    component_id: None,
+                    variant_id,
+                },
+            );
+            plan.creates.push(PlannedComponentCreate {
+                placeholder,
+                schema_id,
+                schema_installed: variant_id.is_some(),
+                properties,
+                parent: operation.parent,
+            });
+        }
+
+        let mut updates: Vec<_> = self
+            .operations
+            .update
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        updates.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (placeholder, operation) in updates {
+            let Some(planned) = self
+                .resolve_planned_component(&placeholder, &planned_components)
+                .await?
+            else {
+                plan.errors.push(
+                    ManagementError::ComponentWithPlaceholderNotFound(placeholder).to_string(),
+                );
+                continue;
+            };
+            let properties = match &operation.properties {
+                Some(properties) => {
+                    plan_property_changes(
+                        self.ctx,
+                        planned.variant_id,
+                        planned.component_id,
+                        properties,
+                        &[],
+                        &mut plan,
+                    )
+                    .await?
+                }
+                None => vec![],
+            };
+
+            let mut geometry_views: Vec<String> = operation
+                .geometry
+                .iter()
+                .flat_map(|geometry| geometry.keys())
+                .filter(|view_name| self.views.contains_key(*view_name))
+                .cloned()
+                .collect();
+            geometry_views.sort();
+
+            let mut removed_from_views = vec![];
+            for view_name in operation.remove_from_views.iter().flatten() {
+                if self.views.contains_key(view_name) {
+                    removed_from_views.push(view_name.to_owned());
+                } else {
+                    plan.errors
+                        .push(ManagementError::ViewNotFound(view_name.to_owned()).to_string());
+                }
+            }
+
+            if let Some(connect) = &operation.connect {
+                for connection in connect.remove.iter().flatten() {
+                    pending_connections.push((
+                        PlannedChange::Remove,
+                        placeholder.clone(),
+                        connection.clone(),
+                    ));
+                }
+                for connection in connect.add.iter().flatten() {
+                    pending_connections.push((
+                        PlannedChange::Add,
+                        placeholder.clone(),
+                        connection.clone(),
+                    ));
+                }
+            }
+
+            // Creates run before updates, so updating a component created by the same
+            // operations amends its creation.
+            let Some(component_id) = planned.component_id else {
+                if let Some(create) = plan
+                    .creates
+                    .iter_mut()
+                    .find(|create| create.placeholder == placeholder)
+                {
+                    create.properties.extend(properties);
+                    if operation.parent.is_some() {
+                        create.parent = operation.parent;
+                    }
+                }
+                continue;
+            };
+
+            plan.updates.push(PlannedComponentUpdate {
+                component_name: Component::name_by_id(self.ctx, component_id).await?,
+                placeholder,
+                component_id,
+                properties,
+                geometry_views,
+                removed_from_views,
+                unparent: operation.parent.is_none() && operation.unparent == Some(true),
+                parent: operation.parent,
+            });
+        }
+
+        let parents: Vec<String> = plan
+            .creates
+            .iter()
+            .filter_map(|create| create.parent.clone())
+            .chain(
+                plan.updates
+                    .iter()
+                    .filter_map(|update| update.parent.clone()),
+            )
+            .collect();
+        for parent in parents {
+            if self
+                .resolve_planned_component(&parent, &planned_components)
+                .await?
+                .is_none()
+            {
+                plan.errors
+                    .push(ManagementError::ComponentWithPlaceholderNotFound(parent).to_string());
+            }
+        }
+
+        for (change, from_placeholder, connection) in pending_connections {
+            if let Some(planned_connection) = self
+                .plan_connection(
+                    change,
+                    from_placeholder,
+                    connection,
+                    &planned_components,
+                    &mut plan,
+                )
+                .await?
+            {
+                plan.connections.push(planned_connection);
+            }
+        }
+
+        let mut actions: Vec<_> = self
+            .operations
+            .actions
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        actions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (placeholder, operation) in actions {
+            let Some(planned) = self
+                .resolve_planned_component(&placeholder, &planned_components)
+                .await?
+            else {
+                plan.errors.push(
+                    ManagementError::ComponentWithPlaceholderNotFound(placeholder).to_string(),
+                );
+                continue;
+            };
+
+            for action in operation.remove.iter().flatten() {
+                let action = identify_action(action);
+                plan.actions.push(PlannedAction {
+                    change: PlannedChange::Remove,
+                    placeholder: placeholder.clone(),
+                    component_id: planned.component_id,
+                    kind: action.kind,
+                    manual_func_name: action.manual_func_name,
+                });
+            }
+
+            let available_actions = match planned.variant_id {
+                Some(variant_id) => Some(ActionPrototype::for_variant(self.ctx, variant_id).await?),
+                None => None,
+            };
+            for action_name in operation.add.iter().flatten() {
+                let action = identify_action(action_name);
+                if let Some(available_actions) = &available_actions {
+                    if !has_action(
+                        self.ctx,
+                        available_actions,
+                        action.kind,
+                        &action.manual_func_name,
+                    )
+                    .await?
+                    {
+                        plan.errors.push(
+                            ManagementError::PlaceholderDoesNotHaveAction(
+                                placeholder.clone(),
+                                action_name.clone(),
+                            )
+                            .to_string(),
+                        );
+                        continue;
+                    }
+                }
+                plan.actions.push(PlannedAction {
+                    change: PlannedChange::Add,
+                    placeholder: placeholder.clone(),
+                    component_id: planned.component_id,
+                    kind: action.kind,
+                    manual_func_name: action.manual_func_name,
+                });
+            }
+        }
+
+        let removals = self
+            .operations
+            .delete
+            .iter()
+            .flatten()
+            .map(|placeholder| (placeholder.to_owned(), false))
+            .chain(
+                self.operations
+                    .erase
+                    .iter()
+                    .flatten()
+                    .map(|placeholder| (placeholder.to_owned(), true)),
+            )
+            .collect::<Vec<_>>();
+        for (placeholder, erase) in removals {
+            let Some(component_id) = self.component_id_placeholders.get(&placeholder).copied()
+            else {
+                plan.errors.push(
+                    ManagementError::ComponentWithPlaceholderNotFound(placeholder).to_string(),
+                );
+                continue;
+            };
+            plan.removals.push(PlannedRemoval {
+                component_name: Component::name_by_id(self.ctx, component_id).await?,
+                placeholder,
+                component_id,
+                erase,
+            });
+        }
+
+        Ok(plan)
+    }
+
+    /// Resolves a placeholder to a component created by the plan or one that already exists.
+    async fn resolve_planned_component(
+        &mut self,
+        placeholder: &str,
+        planned_components: &HashMap<String, PlannedComponent>,
+    ) -> ManagementResult<Option<PlannedComponent>> {
+        if let Some(planned) = planned_components.get(placeholder) {
+            return Ok(Some(*planned));
+        }
+
+        let Some(component_id) = self.component_id_placeholders.get(placeholder).copied() else {
+            return Ok(None);
+        };
+        let variant_id = self
+            .component_schema_map
+            .variant_for_component_id(self.ctx, component_id)
+            .await?;
+
+        Ok(Some(PlannedComponent {
+            component_id: Some(component_id),
+            variant_id: Some(variant_id),
+        }))
+    }
+
+    async fn plan_connection(
+        &mut self,
+        change: PlannedChange,
+        from_placeholder: String,
+        connection: ManagementConnection,
+        planned_components: &HashMap<String, PlannedComponent>,
+        plan: &mut ManagementPlan,
+    ) -> ManagementResult<Option<PlannedConnection>> {
+        let Some(from) = self
+            .resolve_planned_component(&from_placeholder, planned_components)
+            .await?
+        else {
+            plan.errors.push(
+                ManagementError::ComponentWithPlaceholderNotFound(from_placeholder).to_string(),
+            );
+            return Ok(None);
+        };
+        let Some(to) = self
+            .resolve_planned_component(&connection.to.component, planned_components)
+            .await?
+        else {
+            plan.errors.push(
+                ManagementError::ComponentWithPlaceholderNotFound(connection.to.component)
+                    .to_string(),
+            );
+            return Ok(None);
+        };
+
+        let mut valid = true;
+        if let Some(from_variant_id) = from.variant_id {
+            self.socket_map
+                .add_sockets_for_variant(self.ctx, from_variant_id)
+                .await?;
+            if self
+                .socket_map
+                .output_socket_id(from_variant_id, &connection.from)
+                .is_none()
+            {
+                plan.errors.push(
+                    ManagementError::PlaceholderOutputSocketDoesNotExist(
+                        from_placeholder.clone(),
+                        connection.from.clone(),
+                    )
+                    .to_string(),
+                );
+                valid = false;
+            }
+        }
+        if let Some(to_variant_id) = to.variant_id {
+            self.socket_map
+                .add_sockets_for_variant(self.ctx, to_variant_id)
+                .await?;
+            if self
+                .socket_map
+                .input_socket_id(to_variant_id, &connection.to.socket)
+                .is_none()
+            {
+                plan.errors.push(
+                    ManagementError::PlaceholderInputSocketDoesNotExist(
+                        connection.to.component.clone(),
+                        connection.to.socket.clone(),
+                    )
+                    .to_string(),
+                );
+                valid = false;
+            }
+        }
+
+        Ok(valid.then_some(PlannedConnection {
+            change,
+            from_placeholder,
+            from_component_id: from.component_id,
+            from_socket: connection.from,
+            to_placeholder: connection.to.component,
+            to_component_id: to.component_id,
+            to_socket: connection.to.socket,
+        }))
+    }
+}
+
+async fn has_action(
+    ctx: &DalContext,
+    available_actions: &[ActionPrototype],
+    kind: ActionKind,
+    manual_func_name: &Option<String>,
+) -> ManagementResult<bool> {
+    for prototype in available_actions.iter().filter(|proto| proto.kind == kind) {
+        let Some(manual_func_name) = manual_func_name else {
+            return Ok(true);
+        };
+        let func_id = ActionPrototype::func_id(ctx, prototype.id()).await?;
+        if Func::get_by_id_or_error(ctx, func_id).await?.name == *manual_func_name {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Runs the same walk over the properties that [`ManagementOperator::operate`] does, recording
+/// the changes, skipped paths and validation errors instead of acting on them.
+async fn plan_property_changes(
+    ctx: &DalContext,
+    variant_id: Option<SchemaVariantId>,
+    component_id: Option<ComponentId>,
+    properties: &Value,
+    extra_ignore_paths: &[&[&str]],
+    plan: &mut ManagementPlan,
+) -> ManagementResult<Vec<PlannedPropertyChange>> {
+    let walk = walk_properties(
+        ctx,
+        variant_id,
+        component_id,
+        properties,
+        extra_ignore_paths,
+    )
+    .await?;
+
+    plan.ignored_properties.extend(walk.ignored_paths);
+    plan.errors
+        .extend(walk.kind_mismatches.iter().map(ToString::to_string));
+
+    Ok(walk
+        .changes
+        .into_iter()
+        .map(|change| change.planned)
+        .collect())
+}
@@ -0,0 +1,2 @@
+ALTER TABLE change_set_pointers
+    ADD COLUMN rollback_on_failure boolean NOT NULL DEFAULT false;
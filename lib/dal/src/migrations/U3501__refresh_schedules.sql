@@ -0,0 +1,18 @@
+CREATE TABLE refresh_schedules
+(
+    id                ident primary key        NOT NULL DEFAULT ident_create_v1(),
+    created_at        timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at        timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    workspace_id      ident                    NOT NULL REFERENCES workspaces (pk) DEFERRABLE,
+    component_id      ident,
+    schema_variant_id ident,
+    interval_seconds  bigint,
+    cron_expression   text,
+    next_run_at       timestamp with time zone NOT NULL,
+    last_run_at       timestamp with time zone,
+    CHECK ((component_id IS NULL) <> (schema_variant_id IS NULL)),
+    CHECK ((interval_seconds IS NULL) <> (cron_expression IS NULL))
+);
+
+CREATE INDEX refresh_schedules_next_run_at ON refresh_schedules (next_run_at);
+CREATE INDEX refresh_schedules_workspace_id ON refresh_schedules (workspace_id);
@@ -0,0 +1,2 @@
+ALTER TABLE workspaces
+    ADD COLUMN value_concurrency_limit integer NULL;
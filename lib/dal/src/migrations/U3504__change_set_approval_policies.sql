@@ -0,0 +1,19 @@
+CREATE TABLE change_set_approval_policies
+(
+    workspace_id ident primary key        NOT NULL REFERENCES workspaces (pk) DEFERRABLE,
+    created_at   timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at   timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    policy       jsonb                    NOT NULL
+);
+
+CREATE TABLE change_set_approvals
+(
+    id                         ident primary key        NOT NULL DEFAULT ident_create_v1(),
+    created_at                 timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    change_set_id              ident                    NOT NULL REFERENCES change_set_pointers (id) DEFERRABLE,
+    user_id                    ident                    NOT NULL,
+    workspace_snapshot_address text                     NOT NULL,
+    UNIQUE (change_set_id, user_id)
+);
+
+CREATE INDEX change_set_approvals_change_set_id ON change_set_approvals (change_set_id);
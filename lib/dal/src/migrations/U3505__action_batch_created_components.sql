@@ -0,0 +1,8 @@
+CREATE TABLE action_batch_created_components
+(
+    change_set_id ident                    NOT NULL REFERENCES change_set_pointers (id) DEFERRABLE,
+    component_id  ident                    NOT NULL,
+    created_at    timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX action_batch_created_components_change_set_id ON action_batch_created_components (change_set_id);
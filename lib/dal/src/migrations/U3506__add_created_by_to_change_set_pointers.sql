@@ -0,0 +1,2 @@
+ALTER TABLE change_set_pointers
+    ADD COLUMN created_by_user_id ident NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE change_set_pointers
+    ADD COLUMN applied_base_snapshot_address text NULL;
@@ -0,0 +1,5 @@
+CREATE TABLE action_batch_rollbacks
+(
+    change_set_id  ident                    PRIMARY KEY REFERENCES change_set_pointers (id) DEFERRABLE,
+    rolled_back_at timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
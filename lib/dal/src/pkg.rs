@@ -11,6 +11,8 @@ use crate::attribute::prototype::argument::{
 use crate::attribute::prototype::AttributePrototypeError;
 use crate::attribute::value::AttributeValueError;
 use crate::func::argument::FuncArgumentId;
+use crate::func::test_case::FuncTestCaseError;
+use crate::func::version::FuncVersionError;
 use crate::management::prototype::ManagementPrototypeError;
 use crate::schema::variant::SchemaVariantError;
 use crate::{
@@ -64,6 +66,10 @@ pub enum PkgError {
     FuncArgumentNotFoundByName(FuncId, String),
     #[error("func {0} could not be found by name")]
     FuncNotFoundByName(String),
+    #[error("func test case error: {0}")]
+    FuncTestCase(#[from] FuncTestCaseError),
+    #[error("func version error: {0}")]
+    FuncVersion(#[from] FuncVersionError),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     #[error("input socket error: {0}")]
@@ -153,6 +159,7 @@ impl From<FuncBackendKind> for FuncSpecBackendKind {
             FuncBackendKind::Validation => Self::Validation,
             FuncBackendKind::JsAuthentication => Self::JsAuthentication,
             FuncBackendKind::Management => Self::Management,
+            FuncBackendKind::Float => Self::Float,
         }
     }
 }
@@ -178,6 +185,7 @@ impl From<FuncSpecBackendKind> for FuncBackendKind {
             FuncSpecBackendKind::Validation => Self::Validation,
             FuncSpecBackendKind::JsAuthentication => Self::JsAuthentication,
             FuncSpecBackendKind::Management => Self::Management,
+            FuncSpecBackendKind::Float => Self::Float,
         }
     }
 }
@@ -202,6 +210,7 @@ impl From<FuncBackendResponseType> for FuncSpecBackendResponseType {
             FuncBackendResponseType::Validation => Self::Validation,
             FuncBackendResponseType::Void => Self::Void,
             FuncBackendResponseType::Management => Self::Management,
+            FuncBackendResponseType::Float => Self::Float,
         }
     }
 }
@@ -226,6 +235,7 @@ impl From<FuncSpecBackendResponseType> for FuncBackendResponseType {
             FuncSpecBackendResponseType::Validation => Self::Validation,
             FuncSpecBackendResponseType::Void => Self::Void,
             FuncSpecBackendResponseType::Management => Self::Management,
+            FuncSpecBackendResponseType::Float => Self::Float,
         }
     }
 }
@@ -6,11 +6,11 @@ use strum::IntoEnumIterator;
 
 use si_pkg::{
     ActionFuncSpec, AttrFuncInputSpec, AttrFuncInputSpecKind, AuthenticationFuncSpec,
-    ComponentSpec, EdgeSpec, FuncArgumentSpec, FuncSpec, FuncSpecData, LeafFunctionSpec,
-    ManagementFuncSpec, MapKeyFuncSpec, PkgSpec, PropSpec, PropSpecBuilder, PropSpecKind,
-    RootPropFuncSpec, SchemaSpec, SchemaSpecData, SchemaVariantSpec, SchemaVariantSpecBuilder,
-    SchemaVariantSpecData, SchemaVariantSpecPropRoot, SiPkg, SiPkgKind, SiPropFuncSpec,
-    SiPropFuncSpecKind, SocketSpec, SocketSpecData, SocketSpecKind, SpecError,
+    ComponentSpec, EdgeSpec, FuncArgumentSpec, FuncSpec, FuncSpecData, FuncTestCaseSpec,
+    FuncVersionSpec, LeafFunctionSpec, ManagementFuncSpec, MapKeyFuncSpec, PkgSpec, PropSpec,
+    PropSpecBuilder, PropSpecKind, RootPropFuncSpec, SchemaSpec, SchemaSpecData, SchemaVariantSpec,
+    SchemaVariantSpecBuilder, SchemaVariantSpecData, SchemaVariantSpecPropRoot, SiPkg, SiPkgKind,
+    SiPropFuncSpec, SiPropFuncSpecKind, SocketSpec, SocketSpecData, SocketSpecKind, SpecError,
 };
 use telemetry::prelude::*;
 
@@ -23,7 +23,10 @@ use crate::func::is_intrinsic;
 use crate::management::prototype::ManagementPrototype;
 use crate::schema::variant::leaves::{LeafInputLocation, LeafKind};
 use crate::{
-    func::{argument::FuncArgument, intrinsics::IntrinsicFunc},
+    func::{
+        argument::FuncArgument, intrinsics::IntrinsicFunc, test_case::FuncTestCase,
+        version::FuncVersion,
+    },
     prop::PropPath,
     AttributePrototype, DalContext, Func, FuncId, Prop, PropId, PropKind, Schema, SchemaId,
     SchemaVariant, SchemaVariantId, Workspace,
@@ -646,6 +649,7 @@ impl PkgExporter {
                     PropKind::Array => PropSpecKind::Array,
                     PropKind::Boolean => PropSpecKind::Boolean,
                     PropKind::Integer => PropSpecKind::Number,
+                    PropKind::Float => PropSpecKind::Float,
                     PropKind::Object => PropSpecKind::Object,
                     PropKind::String => PropSpecKind::String,
                     PropKind::Map => PropSpecKind::Map,
@@ -721,6 +725,7 @@ impl PkgExporter {
                         PropSpecKind::Json
                         | PropSpecKind::String
                         | PropSpecKind::Number
+                        | PropSpecKind::Float
                         | PropSpecKind::Boolean => {
                             return Err(PkgError::PropSpecChildrenInvalid(format!(
                                 "primitve prop type should have no children for prop id {}",
@@ -907,6 +912,10 @@ impl PkgExporter {
 
         data_builder.hidden(func.hidden);
 
+        if let Some(timeout_secs) = func.timeout_secs {
+            data_builder.timeout_secs(timeout_secs);
+        }
+
         func_spec_builder.data(data_builder.build()?);
         func_spec_builder.unique_id(func.id.to_string());
         func_spec_builder.is_from_builtin(Some(func.builtin));
@@ -926,6 +935,30 @@ impl PkgExporter {
             );
         }
 
+        for test_case in FuncTestCase::list_for_func(ctx, func.id).await? {
+            func_spec_builder.test_case(
+                FuncTestCaseSpec::builder()
+                    .name(test_case.name)
+                    .description(test_case.description)
+                    .fixture(serde_json::to_value(&test_case.fixture)?)
+                    .expectation(serde_json::to_value(&test_case.expectation)?)
+                    .build()?,
+            );
+        }
+
+        for version in FuncVersion::list_for_func(ctx, func.id).await? {
+            func_spec_builder.version(
+                FuncVersionSpec::builder()
+                    .version(version.version.to_string())
+                    .created_at(version.timestamp.created_at)
+                    .message(version.message)
+                    .author(version.author.map(|author| author.to_string()))
+                    .handler(version.handler)
+                    .code_base64(version.code_base64)
+                    .build()?,
+            );
+        }
+
         let func_spec = func_spec_builder.build()?;
         // If we have data, or change set specific arguments, we're valid for this changeset
         let include_in_export = func_spec.data.is_some() || !args.is_empty();
@@ -981,10 +1014,11 @@ impl PkgExporter {
         for intrinsic in IntrinsicFunc::iter() {
             let intrinsic_name = intrinsic.name();
             // We need a unique id for intrinsic funcs to refer to them in custom bindings (for example
-            // mapping one prop to another via si:identity)
-            let intrinsic_func_id = Func::find_id_by_name(ctx, intrinsic_name)
-                .await?
-                .ok_or(PkgError::MissingIntrinsicFunc(intrinsic_name.to_string()))?;
+            // mapping one prop to another via si:identity). Workspaces that predate an intrinsic
+            // cannot reference it, so there is nothing to export for it.
+            let Some(intrinsic_func_id) = Func::find_id_by_name(ctx, intrinsic_name).await? else {
+                continue;
+            };
 
             let intrinsic_func = Func::get_by_id_or_error(ctx, intrinsic_func_id).await?;
 
@@ -1063,9 +1097,11 @@ impl PkgExporter {
     async fn export_intrinsics(&mut self, ctx: &DalContext) -> PkgResult<Vec<FuncSpec>> {
         let mut funcs = vec![];
         for instrinsic in IntrinsicFunc::iter() {
-            let intrinsic_func_id = Func::find_id_by_name(ctx, instrinsic.name()).await?.ok_or(
-                PkgError::MissingIntrinsicFunc(instrinsic.name().to_string()),
-            )?;
+            // Intrinsics added after the workspace was created may not be installed yet
+            let Some(intrinsic_func_id) = Func::find_id_by_name(ctx, instrinsic.name()).await?
+            else {
+                continue;
+            };
 
             let spec = instrinsic.to_spec()?;
             funcs.push(spec.clone());
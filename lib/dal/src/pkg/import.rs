@@ -2,9 +2,10 @@ use chrono::NaiveDateTime;
 use si_events::ulid::Ulid;
 use si_pkg::{
     SchemaVariantSpecPropRoot, SiPkg, SiPkgActionFunc, SiPkgAttrFuncInputView, SiPkgAuthFunc,
-    SiPkgComponent, SiPkgEdge, SiPkgError, SiPkgFunc, SiPkgFuncArgument, SiPkgFuncData, SiPkgKind,
-    SiPkgLeafFunction, SiPkgManagementFunc, SiPkgMetadata, SiPkgProp, SiPkgPropData, SiPkgSchema,
-    SiPkgSchemaData, SiPkgSchemaVariant, SiPkgSocket, SiPkgSocketData, SocketSpecKind,
+    SiPkgComponent, SiPkgEdge, SiPkgError, SiPkgFunc, SiPkgFuncArgument, SiPkgFuncData,
+    SiPkgFuncTestCase, SiPkgFuncVersion, SiPkgKind, SiPkgLeafFunction, SiPkgManagementFunc,
+    SiPkgMetadata, SiPkgProp, SiPkgPropData, SiPkgSchema, SiPkgSchemaData, SiPkgSchemaVariant,
+    SiPkgSocket, SiPkgSocketData, SocketSpecKind,
 };
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -26,11 +27,11 @@ use crate::socket::connection_annotation::ConnectionAnnotation;
 use crate::SocketKind;
 use crate::{
     action::prototype::ActionPrototype,
-    func::argument::FuncArgument,
+    func::{argument::FuncArgument, test_case::FuncTestCase, version::FuncVersion},
     prop::PropPath,
     schema::variant::leaves::{LeafInputLocation, LeafKind},
     DalContext, EdgeWeightKind, Func, FuncId, InputSocket, OutputSocket, OutputSocketId, Prop,
-    PropId, PropKind, Schema, SchemaVariant, SchemaVariantId,
+    PropId, PropKind, Schema, SchemaVariant, SchemaVariantId, Timestamp,
 };
 use crate::{AttributePrototype, AttributePrototypeId};
 
@@ -163,6 +164,8 @@ async fn import_change_set(
                 if !args.is_empty() {
                     import_func_arguments(ctx, func.id, &args, thing_map).await?;
                 }
+
+                import_func_test_cases(ctx, func.id, &func_spec.test_cases()?).await?;
             }
         };
     }
@@ -320,6 +323,17 @@ async fn create_func(
     )
     .await?;
 
+    let func = match func_spec_data.timeout_secs() {
+        Some(timeout_secs) => {
+            func.modify(ctx, |func| {
+                func.timeout_secs = Some(timeout_secs);
+                Ok(())
+            })
+            .await?
+        }
+        None => func,
+    };
+
     Ok(func)
 }
 
@@ -342,6 +356,7 @@ async fn update_func(
             func.handler = Some(func_spec_data.handler().to_owned());
             func.hidden = func_spec_data.hidden();
             func.link = func_spec_data.link().map(|l| l.to_string());
+            func.timeout_secs = func_spec_data.timeout_secs();
 
             Ok(())
         })
@@ -373,6 +388,8 @@ pub async fn import_func(
         func
     } else {
         let func = create_func(ctx, func_spec, false).await?;
+        // Versions are imported before locking so that an unchanged func does not get a new one
+        import_func_versions(ctx, func.id, &func_spec.versions()?).await?;
 
         if !create_unlocked {
             func.lock(ctx).await?
@@ -415,7 +432,7 @@ async fn create_func_argument(
     .await?)
 }
 
-async fn import_func_arguments(
+pub(crate) async fn import_func_arguments(
     ctx: &DalContext,
     func_id: FuncId,
     func_arguments: &[SiPkgFuncArgument<'_>],
@@ -428,6 +445,58 @@ async fn import_func_arguments(
     Ok(())
 }
 
+/// Creates the [`FuncTestCases`](FuncTestCase) from the package, skipping any whose name is
+/// already in use on the func (e.g. when re-importing onto an existing func).
+async fn import_func_test_cases(
+    ctx: &DalContext,
+    func_id: FuncId,
+    test_cases: &[SiPkgFuncTestCase<'_>],
+) -> PkgResult<()> {
+    for test_case in test_cases {
+        if FuncTestCase::find_by_name_for_func(ctx, func_id, test_case.name())
+            .await?
+            .is_some()
+        {
+            continue;
+        }
+
+        FuncTestCase::new(
+            ctx,
+            func_id,
+            test_case.name(),
+            test_case.description().map(ToOwned::to_owned),
+            serde_json::from_value(test_case.fixture().to_owned())?,
+            serde_json::from_value(test_case.expectation().to_owned())?,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Adds the released [`FuncVersions`](FuncVersion) from the package to the history of the func.
+async fn import_func_versions(
+    ctx: &DalContext,
+    func_id: FuncId,
+    versions: &[SiPkgFuncVersion<'_>],
+) -> PkgResult<()> {
+    for version in versions {
+        FuncVersion::import(
+            ctx,
+            func_id,
+            version.version().parse()?,
+            version.message().map(ToOwned::to_owned),
+            version.author().and_then(|author| author.parse().ok()),
+            version.handler().map(ToOwned::to_owned),
+            version.code_base64().map(ToOwned::to_owned),
+            Timestamp::assemble(version.created_at(), version.created_at()),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 async fn create_schema(
     ctx: &DalContext,
     maybe_existing_schema_id: Option<Ulid>,
@@ -643,6 +712,10 @@ enum DefaultValueInfo {
         prop_id: PropId,
         default_value: bool,
     },
+    Float {
+        prop_id: PropId,
+        default_value: f64,
+    },
     Number {
         prop_id: PropId,
         default_value: i64,
@@ -1376,6 +1449,7 @@ async fn set_default_value(
 ) -> PkgResult<()> {
     let prop_id = match &default_value_info {
         DefaultValueInfo::Number { prop_id, .. }
+        | DefaultValueInfo::Float { prop_id, .. }
         | DefaultValueInfo::String { prop_id, .. }
         | DefaultValueInfo::Boolean { prop_id, .. } => *prop_id,
     };
@@ -1387,6 +1461,9 @@ async fn set_default_value(
         DefaultValueInfo::Number { default_value, .. } => {
             Prop::set_default_value(ctx, prop_id, default_value).await?
         }
+        DefaultValueInfo::Float { default_value, .. } => {
+            Prop::set_default_value(ctx, prop_id, default_value).await?
+        }
         DefaultValueInfo::String { default_value, .. } => {
             Prop::set_default_value(ctx, prop_id, default_value).await?
         }
@@ -1606,6 +1683,7 @@ fn prop_kind_for_pkg_prop(pkg_prop: &SiPkgProp<'_>) -> PropKind {
     match pkg_prop {
         SiPkgProp::Array { .. } => PropKind::Array,
         SiPkgProp::Boolean { .. } => PropKind::Boolean,
+        SiPkgProp::Float { .. } => PropKind::Float,
         SiPkgProp::Json { .. } => PropKind::Json,
         SiPkgProp::Map { .. } => PropKind::Map,
         SiPkgProp::Number { .. } => PropKind::Integer,
@@ -1709,6 +1787,18 @@ async fn create_prop(
                     None
                 }
             }
+            SiPkgProp::Float { .. } => {
+                if let Some(serde_json::Value::Number(default_value_number)) = &data.default_value {
+                    default_value_number
+                        .as_f64()
+                        .map(|dv_f64| DefaultValueInfo::Float {
+                            prop_id,
+                            default_value: dv_f64,
+                        })
+                } else {
+                    None
+                }
+            }
             SiPkgProp::Boolean { .. } => {
                 if let Some(serde_json::Value::Bool(default_value)) = &data.default_value {
                     Some(DefaultValueInfo::Boolean {
@@ -270,6 +270,8 @@ impl From<String> for PropPath {
 )]
 #[serde(rename_all = "camelCase")]
 #[strum(serialize_all = "camelCase")]
+// NOTE: this type is postcard serialized, so new variants must go at the end, even if it's not in
+// lexical order!
 pub enum PropKind {
     Array,
     Boolean,
@@ -278,6 +280,7 @@ pub enum PropKind {
     Map,
     Object,
     String,
+    Float,
 }
 
 impl From<PropKind> for si_frontend_types::PropKind {
@@ -285,6 +288,7 @@ impl From<PropKind> for si_frontend_types::PropKind {
         match value {
             PropKind::Array => si_frontend_types::PropKind::Array,
             PropKind::Boolean => si_frontend_types::PropKind::Boolean,
+            PropKind::Float => si_frontend_types::PropKind::Float,
             PropKind::Integer => si_frontend_types::PropKind::Integer,
             PropKind::Json => si_frontend_types::PropKind::Json,
             PropKind::Map => si_frontend_types::PropKind::Map,
@@ -314,7 +318,7 @@ impl PropKind {
     pub fn is_scalar(&self) -> bool {
         matches!(
             self,
-            PropKind::String | PropKind::Boolean | PropKind::Integer
+            PropKind::String | PropKind::Boolean | PropKind::Integer | PropKind::Float
         )
     }
 }
@@ -326,6 +330,7 @@ impl From<PropKind> for PropSpecKind {
             PropKind::Boolean => Self::Boolean,
             PropKind::String => Self::String,
             PropKind::Integer => Self::Number,
+            PropKind::Float => Self::Float,
             PropKind::Json => PropSpecKind::Json,
             PropKind::Object => Self::Object,
             PropKind::Map => Self::Map,
@@ -340,7 +345,7 @@ impl From<PropKind> for WidgetKind {
         match prop {
             PropKind::Array => Self::Array,
             PropKind::Boolean => Self::Checkbox,
-            PropKind::Json | PropKind::String | PropKind::Integer => Self::Text,
+            PropKind::Json | PropKind::String | PropKind::Integer | PropKind::Float => Self::Text,
             PropKind::Object => Self::Header,
             PropKind::Map => Self::Map,
         }
@@ -352,6 +357,7 @@ impl From<PropKind> for FuncBackendResponseType {
         match prop {
             PropKind::Array => Self::Array,
             PropKind::Boolean => Self::Boolean,
+            PropKind::Float => Self::Float,
             PropKind::Integer => Self::Integer,
             PropKind::Object => Self::Object,
             PropKind::Json => Self::Json,
@@ -1139,7 +1145,7 @@ impl Prop {
 
         Ok(match self.kind {
             PropKind::Boolean => "boolean".to_string(),
-            PropKind::Integer => "number".to_string(),
+            PropKind::Integer | PropKind::Float => "number".to_string(),
             PropKind::String => "string".to_string(),
             PropKind::Array => {
                 let element_prop_id = Self::element_prop_id(ctx, self.id).await?;
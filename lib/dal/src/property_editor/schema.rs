@@ -169,6 +169,7 @@ pub struct PropertyEditorProp {
 pub enum PropertyEditorPropKind {
     Array,
     Boolean,
+    Float,
     Integer,
     Json,
     Map,
@@ -181,6 +182,7 @@ impl From<PropKind> for PropertyEditorPropKind {
         match prop_kind {
             PropKind::Array => Self::Array,
             PropKind::Boolean => Self::Boolean,
+            PropKind::Float => Self::Float,
             PropKind::Integer => Self::Integer,
             PropKind::Json => Self::Json,
             PropKind::Object => Self::Object,
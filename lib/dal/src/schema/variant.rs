@@ -1878,6 +1878,7 @@ impl SchemaVariant {
                     }
                     IntrinsicFunc::SetArray
                     | IntrinsicFunc::SetBoolean
+                    | IntrinsicFunc::SetFloat
                     | IntrinsicFunc::SetInteger
                     | IntrinsicFunc::SetJson
                     | IntrinsicFunc::SetMap
@@ -2286,7 +2287,9 @@ impl SchemaVariant {
                 | EdgeWeightKindDiscriminants::SocketValue
                 | EdgeWeightKindDiscriminants::ValidationOutput
                 | EdgeWeightKindDiscriminants::Manages
-                | EdgeWeightKindDiscriminants::DiagramObject => {}
+                | EdgeWeightKindDiscriminants::DiagramObject
+                | EdgeWeightKindDiscriminants::FuncTestCase
+                | EdgeWeightKindDiscriminants::FuncVersion => {}
             }
         }
 
@@ -46,6 +46,17 @@ pub enum StatusUpdate {
         component_id: ComponentId,
         timestamp: DateTime<Utc>,
     },
+    /// How far along the dependent values update job is
+    #[serde(rename_all = "camelCase")]
+    DependentValueUpdateProgress {
+        /// The number of values the job set out to compute
+        total: usize,
+        /// The number of values computed so far, including those whose function failed
+        computed: usize,
+        /// The number of values skipped because newer changes will recompute them
+        skipped: usize,
+        timestamp: DateTime<Utc>,
+    },
     /// Updates sent by the rebaser
     #[serde(rename_all = "camelCase")]
     Rebase {
@@ -65,6 +76,16 @@ impl StatusUpdate {
         }
     }
 
+    /// Create a progress message for a dependent values update
+    pub fn new_dvu_progress(total: usize, computed: usize, skipped: usize) -> Self {
+        Self::DependentValueUpdateProgress {
+            total,
+            computed,
+            skipped,
+            timestamp: Utc::now(),
+        }
+    }
+
     /// Create a status update message for a rebase operation
     pub fn new_rebase(status: StatusMessageState) -> Self {
         Self::Rebase {
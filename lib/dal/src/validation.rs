@@ -270,24 +270,35 @@ impl ValidationOutput {
             return Ok(None);
         };
 
+        Self::compute_for_format(ctx, attribute_value_id, value, validation_format)
+            .await
+            .map(Some)
+    }
+
+    /// Run a validation for the given format and value. The [`AttributeValue`] is only used to
+    /// attribute the func run, so it may belong to a different prop than the format, e.g. the
+    /// map a new entry is about to be inserted into.
+    pub async fn compute_for_format(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+        value: Option<serde_json::Value>,
+        validation_format: String,
+    ) -> ValidationResult<ValidationOutput> {
         let result_channel =
             FuncRunner::run_validation_format(ctx, attribute_value_id, value, validation_format)
                 .await
                 .map_err(Box::new)?;
 
-        let mut validation_output = None;
-
         let func_result_value = match result_channel
             .await
             .map_err(|_| ValidationError::FuncRunGone)?
         {
             Ok(func_run_result) => func_run_result,
             Err(FuncRunnerError::ResultFailure { kind, message, .. }) => {
-                let _ = validation_output.insert(ValidationOutput {
+                return Ok(ValidationOutput {
                     status: ValidationStatus::Error,
                     message: Some(format!("{kind}: {message}")),
                 });
-                return Ok(validation_output);
             }
             Err(e) => return Err(Box::new(e).into()),
         };
@@ -319,7 +330,7 @@ impl ValidationOutput {
             )
             .await?;
 
-        Ok(Some(output))
+        Ok(output)
     }
 
     pub async fn list_for_component(
@@ -41,6 +41,7 @@ const DEFAULT_BUILTIN_WORKSPACE_NAME: &str = "builtin";
 const DEFAULT_BUILTIN_WORKSPACE_TOKEN: &str = "builtin";
 const DEFAULT_CHANGE_SET_NAME: &str = "HEAD";
 const DEFAULT_COMPONENT_CONCURRENCY_LIMIT: i32 = 256;
+const DEFAULT_VALUE_CONCURRENCY_LIMIT: i32 = 1024;
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -119,6 +120,7 @@ pub struct Workspace {
     token: Option<String>,
     snapshot_version: WorkspaceSnapshotGraphDiscriminants,
     component_concurrency_limit: Option<i32>,
+    value_concurrency_limit: Option<i32>,
 }
 
 impl TryFrom<PgRow> for Workspace {
@@ -137,6 +139,7 @@ impl TryFrom<PgRow> for Workspace {
             token: row.try_get("token")?,
             snapshot_version: WorkspaceSnapshotGraphDiscriminants::from_str(&snapshot_version)?,
             component_concurrency_limit: row.try_get("component_concurrency_limit")?,
+            value_concurrency_limit: row.try_get("value_concurrency_limit")?,
         })
     }
 }
@@ -277,6 +280,21 @@ impl Workspace {
         Ok(maybe_builtin)
     }
 
+    /// Lists every [`Workspace`] but the builtin one.
+    pub async fn list_all_user_workspaces(ctx: &DalContext) -> WorkspaceResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * FROM workspaces WHERE pk != $1 ORDER BY created_at",
+                &[&WorkspacePk::NONE],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
     pub async fn list_for_user(ctx: &DalContext) -> WorkspaceResult<Vec<Self>> {
         let user_pk = match ctx.history_actor() {
             HistoryActor::User(user_pk) => *user_pk,
@@ -743,6 +761,40 @@ impl Workspace {
         Ok(())
     }
 
+    /// The maximum number of attribute value functions a dependent values update runs at once.
+    pub fn value_concurrency_limit(&self) -> i32 {
+        self.value_concurrency_limit
+            .unwrap_or(DEFAULT_VALUE_CONCURRENCY_LIMIT)
+    }
+
+    pub fn raw_value_concurrency_limit(&self) -> Option<i32> {
+        self.value_concurrency_limit
+    }
+
+    pub async fn set_value_concurrency_limit(
+        &mut self,
+        ctx: &DalContext,
+        limit: Option<i32>,
+    ) -> WorkspaceResult<()> {
+        let limit = match limit {
+            Some(limit) if limit <= 0 => None,
+            other => other,
+        };
+
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "UPDATE workspaces SET value_concurrency_limit = $2 WHERE pk = $1",
+                &[&self.pk, &limit],
+            )
+            .await?;
+
+        self.value_concurrency_limit = limit;
+
+        Ok(())
+    }
+
     pub fn timestamp(&self) -> &Timestamp {
         &self.timestamp
     }
@@ -1612,6 +1612,8 @@ impl WorkspaceSnapshot {
                 | ContentAddressDiscriminants::DeprecatedActionRunner
                 | ContentAddressDiscriminants::Func
                 | ContentAddressDiscriminants::FuncArg
+                | ContentAddressDiscriminants::FuncTestCase
+                | ContentAddressDiscriminants::FuncVersion
                 | ContentAddressDiscriminants::Geometry
                 | ContentAddressDiscriminants::InputSocket
                 | ContentAddressDiscriminants::JsonValue
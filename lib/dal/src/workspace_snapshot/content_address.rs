@@ -38,6 +38,8 @@ pub enum ContentAddress {
     ManagementPrototype(ContentHash),
     Geometry(ContentHash),
     View(ContentHash),
+    FuncTestCase(ContentHash),
+    FuncVersion(ContentHash),
 }
 
 impl ContentAddress {
@@ -51,6 +53,8 @@ impl ContentAddress {
             | ContentAddress::DeprecatedActionBatch(id)
             | ContentAddress::DeprecatedActionRunner(id)
             | ContentAddress::FuncArg(id)
+            | ContentAddress::FuncTestCase(id)
+            | ContentAddress::FuncVersion(id)
             | ContentAddress::Func(id)
             | ContentAddress::Geometry(id)
             | ContentAddress::InputSocket(id)
@@ -69,6 +69,10 @@ pub enum EdgeWeightKind {
     Manages,
     /// From a view node to a diagram object node, to which geometries can be connected.
     DiagramObject,
+    /// From a [`Func`](crate::Func) to one of its [`FuncTestCase`](crate::func::test_case::FuncTestCase)s.
+    FuncTestCase,
+    /// From a [`Func`](crate::Func) to one of its [`FuncVersion`](crate::func::version::FuncVersion)s.
+    FuncVersion,
 }
 
 impl EdgeWeightKind {
@@ -1235,7 +1235,9 @@ impl WorkspaceSnapshotGraphV2 {
                     | EdgeWeightKind::ManagementPrototype
                     | EdgeWeightKind::ValidationOutput
                     | EdgeWeightKind::Manages
-                    | EdgeWeightKind::DiagramObject => {}
+                    | EdgeWeightKind::DiagramObject
+                    | EdgeWeightKind::FuncTestCase
+                    | EdgeWeightKind::FuncVersion => {}
                 }
             }
         }
@@ -751,6 +751,8 @@ impl WorkspaceSnapshotGraphV3 {
                     EdgeWeightKindDiscriminants::ManagementPrototype => "pink",
                     EdgeWeightKindDiscriminants::Manages => "pink",
                     EdgeWeightKindDiscriminants::DiagramObject => "black",
+                    EdgeWeightKindDiscriminants::FuncTestCase => "darkcyan",
+                    EdgeWeightKindDiscriminants::FuncVersion => "darkcyan",
                 };
 
                 match edgeref.weight().kind() {
@@ -798,6 +800,8 @@ impl WorkspaceSnapshotGraphV3 {
                             ContentAddressDiscriminants::ValidationOutput => "darkcyan",
                             ContentAddressDiscriminants::ManagementPrototype => "black",
                             ContentAddressDiscriminants::View => "black",
+                            ContentAddressDiscriminants::FuncTestCase => "darkcyan",
+                            ContentAddressDiscriminants::FuncVersion => "darkcyan",
                         };
                         (discrim.to_string(), color)
                     }
@@ -1414,7 +1418,9 @@ impl WorkspaceSnapshotGraphV3 {
                     | EdgeWeightKind::ValidationOutput
                     | EdgeWeightKind::ManagementPrototype
                     | EdgeWeightKind::Manages
-                    | EdgeWeightKind::DiagramObject => {}
+                    | EdgeWeightKind::DiagramObject
+                    | EdgeWeightKind::FuncTestCase
+                    | EdgeWeightKind::FuncVersion => {}
                 }
             }
         }
@@ -874,6 +874,8 @@ impl WorkspaceSnapshotGraphV4 {
                     EdgeWeightKindDiscriminants::ManagementPrototype => "pink",
                     EdgeWeightKindDiscriminants::Manages => "pink",
                     EdgeWeightKindDiscriminants::DiagramObject => "black",
+                    EdgeWeightKindDiscriminants::FuncTestCase => "darkcyan",
+                    EdgeWeightKindDiscriminants::FuncVersion => "darkcyan",
                 };
 
                 match edgeref.weight().kind() {
@@ -921,6 +923,8 @@ impl WorkspaceSnapshotGraphV4 {
                             ContentAddressDiscriminants::ValidationOutput => "darkcyan",
                             ContentAddressDiscriminants::ManagementPrototype => "black",
                             ContentAddressDiscriminants::View => "black",
+                            ContentAddressDiscriminants::FuncTestCase => "darkcyan",
+                            ContentAddressDiscriminants::FuncVersion => "darkcyan",
                         };
                         (discrim.to_string(), color)
                     }
@@ -1545,7 +1549,9 @@ impl WorkspaceSnapshotGraphV4 {
                     | EdgeWeightKind::ValidationOutput
                     | EdgeWeightKind::ManagementPrototype
                     | EdgeWeightKind::Manages
-                    | EdgeWeightKind::DiagramObject => {}
+                    | EdgeWeightKind::DiagramObject
+                    | EdgeWeightKind::FuncTestCase
+                    | EdgeWeightKind::FuncVersion => {}
                 }
             }
         }
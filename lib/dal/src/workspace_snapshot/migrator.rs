@@ -185,7 +185,10 @@ impl SnapshotGraphMigrator {
         );
 
         let mut working_graph: WorkspaceSnapshotGraph =
-            si_layer_cache::db::serialize::from_bytes(&snapshot_bytes)?;
+            si_layer_cache::db::serialize::from_bytes_with_dictionaries(
+                &snapshot_bytes,
+                ctx.layer_db().zstd_dictionaries(),
+            )?;
 
         // Incrementally migrate the graph until we reach the newest version.
         loop {
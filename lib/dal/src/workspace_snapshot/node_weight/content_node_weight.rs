@@ -115,6 +115,8 @@ impl ContentNodeWeight {
                     "Content".to_string(),
                 ));
             }
+            ContentAddress::FuncTestCase(_) => ContentAddress::FuncTestCase(content_hash),
+            ContentAddress::FuncVersion(_) => ContentAddress::FuncVersion(content_hash),
             ContentAddress::JsonValue(_) => ContentAddress::JsonValue(content_hash),
             ContentAddress::Module(_) => ContentAddress::Module(content_hash),
             ContentAddress::Prop(_) => {
@@ -12,6 +12,7 @@ use crate::change_set::event::{
     ChangeSetActorPayload, ChangeSetAppliedPayload, ChangeSetMergeVotePayload,
     ChangeSetStateChangePayload,
 };
+use crate::component::drift::ComponentDrift;
 use crate::component::{
     ComponentCreatedPayload, ComponentDeletedPayload, ComponentSetPositionPayload,
     ComponentUpdatedPayload, ComponentUpgradedPayload, ConnectionDeletedPayload,
@@ -22,6 +23,7 @@ use crate::diagram::view::{
     ViewObjectRemovedPayload, ViewWsPayload,
 };
 use crate::func::runner::FuncRunLogUpdatedPayload;
+use crate::func::test_case::{FuncTestCasesRunFailedPayload, FuncTestCasesRunPayload};
 use crate::func::{
     FuncWsEventCodeSaved, FuncWsEventFuncSummary, FuncWsEventGenerating, FuncWsEventPayload,
 };
@@ -95,6 +97,7 @@ pub enum WsPayload {
     CheckedQualifications(QualificationCheckPayload),
     ComponentCreated(ComponentCreatedPayload),
     ComponentDeleted(ComponentDeletedPayload),
+    ComponentDriftUpdated(ComponentDrift),
     ComponentUpdated(ComponentUpdatedPayload),
     ComponentUpgraded(ComponentUpgradedPayload),
     ConnectionDeleted(ConnectionDeletedPayload),
@@ -107,6 +110,8 @@ pub enum WsPayload {
     FuncGenerating(FuncWsEventGenerating),
     FuncRunLogUpdated(FuncRunLogUpdatedPayload),
     FuncSaved(FuncWsEventPayload),
+    FuncTestCasesRun(FuncTestCasesRunPayload),
+    FuncTestCasesRunFailed(FuncTestCasesRunFailedPayload),
     FuncUpdated(FuncWsEventFuncSummary),
     ImportWorkspaceVote(ImportWorkspaceVotePayload),
     InferredEdgeRemove(InferredEdgeRemovePayload),
@@ -1,8 +1,12 @@
 use dal::action::dependency_graph::ActionDependencyGraph;
+use dal::action::refresh_schedule::{
+    RefreshCadence, RefreshSchedule, RefreshScheduleError, RefreshScheduleTarget,
+};
 use dal::component::frame::Frame;
+use dal::component::resource::ResourceData;
 use dal::{
     action::prototype::ActionKind, action::prototype::ActionPrototype, action::Action,
-    action::ActionState, AttributeValue, Component, DalContext,
+    action::ActionState, AttributeValue, ChangeSet, Component, ComponentId, DalContext,
 };
 use dal_test::helpers::create_component_for_default_schema_name_in_default_view;
 use dal_test::helpers::create_component_for_schema_name_with_type_on_default_view;
@@ -12,6 +16,7 @@ use dal_test::helpers::{
 };
 use dal_test::test;
 use pretty_assertions_sorted::assert_eq;
+use veritech_client::ResourceStatus;
 
 #[test]
 async fn prototype_id(ctx: &mut DalContext) {
@@ -529,3 +534,347 @@ async fn actions_are_ordered_correctly(ctx: &mut DalContext) {
         vec![first_component_action]
     );
 }
+
+#[test]
+async fn rollback_batch_for_failed_action(ctx: &mut DalContext) {
+    let first =
+        create_component_for_default_schema_name_in_default_view(ctx, "small odd lego", "first")
+            .await
+            .expect("could not create component");
+    let dependent =
+        create_component_for_default_schema_name_in_default_view(ctx, "small even lego", "second")
+            .await
+            .expect("could not create component");
+    let unrelated =
+        create_component_for_default_schema_name_in_default_view(ctx, "small even lego", "third")
+            .await
+            .expect("could not create component");
+    connect_components_with_socket_names(ctx, first.id(), "two", dependent.id(), "two")
+        .await
+        .expect("could not connect components");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let first_action_id = Action::find_for_component_id(ctx, first.id())
+        .await
+        .expect("unable to find actions")
+        .pop()
+        .expect("no action for component");
+    let dependent_action_id = Action::find_for_component_id(ctx, dependent.id())
+        .await
+        .expect("unable to find actions")
+        .pop()
+        .expect("no action for component");
+    let unrelated_action_id = Action::find_for_component_id(ctx, unrelated.id())
+        .await
+        .expect("unable to find actions")
+        .pop()
+        .expect("no action for component");
+    Action::set_state(ctx, first_action_id, ActionState::Failed)
+        .await
+        .expect("unable to set state");
+
+    // Without opting in, a failure leaves the rest of the batch alone.
+    let compensating_action_ids = Action::rollback_batch_for_failed_action(ctx, first_action_id)
+        .await
+        .expect("unable to roll back batch");
+    assert!(compensating_action_ids.is_empty());
+    assert!(Action::all_ids(ctx)
+        .await
+        .expect("unable to list actions")
+        .contains(&dependent_action_id));
+
+    let mut change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("unable to find change set")
+        .expect("change set not found");
+    change_set
+        .set_rollback_on_failure(ctx, true)
+        .await
+        .expect("unable to opt into rollback");
+
+    // Nothing in the batch has created a resource yet, so there is nothing to destroy, but the
+    // actions that depend on the failure are dropped. The rest of the batch still runs.
+    let compensating_action_ids = Action::rollback_batch_for_failed_action(ctx, first_action_id)
+        .await
+        .expect("unable to roll back batch");
+    assert!(compensating_action_ids.is_empty());
+    let action_ids = Action::all_ids(ctx).await.expect("unable to list actions");
+    assert!(action_ids.contains(&first_action_id));
+    assert!(!action_ids.contains(&dependent_action_id));
+    assert!(action_ids.contains(&unrelated_action_id));
+}
+
+#[test]
+async fn rollback_batch_destroys_created_resources(ctx: &mut DalContext) {
+    let first =
+        create_component_for_default_schema_name_in_default_view(ctx, "small even lego", "first")
+            .await
+            .expect("could not create component");
+    let second =
+        create_component_for_default_schema_name_in_default_view(ctx, "small even lego", "second")
+            .await
+            .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let mut change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("unable to find change set")
+        .expect("change set not found");
+    change_set
+        .set_rollback_on_failure(ctx, true)
+        .await
+        .expect("unable to opt into rollback");
+
+    let first_action_id = Action::find_for_component_id(ctx, first.id())
+        .await
+        .expect("unable to find actions")
+        .pop()
+        .expect("no action for component");
+    let second_action_id = Action::find_for_component_id(ctx, second.id())
+        .await
+        .expect("unable to find actions")
+        .pop()
+        .expect("no action for component");
+
+    // The first create succeeds, the second fails.
+    first
+        .set_resource(
+            ctx,
+            ResourceData::new(
+                ResourceStatus::Ok,
+                Some(serde_json::json!({ "name": "first" })),
+            ),
+        )
+        .await
+        .expect("unable to set resource");
+    Action::record_created_by_batch(ctx, first_action_id, first.id())
+        .await
+        .expect("unable to record created component");
+    Action::remove_by_id(ctx, first_action_id)
+        .await
+        .expect("unable to remove action");
+    Action::set_state(ctx, second_action_id, ActionState::Failed)
+        .await
+        .expect("unable to set state");
+
+    let compensating_action_ids = Action::rollback_batch_for_failed_action(ctx, second_action_id)
+        .await
+        .expect("unable to roll back batch");
+    assert_eq!(1, compensating_action_ids.len());
+    let destroy_action_id = compensating_action_ids[0];
+    let destroy_prototype_id = Action::prototype_id(ctx, destroy_action_id)
+        .await
+        .expect("unable to get prototype id");
+    assert_eq!(
+        ActionKind::Destroy,
+        ActionPrototype::get_by_id(ctx, destroy_prototype_id)
+            .await
+            .expect("unable to get prototype")
+            .kind
+    );
+    assert_eq!(
+        Some(first.id()),
+        Action::component_id(ctx, destroy_action_id)
+            .await
+            .expect("unable to get component id")
+    );
+    // The compensation belongs to the same batch.
+    assert_eq!(
+        ctx.change_set_id(),
+        Action::get_by_id(ctx, destroy_action_id)
+            .await
+            .expect("unable to get action")
+            .originating_changeset_id()
+    );
+
+    // Another failure in the batch keeps the compensation rather than enqueueing it again.
+    let compensating_action_ids = Action::rollback_batch_for_failed_action(ctx, second_action_id)
+        .await
+        .expect("unable to roll back batch");
+    assert!(compensating_action_ids.is_empty());
+
+    // A create that was already running when the batch was rolled back is compensated for
+    // once it succeeds.
+    let in_flight =
+        create_component_for_default_schema_name_in_default_view(ctx, "small even lego", "third")
+            .await
+            .expect("could not create component");
+    let in_flight_action_id = Action::find_for_component_id(ctx, in_flight.id())
+        .await
+        .expect("unable to find actions")
+        .pop()
+        .expect("no action for component");
+    in_flight
+        .set_resource(
+            ctx,
+            ResourceData::new(
+                ResourceStatus::Ok,
+                Some(serde_json::json!({ "name": "third" })),
+            ),
+        )
+        .await
+        .expect("unable to set resource");
+    Action::record_created_by_batch(ctx, in_flight_action_id, in_flight.id())
+        .await
+        .expect("unable to record created component");
+    Action::remove_by_id(ctx, in_flight_action_id)
+        .await
+        .expect("unable to remove action");
+    let mut in_flight_kinds = Vec::new();
+    for action_id in Action::find_for_component_id(ctx, in_flight.id())
+        .await
+        .expect("unable to find actions")
+    {
+        let prototype_id = Action::prototype_id(ctx, action_id)
+            .await
+            .expect("unable to get prototype id");
+        in_flight_kinds.push(
+            ActionPrototype::get_by_id(ctx, prototype_id)
+                .await
+                .expect("unable to get prototype")
+                .kind,
+        );
+    }
+    assert_eq!(vec![ActionKind::Destroy], in_flight_kinds);
+
+    // A failing compensation does not start another rollback.
+    Action::set_state(ctx, destroy_action_id, ActionState::Failed)
+        .await
+        .expect("unable to set state");
+    let compensating_action_ids = Action::rollback_batch_for_failed_action(ctx, destroy_action_id)
+        .await
+        .expect("unable to roll back batch");
+    assert!(compensating_action_ids.is_empty());
+    assert!(Action::all_ids(ctx)
+        .await
+        .expect("unable to list actions")
+        .contains(&destroy_action_id));
+}
+
+#[test]
+async fn refresh_schedules(ctx: &mut DalContext) {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "small even lego", "lego")
+            .await
+            .expect("could not create component");
+
+    let too_short = RefreshSchedule::new(
+        ctx,
+        RefreshScheduleTarget::Component {
+            component_id: component.id(),
+        },
+        RefreshCadence::Interval { seconds: 1 },
+    )
+    .await;
+    assert!(matches!(
+        too_short,
+        Err(RefreshScheduleError::IntervalTooShort(1))
+    ));
+
+    let missing_component_id = ComponentId::generate();
+    let missing_target = RefreshSchedule::new(
+        ctx,
+        RefreshScheduleTarget::Component {
+            component_id: missing_component_id,
+        },
+        RefreshCadence::Interval { seconds: 60 },
+    )
+    .await;
+    assert!(matches!(
+        missing_target,
+        Err(RefreshScheduleError::ComponentNotFound(id)) if id == missing_component_id
+    ));
+
+    let schedule = RefreshSchedule::new(
+        ctx,
+        RefreshScheduleTarget::Component {
+            component_id: component.id(),
+        },
+        RefreshCadence::Cron {
+            expression: "*/15 * * * *".to_string(),
+        },
+    )
+    .await
+    .expect("unable to create schedule");
+    assert_eq!(
+        vec![schedule.clone()],
+        RefreshSchedule::list(ctx)
+            .await
+            .expect("unable to list schedules")
+    );
+
+    // Claiming moves the schedule on to its next run.
+    let now = schedule.next_run_at + chrono::Duration::minutes(1);
+    let claimed = RefreshSchedule::claim_due(ctx, now)
+        .await
+        .expect("unable to claim due schedules");
+    let claimed = claimed
+        .into_iter()
+        .find(|claimed| claimed.id == schedule.id)
+        .expect("schedule was not claimed");
+    assert_eq!(Some(now), claimed.last_run_at);
+    assert_eq!(
+        schedule.next_run_at + chrono::Duration::minutes(15),
+        claimed.next_run_at
+    );
+
+    // The component has no resource, so there is nothing to refresh.
+    assert!(claimed
+        .enqueue_refresh_actions(ctx)
+        .await
+        .expect("unable to enqueue refresh actions")
+        .is_empty());
+
+    assert!(RefreshSchedule::remove(ctx, schedule.id)
+        .await
+        .expect("unable to remove schedule"));
+    assert!(RefreshSchedule::list(ctx)
+        .await
+        .expect("unable to list schedules")
+        .is_empty());
+}
+
+#[test]
+async fn refresh_schedule_with_deleted_target_is_removed(ctx: &mut DalContext) {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "small even lego", "lego")
+            .await
+            .expect("could not create component");
+    let schedule = RefreshSchedule::new(
+        ctx,
+        RefreshScheduleTarget::Component {
+            component_id: component.id(),
+        },
+        RefreshCadence::Interval { seconds: 60 },
+    )
+    .await
+    .expect("unable to create schedule");
+
+    assert!(!schedule
+        .remove_if_target_gone(ctx)
+        .await
+        .expect("unable to check schedule target"));
+    assert_eq!(
+        vec![schedule.clone()],
+        RefreshSchedule::list(ctx)
+            .await
+            .expect("unable to list schedules")
+    );
+
+    Component::remove(ctx, component.id())
+        .await
+        .expect("unable to remove component");
+
+    assert!(schedule
+        .remove_if_target_gone(ctx)
+        .await
+        .expect("unable to check schedule target"));
+    assert!(RefreshSchedule::list(ctx)
+        .await
+        .expect("unable to list schedules")
+        .is_empty());
+}
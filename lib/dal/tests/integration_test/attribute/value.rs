@@ -1,3 +1,4 @@
+use dal::attribute::value::provenance::{ProvenanceNode, ProvenanceSource, ValueOrigin};
 use dal::{AttributeValue, DalContext};
 use dal_test::expected::ExpectComponent;
 use dal_test::helpers::ChangeSetTestHelpers;
@@ -28,3 +29,80 @@ async fn arguments_for_prototype_function_execution(ctx: &mut DalContext) -> Res
     );
     Ok(())
 }
+
+#[test]
+async fn provenance_follows_props_and_connections(ctx: &mut DalContext) -> Result<()> {
+    let source = ExpectComponent::create_named(ctx, "pet_shop", "Petopia").await;
+    let pirate = ExpectComponent::create_named(ctx, "pirate", "Long John Silver").await;
+    source
+        .prop(ctx, ["root", "domain", "parrot_names"])
+        .await
+        .push(ctx, "Captain Flint")
+        .await;
+    source
+        .connect(ctx, "parrot_names", pirate, "parrot_names")
+        .await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    // "/root/domain/name" is the identity of "/root/si/name", which is set manually.
+    let name_av_id = pirate
+        .prop(ctx, ["root", "domain", "name"])
+        .await
+        .attribute_value(ctx)
+        .await
+        .id();
+    let provenance = AttributeValue::provenance(ctx, name_av_id).await?;
+    assert_eq!(ValueOrigin::Function, provenance.origin);
+    assert_eq!(Some(json!("Long John Silver")), provenance.value);
+    let [argument] = provenance.arguments.as_slice() else {
+        panic!("expected a single argument: {:?}", provenance.arguments);
+    };
+    assert_eq!("identity", argument.name);
+    let ProvenanceSource::Prop { upstream, .. } = &argument.source else {
+        panic!("expected a prop source: {:?}", argument.source);
+    };
+    assert_eq!(Some("/root/si/name"), upstream.path.as_deref());
+    assert_eq!(ValueOrigin::Manual, upstream.origin);
+    assert!(upstream.arguments.is_empty());
+
+    // The parrot names come through the input socket, from the pet shop's output socket.
+    let parrots_av_id = pirate
+        .prop(ctx, ["root", "domain", "parrot_names"])
+        .await
+        .attribute_value(ctx)
+        .await
+        .id();
+    let provenance = AttributeValue::provenance(ctx, parrots_av_id).await?;
+    assert_eq!(ValueOrigin::Function, provenance.origin);
+    let [argument] = provenance.arguments.as_slice() else {
+        panic!("expected a single argument: {:?}", provenance.arguments);
+    };
+    let ProvenanceSource::InputSocket { upstream, .. } = &argument.source else {
+        panic!("expected an input socket source: {:?}", argument.source);
+    };
+    assert_eq!(pirate.id(), upstream.component_id);
+    assert!(upstream.arguments.iter().any(|argument| matches!(
+        &argument.source,
+        ProvenanceSource::OutputSocket { upstream, .. } if upstream.component_id == source.id()
+    )));
+    assert!(component_names(&provenance).contains(&"Petopia".to_owned()));
+
+    Ok(())
+}
+
+fn component_names(node: &ProvenanceNode) -> Vec<String> {
+    let mut names = vec![node.component_name.clone()];
+    if let Some(ancestor) = &node.controlling_ancestor {
+        names.extend(component_names(ancestor));
+    }
+    for argument in &node.arguments {
+        match &argument.source {
+            ProvenanceSource::InferredConnection { upstream, .. }
+            | ProvenanceSource::InputSocket { upstream, .. }
+            | ProvenanceSource::OutputSocket { upstream, .. }
+            | ProvenanceSource::Prop { upstream, .. } => names.extend(component_names(upstream)),
+            ProvenanceSource::Secret { .. } | ProvenanceSource::StaticValue { .. } => {}
+        }
+    }
+    names
+}
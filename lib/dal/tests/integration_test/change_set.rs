@@ -1,3 +1,12 @@
+use dal::change_set::approval_policy::{
+    ApprovalGroup, ApprovalPolicy, ApprovalPolicyError, ApprovalPolicyEvaluation,
+    ApprovalRequirementKind, ChangeSetApproval, GroupApprovalRequirement,
+};
+use dal::change_set::cherry_pick::CherryPickError;
+use dal::change_set::diff::{ComponentChange, DiffKind, SnapshotDiff};
+use dal::change_set::fork::ForkBase;
+use dal::change_set::merge_preview::{MergePreview, UpdateClassification};
+use dal::change_set::restore::RestorePoint;
 use dal::change_set::view::OpenChangeSetsView;
 use dal::{
     context::TransactionsErrorDiscriminants, DalContext, DalContextBuilder, HistoryActor,
@@ -5,12 +14,15 @@ use dal::{
 };
 use dal::{ChangeSet, ChangeSetStatus, Component};
 use dal_test::helpers::{
-    create_component_for_default_schema_name_in_default_view, create_user, ChangeSetTestHelpers,
+    connect_components_with_socket_names, create_component_for_default_schema_name_in_default_view,
+    create_user, get_attribute_value_for_component, update_attribute_value_for_component,
+    ChangeSetTestHelpers,
 };
 use dal_test::test;
 use itertools::Itertools;
 use pretty_assertions_sorted::assert_eq;
 use std::collections::HashSet;
+use std::time::Duration;
 
 #[test]
 async fn open_change_sets(ctx: &mut DalContext) {
@@ -474,3 +486,758 @@ async fn change_set_approval_flow(ctx: &mut DalContext) {
         .collect_vec();
     assert_eq!(components.len(), 2);
 }
+
+#[test]
+async fn snapshot_diff(ctx: &mut DalContext) {
+    let component = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "starfield",
+        "the sound of silence",
+    )
+    .await
+    .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let diff = SnapshotDiff::for_change_set(ctx, ctx.change_set_id())
+        .await
+        .expect("could not diff change set");
+    assert_eq!(
+        vec![ComponentChange {
+            component_id: component.id(),
+            kind: DiffKind::Added,
+            name: "the sound of silence".to_string(),
+            old_name: None,
+        }],
+        diff.components
+    );
+    assert!(diff.attribute_values.iter().any(|change| {
+        change.component_id == component.id()
+            && change.path == "/root/si/name"
+            && change.kind == DiffKind::Added
+            && change.after == Some(serde_json::json!("the sound of silence"))
+    }));
+
+    // Apply, fork and rename the component.
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    let diff = SnapshotDiff::for_change_set(ctx, ctx.change_set_id())
+        .await
+        .expect("could not diff change set");
+    assert!(diff.is_empty());
+
+    component
+        .set_name(ctx, "mrs. robinson")
+        .await
+        .expect("could not rename component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let diff = SnapshotDiff::for_change_set(ctx, ctx.change_set_id())
+        .await
+        .expect("could not diff change set");
+    assert_eq!(
+        vec![ComponentChange {
+            component_id: component.id(),
+            kind: DiffKind::Modified,
+            name: "mrs. robinson".to_string(),
+            old_name: Some("the sound of silence".to_string()),
+        }],
+        diff.components
+    );
+    assert!(diff.attribute_values.iter().any(|change| {
+        change.path == "/root/si/name"
+            && change.kind == DiffKind::Modified
+            && change.before == Some(serde_json::json!("the sound of silence"))
+            && change.after == Some(serde_json::json!("mrs. robinson"))
+    }));
+    assert!(diff.connections.is_empty());
+    assert!(diff.funcs.is_empty());
+    assert!(diff.schema_variants.is_empty());
+
+    // Work applied to HEAD after the fork is not the change set's own, so it does not show up
+    // as reverted.
+    let renamed_change_set_id = ctx.change_set_id();
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    create_component_for_default_schema_name_in_default_view(ctx, "starfield", "cecilia")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+    ctx.update_visibility_and_snapshot_to_visibility(renamed_change_set_id)
+        .await
+        .expect("could not update visibility");
+
+    let diff = SnapshotDiff::for_change_set(ctx, renamed_change_set_id)
+        .await
+        .expect("could not diff change set");
+    assert_eq!(
+        vec![ComponentChange {
+            component_id: component.id(),
+            kind: DiffKind::Modified,
+            name: "mrs. robinson".to_string(),
+            old_name: Some("the sound of silence".to_string()),
+        }],
+        diff.components
+    );
+}
+
+#[test]
+async fn restore_head_to_applied_change_set(ctx: &mut DalContext) {
+    let first_component =
+        create_component_for_default_schema_name_in_default_view(ctx, "starfield", "the boxer")
+            .await
+            .expect("could not create component");
+    let restore_to_change_set_id = ctx.change_set_id();
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+
+    // The restore point is HEAD right after the apply, not the change set's own snapshot.
+    let head_change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find change set")
+        .expect("change set not found");
+    assert_eq!(
+        Some(head_change_set.workspace_snapshot_address),
+        ChangeSet::find(ctx, restore_to_change_set_id)
+            .await
+            .expect("could not find change set")
+            .expect("change set not found")
+            .applied_base_snapshot_address
+    );
+
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    let second_component = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "starfield",
+        "the only living boy in new york",
+    )
+    .await
+    .expect("could not create component");
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+
+    let restore_change_set = ChangeSet::fork_head_as_of(
+        ctx,
+        Some("restore".to_string()),
+        RestorePoint::ChangeSet {
+            change_set_id: restore_to_change_set_id,
+        },
+    )
+    .await
+    .expect("could not restore");
+    ctx.update_visibility_and_snapshot_to_visibility(restore_change_set.id)
+        .await
+        .expect("could not update visibility");
+    assert_eq!(
+        vec![first_component.id()],
+        Component::list_ids(ctx)
+            .await
+            .expect("could not list components")
+    );
+
+    // Applying the restore change set reverts HEAD.
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+    let component_ids = Component::list_ids(ctx)
+        .await
+        .expect("could not list components");
+    assert!(component_ids.contains(&first_component.id()));
+    assert!(!component_ids.contains(&second_component.id()));
+
+    // Only change sets applied to HEAD can be restored.
+    assert!(ChangeSet::fork_head_as_of(
+        ctx,
+        None,
+        RestorePoint::ChangeSet {
+            change_set_id: ctx.change_set_id(),
+        },
+    )
+    .await
+    .is_err());
+}
+
+#[test]
+async fn merge_preview_classifies_concurrent_changes(ctx: &mut DalContext) {
+    let edited_on_both = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "starfield",
+        "bridge over troubled water",
+    )
+    .await
+    .expect("could not create component");
+    let edited_here =
+        create_component_for_default_schema_name_in_default_view(ctx, "starfield", "cecilia")
+            .await
+            .expect("could not create component");
+    let deleted_on_head = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "starfield",
+        "el condor pasa",
+    )
+    .await
+    .expect("could not create component");
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+    let head_change_set_id = ctx.change_set_id();
+
+    let change_set = ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    assert_eq!(
+        Some(
+            ChangeSet::find(ctx, head_change_set_id)
+                .await
+                .expect("could not find head")
+                .expect("head not found")
+                .workspace_snapshot_address
+        ),
+        change_set.merge_base_snapshot_address
+    );
+    for (component, name) in [
+        (&edited_on_both, "keep the customer satisfied"),
+        (&edited_here, "the only living boy in new york"),
+        (&deleted_on_head, "so long, frank lloyd wright"),
+    ] {
+        component
+            .set_name(ctx, name)
+            .await
+            .expect("could not rename component");
+    }
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let preview = MergePreview::for_change_set(ctx, change_set.id)
+        .await
+        .expect("could not preview merge");
+    assert!(!preview.has_unresolved_overwrites());
+    assert_eq!(0, preview.dropped);
+    assert_eq!(3, preview.components.len());
+
+    // Move HEAD on without replaying its changes onto the change set, as if the replay had not
+    // arrived yet.
+    ctx.update_visibility_and_snapshot_to_visibility(head_change_set_id)
+        .await
+        .expect("could not update visibility");
+    Component::get_by_id(ctx, edited_on_both.id())
+        .await
+        .expect("could not get component")
+        .set_name(ctx, "the boxer")
+        .await
+        .expect("could not rename component");
+    Component::remove(ctx, deleted_on_head.id())
+        .await
+        .expect("could not remove component");
+    let head_address = ctx
+        .workspace_snapshot()
+        .expect("could not get snapshot")
+        .write(ctx)
+        .await
+        .expect("could not write snapshot");
+    ChangeSet::find(ctx, head_change_set_id)
+        .await
+        .expect("could not find head")
+        .expect("head not found")
+        .update_pointer(ctx, head_address)
+        .await
+        .expect("could not update head pointer");
+    ctx.commit_no_rebase().await.expect("could not commit");
+    ctx.update_visibility_and_snapshot_to_visibility(change_set.id)
+        .await
+        .expect("could not update visibility");
+
+    let preview = MergePreview::for_change_set(ctx, change_set.id)
+        .await
+        .expect("could not preview merge");
+    assert!(preview.has_unresolved_overwrites());
+
+    let component_preview = |component_id| {
+        preview
+            .components
+            .iter()
+            .find(|component| component.component_id == component_id)
+            .expect("component not in preview")
+    };
+
+    let both = component_preview(edited_on_both.id());
+    assert!(!both.deleted_on_base);
+    assert!(both.updates.iter().any(|update| {
+        update.classification == UpdateClassification::Overwrite
+            && update.path.as_deref() == Some("/root/si/name")
+    }));
+
+    let here = component_preview(edited_here.id());
+    assert!(here
+        .updates
+        .iter()
+        .all(|update| update.classification == UpdateClassification::Clean));
+
+    let deleted = component_preview(deleted_on_head.id());
+    assert!(deleted.deleted_on_base);
+    assert!(deleted
+        .updates
+        .iter()
+        .any(|update| update.classification == UpdateClassification::Overwrite));
+}
+
+#[test]
+async fn fork_and_cherry_pick_change_sets(ctx: &mut DalContext) {
+    let head_change_set_id = ctx
+        .get_workspace_default_change_set_id()
+        .await
+        .expect("could not get default change set id");
+    let source_change_set_id = ctx.change_set_id();
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "starfield", "mrs robinson")
+            .await
+            .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    // A fork stacked on the source sees its work and applies back onto it.
+    let stacked = ChangeSet::fork(ctx, "stacked", source_change_set_id, ForkBase::Source)
+        .await
+        .expect("could not fork change set");
+    assert_eq!(Some(source_change_set_id), stacked.base_change_set_id);
+    let templated = ChangeSet::fork(ctx, "templated", source_change_set_id, ForkBase::Head)
+        .await
+        .expect("could not fork change set");
+    assert_eq!(Some(head_change_set_id), templated.base_change_set_id);
+    ctx.commit_no_rebase().await.expect("could not commit");
+
+    for change_set_id in [stacked.id, templated.id] {
+        ctx.update_visibility_and_snapshot_to_visibility(change_set_id)
+            .await
+            .expect("could not update visibility");
+        assert_eq!(
+            "mrs robinson",
+            Component::get_by_id(ctx, component.id())
+                .await
+                .expect("could not get component")
+                .name(ctx)
+                .await
+                .expect("could not get name")
+        );
+    }
+
+    // Cherry-picking the source onto a change set forked from HEAD brings its work over.
+    let target = ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    assert!(Component::try_get_by_id(ctx, component.id())
+        .await
+        .expect("could not look up component")
+        .is_none());
+    let report = ChangeSet::cherry_pick(ctx, source_change_set_id)
+        .await
+        .expect("could not cherry-pick");
+    assert_eq!(source_change_set_id, report.source_change_set_id);
+    assert_eq!(target.id, report.target_change_set_id);
+    assert!(report.applied > 0);
+    assert!(report.unapplied.is_empty());
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+    assert_eq!(
+        "mrs robinson",
+        Component::get_by_id(ctx, component.id())
+            .await
+            .expect("could not get component")
+            .name(ctx)
+            .await
+            .expect("could not get name")
+    );
+
+    let result = ChangeSet::cherry_pick(ctx, target.id).await;
+    assert!(matches!(
+        result,
+        Err(CherryPickError::CherryPickOntoSelf(change_set_id)) if change_set_id == target.id
+    ));
+}
+
+#[test]
+async fn cherry_pick_recomputes_dependent_values_on_the_target(ctx: &mut DalContext) {
+    let odd_lego =
+        create_component_for_default_schema_name_in_default_view(ctx, "small odd lego", "odd")
+            .await
+            .expect("could not create component");
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+
+    // The source sets the value that feeds the odd lego's "two" output socket.
+    let source = ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    update_attribute_value_for_component(
+        ctx,
+        odd_lego.id(),
+        &["root", "domain", "two"],
+        serde_json::json!("cherry"),
+    )
+    .await
+    .expect("could not update value");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    // Only the target has a component that depends on that value.
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    let even_lego =
+        create_component_for_default_schema_name_in_default_view(ctx, "small even lego", "even")
+            .await
+            .expect("could not create component");
+    connect_components_with_socket_names(ctx, odd_lego.id(), "two", even_lego.id(), "two")
+        .await
+        .expect("could not connect components");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+    assert_eq!(
+        None,
+        get_attribute_value_for_component(ctx, even_lego.id(), &["root", "domain", "two"])
+            .await
+            .expect("could not get value")
+    );
+
+    ChangeSet::cherry_pick(ctx, source.id)
+        .await
+        .expect("could not cherry-pick");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    assert_eq!(
+        Some(serde_json::json!("cherry")),
+        get_attribute_value_for_component(ctx, even_lego.id(), &["root", "domain", "two"])
+            .await
+            .expect("could not get value")
+    );
+}
+
+#[test]
+async fn approval_policy_gates_apply(ctx: &mut DalContext) {
+    let author = ChangeSet::extract_userid_from_context(ctx)
+        .await
+        .expect("no user in context");
+    let reviewer = create_user(ctx).await.expect("could not create user");
+    let platform_reviewer = create_user(ctx).await.expect("could not create user");
+    ApprovalPolicy::set(
+        ctx,
+        Some(ApprovalPolicy {
+            required_approvals: 2,
+            groups: vec![ApprovalGroup {
+                name: "platform".to_string(),
+                members: vec![platform_reviewer.pk()],
+            }],
+            group_requirements: vec![GroupApprovalRequirement {
+                group: "platform".to_string(),
+                required_approvals: 1,
+                schema_names: vec!["starfield".to_string()],
+            }],
+            allow_self_approval: false,
+            invalidate_on_change: true,
+        }),
+    )
+    .await
+    .expect("could not set approval policy");
+
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "starfield", "scarborough")
+            .await
+            .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+    let change_set_id = ctx.change_set_id();
+    ChangeSet::find(ctx, change_set_id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found")
+        .request_change_set_approval(ctx)
+        .await
+        .expect("could not request approval");
+
+    // The author cannot approve their own change set.
+    let result = ChangeSet::find(ctx, change_set_id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found")
+        .approve_change_set_for_apply(ctx)
+        .await;
+    assert!(matches!(
+        result,
+        Err(dal::ChangeSetError::ApprovalPolicy(err))
+            if matches!(*err, ApprovalPolicyError::SelfApprovalNotAllowed(_, user) if user == author)
+    ));
+
+    // A single approval outside of the platform group isn't enough.
+    ctx.update_history_actor(HistoryActor::User(reviewer.pk()));
+    ChangeSet::find(ctx, change_set_id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found")
+        .approve_change_set_for_apply(ctx)
+        .await
+        .expect("could not approve");
+    assert_eq!(
+        ChangeSetStatus::NeedsApproval,
+        ChangeSet::find(ctx, change_set_id)
+            .await
+            .expect("could not find change set")
+            .expect("change set not found")
+            .status
+    );
+    let evaluation = ApprovalPolicyEvaluation::for_change_set(ctx, change_set_id)
+        .await
+        .expect("could not evaluate approval policy");
+    assert!(!evaluation.satisfied);
+    assert_eq!(2, evaluation.requirements.len());
+    assert!(evaluation.requirements.iter().any(|requirement| {
+        requirement.requirement
+            == ApprovalRequirementKind::Group {
+                group: "platform".to_string(),
+            }
+            && !requirement.satisfied
+    }));
+    assert!(
+        ChangeSetTestHelpers::apply_change_set_to_base_approvals(ctx)
+            .await
+            .is_err()
+    );
+
+    ctx.update_history_actor(HistoryActor::User(platform_reviewer.pk()));
+    ChangeSet::find(ctx, change_set_id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found")
+        .approve_change_set_for_apply(ctx)
+        .await
+        .expect("could not approve");
+    assert_eq!(
+        ChangeSetStatus::Approved,
+        ChangeSet::find(ctx, change_set_id)
+            .await
+            .expect("could not find change set")
+            .expect("change set not found")
+            .status
+    );
+    assert!(
+        ApprovalPolicyEvaluation::for_change_set(ctx, change_set_id)
+            .await
+            .expect("could not evaluate approval policy")
+            .satisfied
+    );
+
+    // Changing the change set invalidates the approvals.
+    component
+        .set_name(ctx, "parsley, sage, rosemary and thyme")
+        .await
+        .expect("could not rename component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+    let evaluation = ApprovalPolicyEvaluation::for_change_set(ctx, change_set_id)
+        .await
+        .expect("could not evaluate approval policy");
+    assert!(!evaluation.satisfied);
+    assert_eq!(2, evaluation.discarded_approvals.len());
+
+    // Reopening the change set clears its approvals.
+    ChangeSet::find(ctx, change_set_id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found")
+        .reopen_change_set(ctx)
+        .await
+        .expect("could not reopen change set");
+    assert!(ChangeSetApproval::list_for_change_set(ctx, change_set_id)
+        .await
+        .expect("could not list approvals")
+        .is_empty());
+}
+
+#[test]
+async fn approval_policy_applies_to_force_apply_and_reject(ctx: &mut DalContext) {
+    let author = ChangeSet::extract_userid_from_context(ctx)
+        .await
+        .expect("no user in context");
+    let reviewer = create_user(ctx).await.expect("could not create user");
+    ApprovalPolicy::set(
+        ctx,
+        Some(ApprovalPolicy {
+            required_approvals: 2,
+            allow_self_approval: false,
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect("could not set approval policy");
+
+    create_component_for_default_schema_name_in_default_view(ctx, "starfield", "scarborough")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+    let change_set_id = ctx.change_set_id();
+    ChangeSet::find(ctx, change_set_id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found")
+        .request_change_set_approval(ctx)
+        .await
+        .expect("could not request approval");
+
+    // Forcing the apply only adds the reviewer's approval, which isn't enough.
+    ctx.update_history_actor(HistoryActor::User(reviewer.pk()));
+    let result = ChangeSet::prepare_for_force_apply(ctx).await;
+    assert!(
+        matches!(
+            result,
+            Err(dal::ChangeSetError::ApprovalPolicyNotSatisfied(id)) if id == change_set_id
+        ),
+        "{result:?}"
+    );
+    let change_set = ChangeSet::find(ctx, change_set_id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found");
+    assert_eq!(ChangeSetStatus::NeedsApproval, change_set.status);
+    assert_eq!(Some(author), change_set.merge_requested_by_user_id);
+    assert_eq!(
+        vec![reviewer.pk()],
+        ChangeSetApproval::list_for_change_set(ctx, change_set_id)
+            .await
+            .expect("could not list approvals")
+            .into_iter()
+            .map(|approval| approval.user_id)
+            .collect::<Vec<_>>()
+    );
+
+    // Rejecting the change set clears its approvals.
+    ChangeSet::find(ctx, change_set_id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found")
+        .reject_change_set_for_apply(ctx)
+        .await
+        .expect("could not reject change set");
+    assert!(ChangeSetApproval::list_for_change_set(ctx, change_set_id)
+        .await
+        .expect("could not list approvals")
+        .is_empty());
+}
+
+#[test]
+async fn approval_policy_counts_the_author_as_self(ctx: &mut DalContext) {
+    let author = ChangeSet::extract_userid_from_context(ctx)
+        .await
+        .expect("no user in context");
+    let requester = create_user(ctx).await.expect("could not create user");
+    ApprovalPolicy::set(
+        ctx,
+        Some(ApprovalPolicy {
+            allow_self_approval: false,
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect("could not set approval policy");
+
+    let change_set = ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    assert_eq!(Some(author), change_set.created_by_user_id);
+
+    // Requesting the approval from a second account doesn't make the author's approval count.
+    ctx.update_history_actor(HistoryActor::User(requester.pk()));
+    ChangeSet::find(ctx, change_set.id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found")
+        .request_change_set_approval(ctx)
+        .await
+        .expect("could not request approval");
+
+    ctx.update_history_actor(HistoryActor::User(author));
+    let result = ChangeSet::find(ctx, change_set.id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found")
+        .approve_change_set_for_apply(ctx)
+        .await;
+    assert!(matches!(
+        result,
+        Err(dal::ChangeSetError::ApprovalPolicy(err))
+            if matches!(*err, ApprovalPolicyError::SelfApprovalNotAllowed(_, user) if user == author)
+    ));
+    assert!(matches!(
+        ChangeSet::ensure_approval_policy_satisfied(ctx, change_set.id).await,
+        Err(dal::ChangeSetError::ApprovalPolicyNotSatisfied(id)) if id == change_set.id
+    ));
+}
+
+#[test]
+async fn replayed_head_updates_record_their_head_snapshot_as_merge_base(ctx: &mut DalContext) {
+    let applying_change_set_id = ctx.change_set_id();
+    let replayed_onto = ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    ctx.update_visibility_and_snapshot_to_visibility(applying_change_set_id)
+        .await
+        .expect("could not update visibility");
+
+    create_component_for_default_schema_name_in_default_view(ctx, "starfield", "america")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+    let head_change_set_id = ctx
+        .get_workspace_default_change_set_id()
+        .await
+        .expect("could not get head change set id");
+    let head_address = ChangeSet::find(ctx, head_change_set_id)
+        .await
+        .expect("could not find head")
+        .expect("head not found")
+        .workspace_snapshot_address;
+    assert_ne!(
+        Some(head_address),
+        replayed_onto.merge_base_snapshot_address
+    );
+
+    let mut merge_base = None;
+    for _ in 0..100 {
+        merge_base = ChangeSet::find(ctx, replayed_onto.id)
+            .await
+            .expect("could not find change set")
+            .expect("change set not found")
+            .merge_base_snapshot_address;
+        if merge_base == Some(head_address) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(Some(head_address), merge_base);
+}
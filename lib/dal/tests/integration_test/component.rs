@@ -14,8 +14,10 @@ use dal_test::test;
 use pretty_assertions_sorted::assert_eq;
 use serde_json::json;
 
+mod bulk_update;
 mod debug;
 mod delete;
+mod drift;
 mod get_code;
 mod get_diff;
 mod property_order;
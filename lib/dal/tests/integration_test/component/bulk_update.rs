@@ -0,0 +1,118 @@
+use dal::component::bulk_update::{AttributePatch, BulkUpdateTargets};
+use dal::{Component, DalContext};
+use dal_test::expected::ExpectComponent;
+use dal_test::helpers::ChangeSetTestHelpers;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+#[test]
+async fn bulk_update_schema_variant_components(ctx: &mut DalContext) {
+    let blackbeard = ExpectComponent::create_named(ctx, "pirate", "Blackbeard").await;
+    let anne = ExpectComponent::create_named(ctx, "pirate", "Anne Bonny").await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let schema_variant_id = blackbeard.schema_variant(ctx).await.id();
+    let report = Component::bulk_update_attributes(
+        ctx,
+        BulkUpdateTargets::SchemaVariant { schema_variant_id },
+        vec![
+            AttributePatch {
+                path: "/root/domain/working_eyes".to_owned(),
+                value: Some(json!(1)),
+            },
+            AttributePatch {
+                path: "/root/domain/treasure/island".to_owned(),
+                value: Some(json!("tortuga")),
+            },
+        ],
+    )
+    .await
+    .expect("unable to bulk update");
+    assert!(report.applied());
+    assert_eq!(4, report.changes.len());
+    let mut component_ids = report.component_ids();
+    component_ids.sort();
+    let mut expected_component_ids = vec![blackbeard.id(), anne.id()];
+    expected_component_ids.sort();
+    assert_eq!(expected_component_ids, component_ids);
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    for pirate in [blackbeard, anne] {
+        assert_eq!(
+            json!(1),
+            pirate
+                .prop(ctx, ["root", "domain", "working_eyes"])
+                .await
+                .get(ctx)
+                .await
+        );
+        assert_eq!(
+            json!({ "island": "tortuga" }),
+            pirate
+                .prop(ctx, ["root", "domain", "treasure"])
+                .await
+                .get(ctx)
+                .await
+        );
+    }
+}
+
+#[test]
+async fn bulk_update_is_not_applied_if_any_value_fails(ctx: &mut DalContext) {
+    let blackbeard = ExpectComponent::create_named(ctx, "pirate", "Blackbeard").await;
+    let anne = ExpectComponent::create_named(ctx, "pirate", "Anne Bonny").await;
+    blackbeard
+        .prop(ctx, ["root", "domain", "working_eyes"])
+        .await
+        .set(ctx, 2)
+        .await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let report = Component::bulk_update_attributes(
+        ctx,
+        BulkUpdateTargets::Components {
+            component_ids: vec![blackbeard.id(), anne.id()],
+        },
+        vec![
+            AttributePatch {
+                path: "/root/domain/working_eyes".to_owned(),
+                value: Some(json!(3)),
+            },
+            AttributePatch {
+                path: "/root/domain/parrot_names/-".to_owned(),
+                value: Some(json!("polly")),
+            },
+            AttributePatch {
+                path: "/root/domain/peg_legs".to_owned(),
+                value: Some(json!(1)),
+            },
+        ],
+    )
+    .await
+    .expect("unable to bulk update");
+
+    // Too many eyes, a function-set array and a missing prop, for each pirate.
+    assert!(!report.applied());
+    assert!(report.changes.is_empty());
+    assert_eq!(6, report.failures.len());
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+    assert_eq!(
+        json!(2),
+        blackbeard
+            .prop(ctx, ["root", "domain", "working_eyes"])
+            .await
+            .get(ctx)
+            .await
+    );
+}
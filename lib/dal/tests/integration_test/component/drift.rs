@@ -0,0 +1,114 @@
+use dal::component::drift::{DriftStatus, DriftSummary, PropDrift};
+use dal::component::resource::ResourceData;
+use dal::{Component, DalContext};
+use dal_test::expected::ExpectComponent;
+use dal_test::helpers::ChangeSetTestHelpers;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+use veritech_client::ResourceStatus;
+
+#[test]
+async fn drift_compares_domain_against_resource(ctx: &mut DalContext) {
+    let docker_image = ExpectComponent::create_named(ctx, "Docker Image", "drifter").await;
+    docker_image
+        .prop(ctx, ["root", "domain", "image"])
+        .await
+        .set(ctx, "nginx")
+        .await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    // Without a resource there is nothing to compare against.
+    let drift = Component::drift(ctx, docker_image.id())
+        .await
+        .expect("unable to compute drift");
+    assert_eq!(DriftStatus::Unknown, drift.status);
+    assert!(drift.drifted_props.is_empty());
+    assert_eq!(None, drift.last_synced);
+    assert_eq!(
+        None,
+        Component::resource_drift(ctx, docker_image.id())
+            .await
+            .expect("unable to compute resource drift")
+    );
+
+    let component = docker_image.component(ctx).await;
+    component
+        .set_resource(
+            ctx,
+            ResourceData::new(ResourceStatus::Ok, Some(json!({ "image": "nginx" }))),
+        )
+        .await
+        .expect("unable to set resource");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let drift = Component::drift(ctx, docker_image.id())
+        .await
+        .expect("unable to compute drift");
+    assert_eq!(DriftStatus::InSync, drift.status);
+    assert!(drift.drifted_props.is_empty());
+    assert!(drift.last_synced.is_some());
+
+    component
+        .set_resource(
+            ctx,
+            ResourceData::new(ResourceStatus::Ok, Some(json!({ "image": "httpd" }))),
+        )
+        .await
+        .expect("unable to set resource");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let drift = Component::drift(ctx, docker_image.id())
+        .await
+        .expect("unable to compute drift");
+    assert_eq!(DriftStatus::Drifted, drift.status);
+    assert_eq!(
+        vec![PropDrift {
+            path: "/root/domain/image".to_string(),
+            modeled: json!("nginx"),
+            actual: json!("httpd"),
+        }],
+        drift.drifted_props
+    );
+
+    let summary = DriftSummary::get_summary(ctx)
+        .await
+        .expect("unable to get drift summary");
+    let component_drift = summary
+        .components
+        .iter()
+        .find(|component_drift| component_drift.component_id == docker_image.id())
+        .expect("component missing from summary");
+    assert_eq!(DriftStatus::Drifted, component_drift.status);
+    assert_eq!(
+        summary.total,
+        summary.in_sync + summary.drifted + summary.unknown
+    );
+
+    // A resource reporting the prop with a different shape says nothing about it.
+    component
+        .set_resource(
+            ctx,
+            ResourceData::new(
+                ResourceStatus::Ok,
+                Some(json!({ "image": { "name": "httpd" } })),
+            ),
+        )
+        .await
+        .expect("unable to set resource");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let drift = Component::drift(ctx, docker_image.id())
+        .await
+        .expect("unable to compute drift");
+    assert_eq!(DriftStatus::InSync, drift.status);
+    assert!(drift.drifted_props.is_empty());
+}
@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use dal::component::resource::ResourceData;
+use dal::status::StatusUpdate;
 use dal::{
     AttributeValue, Component, DalContext, InputSocket, OutputSocket, Schema, SchemaVariant,
 };
@@ -7,6 +10,7 @@ use dal_test::helpers::{
     create_named_component_for_schema_variant_on_default_view, ChangeSetTestHelpers,
 };
 use dal_test::test;
+use futures::StreamExt;
 use serde_json::json;
 use veritech_client::ResourceStatus;
 
@@ -519,3 +523,157 @@ async fn component_concurrency_limit(ctx: &mut DalContext) {
         )
     }
 }
+
+/// With a single value running at a time, the remaining independent values must still be
+/// picked up as running ones finish, within the same job.
+#[test]
+async fn value_concurrency_limit(ctx: &mut DalContext) {
+    let mut workspace = ctx.get_workspace().await.expect("get workspace");
+    workspace
+        .set_value_concurrency_limit(ctx, Some(1))
+        .await
+        .expect("set value concurrency limit");
+    ctx.commit_no_rebase().await.expect("commit");
+
+    let etoiles = ExpectComponent::create(ctx, "etoiles").await;
+    let mut morningstars = vec![];
+    for i in 0..4 {
+        let name: String = (i + 1).to_string();
+        let morningstar = ExpectComponent::create_named(ctx, "morningstar", name).await;
+        etoiles
+            .connect(
+                ctx,
+                "naming_and_necessity",
+                morningstar,
+                "naming_and_necessity",
+            )
+            .await;
+        morningstars.push(morningstar);
+    }
+    etoiles
+        .prop(
+            ctx,
+            [
+                "root",
+                "domain",
+                "possible_world_a",
+                "wormhole_1",
+                "wormhole_2",
+                "wormhole_3",
+                "rigid_designator",
+            ],
+        )
+        .await
+        .set(ctx, "hesperus")
+        .await;
+
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+
+    assert!(
+        !ctx.workspace_snapshot()
+            .expect("workspace_snapshot")
+            .has_dependent_value_roots()
+            .await
+            .expect("call has dvu roots"),
+        "all roots should be processed and off the graph"
+    );
+    for morningstar in morningstars {
+        assert_eq!(
+            json!("phosphorus"),
+            morningstar
+                .prop(ctx, ["root", "domain", "stars"])
+                .await
+                .get(ctx)
+                .await,
+        );
+    }
+}
+
+/// Progress goes out when the job starts and when it finishes, with throttled updates in between.
+#[test]
+async fn progress_events(ctx: &mut DalContext) {
+    let etoiles = ExpectComponent::create(ctx, "etoiles").await;
+    let morningstar = ExpectComponent::create(ctx, "morningstar").await;
+    etoiles
+        .connect(
+            ctx,
+            "naming_and_necessity",
+            morningstar,
+            "naming_and_necessity",
+        )
+        .await;
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+
+    let mut subscriber = ctx
+        .nats_conn()
+        .subscribe(format!(
+            "si.workspace_pk.{}.event",
+            ctx.workspace_pk().expect("get workspace pk")
+        ))
+        .await
+        .expect("subscribe to workspace events");
+
+    etoiles
+        .prop(
+            ctx,
+            [
+                "root",
+                "domain",
+                "possible_world_a",
+                "wormhole_1",
+                "wormhole_2",
+                "wormhole_3",
+                "rigid_designator",
+            ],
+        )
+        .await
+        .set(ctx, "hesperus")
+        .await;
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+
+    let mut progress = vec![];
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(10), subscriber.next())
+            .await
+            .expect("timed out waiting for progress events")
+            .expect("subscription closed");
+        let event: serde_json::Value =
+            serde_json::from_slice(message.payload()).expect("deserialize event");
+        if event["payload"]["kind"] != "StatusUpdate" {
+            continue;
+        }
+        let Ok(StatusUpdate::DependentValueUpdateProgress {
+            total,
+            computed,
+            skipped,
+            timestamp,
+        }) = serde_json::from_value(event["payload"]["data"].clone())
+        else {
+            continue;
+        };
+        progress.push((total, computed, skipped, timestamp));
+        if computed + skipped == total {
+            break;
+        }
+    }
+
+    let (total, computed, skipped, _) = progress.first().copied().expect("no progress sent");
+    assert!(total > 0, "the update should have values to compute");
+    assert_eq!((0, 0), (computed, skipped), "progress starts from nothing");
+    let (_, computed, skipped, _) = progress.last().copied().expect("no progress sent");
+    assert_eq!(
+        (total, 0),
+        (computed, skipped),
+        "every value is computed by the end"
+    );
+    // Only the final event may follow the previous one within the throttling interval.
+    let throttled = &progress[..progress.len() - 1];
+    for window in throttled.windows(2) {
+        let (_, _, _, earlier) = window[0];
+        let (_, _, _, later) = window[1];
+        assert!(
+            (later - earlier).num_milliseconds() >= 500,
+            "expected throttled progress, got {progress:?}"
+        );
+    }
+}
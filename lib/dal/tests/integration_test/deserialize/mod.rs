@@ -197,6 +197,8 @@ fn make_me_one_with_everything(graph: &mut WorkspaceSnapshotGraphVCurrent) {
             EdgeWeightKindDiscriminants::Represents => EdgeWeightKind::Represents,
             EdgeWeightKindDiscriminants::Manages => EdgeWeightKind::Manages,
             EdgeWeightKindDiscriminants::DiagramObject => EdgeWeightKind::DiagramObject,
+            EdgeWeightKindDiscriminants::FuncTestCase => EdgeWeightKind::FuncTestCase,
+            EdgeWeightKindDiscriminants::FuncVersion => EdgeWeightKind::FuncVersion,
         };
 
         let edge_weight = EdgeWeight::new(edge_weight_kind);
@@ -7,6 +7,8 @@ use pretty_assertions_sorted::assert_eq;
 mod argument;
 mod authoring;
 mod kill_execution;
+mod test_case;
+mod version;
 
 #[test]
 async fn summary(ctx: &mut DalContext) {
@@ -897,6 +897,7 @@ async fn return_the_right_bindings(ctx: &mut DalContext, nw: &WorkspaceSignup) {
                                     }
                                     dal::func::intrinsics::IntrinsicFunc::SetArray
                                     | dal::func::intrinsics::IntrinsicFunc::SetBoolean
+                                    | dal::func::intrinsics::IntrinsicFunc::SetFloat
                                     | dal::func::intrinsics::IntrinsicFunc::SetInteger
                                     | dal::func::intrinsics::IntrinsicFunc::SetJson
                                     | dal::func::intrinsics::IntrinsicFunc::SetMap
@@ -956,6 +957,7 @@ async fn return_the_right_bindings(ctx: &mut DalContext, nw: &WorkspaceSignup) {
                                     // these intrinsics only have one arg
                                     dal::func::intrinsics::IntrinsicFunc::SetArray
                                     | dal::func::intrinsics::IntrinsicFunc::SetBoolean
+                                    | dal::func::intrinsics::IntrinsicFunc::SetFloat
                                     | dal::func::intrinsics::IntrinsicFunc::SetInteger
                                     | dal::func::intrinsics::IntrinsicFunc::SetJson
                                     | dal::func::intrinsics::IntrinsicFunc::SetMap
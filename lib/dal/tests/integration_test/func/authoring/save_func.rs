@@ -1,4 +1,4 @@
-use dal::func::authoring::FuncAuthoringClient;
+use dal::func::authoring::{FuncAuthoringClient, FuncAuthoringError, MAX_FUNC_TIMEOUT_SECS};
 use dal::{DalContext, Func, FuncId};
 use dal_test::helpers::ChangeSetTestHelpers;
 use dal_test::test;
@@ -29,6 +29,52 @@ async fn qualification(ctx: &mut DalContext) {
         save_func_setup(ctx, "test:qualificationDummySecretStringIsTodd").await;
 }
 
+#[test]
+async fn timeout(ctx: &mut DalContext) {
+    let old_func_id = Func::find_id_by_name(ctx, "test:createActionStarfield")
+        .await
+        .expect("could not perform find func by name")
+        .expect("no func found");
+    let func_id = FuncAuthoringClient::create_unlocked_func_copy(ctx, old_func_id, None)
+        .await
+        .expect("could not create unlocked copy")
+        .id;
+
+    // Timeouts outside of the supported range are rejected.
+    for invalid in [0, MAX_FUNC_TIMEOUT_SECS + 1] {
+        let result = FuncAuthoringClient::update_func_timeout(ctx, func_id, Some(invalid)).await;
+        assert!(matches!(
+            result,
+            Err(FuncAuthoringError::InvalidFuncTimeout(timeout)) if timeout == invalid
+        ));
+    }
+
+    FuncAuthoringClient::update_func_timeout(ctx, func_id, Some(20 * 60))
+        .await
+        .expect("could not update func timeout");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let func = Func::get_by_id_or_error(ctx, func_id)
+        .await
+        .expect("could not get func by id");
+    assert_eq!(Some(20 * 60), func.timeout_secs);
+
+    // The timeout survives locking and unlocking the func.
+    let locked = func.lock(ctx).await.expect("could not lock func");
+    let unlocked = FuncAuthoringClient::create_unlocked_func_copy(ctx, locked.id, None)
+        .await
+        .expect("could not create unlocked copy");
+    assert_eq!(Some(20 * 60), unlocked.timeout_secs);
+
+    // Clearing the timeout falls back to the default.
+    let cleared = FuncAuthoringClient::update_func_timeout(ctx, unlocked.id, None)
+        .await
+        .expect("could not clear func timeout");
+    assert_eq!(None, cleared.timeout_secs);
+}
+
 // Sets up the tests within the module. Find the func to be saved by name and then save it
 // immediately when found. This is the basic "does it work in place" check.
 pub async fn save_func_setup(
@@ -0,0 +1,139 @@
+use dal::func::authoring::FuncAuthoringClient;
+use dal::func::test_case::{
+    FuncTestCase, FuncTestCaseAssertion, FuncTestCaseCheck, FuncTestCaseError,
+    FuncTestCaseExpectation, FuncTestCaseFixture,
+};
+use dal::{DalContext, Func};
+use dal_test::helpers::ChangeSetTestHelpers;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+#[test]
+async fn create_run_and_copy_test_cases(ctx: &mut DalContext) {
+    let func_id = Func::find_id_by_name(ctx, "test:createActionStarfield")
+        .await
+        .expect("could not perform find func by name")
+        .expect("no func found");
+
+    let passing = FuncTestCase::new(
+        ctx,
+        func_id,
+        "creates the resource",
+        None,
+        FuncTestCaseFixture {
+            component: Some(json!({ "domain": { "name": "starfield" } })),
+            ..Default::default()
+        },
+        FuncTestCaseExpectation::Assertions(vec![
+            FuncTestCaseAssertion {
+                pointer: "/status".to_string(),
+                check: FuncTestCaseCheck::Equals(json!("ok")),
+            },
+            FuncTestCaseAssertion {
+                pointer: "/payload/poop".to_string(),
+                check: FuncTestCaseCheck::Equals(json!(true)),
+            },
+        ]),
+    )
+    .await
+    .expect("could not create test case");
+    let failing = FuncTestCase::new(
+        ctx,
+        func_id,
+        "reports an error",
+        Some("the create action never fails".to_string()),
+        FuncTestCaseFixture::default(),
+        FuncTestCaseExpectation::Assertions(vec![FuncTestCaseAssertion {
+            pointer: "/status".to_string(),
+            check: FuncTestCaseCheck::Equals(json!("error")),
+        }]),
+    )
+    .await
+    .expect("could not create test case");
+
+    // Names are unique per func
+    let duplicate = FuncTestCase::new(
+        ctx,
+        func_id,
+        "creates the resource",
+        None,
+        FuncTestCaseFixture::default(),
+        FuncTestCaseExpectation::Output(json!(null)),
+    )
+    .await;
+    assert!(matches!(duplicate, Err(FuncTestCaseError::NameInUse(..))));
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    assert_eq!(
+        vec![passing.clone(), failing.clone()], // expected
+        FuncTestCase::list_for_func(ctx, func_id)
+            .await
+            .expect("could not list test cases")  // actual
+    );
+
+    let results = FuncTestCase::run_all_for_func(ctx, func_id)
+        .await
+        .expect("could not run test cases");
+    assert_eq!(2, results.len());
+    let passing_result = results.first().expect("has a result");
+    assert_eq!(passing.id, passing_result.test_case_id);
+    assert!(passing_result.passed);
+    assert!(passing_result.func_run_id.is_some());
+    let failing_result = results.get(1).expect("has a second result");
+    assert_eq!(failing.id, failing_result.test_case_id);
+    assert!(!failing_result.passed);
+    assert_eq!(
+        vec!["expected \"error\" at /status, found \"ok\"".to_string()], // expected
+        failing_result.failures                                          // actual
+    );
+
+    // Fix up the failing test case
+    let fixed = FuncTestCase::modify_by_id(ctx, failing.id, |test_case| {
+        test_case.name = "reports success".to_string();
+        test_case.expectation = FuncTestCaseExpectation::Assertions(vec![FuncTestCaseAssertion {
+            pointer: "/status".to_string(),
+            check: FuncTestCaseCheck::NotEquals(json!("error")),
+        }]);
+        Ok(())
+    })
+    .await
+    .expect("could not modify test case");
+    assert!(
+        FuncTestCase::run(ctx, fixed.id)
+            .await
+            .expect("could not run test case")
+            .passed
+    );
+
+    // Unlocking the func brings its test cases along
+    let unlocked = FuncAuthoringClient::create_unlocked_func_copy(ctx, func_id, None)
+        .await
+        .expect("could not create unlocked copy");
+    let copied_names: Vec<String> = FuncTestCase::list_for_func(ctx, unlocked.id)
+        .await
+        .expect("could not list test cases")
+        .into_iter()
+        .map(|test_case| test_case.name)
+        .collect();
+    assert_eq!(
+        vec![
+            "creates the resource".to_string(),
+            "reports success".to_string()
+        ], // expected
+        copied_names // actual
+    );
+
+    FuncTestCase::remove(ctx, passing.id)
+        .await
+        .expect("could not remove test case");
+    assert_eq!(
+        vec![fixed.id], // expected
+        FuncTestCase::list_ids_for_func(ctx, func_id)
+            .await
+            .expect("could not list test case ids")  // actual
+    );
+}
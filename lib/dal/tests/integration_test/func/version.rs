@@ -0,0 +1,118 @@
+use dal::func::authoring::FuncAuthoringClient;
+use dal::func::version::{FuncSemVer, FuncVersion, FuncVersionBump, FuncVersionError};
+use dal::{DalContext, Func};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn lock_records_versions_and_restore(ctx: &mut DalContext) {
+    let builtin_func_id = Func::find_id_by_name(ctx, "test:createActionStarfield")
+        .await
+        .expect("could not perform find func by name")
+        .expect("no func found");
+
+    // The first lock of an authored func records 1.0.0
+    let func = FuncAuthoringClient::create_unlocked_func_copy(ctx, builtin_func_id, None)
+        .await
+        .expect("could not create unlocked copy");
+    let original_code = func
+        .code_plaintext()
+        .expect("could not decode code")
+        .expect("func has code");
+    let func = func.lock(ctx).await.expect("could not lock func");
+    let versions = FuncVersion::list_for_func(ctx, func.id)
+        .await
+        .expect("could not list versions");
+    assert_eq!(
+        vec![FuncSemVer::INITIAL],
+        versions
+            .iter()
+            .map(|version| version.version)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Some(func.id), versions[0].source_func_id);
+
+    // Drafts can only be staged on unlocked funcs
+    let result = FuncVersion::set_draft(ctx, func.id, FuncVersionBump::Minor, None).await;
+    assert!(matches!(result, Err(FuncVersionError::FuncLocked(_))));
+
+    // Edit an unlocked copy and lock it with a staged draft
+    let copy = FuncAuthoringClient::create_unlocked_func_copy(ctx, func.id, None)
+        .await
+        .expect("could not create unlocked copy");
+    let new_code = format!("{original_code}\n// adds a comment");
+    FuncAuthoringClient::save_code(ctx, copy.id, new_code.clone())
+        .await
+        .expect("could not save code");
+    FuncVersion::set_draft(
+        ctx,
+        copy.id,
+        FuncVersionBump::Minor,
+        Some("adds a comment".to_string()),
+    )
+    .await
+    .expect("could not set draft");
+    let copy = Func::get_by_id_or_error(ctx, copy.id)
+        .await
+        .expect("could not get func")
+        .lock(ctx)
+        .await
+        .expect("could not lock func");
+
+    let versions = FuncVersion::list_for_func(ctx, copy.id)
+        .await
+        .expect("could not list versions");
+    let minor: FuncSemVer = "1.1.0".parse().expect("could not parse version");
+    assert_eq!(
+        vec![FuncSemVer::INITIAL, minor],
+        versions
+            .iter()
+            .map(|version| version.version)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Some("adds a comment"), versions[1].message.as_deref());
+    assert!(FuncVersion::get_draft(ctx, copy.id)
+        .await
+        .expect("could not get draft")
+        .is_none());
+
+    let diff = FuncVersion::diff(ctx, copy.id, FuncSemVer::INITIAL, Some(minor))
+        .await
+        .expect("could not diff versions");
+    assert!(diff
+        .diff
+        .code
+        .expect("diff has code")
+        .contains("+// adds a comment"));
+
+    // Locking again without changes does not record a new version
+    let unchanged = FuncAuthoringClient::create_unlocked_func_copy(ctx, copy.id, None)
+        .await
+        .expect("could not create unlocked copy")
+        .lock(ctx)
+        .await
+        .expect("could not lock func");
+    assert_eq!(
+        2,
+        FuncVersion::list_for_func(ctx, unchanged.id)
+            .await
+            .expect("could not list versions")
+            .len()
+    );
+
+    // Restoring onto a locked func creates an unlocked copy with the earlier code
+    let restored = FuncAuthoringClient::restore_func_version(ctx, copy.id, FuncSemVer::INITIAL)
+        .await
+        .expect("could not restore version");
+    assert_ne!(copy.id, restored.id);
+    assert!(!restored.is_locked);
+    assert_eq!(
+        Some(original_code),
+        restored.code_plaintext().expect("could not decode code")
+    );
+    let draft = FuncVersion::get_draft(ctx, restored.id)
+        .await
+        .expect("could not get draft")
+        .expect("restore stages a draft");
+    assert_eq!(Some("Restore version 1.0.0"), draft.message.as_deref());
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use base64::{engine::general_purpose, Engine};
+use dal::{garbage_collection, DalContext, Func, FuncBackendKind, FuncBackendResponseType};
+use dal_test::helpers::ChangeSetTestHelpers;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use si_layer_cache::db::gc::GcOptions;
+
+#[test]
+async fn func_code_survives_collection(ctx: &mut DalContext) {
+    let code = "function main() { return true; }";
+    let func = Func::new(
+        ctx,
+        "garbage collection test",
+        None::<String>,
+        None::<String>,
+        None::<String>,
+        false,
+        false,
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::Boolean,
+        Some("main"),
+        Some(general_purpose::STANDARD_NO_PAD.encode(code)),
+    )
+    .await
+    .expect("able to make a func");
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    // Other tests share the layer db, so only report what would be collected rather than
+    // deleting it out from under them.
+    let report = garbage_collection::collect(
+        ctx,
+        GcOptions::default()
+            .with_retention(Duration::ZERO)
+            .with_dry_run(true),
+    )
+    .await
+    .expect("able to collect garbage");
+
+    // The code blob is only referenced from inside the func's content, never by the graph.
+    assert!(!report.cas.collected.contains(&func.code_blake3.to_string()));
+
+    let func = Func::get_by_id_or_error(ctx, func.id)
+        .await
+        .expect("able to get func by id");
+    assert_eq!(
+        Some(code.to_string()),
+        func.code_plaintext().expect("able to decode code")
+    );
+    assert!(ctx
+        .layer_db()
+        .cas()
+        .read(&func.code_blake3)
+        .await
+        .expect("able to read from the cas")
+        .is_some());
+}
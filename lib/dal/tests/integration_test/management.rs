@@ -1,19 +1,20 @@
 use std::collections::HashSet;
 
 use dal::{
+    component::resource::ResourceData,
     diagram::{geometry::Geometry, view::View},
     management::{
-        prototype::ManagementPrototype, ManagementFuncReturn, ManagementGeometry,
-        ManagementOperator,
+        plan::PlannedPropertyChange, prototype::ManagementPrototype, ManagementError,
+        ManagementFuncReturn, ManagementGeometry, ManagementOperations, ManagementOperator,
     },
-    AttributeValue, Component, DalContext, SchemaId,
+    AttributeValue, Component, DalContext, PropKind, SchemaId,
 };
 use dal_test::expected::{apply_change_set_to_base, ExpectView};
 use dal_test::{
     helpers::create_component_for_default_schema_name_in_default_view, test,
     SCHEMA_ID_SMALL_EVEN_LEGO,
 };
-use veritech_client::ManagementFuncStatus;
+use veritech_client::{ManagementFuncStatus, ResourceStatus};
 
 #[test]
 async fn update_managed_components_in_view(ctx: &DalContext) {
@@ -116,6 +117,101 @@ async fn update_managed_components_in_view(ctx: &DalContext) {
     assert_eq!(750, new_view_geometry.y());
 }
 
+#[test]
+async fn update_managed_components_in_other_view(ctx: &DalContext) {
+    let small_odd_lego = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "small odd lego",
+        "small odd lego",
+    )
+    .await
+    .expect("could not create component");
+    let small_even_lego = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "small even lego",
+        "small even lego",
+    )
+    .await
+    .expect("could not create component");
+
+    let view_name = "a view askew";
+    let new_view_id = ExpectView::create_with_name(ctx, view_name).await.id();
+    Geometry::new_for_component(ctx, small_odd_lego.id(), new_view_id)
+        .await
+        .expect("create geometry in view");
+    Geometry::new_for_component(ctx, small_even_lego.id(), new_view_id)
+        .await
+        .expect("create geometry in view");
+
+    Component::manage_component(ctx, small_odd_lego.id(), small_even_lego.id())
+        .await
+        .expect("add manages edge");
+
+    let manager_variant = small_odd_lego
+        .schema_variant(ctx)
+        .await
+        .expect("get variant");
+
+    let management_prototype = ManagementPrototype::list_for_variant_id(ctx, manager_variant.id())
+        .await
+        .expect("get prototypes")
+        .into_iter()
+        .find(|proto| proto.name() == "Update in View")
+        .expect("could not find prototype");
+
+    // The func keys its geometry by the name of the view it runs in, so this places the managed
+    // component in "a view askew"...
+    let mut execution_result = management_prototype
+        .execute(ctx, small_odd_lego.id(), Some(new_view_id))
+        .await
+        .expect("should execute management prototype func");
+
+    let result: ManagementFuncReturn = execution_result
+        .result
+        .take()
+        .expect("should have a result success")
+        .try_into()
+        .expect("should be a valid management func return");
+
+    assert_eq!(ManagementFuncStatus::Ok, result.status);
+    assert_eq!(Some(view_name), result.message.as_deref());
+
+    let operations = result.operations.expect("should have operations");
+
+    // ...even when the operations are applied from the default view.
+    let default_view_id = ExpectView::get_id_for_default(ctx).await;
+    ManagementOperator::new(
+        ctx,
+        small_odd_lego.id(),
+        operations,
+        execution_result,
+        Some(default_view_id),
+    )
+    .await
+    .expect("should create operator")
+    .operate()
+    .await
+    .expect("should operate");
+
+    let small_even_lego = Component::get_by_id(ctx, small_even_lego.id())
+        .await
+        .expect("get component");
+
+    let default_view_geometry = small_even_lego
+        .geometry(ctx, default_view_id)
+        .await
+        .expect("get geometry for default view");
+    assert_eq!(0, default_view_geometry.x());
+    assert_eq!(0, default_view_geometry.y());
+
+    let new_view_geometry = small_even_lego
+        .geometry(ctx, new_view_id)
+        .await
+        .expect("get geo for view askew");
+    assert_eq!(1000, new_view_geometry.x());
+    assert_eq!(750, new_view_geometry.y());
+}
+
 #[test]
 async fn update_managed_components(ctx: &DalContext) {
     let small_odd_lego = create_component_for_default_schema_name_in_default_view(
@@ -852,3 +948,279 @@ async fn deeply_nested_children(ctx: &DalContext) {
         component_names
     )
 }
+
+#[test]
+async fn plan_without_operating(ctx: &DalContext) {
+    let small_odd_lego = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "small odd lego",
+        "small odd lego",
+    )
+    .await
+    .expect("could not create component");
+    let small_even_lego = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "small even lego",
+        "small even lego",
+    )
+    .await
+    .expect("could not create component");
+
+    let av_id = Component::attribute_value_for_prop_by_id(
+        ctx,
+        small_even_lego.id(),
+        &["root", "si", "resourceId"],
+    )
+    .await
+    .expect("av should exist");
+    AttributeValue::update(
+        ctx,
+        av_id,
+        Some(serde_json::json!("small even lego resource id")),
+    )
+    .await
+    .expect("able to update value");
+
+    Component::manage_component(ctx, small_odd_lego.id(), small_even_lego.id())
+        .await
+        .expect("add manages edge");
+
+    let manager_variant = small_odd_lego
+        .schema_variant(ctx)
+        .await
+        .expect("get variant");
+
+    let management_prototype = ManagementPrototype::list_for_variant_id(ctx, manager_variant.id())
+        .await
+        .expect("get prototypes")
+        .into_iter()
+        .find(|proto| proto.name() == "Clone")
+        .expect("could not find prototype");
+
+    let mut execution_result = management_prototype
+        .execute(ctx, small_odd_lego.id(), None)
+        .await
+        .expect("should execute management prototype func");
+
+    let result: ManagementFuncReturn = execution_result
+        .result
+        .take()
+        .expect("should have a result success")
+        .try_into()
+        .expect("should be a valid management func return");
+
+    assert_eq!(result.status, ManagementFuncStatus::Ok);
+
+    let operations = result.operations.expect("should have operations");
+
+    let plan =
+        ManagementOperator::new(ctx, small_odd_lego.id(), operations, execution_result, None)
+            .await
+            .expect("should create operator")
+            .plan()
+            .await
+            .expect("should plan");
+
+    assert!(plan.is_valid(), "{:?}", plan.errors);
+    assert!(plan.updates.is_empty());
+    assert_eq!(
+        vec!["small even lego_clone", "small odd lego_clone"],
+        plan.creates
+            .iter()
+            .map(|create| create.placeholder.as_str())
+            .collect::<Vec<_>>()
+    );
+
+    let even_clone = &plan.creates[0];
+    assert!(even_clone.schema_installed);
+    assert!(even_clone.properties.contains(&PlannedPropertyChange {
+        path: "/root/si/resourceId".to_string(),
+        current: None,
+        proposed: Some(serde_json::json!("small even lego resource id")),
+    }));
+
+    // Nothing was created.
+    assert_eq!(
+        2,
+        Component::list(ctx).await.expect("list components").len()
+    );
+}
+
+#[test]
+async fn plan_rejects_property_kind_mismatches_that_operate_tolerates(ctx: &DalContext) {
+    let small_odd_lego = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "small odd lego",
+        "small odd lego",
+    )
+    .await
+    .expect("could not create component");
+
+    let manager_variant = small_odd_lego
+        .schema_variant(ctx)
+        .await
+        .expect("get variant");
+
+    let management_prototype = ManagementPrototype::list_for_variant_id(ctx, manager_variant.id())
+        .await
+        .expect("get prototypes")
+        .into_iter()
+        .find(|proto| proto.name() == "Update")
+        .expect("could not find prototype");
+
+    // "one" and "two" are string props. The number has an obvious string equivalent and is
+    // coerced, while the array does not.
+    let operations: ManagementOperations = serde_json::from_value(serde_json::json!({
+        "update": {
+            "self": {
+                "properties": {
+                    "domain": { "one": 80, "two": ["deux"] },
+                },
+            },
+        },
+    }))
+    .expect("valid operations");
+
+    let execution_result = management_prototype
+        .execute(ctx, small_odd_lego.id(), None)
+        .await
+        .expect("should execute management prototype func");
+    let plan = ManagementOperator::new(
+        ctx,
+        small_odd_lego.id(),
+        operations.clone(),
+        execution_result.clone(),
+        None,
+    )
+    .await
+    .expect("should create operator")
+    .plan()
+    .await
+    .expect("should plan");
+
+    assert!(!plan.is_valid());
+    assert_eq!(
+        vec![ManagementError::PropertyKindMismatch(
+            "/root/domain/two".to_string(),
+            PropKind::String
+        )
+        .to_string()],
+        plan.errors
+    );
+
+    // Operating only warns about the mismatch, so existing funcs keep working.
+    ManagementOperator::new(ctx, small_odd_lego.id(), operations, execution_result, None)
+        .await
+        .expect("should create operator")
+        .operate()
+        .await
+        .expect("should operate");
+
+    for (prop_name, expected) in [
+        ("one", serde_json::json!("80")),
+        ("two", serde_json::json!(["deux"])),
+    ] {
+        let av_id = Component::attribute_value_for_prop_by_id(
+            ctx,
+            small_odd_lego.id(),
+            &["root", "domain", prop_name],
+        )
+        .await
+        .expect("av should exist");
+        assert_eq!(
+            Some(expected),
+            AttributeValue::get_by_id(ctx, av_id)
+                .await
+                .expect("get av")
+                .view(ctx)
+                .await
+                .expect("get view")
+        );
+    }
+}
+
+#[test]
+async fn delete_and_erase_managed_components(ctx: &DalContext) {
+    let small_odd_lego = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "small odd lego",
+        "small odd lego",
+    )
+    .await
+    .expect("could not create component");
+
+    let mut managed = vec![];
+    for name in [
+        "delete with resource",
+        "delete without resource",
+        "erase me",
+    ] {
+        let component =
+            create_component_for_default_schema_name_in_default_view(ctx, "small even lego", name)
+                .await
+                .expect("could not create component");
+        if name != "delete without resource" {
+            component
+                .set_resource(
+                    ctx,
+                    ResourceData::new(ResourceStatus::Ok, Some(serde_json::json!({}))),
+                )
+                .await
+                .expect("unable to set resource");
+        }
+        Component::manage_component(ctx, small_odd_lego.id(), component.id())
+            .await
+            .expect("add manages edge");
+        managed.push(component.id());
+    }
+
+    let manager_variant = small_odd_lego
+        .schema_variant(ctx)
+        .await
+        .expect("get variant");
+
+    let management_prototype = ManagementPrototype::list_for_variant_id(ctx, manager_variant.id())
+        .await
+        .expect("get prototypes")
+        .into_iter()
+        .find(|proto| proto.name() == "Delete Managed")
+        .expect("could not find prototype");
+
+    let mut execution_result = management_prototype
+        .execute(ctx, small_odd_lego.id(), None)
+        .await
+        .expect("should execute management prototype func");
+
+    let result: ManagementFuncReturn = execution_result
+        .result
+        .take()
+        .expect("should have a result success")
+        .try_into()
+        .expect("should be a valid management func return");
+
+    assert_eq!(result.status, ManagementFuncStatus::Ok);
+
+    let operations = result.operations.expect("should have operations");
+
+    ManagementOperator::new(ctx, small_odd_lego.id(), operations, execution_result, None)
+        .await
+        .expect("should create operator")
+        .operate()
+        .await
+        .expect("should operate");
+
+    // Components with resources are only marked for deletion, unless they are erased.
+    let with_resource = Component::try_get_by_id(ctx, managed[0])
+        .await
+        .expect("get component")
+        .expect("component with a resource should still exist");
+    assert!(with_resource.to_delete());
+    assert!(Component::try_get_by_id(ctx, managed[1])
+        .await
+        .expect("get component")
+        .is_none());
+    assert!(Component::try_get_by_id(ctx, managed[2])
+        .await
+        .expect("get component")
+        .is_none());
+}
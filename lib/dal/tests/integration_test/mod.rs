@@ -11,6 +11,7 @@ mod deserialize;
 mod diagram;
 mod frame;
 mod func;
+mod garbage_collection;
 mod input_sources;
 mod management;
 mod module;
@@ -1,9 +1,23 @@
+use dal::func::intrinsics::IntrinsicFunc;
+use dal::func::FuncError;
 use dal::pkg::export::PkgExporter;
 use dal::pkg::{import_pkg_from_pkg, ImportOptions};
+use dal::prop::PropPath;
 use dal::schema::variant::authoring::VariantAuthoringClient;
-use dal::{DalContext, FuncBackendKind, FuncBackendResponseType};
+use dal::{
+    ComponentType, DalContext, Func, FuncBackendKind, FuncBackendResponseType, Prop, PropKind,
+    SchemaVariant, SchemaVariantId,
+};
+use dal_test::helpers::{
+    create_component_for_schema_variant_on_default_view, get_attribute_value_for_component,
+    update_attribute_value_for_component, ChangeSetTestHelpers,
+};
 use dal_test::test;
-use si_pkg::{FuncSpec, FuncSpecData, PkgSpec, SchemaSpec, SchemaSpecData, SiPkg};
+use pretty_assertions_sorted::assert_eq;
+use si_pkg::{
+    FuncSpec, FuncSpecData, PkgSpec, PropSpec, SchemaSpec, SchemaSpecData, SchemaVariantSpec,
+    SchemaVariantSpecData, SiPkg,
+};
 
 #[test]
 async fn import_pkg_from_pkg_set_latest_default(ctx: &mut DalContext) {
@@ -111,3 +125,226 @@ async fn import_pkg_from_pkg_set_latest_default(ctx: &mut DalContext) {
         Some(variants.pop().expect("should pop"))
     );
 }
+
+#[test]
+async fn import_and_export_float_prop(ctx: &mut DalContext) {
+    let schema_variant_id = import_float_prop_variant(ctx, "floaty").await;
+
+    let ratio_prop_id = Prop::find_prop_id_by_path(
+        ctx,
+        schema_variant_id,
+        &PropPath::new(["root", "domain", "ratio"]),
+    )
+    .await
+    .expect("could not find ratio prop");
+    let ratio_prop = Prop::get_by_id(ctx, ratio_prop_id)
+        .await
+        .expect("could not get ratio prop");
+    assert_eq!(PropKind::Float, ratio_prop.kind);
+    assert_eq!(
+        Some(serde_json::json!(0.5)),
+        Prop::default_value(ctx, ratio_prop_id)
+            .await
+            .expect("could not get default value")
+    );
+
+    // Exporting the variant should round trip the float prop and its default value
+    let variant = SchemaVariant::get_by_id_or_error(ctx, schema_variant_id)
+        .await
+        .expect("could not get schema variant");
+    let (variant_spec, variant_funcs) =
+        PkgExporter::export_variant_standalone(ctx, &variant, "floaty", None)
+            .await
+            .expect("could not export variant");
+
+    let ratio_spec = variant_spec
+        .domain
+        .direct_children()
+        .into_iter()
+        .find(|prop| prop.name() == "ratio")
+        .expect("could not find ratio prop spec");
+    assert!(matches!(ratio_spec, PropSpec::Float { .. }));
+    assert_eq!(
+        Some(&serde_json::json!(0.5)),
+        ratio_spec
+            .data()
+            .and_then(|data| data.default_value.as_ref())
+    );
+    assert!(variant_funcs
+        .iter()
+        .any(|func| func.name == IntrinsicFunc::SetFloat.name()));
+}
+
+#[test]
+async fn set_and_get_float_prop_value(ctx: &mut DalContext) {
+    let schema_variant_id = import_float_prop_variant(ctx, "floaty").await;
+    let component = create_component_for_schema_variant_on_default_view(ctx, schema_variant_id)
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    assert_eq!(
+        Some(serde_json::json!(0.5)),
+        get_attribute_value_for_component(ctx, component.id(), &["root", "domain", "ratio"])
+            .await
+            .expect("could not get value")
+    );
+
+    update_attribute_value_for_component(
+        ctx,
+        component.id(),
+        &["root", "domain", "ratio"],
+        serde_json::json!(2.75),
+    )
+    .await
+    .expect("could not update value");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    assert_eq!(
+        Some(serde_json::json!(2.75)),
+        get_attribute_value_for_component(ctx, component.id(), &["root", "domain", "ratio"])
+            .await
+            .expect("could not get value")
+    );
+    assert!(Func::find_id_by_name(ctx, IntrinsicFunc::SetFloat.name())
+        .await
+        .expect("could not find func")
+        .is_some());
+}
+
+#[test]
+async fn install_missing_intrinsics(ctx: &mut DalContext) {
+    // Simulate a workspace created before "si:setFloat" existed
+    let set_float_func_id = Func::find_id_by_name(ctx, IntrinsicFunc::SetFloat.name())
+        .await
+        .expect("could not find func")
+        .expect("intrinsic not installed");
+    Func::delete_by_id(ctx, set_float_func_id)
+        .await
+        .expect("could not delete func");
+    assert!(Func::find_id_by_name(ctx, IntrinsicFunc::SetFloat.name())
+        .await
+        .expect("could not find func")
+        .is_none());
+
+    // Looking an intrinsic up never installs it
+    assert!(matches!(
+        Func::find_intrinsic(ctx, IntrinsicFunc::SetFloat).await,
+        Err(FuncError::IntrinsicFuncNotFound(name)) if name == IntrinsicFunc::SetFloat.name()
+    ));
+    assert!(Func::find_id_by_name(ctx, IntrinsicFunc::SetFloat.name())
+        .await
+        .expect("could not find func")
+        .is_none());
+
+    // The upgrade step installs only what is missing
+    assert_eq!(
+        vec![IntrinsicFunc::SetFloat],
+        IntrinsicFunc::install_missing(ctx)
+            .await
+            .expect("could not install missing intrinsics")
+    );
+    assert!(IntrinsicFunc::install_missing(ctx)
+        .await
+        .expect("could not install missing intrinsics")
+        .is_empty());
+
+    // Setting the default value of the float prop needs the intrinsic
+    let schema_variant_id = import_float_prop_variant(ctx, "floaty").await;
+    let ratio_prop_id = Prop::find_prop_id_by_path(
+        ctx,
+        schema_variant_id,
+        &PropPath::new(["root", "domain", "ratio"]),
+    )
+    .await
+    .expect("could not find ratio prop");
+    assert_eq!(
+        Some(serde_json::json!(0.5)),
+        Prop::default_value(ctx, ratio_prop_id)
+            .await
+            .expect("could not get default value")
+    );
+    assert!(Func::find_id_by_name(ctx, IntrinsicFunc::SetFloat.name())
+        .await
+        .expect("could not find func")
+        .is_some());
+}
+
+/// Imports a schema whose variant has a single float prop, "/root/domain/ratio", defaulting to 0.5.
+async fn import_float_prop_variant(ctx: &DalContext, schema_name: &str) -> SchemaVariantId {
+    let asset_func_name = format!("test:scaffold{schema_name}Asset");
+    let asset_func_spec = FuncSpec::builder()
+        .name(&asset_func_name)
+        .unique_id(&asset_func_name)
+        .data(
+            FuncSpecData::builder()
+                .name(&asset_func_name)
+                .backend_kind(FuncBackendKind::JsSchemaVariantDefinition)
+                .response_type(FuncBackendResponseType::SchemaVariantDefinition)
+                .handler("main")
+                .code_plaintext("function main() { return new AssetBuilder().build(); }")
+                .build()
+                .expect("should build data"),
+        )
+        .build()
+        .expect("should build func spec");
+
+    let schema_spec = SchemaSpec::builder()
+        .name(schema_name)
+        .data(
+            SchemaSpecData::builder()
+                .name(schema_name)
+                .category("Integration Tests")
+                .build()
+                .expect("should build data"),
+        )
+        .variant(
+            SchemaVariantSpec::builder()
+                .version("v0")
+                .data(
+                    SchemaVariantSpecData::builder()
+                        .version("v0")
+                        .color("#00b0b0")
+                        .func_unique_id(&asset_func_spec.unique_id)
+                        .component_type(ComponentType::Component)
+                        .build()
+                        .expect("should build data"),
+                )
+                .domain_prop(
+                    PropSpec::builder()
+                        .name("ratio")
+                        .kind(PropKind::Float)
+                        .default_value(serde_json::json!(0.5))
+                        .build()
+                        .expect("should build prop spec"),
+                )
+                .build()
+                .expect("should build variant spec"),
+        )
+        .build()
+        .expect("should build schema spec");
+
+    let pkg_spec = PkgSpec::builder()
+        .name(schema_name)
+        .version("0")
+        .created_by("sally@systeminit.com")
+        .func(
+            IntrinsicFunc::Identity
+                .to_spec()
+                .expect("should build identity func spec"),
+        )
+        .func(asset_func_spec)
+        .schema(schema_spec)
+        .build()
+        .expect("should build pkg spec");
+    let pkg = SiPkg::load_from_spec(pkg_spec).expect("should load from spec");
+
+    let (_, mut variants, _) = import_pkg_from_pkg(ctx, &pkg, None)
+        .await
+        .expect("should import");
+    variants.pop().expect("should have imported a variant")
+}
@@ -0,0 +1,217 @@
+use bytes::Bytes;
+use si_data_nats::{
+    async_nats::jetstream::{
+        self,
+        stream::{RawMessageErrorKind, Stream},
+    },
+    jetstream::Context,
+    HeaderMap,
+};
+use telemetry::prelude::*;
+use time::OffsetDateTime;
+
+use crate::{
+    prefixed_stream_name, prefixed_subject, Error, Result, DEAD_LETTER_SUBJECT_PREFIX,
+    HEADER_CONSUMER, HEADER_DELIVERED, HEADER_ERROR, HEADER_ORIGINAL_SUBJECT, HEADER_PREFIX,
+    HEADER_STATUS, HEADER_STREAM, HEADER_STREAM_SEQUENCE, STREAM_NAME,
+};
+
+/// A message which exhausted its retries and was republished to the "dead letter queue" stream.
+#[derive(Clone, Debug)]
+pub struct DeadLetterMessage {
+    /// The sequence number of the dead letter in the "dead letter queue" stream
+    pub sequence: u64,
+    /// When the message was dead lettered
+    pub dead_lettered_at: OffsetDateTime,
+    /// The subject the message was originally published to
+    pub original_subject: String,
+    /// The stream the message was consumed from
+    pub stream: String,
+    /// The consumer which failed to process the message
+    pub consumer: String,
+    /// The sequence number of the message in its original stream
+    pub stream_sequence: u64,
+    /// The number of delivery attempts made before the message was dead lettered
+    pub delivered: u64,
+    /// The status code of the final failed attempt
+    pub status: u16,
+    /// A description of the final failure, if one was available
+    pub error: Option<String>,
+    /// The original message headers
+    pub headers: HeaderMap,
+    /// The original message payload
+    pub payload: Bytes,
+}
+
+impl DeadLetterMessage {
+    fn from_stream_message(
+        prefix: Option<&str>,
+        message: jetstream::message::StreamMessage,
+    ) -> Result<Self> {
+        let sequence = message.sequence;
+        if !message
+            .subject
+            .as_str()
+            .starts_with(&prefixed_subject(prefix, DEAD_LETTER_SUBJECT_PREFIX))
+        {
+            return Err(Error::NotADeadLetter(sequence));
+        }
+
+        let header = |name: &'static str| {
+            message
+                .headers
+                .get(name)
+                .map(|value| value.as_str().to_owned())
+                .ok_or(Error::MissingHeader(sequence, name))
+        };
+        let parsed = |name: &'static str| {
+            header(name).and_then(|value| {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| Error::MissingHeader(sequence, name))
+            })
+        };
+
+        let original_subject = header(HEADER_ORIGINAL_SUBJECT)?;
+        let stream = header(HEADER_STREAM)?;
+        let consumer = header(HEADER_CONSUMER)?;
+        let stream_sequence = parsed(HEADER_STREAM_SEQUENCE)?;
+        let delivered = parsed(HEADER_DELIVERED)?;
+        let status = u16::try_from(parsed(HEADER_STATUS)?)
+            .map_err(|_| Error::MissingHeader(sequence, HEADER_STATUS))?;
+        let error = header(HEADER_ERROR).ok();
+
+        // Strip the dead letter headers so the original message can be republished as it was
+        let mut headers = HeaderMap::new();
+        for (name, values) in message.headers.iter() {
+            if name.to_string().starts_with(HEADER_PREFIX) {
+                continue;
+            }
+            for value in values {
+                headers.append(name.clone(), value.clone());
+            }
+        }
+
+        Ok(Self {
+            sequence,
+            dead_lettered_at: message.time,
+            original_subject,
+            stream,
+            consumer,
+            stream_sequence,
+            delivered,
+            status,
+            error,
+            headers,
+            payload: message.payload,
+        })
+    }
+}
+
+/// A client for listing, inspecting and redriving messages in the "dead letter queue" stream.
+#[derive(Clone, Debug)]
+pub struct DeadLetterQueue {
+    context: Context,
+}
+
+impl DeadLetterQueue {
+    /// Creates a new [`DeadLetterQueue`] client.
+    pub fn new(context: Context) -> Self {
+        Self { context }
+    }
+
+    /// Lists up to `limit` dead letters, starting at the given stream sequence (or the start of
+    /// the stream).
+    ///
+    /// Consumer max deliveries advisories, which are also captured by the stream, are skipped.
+    #[instrument(name = "dead_letter_queue.list", level = "debug", skip(self))]
+    pub async fn list(
+        &self,
+        start_sequence: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<DeadLetterMessage>> {
+        let stream = self.stream().await?;
+        let state = stream.get_info().await?.state;
+
+        let start_sequence = start_sequence
+            .unwrap_or(state.first_sequence)
+            .max(state.first_sequence);
+
+        let mut dead_letters = Vec::new();
+        for sequence in start_sequence..=state.last_sequence {
+            if dead_letters.len() >= limit {
+                break;
+            }
+            match self.get(&stream, sequence).await {
+                Ok(dead_letter) => dead_letters.push(dead_letter),
+                Err(Error::NotADeadLetter(_)) => continue,
+                Err(Error::RawMessage(err))
+                    if err.kind() == RawMessageErrorKind::NoMessageFound =>
+                {
+                    // Messages are deleted from the stream once redriven or discarded
+                    continue;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(dead_letters)
+    }
+
+    /// Returns the dead letter with the given stream sequence.
+    #[instrument(name = "dead_letter_queue.inspect", level = "debug", skip(self))]
+    pub async fn inspect(&self, sequence: u64) -> Result<DeadLetterMessage> {
+        let stream = self.stream().await?;
+        self.get(&stream, sequence).await
+    }
+
+    /// Republishes the dead letter to its original subject and removes it from the queue.
+    ///
+    /// The redriven message is a new message to its original stream and so starts over with a
+    /// fresh delivery count.
+    #[instrument(name = "dead_letter_queue.redrive", level = "info", skip(self))]
+    pub async fn redrive(&self, sequence: u64) -> Result<()> {
+        let stream = self.stream().await?;
+        let dead_letter = self.get(&stream, sequence).await?;
+
+        self.context
+            .publish_with_headers(
+                dead_letter.original_subject.clone(),
+                dead_letter.headers,
+                dead_letter.payload,
+            )
+            .await?
+            .await?;
+        stream.delete_message(sequence).await?;
+
+        info!(
+            messaging.destination.name = dead_letter.original_subject.as_str(),
+            sequence, "redrove dead letter",
+        );
+        Ok(())
+    }
+
+    /// Removes the dead letter from the queue without redriving it.
+    #[instrument(name = "dead_letter_queue.discard", level = "info", skip(self))]
+    pub async fn discard(&self, sequence: u64) -> Result<()> {
+        let stream = self.stream().await?;
+        // Ensure that only dead letters can be discarded
+        self.get(&stream, sequence).await?;
+        stream.delete_message(sequence).await?;
+
+        Ok(())
+    }
+
+    async fn stream(&self) -> Result<Stream> {
+        let prefix = self.context.metadata().subject_prefix();
+        Ok(self
+            .context
+            .get_stream(prefixed_stream_name(prefix, STREAM_NAME))
+            .await?)
+    }
+
+    async fn get(&self, stream: &Stream, sequence: u64) -> Result<DeadLetterMessage> {
+        let message = stream.get_raw_message(sequence).await?;
+        DeadLetterMessage::from_stream_message(self.context.metadata().subject_prefix(), message)
+    }
+}
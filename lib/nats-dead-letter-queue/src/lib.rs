@@ -1,12 +1,18 @@
 use si_data_nats::{
     async_nats::jetstream::{
-        context::CreateStreamError,
-        stream::{Config, RetentionPolicy},
+        context::{CreateStreamError, GetStreamError, PublishError, UpdateStreamError},
+        stream::{Config, DeleteMessageError, InfoError, RawMessageError, RetentionPolicy},
     },
     jetstream::Context,
 };
 use thiserror::Error;
 
+mod dead_letter_queue;
+mod on_dead_letter;
+
+pub use dead_letter_queue::{DeadLetterMessage, DeadLetterQueue};
+pub use on_dead_letter::PublishOnDeadLetter;
+
 const STREAM_NAME: &str = "DEAD_LETTER_QUEUES";
 const STREAM_DESCRIPTION: &str = "Dead Letter Queues";
 // Subscribe to *all* stream and consumer max deliveries events. This subject is of the form:
@@ -14,6 +20,27 @@ const STREAM_DESCRIPTION: &str = "Dead Letter Queues";
 //
 // See: https://docs.nats.io/running-a-nats-service/nats_admin/monitoring/monitoring_jetstream
 const STREAM_SUBJECTS: &str = "$JS.EVENT.ADVISORY.CONSUMER.MAX_DELIVERIES.*.*";
+// Messages which have exhausted their retries are republished to subjects of the form:
+// `dead_letter.<STREAM>.<CONSUMER>`
+const DEAD_LETTER_SUBJECT_PREFIX: &str = "dead_letter";
+const DEAD_LETTER_SUBJECTS: &str = "dead_letter.*.*";
+
+/// Prefix shared by all headers added to a dead lettered message.
+pub const HEADER_PREFIX: &str = "X-Dead-Letter-";
+/// Header holding the subject the message was originally published to.
+pub const HEADER_ORIGINAL_SUBJECT: &str = "X-Dead-Letter-Original-Subject";
+/// Header holding the name of the stream the message was consumed from.
+pub const HEADER_STREAM: &str = "X-Dead-Letter-Stream";
+/// Header holding the name of the consumer which failed to process the message.
+pub const HEADER_CONSUMER: &str = "X-Dead-Letter-Consumer";
+/// Header holding the sequence number of the message in its original stream.
+pub const HEADER_STREAM_SEQUENCE: &str = "X-Dead-Letter-Stream-Sequence";
+/// Header holding the number of delivery attempts made before the message was dead lettered.
+pub const HEADER_DELIVERED: &str = "X-Dead-Letter-Delivered";
+/// Header holding the status code of the final failed attempt.
+pub const HEADER_STATUS: &str = "X-Dead-Letter-Status";
+/// Header holding a description of the final failure, if one was available.
+pub const HEADER_ERROR: &str = "X-Dead-Letter-Error";
 
 #[allow(missing_docs)]
 #[remain::sorted]
@@ -21,6 +48,22 @@ const STREAM_SUBJECTS: &str = "$JS.EVENT.ADVISORY.CONSUMER.MAX_DELIVERIES.*.*";
 pub enum Error {
     #[error("create stream error: {0}")]
     CreateStream(#[from] CreateStreamError),
+    #[error("delete message error: {0}")]
+    DeleteMessage(#[from] DeleteMessageError),
+    #[error("get stream error: {0}")]
+    GetStream(#[from] GetStreamError),
+    #[error("dead letter at sequence {0} is missing header: {1}")]
+    MissingHeader(u64, &'static str),
+    #[error("message at sequence {0} is not a dead letter")]
+    NotADeadLetter(u64),
+    #[error("publish error: {0}")]
+    Publish(#[from] PublishError),
+    #[error("raw message error: {0}")]
+    RawMessage(#[from] RawMessageError),
+    #[error("stream info error: {0}")]
+    StreamInfo(#[from] InfoError),
+    #[error("update stream error: {0}")]
+    UpdateStream(#[from] UpdateStreamError),
 }
 
 pub type NatsDeadLetterQueueError = Error;
@@ -30,20 +73,38 @@ type Result<T, E = Error> = std::result::Result<T, E>;
 /// Ensures that the "dead letter queue" stream is created
 pub async fn create_stream(context: &Context) -> Result<()> {
     let prefix = context.metadata().subject_prefix();
+    let config = stream_config(prefix);
 
-    context
-        .get_or_create_stream(Config {
-            name: prefixed_stream_name(prefix, STREAM_NAME),
-            description: Some(STREAM_DESCRIPTION.to_string()),
-            retention: RetentionPolicy::Limits,
-            subjects: vec![prefixed_subject(prefix, STREAM_SUBJECTS)],
-            ..Default::default()
-        })
-        .await?;
+    let stream = context.get_or_create_stream(config.clone()).await?;
+
+    // Streams created before messages were republished as dead letters only capture advisories
+    if stream.cached_info().config.subjects != config.subjects {
+        context.update_stream(&config).await?;
+    }
 
     Ok(())
 }
 
+fn stream_config(prefix: Option<&str>) -> Config {
+    Config {
+        name: prefixed_stream_name(prefix, STREAM_NAME),
+        description: Some(STREAM_DESCRIPTION.to_string()),
+        retention: RetentionPolicy::Limits,
+        subjects: vec![
+            prefixed_subject(prefix, STREAM_SUBJECTS),
+            prefixed_subject(prefix, DEAD_LETTER_SUBJECTS),
+        ],
+        ..Default::default()
+    }
+}
+
+fn dead_letter_subject(prefix: Option<&str>, stream: &str, consumer: &str) -> String {
+    prefixed_subject(
+        prefix,
+        &format!("{DEAD_LETTER_SUBJECT_PREFIX}.{stream}.{consumer}"),
+    )
+}
+
 fn prefixed_stream_name(prefix: Option<&str>, stream_name: &str) -> String {
     match prefix {
         Some(prefix) => format!("{prefix}_{stream_name}"),
@@ -0,0 +1,86 @@
+use futures::future::BoxFuture;
+use naxum::{
+    middleware::retry::{DeadLetter, OnDeadLetter},
+    BoxError,
+};
+use si_data_nats::{jetstream::Context, HeaderMap};
+use telemetry::prelude::*;
+
+use crate::{
+    dead_letter_subject, HEADER_CONSUMER, HEADER_DELIVERED, HEADER_ERROR, HEADER_ORIGINAL_SUBJECT,
+    HEADER_STATUS, HEADER_STREAM, HEADER_STREAM_SEQUENCE,
+};
+
+// Header values are single line and error messages can be arbitrarily long
+const MAX_ERROR_HEADER_LEN: usize = 1024;
+
+/// Republishes dead letters from naxum's retry middleware to the "dead letter queue" stream.
+///
+/// The original message headers and payload are preserved, with additional headers describing
+/// where the message came from and why it failed.
+#[derive(Clone, Debug)]
+pub struct PublishOnDeadLetter {
+    context: Context,
+}
+
+impl PublishOnDeadLetter {
+    /// Creates a new [`PublishOnDeadLetter`].
+    pub fn new(context: Context) -> Self {
+        Self { context }
+    }
+}
+
+impl OnDeadLetter for PublishOnDeadLetter {
+    fn call(&mut self, dead_letter: DeadLetter) -> BoxFuture<'static, Result<(), BoxError>> {
+        let context = self.context.clone();
+
+        Box::pin(async move {
+            let DeadLetter {
+                head,
+                payload,
+                info,
+                status,
+                error,
+            } = dead_letter;
+
+            let subject = dead_letter_subject(
+                context.metadata().subject_prefix(),
+                &info.stream,
+                &info.consumer,
+            );
+
+            let mut headers = head.headers.clone().unwrap_or_else(HeaderMap::new);
+            headers.insert(HEADER_ORIGINAL_SUBJECT, head.subject.as_str());
+            headers.insert(HEADER_STREAM, info.stream.as_str());
+            headers.insert(HEADER_CONSUMER, info.consumer.as_str());
+            headers.insert(HEADER_STREAM_SEQUENCE, info.stream_sequence.to_string());
+            headers.insert(HEADER_DELIVERED, info.delivered.to_string());
+            headers.insert(HEADER_STATUS, status.as_u16().to_string());
+            if let Some(error) = error {
+                headers.insert(HEADER_ERROR, sanitize_header_value(&error));
+            }
+
+            context
+                .publish_with_headers(subject.clone(), headers, payload)
+                .await?
+                .await?;
+
+            info!(
+                messaging.destination.name = subject.as_str(),
+                original_subject = head.subject.as_str(),
+                delivered = info.delivered,
+                "published dead letter",
+            );
+
+            Ok(())
+        })
+    }
+}
+
+fn sanitize_header_value(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(MAX_ERROR_HEADER_LEN)
+        .collect()
+}
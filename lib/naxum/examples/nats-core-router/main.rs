@@ -0,0 +1,133 @@
+use std::{convert::Infallible, env, error, str};
+
+use futures::StreamExt;
+use naxum::{
+    extract::{MatchedSubject, State, SubjectTokens},
+    middleware::trace::TraceLayer,
+    Message, Router,
+};
+use serde::Deserialize;
+use tokio::signal::unix::{self, SignalKind};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+use tracing::{info, warn};
+use tracing_subscriber::{
+    fmt::{self, format::FmtSpan},
+    layer::SubscriberExt as _,
+    util::SubscriberInitExt as _,
+    EnvFilter, Registry,
+};
+
+const TRACING_LOG_ENV_VAR: &str = "SI_LOG";
+const DEFAULT_TRACING_DIRECTIVES: &str = "nats_core_router=trace,naxum=trace,info";
+
+#[derive(Clone, Debug)]
+struct AppState {}
+
+#[derive(Debug, Deserialize)]
+struct ChangeSetTokens {
+    workspace_id: String,
+    change_set_id: String,
+}
+
+async fn process_change_set(
+    State(_state): State<AppState>,
+    SubjectTokens(tokens): SubjectTokens<ChangeSetTokens>,
+    msg: Message<async_nats::Message>,
+) {
+    let payload = str::from_utf8(&msg.payload).unwrap_or("<invalid utf8>");
+    info!(
+        workspace_id = tokens.workspace_id,
+        change_set_id = tokens.change_set_id,
+        payload,
+        "processing change set message",
+    );
+}
+
+async fn process_audit(SubjectTokens(workspace_id): SubjectTokens<String>) {
+    info!(workspace_id, "processing audit message");
+}
+
+async fn unknown_admin(matched_subject: Option<MatchedSubject>, msg: Message<async_nats::Message>) {
+    warn!(
+        subject = msg.subject.as_str(),
+        matched_subject = matched_subject.as_ref().map(MatchedSubject::as_str),
+        "unknown admin message",
+    );
+}
+
+async fn unknown(msg: Message<async_nats::Message>) {
+    warn!(subject = msg.subject.as_str(), "no route for message");
+}
+
+#[allow(clippy::disallowed_methods)] // env vars are supporting alternatives in an example
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn error::Error>> {
+    Registry::default()
+        .with(
+            EnvFilter::try_from_env(TRACING_LOG_ENV_VAR)
+                .unwrap_or_else(|_| EnvFilter::new(DEFAULT_TRACING_DIRECTIVES)),
+        )
+        .with(
+            fmt::layer()
+                .with_thread_ids(true)
+                .with_thread_names(true)
+                .with_span_events(FmtSpan::NEW | FmtSpan::CLOSE)
+                .pretty(),
+        )
+        .try_init()?;
+
+    let url = env::var("NATS_URL").unwrap_or_else(|_| "nats://localhost:4222".to_owned());
+    let subject = "naxum.test.router.>";
+
+    let client = async_nats::connect(url).await?;
+    let messages = client.subscribe(subject).await?.map(Ok::<_, Infallible>);
+
+    // Routes are matched in order, with named tokens available to the `SubjectTokens` extractor
+    let admin = Router::new()
+        .route("audit.:workspace_id", process_audit)
+        .fallback(unknown_admin);
+    let app = Router::new()
+        .route(
+            "naxum.test.router.:workspace_id.:change_set_id",
+            process_change_set,
+        )
+        .nest("naxum.test.router.admin", admin)
+        .fallback(unknown)
+        .layer(TraceLayer::new())
+        .with_state(AppState {});
+
+    let tracker = TaskTracker::new();
+    let token = CancellationToken::new();
+
+    let naxum_token = token.clone();
+    tracker.spawn(async move {
+        info!(
+            subject,
+            "ready to route messages on a core nats subscription"
+        );
+        naxum::serve(messages, app.into_make_service())
+            .with_graceful_shutdown(naxum::wait_on_cancelled(naxum_token))
+            .await
+    });
+
+    let mut sig_int = unix::signal(SignalKind::interrupt())?;
+    let mut sig_term = unix::signal(SignalKind::terminate())?;
+
+    tokio::select! {
+        _ = sig_int.recv() => {
+            info!("received SIGINT, performing graceful shutdown");
+            tracker.close();
+            token.cancel();
+        }
+        _ = sig_term.recv() => {
+            info!("received SIGTERM, performing graceful shutdown");
+            tracker.close();
+            token.cancel();
+        }
+    }
+
+    tracker.wait().await;
+
+    info!("graceful shutdown complete");
+    Ok(())
+}
@@ -18,6 +18,10 @@ impl Body {
     pub const fn empty() -> Self {
         Self(Bytes::new())
     }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
 }
 
 impl Default for Body {
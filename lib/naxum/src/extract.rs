@@ -11,9 +11,14 @@ mod matched_subject;
 pub mod message_parts;
 pub mod rejection;
 mod state;
+mod subject_tokens;
 mod tuple;
 
-pub use self::{matched_subject::MatchedSubject, state::State};
+pub use self::{
+    matched_subject::MatchedSubject,
+    state::State,
+    subject_tokens::{RawSubjectTokens, SubjectTokens},
+};
 
 mod private {
     #[derive(Debug, Clone, Copy)]
@@ -128,3 +128,33 @@ composite_rejection! {
         MatchedSubjectMissing,
     }
 }
+
+define_rejection! {
+    #[status_code = 500]
+    #[body = "No subject tokens found, is the handler routed with a `Router`?"]
+    /// Rejection type for [`SubjectTokens`](super::SubjectTokens) and
+    /// [`RawSubjectTokens`](super::RawSubjectTokens).
+    ///
+    /// This rejection is used if the message was not dispatched by a [`Router`](crate::Router).
+    pub struct MissingSubjectTokens;
+}
+
+define_rejection! {
+    #[status_code = 400]
+    #[body = "Failed to deserialize the subject tokens into the target type"]
+    /// Rejection type for [`SubjectTokens`](super::SubjectTokens).
+    ///
+    /// This rejection is used if the captured subject tokens couldn't be deserialized into the
+    /// target type.
+    pub struct InvalidSubjectTokens(Error);
+}
+
+composite_rejection! {
+    /// Rejection type for [`SubjectTokens`](super::SubjectTokens).
+    ///
+    /// Contains one vaiant for each way the extractor can fail.
+    pub enum SubjectTokensRejection {
+        MissingSubjectTokens,
+        InvalidSubjectTokens,
+    }
+}
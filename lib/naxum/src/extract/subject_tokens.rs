@@ -0,0 +1,87 @@
+use std::{ops::Deref, sync::Arc};
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::Head;
+
+use super::{
+    rejection::{InvalidSubjectTokens, MissingSubjectTokens, SubjectTokensRejection},
+    FromMessageHead,
+};
+
+mod de;
+
+/// Extractor for the named tokens captured by a [`Router`](crate::Router) subject pattern.
+///
+/// The target type can be a struct (or map) keyed by token name, a tuple with one element per
+/// named token in pattern order, or a single value when the pattern has exactly one named token.
+///
+/// ```ignore
+/// #[derive(Deserialize)]
+/// struct Ids {
+///     workspace_id: WorkspacePk,
+///     change_set_id: ChangeSetId,
+/// }
+///
+/// async fn process(SubjectTokens(ids): SubjectTokens<Ids>) { /* ... */ }
+///
+/// let app = Router::new().route("rebaser.requests.:workspace_id.:change_set_id", process);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SubjectTokens<T>(pub T);
+
+impl<T> Deref for SubjectTokens<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<T, S> FromMessageHead<S> for SubjectTokens<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = SubjectTokensRejection;
+
+    async fn from_message_head(head: &mut Head, state: &S) -> Result<Self, Self::Rejection> {
+        let raw = RawSubjectTokens::from_message_head(head, state).await?;
+
+        <T as Deserialize>::deserialize(de::SubjectTokensDeserializer::new(&raw.0))
+            .map(Self)
+            .map_err(|err| InvalidSubjectTokens::from_err(err).into())
+    }
+}
+
+/// Extractor for the named tokens captured by a [`Router`](crate::Router) subject pattern as
+/// name/value string pairs, in pattern order.
+#[derive(Clone, Debug)]
+pub struct RawSubjectTokens(Arc<[(Arc<str>, Arc<str>)]>);
+
+impl RawSubjectTokens {
+    pub(crate) fn new(tokens: Vec<(Arc<str>, Arc<str>)>) -> Self {
+        Self(tokens.into())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(name, value)| (&**name, &**value))
+    }
+}
+
+#[async_trait]
+impl<S> FromMessageHead<S> for RawSubjectTokens
+where
+    S: Send + Sync,
+{
+    type Rejection = MissingSubjectTokens;
+
+    async fn from_message_head(head: &mut Head, _state: &S) -> Result<Self, Self::Rejection> {
+        head.extensions
+            .get::<Self>()
+            .cloned()
+            .ok_or(MissingSubjectTokens)
+    }
+}
@@ -0,0 +1,421 @@
+//! A minimal [`serde`] deserializer over captured subject tokens.
+
+use std::{error, fmt, slice, str::FromStr, sync::Arc};
+
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, StrDeserializer},
+        DeserializeSeed, IntoDeserializer, SeqAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+#[derive(Debug)]
+pub(super) struct SubjectTokensDeserializeError(String);
+
+impl fmt::Display for SubjectTokensDeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl error::Error for SubjectTokensDeserializeError {}
+
+impl de::Error for SubjectTokensDeserializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Deserializes all tokens as a map/struct keyed by token name, a sequence/tuple of token values,
+/// or as a single value when exactly one token was captured.
+pub(super) struct SubjectTokensDeserializer<'a> {
+    tokens: &'a [(Arc<str>, Arc<str>)],
+}
+
+impl<'a> SubjectTokensDeserializer<'a> {
+    pub(super) fn new(tokens: &'a [(Arc<str>, Arc<str>)]) -> Self {
+        Self { tokens }
+    }
+
+    fn single(&self) -> Result<TokenDeserializer<'a>, SubjectTokensDeserializeError> {
+        match self.tokens {
+            [(name, value)] => Ok(TokenDeserializer { name, value }),
+            _ => Err(de::Error::custom(format!(
+                "expected 1 subject token but {} were captured",
+                self.tokens.len()
+            ))),
+        }
+    }
+}
+
+macro_rules! forward_to_single {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.single()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for SubjectTokensDeserializer<'_> {
+    type Error = SubjectTokensDeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(MapDeserializer::new(
+            self.tokens
+                .iter()
+                .map(|(name, value)| (&**name, TokenDeserializer { name, value })),
+        ))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(TokenSeq {
+            tokens: self.tokens.iter(),
+        })
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if len != self.tokens.len() {
+            return Err(de::Error::custom(format!(
+                "expected {len} subject tokens but {} were captured",
+                self.tokens.len()
+            )));
+        }
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.single()?.deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_single! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_option
+        deserialize_unit deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.single()?.deserialize_unit_struct(name, visitor)
+    }
+}
+
+struct TokenSeq<'a> {
+    tokens: slice::Iter<'a, (Arc<str>, Arc<str>)>,
+}
+
+impl<'de> SeqAccess<'de> for TokenSeq<'_> {
+    type Error = SubjectTokensDeserializeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.tokens.next() {
+            Some((name, value)) => seed
+                .deserialize(TokenDeserializer { name, value })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Deserializes a single token value, parsing it into primitives as requested.
+struct TokenDeserializer<'a> {
+    name: &'a str,
+    value: &'a str,
+}
+
+impl IntoDeserializer<'_, SubjectTokensDeserializeError> for TokenDeserializer<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl TokenDeserializer<'_> {
+    fn parse<T>(&self) -> Result<T, SubjectTokensDeserializeError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.value.parse().map_err(|err| {
+            de::Error::custom(format!(
+                "failed to parse subject token `{}` from `{}`: {err}",
+                self.name, self.value
+            ))
+        })
+    }
+}
+
+macro_rules! parse_token {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for TokenDeserializer<'_> {
+    type Error = SubjectTokensDeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.value)
+    }
+
+    parse_token! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Only unit variants can be represented by a single token
+        let deserializer: StrDeserializer<'_, Self::Error> = self.value.into_deserializer();
+        deserializer.deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    fn tokens(pairs: &[(&str, &str)]) -> Vec<(Arc<str>, Arc<str>)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (Arc::from(*name), Arc::from(*value)))
+            .collect()
+    }
+
+    fn deserialize<'de, T>(tokens: &[(Arc<str>, Arc<str>)]) -> Result<T, String>
+    where
+        T: Deserialize<'de>,
+    {
+        T::deserialize(SubjectTokensDeserializer::new(tokens)).map_err(|err| err.to_string())
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Ids {
+        workspace_id: String,
+        attempt: u32,
+        dry_run: Option<bool>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum Kind {
+        Create,
+        Destroy,
+    }
+
+    #[test]
+    fn struct_by_token_name() {
+        let tokens = tokens(&[("attempt", "3"), ("workspace_id", "w1")]);
+
+        assert_eq!(
+            Ok(Ids {
+                workspace_id: "w1".to_owned(),
+                attempt: 3,
+                dry_run: None,
+            }),
+            deserialize(&tokens)
+        );
+    }
+
+    #[test]
+    fn struct_with_missing_field_fails() {
+        let tokens = tokens(&[("workspace_id", "w1")]);
+
+        let err = deserialize::<Ids>(&tokens).expect_err("missing field");
+        assert!(err.contains("attempt"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn tuple_in_pattern_order() {
+        let tokens = tokens(&[("workspace_id", "w1"), ("attempt", "7")]);
+
+        assert_eq!(
+            Ok(("w1".to_owned(), 7u8)),
+            deserialize::<(String, u8)>(&tokens)
+        );
+        assert_eq!(
+            Ok(vec!["w1".to_owned(), "7".to_owned()]),
+            deserialize::<Vec<String>>(&tokens)
+        );
+    }
+
+    #[test]
+    fn tuple_with_wrong_length_fails() {
+        let tokens = tokens(&[("workspace_id", "w1"), ("attempt", "7")]);
+
+        let err = deserialize::<(String,)>(&tokens).expect_err("wrong tuple length");
+        assert_eq!("expected 1 subject tokens but 2 were captured", err);
+    }
+
+    #[test]
+    fn single_value() {
+        assert_eq!(Ok(42i64), deserialize(&tokens(&[("id", "42")])));
+        assert_eq!(Ok(true), deserialize(&tokens(&[("flag", "true")])));
+        assert_eq!(
+            Ok(Kind::Destroy),
+            deserialize(&tokens(&[("kind", "destroy")]))
+        );
+        assert_eq!(
+            Ok("w1".to_owned()),
+            deserialize::<String>(&tokens(&[("id", "w1")]))
+        );
+    }
+
+    #[test]
+    fn single_value_requires_exactly_one_token() {
+        let err = deserialize::<u32>(&tokens(&[("a", "1"), ("b", "2")])).expect_err("two tokens");
+        assert_eq!("expected 1 subject token but 2 were captured", err);
+
+        let err = deserialize::<u32>(&[]).expect_err("no tokens");
+        assert_eq!("expected 1 subject token but 0 were captured", err);
+    }
+
+    #[test]
+    fn type_errors_name_the_token() {
+        let err = deserialize::<u16>(&tokens(&[("id", "nope")])).expect_err("not a number");
+        assert!(
+            err.starts_with("failed to parse subject token `id` from `nope`"),
+            "unexpected error: {err}"
+        );
+
+        let err = deserialize::<Ids>(&tokens(&[("workspace_id", "w1"), ("attempt", "-1")]))
+            .expect_err("negative u32");
+        assert!(
+            err.starts_with("failed to parse subject token `attempt` from `-1`"),
+            "unexpected error: {err}"
+        );
+
+        assert!(deserialize::<Kind>(&tokens(&[("kind", "update")])).is_err());
+    }
+}
@@ -1,6 +1,7 @@
+use async_nats::StatusCode;
 use async_trait::async_trait;
 use bytes::Bytes;
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     extract::{
@@ -8,6 +9,7 @@ use crate::{
         FromMessage,
     },
     message::{Message, MessageHead},
+    response::{IntoResponse, Response},
 };
 
 #[derive(Clone, Copy, Default, Debug)]
@@ -63,3 +65,78 @@ where
         Ok(Json(value))
     }
 }
+
+impl<T> IntoResponse for Json<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        match serde_json::to_vec(&self.0) {
+            Ok(bytes) => bytes.into_response(),
+            Err(err) => (
+                StatusCode::from_u16(500).expect("status code is in valid range"),
+                err.to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Payload {
+        id: u32,
+        name: String,
+    }
+
+    #[test]
+    fn into_response_serializes_the_value() {
+        let response = Json(Payload {
+            id: 1,
+            name: "one".to_owned(),
+        })
+        .into_response();
+
+        assert!(response.status().is_success());
+        assert_eq!(
+            br#"{"id":1,"name":"one"}"#.as_slice(),
+            response.body().as_bytes()
+        );
+    }
+
+    #[test]
+    fn from_bytes_round_trips() {
+        let Json(payload) =
+            Json::<Payload>::from_bytes(br#"{"id":2,"name":"two"}"#).expect("valid payload");
+
+        assert_eq!(
+            Payload {
+                id: 2,
+                name: "two".to_owned(),
+            },
+            payload
+        );
+    }
+
+    #[test]
+    fn from_bytes_rejects_invalid_json_as_a_bad_request() {
+        let rejection = Json::<Payload>::from_bytes(b"{\"id\":").expect_err("truncated json");
+
+        assert!(matches!(rejection, JsonRejection::JsonSyntaxError(_)));
+        assert_eq!(400, rejection.status().as_u16());
+    }
+
+    #[test]
+    fn from_bytes_rejects_mistyped_json_as_unprocessable() {
+        let rejection =
+            Json::<Payload>::from_bytes(br#"{"id":"x","name":"two"}"#).expect_err("mistyped id");
+
+        assert!(matches!(rejection, JsonRejection::JsonDataError(_)));
+        assert_eq!(422, rejection.status().as_u16());
+    }
+}
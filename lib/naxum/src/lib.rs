@@ -12,6 +12,7 @@ mod make_service;
 mod message;
 pub mod middleware;
 pub mod response;
+pub mod routing;
 pub mod serve;
 mod service_ext;
 
@@ -20,6 +21,7 @@ pub use self::error::Error;
 pub use self::json::Json;
 pub use self::make_service::IntoMakeService;
 pub use self::message::{Extensions, Head, HeadRef, Message, MessageHead};
+pub use self::routing::Router;
 pub use self::serve::{serve, serve_with_incoming_limit};
 pub use self::service_ext::ServiceExt;
 
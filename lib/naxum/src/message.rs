@@ -419,3 +419,19 @@ pub struct HeadRef<'a> {
     /// The message's extensions
     pub extensions: &'a Extensions,
 }
+
+#[cfg(test)]
+impl Message<async_nats::Message> {
+    /// Builds a core NATS message for unit tests.
+    pub(crate) fn for_test(subject: &str, headers: Option<HeaderMap>, payload: Bytes) -> Self {
+        Self::new(async_nats::Message {
+            subject: subject.into(),
+            reply: Some("_INBOX.test".into()),
+            length: subject.len() + payload.len(),
+            payload,
+            headers,
+            status: None,
+            description: None,
+        })
+    }
+}
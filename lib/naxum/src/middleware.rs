@@ -1,7 +1,10 @@
 pub mod ack;
 pub mod delay;
+pub mod limit;
 pub mod matched_subject;
 pub mod post_process;
+pub mod reply;
+pub mod retry;
 pub mod trace;
 
 #[non_exhaustive]
@@ -22,6 +22,8 @@ pin_project! {
         pub(crate) on_success_fut: BoxFuture<'static, ()>,
         #[pin]
         pub(crate) on_failure_fut: BoxFuture<'static, ()>,
+        #[pin]
+        pub(crate) on_throttled_fut: BoxFuture<'static, ()>,
         pub(crate) state: State<S::Response, S::Error>,
         pub(crate) shutdown_guard: Option<DropGuard>,
     }
@@ -33,6 +35,7 @@ pub(crate) enum State<T, E> {
     Initial,
     Success(Option<T>),
     Failure(Option<T>),
+    Throttled(Option<T>),
     Err(Option<E>),
 }
 
@@ -66,6 +69,9 @@ where
                             if response.status().is_success() {
                                 // Transition the state to run the success case
                                 *this.state = State::Success(Some(response));
+                            } else if response.status().as_u16() == 429 {
+                                // Transition the state to run the throttled case
+                                *this.state = State::Throttled(Some(response));
                             } else {
                                 // Transition the state to run the failure case
                                 *this.state = State::Failure(Some(response));
@@ -91,6 +97,13 @@ where
                         .take()
                         .expect("extracting owned value only happens once")));
                 }
+                // Poll the on_throttled future and when ready return the `Ok` type
+                State::Throttled(throttled_response) => {
+                    futures::ready!(this.on_throttled_fut.poll(cx));
+                    return Poll::Ready(Ok(throttled_response
+                        .take()
+                        .expect("extracting owned value only happens once")));
+                }
                 // Poll the on_failure future and when ready return the `Err` type
                 State::Err(err) => {
                     futures::ready!(this.on_failure_fut.poll(cx));
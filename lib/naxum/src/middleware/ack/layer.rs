@@ -6,11 +6,13 @@ use super::{on_failure::DefaultOnFailure, on_success::DefaultOnSuccess, service:
 
 // Default `ack_wait` period when unset is 30 seconds (a NATS server default)
 const DEFAULT_PROGRESS_PERIOD: Duration = Duration::from_secs(20);
+pub(crate) const DEFAULT_THROTTLED_DELAY: Duration = Duration::from_secs(1);
 
 pub struct AckLayer<OnSuccess = DefaultOnSuccess, OnFailure = DefaultOnFailure> {
     pub(crate) on_success: OnSuccess,
     pub(crate) on_failure: OnFailure,
     pub(crate) progress_period: Duration,
+    pub(crate) throttled_delay: Duration,
 }
 
 impl Default for AckLayer {
@@ -19,6 +21,7 @@ impl Default for AckLayer {
             on_success: Default::default(),
             on_failure: Default::default(),
             progress_period: DEFAULT_PROGRESS_PERIOD,
+            throttled_delay: DEFAULT_THROTTLED_DELAY,
         }
     }
 }
@@ -38,11 +41,13 @@ impl<OnSuccess, OnFailure> AckLayer<OnSuccess, OnFailure> {
             on_success: _,
             on_failure,
             progress_period,
+            throttled_delay,
         } = self;
         AckLayer {
             on_success: new_on_success,
             on_failure,
             progress_period,
+            throttled_delay,
         }
     }
 
@@ -54,11 +59,13 @@ impl<OnSuccess, OnFailure> AckLayer<OnSuccess, OnFailure> {
             on_success,
             on_failure: _,
             progress_period,
+            throttled_delay,
         } = self;
         AckLayer {
             on_success,
             on_failure: new_on_failure,
             progress_period,
+            throttled_delay,
         }
     }
 
@@ -67,11 +74,26 @@ impl<OnSuccess, OnFailure> AckLayer<OnSuccess, OnFailure> {
             on_success,
             on_failure,
             progress_period: _,
+            throttled_delay,
         } = self;
         AckLayer {
             on_success,
             on_failure,
             progress_period: new_progress_period,
+            throttled_delay,
+        }
+    }
+
+    /// Sets how long a message is delayed before redelivery when the inner service responds with
+    /// "too many requests", such as when a [`KeyedLimitLayer`] rejects it. These messages skip
+    /// the `on_failure` callback, since redelivering them right away would only be rejected
+    /// again.
+    ///
+    /// [`KeyedLimitLayer`]: crate::middleware::limit::KeyedLimitLayer
+    pub fn throttled_delay(self, throttled_delay: Duration) -> Self {
+        Self {
+            throttled_delay,
+            ..self
         }
     }
 }
@@ -89,6 +111,7 @@ where
             on_success: self.on_success.clone(),
             on_failure: self.on_failure.clone(),
             progress_period: self.progress_period,
+            throttled_delay: self.throttled_delay,
         }
     }
 }
@@ -1,6 +1,6 @@
 mod future;
 mod layer;
-mod maintain_progress;
+pub(crate) mod maintain_progress;
 mod on_failure;
 mod on_success;
 mod service;
@@ -4,18 +4,20 @@ use std::{
     time::Duration,
 };
 
-use async_nats::jetstream;
+use async_nats::jetstream::{self, message::Acker};
 use tokio_util::sync::CancellationToken;
 use tower::Service;
+use tracing::{trace, warn};
 
 use crate::{
     message::{Message, MessageHead},
     response::Response,
+    Head,
 };
 
 use super::{
     future::ResponseFuture,
-    layer::AckLayer,
+    layer::{AckLayer, DEFAULT_THROTTLED_DELAY},
     maintain_progress::MaintainProgressTask,
     on_failure::{DefaultOnFailure, OnFailure},
     on_success::{DefaultOnSuccess, OnSuccess},
@@ -27,6 +29,7 @@ pub struct Ack<S, OnSuccess = DefaultOnSuccess, OnFailure = DefaultOnFailure> {
     pub(crate) on_success: OnSuccess,
     pub(crate) on_failure: OnFailure,
     pub(crate) progress_period: Duration,
+    pub(crate) throttled_delay: Duration,
 }
 
 impl<S> Ack<S> {
@@ -36,6 +39,7 @@ impl<S> Ack<S> {
             on_success: DefaultOnSuccess::default(),
             on_failure: DefaultOnFailure::default(),
             progress_period,
+            throttled_delay: DEFAULT_THROTTLED_DELAY,
         }
     }
 
@@ -100,13 +104,26 @@ where
 
         let on_success_fut = self.on_success.call(head.clone(), acker.clone());
         let on_failure_fut = self.on_failure.call(head.clone(), acker.clone());
+        let on_throttled_fut = Box::pin(nak_throttled(head, acker, self.throttled_delay));
 
         ResponseFuture {
             inner: response,
             on_success_fut,
             on_failure_fut,
+            on_throttled_fut,
             state: super::future::State::default(),
             shutdown_guard: Some(shutdown_guard),
         }
     }
 }
+
+async fn nak_throttled(head: Arc<Head>, acker: Arc<Acker>, delay: Duration) {
+    trace!(?delay, "nacking throttled message");
+    if let Err(err) = acker.ack_with(jetstream::AckKind::Nak(Some(delay))).await {
+        warn!(
+            error = ?err,
+            subject = head.subject.as_str(),
+            "failed to nack the throttled message",
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use crate::{
+    extract::{MatchedSubject, RawSubjectTokens},
+    message::{Message, MessageHead},
+};
+
+/// Determines the key a message is limited by.
+///
+/// Messages for which no key can be made are not limited.
+pub trait MakeKey<R> {
+    fn make_key(&mut self, msg: &Message<R>) -> Option<String>;
+}
+
+impl<F, R> MakeKey<R> for F
+where
+    F: FnMut(&Message<R>) -> Option<String>,
+{
+    fn make_key(&mut self, msg: &Message<R>) -> Option<String> {
+        self(msg)
+    }
+}
+
+/// Makes a key from a token of the message's subject.
+#[derive(Clone, Debug)]
+pub struct SubjectTokenKey {
+    selector: Selector,
+}
+
+#[derive(Clone, Debug)]
+enum Selector {
+    Index(usize),
+    Named(Arc<str>),
+}
+
+impl SubjectTokenKey {
+    /// Uses the subject token at the given (0-based) position.
+    pub fn at(index: usize) -> Self {
+        Self {
+            selector: Selector::Index(index),
+        }
+    }
+
+    /// Uses the subject token captured under the given name.
+    ///
+    /// The name is looked up in the tokens captured by a [`Router`](crate::Router) and otherwise
+    /// in the `:name` tokens of a [`MatchedSubject`], such as one set by the
+    /// [`MatchedSubjectLayer`](crate::middleware::matched_subject::MatchedSubjectLayer).
+    pub fn named(name: impl Into<Arc<str>>) -> Self {
+        Self {
+            selector: Selector::Named(name.into()),
+        }
+    }
+}
+
+impl<R> MakeKey<R> for SubjectTokenKey
+where
+    R: MessageHead,
+{
+    fn make_key(&mut self, msg: &Message<R>) -> Option<String> {
+        let subject = msg.subject().as_str();
+
+        match &self.selector {
+            Selector::Index(index) => subject.split('.').nth(*index).map(ToOwned::to_owned),
+            Selector::Named(name) => {
+                if let Some(tokens) = msg.extensions().get::<RawSubjectTokens>() {
+                    if let Some((_, value)) = tokens.iter().find(|(token, _)| *token == &**name) {
+                        return Some(value.to_owned());
+                    }
+                }
+
+                let matched = msg.extensions().get::<MatchedSubject>()?;
+                let index = matched
+                    .as_str()
+                    .split('.')
+                    .position(|token| token.strip_prefix(':') == Some(&**name))?;
+                subject.split('.').nth(index).map(ToOwned::to_owned)
+            }
+        }
+    }
+}
+
+/// Makes a key from the value of a message header.
+#[derive(Clone, Debug)]
+pub struct HeaderKey {
+    name: Arc<str>,
+}
+
+impl HeaderKey {
+    pub fn new(name: impl Into<Arc<str>>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl<R> MakeKey<R> for HeaderKey
+where
+    R: MessageHead,
+{
+    fn make_key(&mut self, msg: &Message<R>) -> Option<String> {
+        msg.headers()?
+            .get(&*self.name)
+            .map(|value| value.as_str().to_owned())
+    }
+}
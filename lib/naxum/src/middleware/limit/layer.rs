@@ -0,0 +1,33 @@
+use tower::Layer;
+
+use super::{service::KeyedLimit, KeyedLimits};
+
+#[derive(Clone, Debug)]
+pub struct KeyedLimitLayer<K> {
+    pub(crate) make_key: K,
+    pub(crate) limits: KeyedLimits,
+}
+
+impl<K> KeyedLimitLayer<K> {
+    /// Creates a new layer which limits messages by the key made with `make_key`.
+    ///
+    /// The `limits` handle can be kept to change limits at runtime.
+    pub fn new(make_key: K, limits: KeyedLimits) -> Self {
+        Self { make_key, limits }
+    }
+}
+
+impl<S, K> Layer<S> for KeyedLimitLayer<K>
+where
+    K: Clone,
+{
+    type Service = KeyedLimit<S, K>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        KeyedLimit {
+            inner,
+            make_key: self.make_key.clone(),
+            limits: self.limits.clone(),
+        }
+    }
+}
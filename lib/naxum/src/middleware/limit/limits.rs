@@ -0,0 +1,456 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time::{self, Instant},
+};
+use tracing::info;
+
+/// The limits applied to messages of a single key.
+///
+/// A `None` value means that aspect is unlimited.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct KeyLimit {
+    /// The maximum number of messages for the key processed at once
+    pub max_concurrency: Option<usize>,
+    /// The maximum rate at which messages for the key start processing
+    pub rate: Option<Rate>,
+    /// The maximum number of messages for the key waiting on the limits, after which further
+    /// messages are rejected
+    ///
+    /// Waiting messages still count against any limit outside of this one, such as the
+    /// concurrency limit of [`serve_with_incoming_limit`](crate::serve_with_incoming_limit), so
+    /// leaving this unset lets a single key fill them all.
+    pub max_queued: Option<usize>,
+}
+
+impl KeyLimit {
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    pub fn max_concurrency(self, max_concurrency: usize) -> Self {
+        Self {
+            max_concurrency: Some(max_concurrency.max(1)),
+            ..self
+        }
+    }
+
+    pub fn rate(self, rate: Rate) -> Self {
+        Self {
+            rate: Some(rate),
+            ..self
+        }
+    }
+
+    pub fn max_queued(self, max_queued: usize) -> Self {
+        Self {
+            max_queued: Some(max_queued),
+            ..self
+        }
+    }
+
+    fn is_unlimited(&self) -> bool {
+        self.max_concurrency.is_none() && self.rate.is_none()
+    }
+}
+
+/// A rate of `num` messages per `per` duration, allowing bursts of up to `num` messages.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rate {
+    num: u64,
+    per: Duration,
+}
+
+impl Rate {
+    /// # Panics
+    ///
+    /// Panics if `num` or `per` is zero.
+    pub fn new(num: u64, per: Duration) -> Self {
+        assert!(num > 0, "rate must allow at least one message");
+        assert!(per > Duration::ZERO, "rate period must be non-zero");
+        Self { num, per }
+    }
+
+    pub fn num(&self) -> u64 {
+        self.num
+    }
+
+    pub fn per(&self) -> Duration {
+        self.per
+    }
+}
+
+/// A cloneable handle to the per-key limits used by a
+/// [`KeyedLimitLayer`](super::KeyedLimitLayer), which can be updated at runtime.
+///
+/// Changes apply to messages arriving after the change; messages already being processed keep
+/// the permits they were granted.
+#[derive(Clone, Debug, Default)]
+pub struct KeyedLimits {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    default: KeyLimit,
+    overrides: HashMap<String, KeyLimit>,
+    states: HashMap<String, Arc<KeyState>>,
+}
+
+impl KeyedLimits {
+    /// Creates limits which apply `default` to every key.
+    pub fn new(default: KeyLimit) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                default,
+                ..Default::default()
+            })),
+        }
+    }
+
+    /// Returns the limit applied to keys without an override.
+    pub fn default_limit(&self) -> KeyLimit {
+        self.lock().default
+    }
+
+    /// Sets the limit applied to keys without an override.
+    pub fn set_default_limit(&self, limit: KeyLimit) {
+        let mut inner = self.lock();
+        inner.default = limit;
+        let Inner {
+            overrides, states, ..
+        } = &mut *inner;
+        states.retain(|key, _| overrides.contains_key(key));
+    }
+
+    /// Returns the limit applied to the given key.
+    pub fn limit(&self, key: &str) -> KeyLimit {
+        let inner = self.lock();
+        inner.overrides.get(key).copied().unwrap_or(inner.default)
+    }
+
+    /// Sets the limit for a single key, overriding the default limit.
+    pub fn set_limit(&self, key: impl Into<String>, limit: KeyLimit) {
+        let key = key.into();
+        let mut inner = self.lock();
+        inner.states.remove(&key);
+        inner.overrides.insert(key, limit);
+    }
+
+    /// Removes the limit override for a single key, returning it to the default limit.
+    pub fn remove_limit(&self, key: &str) -> Option<KeyLimit> {
+        let mut inner = self.lock();
+        inner.states.remove(key);
+        inner.overrides.remove(key)
+    }
+
+    /// Returns the number of messages for the given key currently waiting on its limits.
+    pub fn queued(&self, key: &str) -> usize {
+        self.lock()
+            .states
+            .get(key)
+            .map(|state| state.queued.load(Ordering::Relaxed))
+            .unwrap_or_default()
+    }
+
+    pub(crate) async fn acquire(&self, key: &str) -> Result<Permit, Rejected> {
+        let state = self.state(key);
+        if state.limit.is_unlimited() {
+            return Ok(Permit(None));
+        }
+
+        let concurrency_full = state
+            .semaphore
+            .as_ref()
+            .is_some_and(|semaphore| semaphore.available_permits() == 0);
+        let can_queue = state
+            .limit
+            .max_queued
+            .is_none_or(|max_queued| state.queued.load(Ordering::Relaxed) < max_queued);
+        if concurrency_full && !can_queue {
+            rejected();
+            return Err(Rejected);
+        }
+
+        let rate_delay = match &state.bucket {
+            Some(bucket) => match bucket
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .reserve(can_queue)
+            {
+                Some(delay) => delay,
+                None => {
+                    rejected();
+                    return Err(Rejected);
+                }
+            },
+            None => Duration::ZERO,
+        };
+
+        if rate_delay.is_zero() {
+            match &state.semaphore {
+                None => return Ok(Permit(None)),
+                Some(semaphore) => {
+                    if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+                        return Ok(Permit(Some(permit)));
+                    }
+                }
+            }
+        }
+
+        let _queued = QueuedGuard::new(state.clone());
+        if !rate_delay.is_zero() {
+            time::sleep(rate_delay).await;
+        }
+        let permit = match &state.semaphore {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed"),
+            ),
+            None => None,
+        };
+
+        Ok(Permit(permit))
+    }
+
+    fn state(&self, key: &str) -> Arc<KeyState> {
+        let mut inner = self.lock();
+        if let Some(state) = inner.states.get(key) {
+            return state.clone();
+        }
+
+        let limit = inner.overrides.get(key).copied().unwrap_or(inner.default);
+        let state = Arc::new(KeyState::new(limit));
+        // Unlimited keys have no state worth keeping around
+        if !limit.is_unlimited() {
+            inner.states.insert(key.to_owned(), state.clone());
+        }
+        state
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[derive(Debug)]
+struct KeyState {
+    limit: KeyLimit,
+    semaphore: Option<Arc<Semaphore>>,
+    bucket: Option<Mutex<Bucket>>,
+    queued: AtomicUsize,
+}
+
+impl KeyState {
+    fn new(limit: KeyLimit) -> Self {
+        Self {
+            limit,
+            semaphore: limit
+                .max_concurrency
+                .map(|max_concurrency| Arc::new(Semaphore::new(max_concurrency))),
+            bucket: limit.rate.map(|rate| Mutex::new(Bucket::new(rate))),
+            queued: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// A token bucket which allows tokens to be reserved ahead of their availability.
+#[derive(Debug)]
+struct Bucket {
+    rate: Rate,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn new(rate: Rate) -> Self {
+        Self {
+            rate,
+            tokens: rate.num as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Reserves a token, returning how long to wait for it to become available, or `None` if a
+    /// wait is needed but not allowed.
+    fn reserve(&mut self, allow_wait: bool) -> Option<Duration> {
+        let now = Instant::now();
+        let per_token = self.rate.per.as_secs_f64() / self.rate.num as f64;
+        let refilled = now.duration_since(self.last_refill).as_secs_f64() / per_token;
+        self.tokens = (self.tokens + refilled).min(self.rate.num as f64);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Some(Duration::ZERO);
+        }
+        if !allow_wait {
+            return None;
+        }
+
+        // Go into debt for the token, which later reservations will wait behind
+        let wait = (1.0 - self.tokens) * per_token;
+        self.tokens -= 1.0;
+        Some(Duration::from_secs_f64(wait))
+    }
+}
+
+struct QueuedGuard(Arc<KeyState>);
+
+impl QueuedGuard {
+    fn new(state: Arc<KeyState>) -> Self {
+        state.queued.fetch_add(1, Ordering::Relaxed);
+        info!(metrics = true, counter.naxum.limit.queued = 1);
+        Self(state)
+    }
+}
+
+impl Drop for QueuedGuard {
+    fn drop(&mut self) {
+        self.0.queued.fetch_sub(1, Ordering::Relaxed);
+        info!(metrics = true, counter.naxum.limit.queued = -1);
+    }
+}
+
+fn rejected() {
+    info!(metrics = true, monotonic_counter.naxum.limit.rejected = 1);
+}
+
+/// Held while a message is processed to count against its key's concurrency limit.
+pub(crate) struct Permit(#[allow(dead_code)] Option<OwnedSemaphorePermit>);
+
+/// A message was rejected because its key's queue is full.
+#[derive(Debug)]
+pub(crate) struct Rejected;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PENDING: Duration = Duration::from_millis(50);
+
+    #[tokio::test]
+    async fn unlimited_keys_are_not_tracked() {
+        let limits = KeyedLimits::default();
+
+        let _permits = [
+            limits.acquire("a").await.expect("unlimited"),
+            limits.acquire("a").await.expect("unlimited"),
+        ];
+
+        assert!(limits.lock().states.is_empty());
+    }
+
+    #[tokio::test]
+    async fn concurrency_is_limited_per_key() {
+        let limits = KeyedLimits::new(KeyLimit::unlimited().max_concurrency(1));
+
+        let first = limits.acquire("a").await.expect("first permit");
+
+        // A second message for the same key waits for the first to finish...
+        let waiting = tokio::spawn({
+            let limits = limits.clone();
+            async move { limits.acquire("a").await.map(|_| ()) }
+        });
+        time::sleep(PENDING).await;
+        assert!(!waiting.is_finished());
+        assert_eq!(1, limits.queued("a"));
+
+        // ...while other keys are unaffected
+        let _other = time::timeout(PENDING, limits.acquire("b"))
+            .await
+            .expect("other key should not wait")
+            .expect("other key permit");
+
+        drop(first);
+        time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("waiting message should proceed")
+            .expect("task should not panic")
+            .expect("second permit");
+        assert_eq!(0, limits.queued("a"));
+    }
+
+    #[tokio::test]
+    async fn full_queue_rejects() {
+        let limits = KeyedLimits::new(KeyLimit::unlimited().max_concurrency(1).max_queued(1));
+
+        let _first = limits.acquire("a").await.expect("first permit");
+        let _queued = tokio::spawn({
+            let limits = limits.clone();
+            async move { limits.acquire("a").await.map(|_| ()) }
+        });
+        time::sleep(PENDING).await;
+        assert_eq!(1, limits.queued("a"));
+
+        assert!(limits.acquire("a").await.is_err());
+        let _other = limits
+            .acquire("b")
+            .await
+            .expect("other key has its own queue");
+    }
+
+    #[tokio::test]
+    async fn rate_is_limited_per_key() {
+        let limits =
+            KeyedLimits::new(KeyLimit::unlimited().rate(Rate::new(2, Duration::from_millis(400))));
+
+        // The burst is allowed straight away
+        let start = Instant::now();
+        limits.acquire("a").await.expect("first permit");
+        limits.acquire("a").await.expect("second permit");
+        limits.acquire("b").await.expect("other key permit");
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        // The next message waits for a token to refill
+        limits.acquire("a").await.expect("third permit");
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_without_queue_rejects() {
+        let limits = KeyedLimits::new(
+            KeyLimit::unlimited()
+                .rate(Rate::new(1, Duration::from_secs(60)))
+                .max_queued(0),
+        );
+
+        limits.acquire("a").await.expect("first permit");
+        assert!(limits.acquire("a").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn overrides_apply_to_a_single_key() {
+        let limits = KeyedLimits::new(KeyLimit::unlimited().max_concurrency(1).max_queued(0));
+        limits.set_limit("big", KeyLimit::unlimited().max_concurrency(2));
+
+        assert_eq!(Some(2), limits.limit("big").max_concurrency);
+        assert_eq!(Some(1), limits.limit("small").max_concurrency);
+
+        let _big = [
+            limits.acquire("big").await.expect("first permit"),
+            limits.acquire("big").await.expect("second permit"),
+        ];
+        let _small = limits.acquire("small").await.expect("first permit");
+        assert!(limits.acquire("small").await.is_err());
+
+        assert_eq!(
+            Some(KeyLimit::unlimited().max_concurrency(2)),
+            limits.remove_limit("big")
+        );
+        assert_eq!(limits.default_limit(), limits.limit("big"));
+    }
+}
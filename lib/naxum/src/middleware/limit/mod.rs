@@ -0,0 +1,14 @@
+//! Middleware which limits the concurrency and rate of message processing per key, such as per
+//! workspace, so that a single key can't starve all others of processing capacity.
+
+mod key;
+mod layer;
+mod limits;
+mod service;
+
+pub use self::{
+    key::{HeaderKey, MakeKey, SubjectTokenKey},
+    layer::KeyedLimitLayer,
+    limits::{KeyLimit, KeyedLimits, Rate},
+    service::KeyedLimit,
+};
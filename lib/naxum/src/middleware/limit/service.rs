@@ -0,0 +1,85 @@
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use tower::Service;
+use tracing::debug;
+
+use crate::{
+    message::{Message, MessageHead},
+    response::Response,
+};
+
+use super::{KeyedLimitLayer, KeyedLimits, MakeKey};
+
+/// Limits the concurrency and rate of messages processed by the inner service per key.
+///
+/// Messages waiting on their key's limits don't hold up messages for other keys. When a key's
+/// [`max_queued`](super::KeyLimit::max_queued) is exceeded, a "too many requests" response is
+/// returned without calling the inner service.
+#[derive(Clone, Debug)]
+pub struct KeyedLimit<S, K> {
+    pub(crate) inner: S,
+    pub(crate) make_key: K,
+    pub(crate) limits: KeyedLimits,
+}
+
+impl<S, K> KeyedLimit<S, K> {
+    pub fn new(inner: S, make_key: K, limits: KeyedLimits) -> Self {
+        Self {
+            inner,
+            make_key,
+            limits,
+        }
+    }
+
+    pub fn layer(make_key: K, limits: KeyedLimits) -> KeyedLimitLayer<K> {
+        KeyedLimitLayer::new(make_key, limits)
+    }
+}
+
+impl<S, K, R> Service<Message<R>> for KeyedLimit<S, K>
+where
+    S: Service<Message<R>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send,
+    K: MakeKey<R>,
+    R: MessageHead + Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Message<R>) -> Self::Future {
+        let key = self.make_key.make_key(&req);
+        let limits = self.limits.clone();
+
+        let clone = self.inner.clone();
+        // Take the service that was ready
+        //
+        // See documentation for [`Service`] trait
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let Some(key) = key else {
+                return inner.call(req).await;
+            };
+
+            let _permit = match limits.acquire(&key).await {
+                Ok(permit) => permit,
+                Err(_) => {
+                    debug!(
+                        key = key.as_str(),
+                        subject = req.subject().as_str(),
+                        "rejecting message, too many queued for key",
+                    );
+                    return Ok(Response::default_too_many_requests());
+                }
+            };
+
+            inner.call(req).await
+        })
+    }
+}
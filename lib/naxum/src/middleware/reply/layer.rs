@@ -0,0 +1,47 @@
+use tower::Layer;
+
+use super::{DefaultReplyTo, Reply};
+
+#[derive(Clone, Debug)]
+pub struct ReplyLayer<ReplyTo = DefaultReplyTo> {
+    pub(crate) client: async_nats::Client,
+    pub(crate) reply_to: ReplyTo,
+}
+
+impl ReplyLayer {
+    /// Creates a new layer which publishes replies with the given client.
+    pub fn new(client: async_nats::Client) -> Self {
+        Self {
+            client,
+            reply_to: DefaultReplyTo::default(),
+        }
+    }
+}
+
+impl<ReplyTo> ReplyLayer<ReplyTo> {
+    pub fn reply_to<NewReplyTo>(self, new_reply_to: NewReplyTo) -> ReplyLayer<NewReplyTo> {
+        let Self {
+            client,
+            reply_to: _,
+        } = self;
+        ReplyLayer {
+            client,
+            reply_to: new_reply_to,
+        }
+    }
+}
+
+impl<S, ReplyTo> Layer<S> for ReplyLayer<ReplyTo>
+where
+    ReplyTo: Clone,
+{
+    type Service = Reply<S, ReplyTo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Reply {
+            inner,
+            client: self.client.clone(),
+            reply_to: self.reply_to.clone(),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Middleware which publishes a handler's response to the message's reply subject, providing
+//! request/reply semantics for naxum services.
+//!
+//! Successful responses are published with the response body as the payload. Failed responses
+//! are published with the standard NATS service error headers
+//! ([`NATS_SERVICE_ERROR_CODE`] and [`NATS_SERVICE_ERROR`]) set from the response's status and
+//! body, so a requester can tell an error reply from a successful one.
+
+mod layer;
+mod reply_to;
+mod service;
+
+pub use async_nats::service::{NATS_SERVICE_ERROR, NATS_SERVICE_ERROR_CODE};
+
+pub use self::{
+    layer::ReplyLayer,
+    reply_to::{DefaultReplyTo, HeaderReplyTo, ReplyTo},
+    service::Reply,
+};
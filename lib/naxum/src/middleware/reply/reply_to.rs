@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use async_nats::Subject;
+
+use crate::message::{Message, MessageHead};
+
+/// Determines the subject a message's response is published to.
+///
+/// Messages without a reply subject are processed without publishing a reply.
+pub trait ReplyTo<R> {
+    fn reply_to(&mut self, msg: &Message<R>) -> Option<Subject>;
+}
+
+/// Replies to the message's reply subject, as set by a core NATS request.
+///
+/// Note that this is not suitable for Jetstream messages, whose reply subject is used for
+/// acknowledgements.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultReplyTo {}
+
+impl DefaultReplyTo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<R> ReplyTo<R> for DefaultReplyTo
+where
+    R: MessageHead,
+{
+    fn reply_to(&mut self, msg: &Message<R>) -> Option<Subject> {
+        msg.reply().cloned()
+    }
+}
+
+/// Replies to a subject found in a message header, which is useful for Jetstream messages.
+#[derive(Clone, Debug)]
+pub struct HeaderReplyTo {
+    name: Arc<str>,
+}
+
+impl HeaderReplyTo {
+    pub fn new(name: impl Into<Arc<str>>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl<R> ReplyTo<R> for HeaderReplyTo
+where
+    R: MessageHead,
+{
+    fn reply_to(&mut self, msg: &Message<R>) -> Option<Subject> {
+        msg.headers()
+            .and_then(|headers| headers.get(&*self.name))
+            .map(|value| Subject::from(value.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_nats::HeaderMap;
+    use bytes::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn default_reply_to_uses_the_reply_subject() {
+        let msg = Message::for_test("svc.request", None, Bytes::new());
+
+        assert_eq!(
+            Some(Subject::from("_INBOX.test")),
+            DefaultReplyTo::new().reply_to(&msg)
+        );
+    }
+
+    #[test]
+    fn header_reply_to_uses_the_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Reply-To", "svc.replies.1");
+        let with_header = Message::for_test("svc.request", Some(headers), Bytes::new());
+        let without_header = Message::for_test("svc.request", None, Bytes::new());
+
+        let mut reply_to = HeaderReplyTo::new("X-Reply-To");
+        assert_eq!(
+            Some(Subject::from("svc.replies.1")),
+            reply_to.reply_to(&with_header)
+        );
+        assert_eq!(None, reply_to.reply_to(&without_header));
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    fmt,
+    task::{Context, Poll},
+};
+
+use async_nats::{HeaderMap, StatusCode, Subject};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use tower::Service;
+use tracing::{trace, warn};
+
+use crate::{
+    message::{Message, MessageHead},
+    response::Response,
+};
+
+use super::{DefaultReplyTo, ReplyLayer, ReplyTo, NATS_SERVICE_ERROR, NATS_SERVICE_ERROR_CODE};
+
+// Header values are single line and error messages can be arbitrarily long
+const MAX_ERROR_HEADER_LEN: usize = 1024;
+
+#[derive(Clone, Debug)]
+pub struct Reply<S, ReplyTo = DefaultReplyTo> {
+    pub(crate) inner: S,
+    pub(crate) client: async_nats::Client,
+    pub(crate) reply_to: ReplyTo,
+}
+
+impl<S> Reply<S> {
+    pub fn new(inner: S, client: async_nats::Client) -> Self {
+        Self {
+            inner,
+            client,
+            reply_to: DefaultReplyTo::default(),
+        }
+    }
+
+    pub fn layer(client: async_nats::Client) -> ReplyLayer {
+        ReplyLayer::new(client)
+    }
+}
+
+impl<S, ReplyToT, R> Service<Message<R>> for Reply<S, ReplyToT>
+where
+    S: Service<Message<R>, Response = Response> + Clone + Send + 'static,
+    S::Error: fmt::Display + Send,
+    S::Future: Send,
+    ReplyToT: ReplyTo<R>,
+    R: MessageHead + Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Message<R>) -> Self::Future {
+        let maybe_reply = self.reply_to.reply_to(&req);
+        let client = self.client.clone();
+
+        let clone = self.inner.clone();
+        // Take the service that was ready
+        //
+        // See documentation for [`Service`] trait
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let result = inner.call(req).await;
+
+            if let Some(reply) = maybe_reply {
+                let (headers, payload) = match &result {
+                    Ok(response) => reply_parts(
+                        response.status(),
+                        Bytes::copy_from_slice(response.body().as_bytes()),
+                    ),
+                    Err(err) => reply_parts(
+                        StatusCode::from_u16(500).expect("status code is in valid range"),
+                        Bytes::from(err.to_string()),
+                    ),
+                };
+                publish_reply(&client, reply, headers, payload).await;
+            }
+
+            result
+        })
+    }
+}
+
+fn reply_parts(status: StatusCode, payload: Bytes) -> (HeaderMap, Bytes) {
+    let mut headers = HeaderMap::new();
+    if !status.is_success() {
+        let description = match String::from_utf8_lossy(&payload) {
+            body if body.is_empty() => status.as_u16().to_string(),
+            body => sanitize_header_value(&body),
+        };
+        headers.insert(
+            NATS_SERVICE_ERROR_CODE,
+            status.as_u16().to_string().as_str(),
+        );
+        headers.insert(NATS_SERVICE_ERROR, description.as_str());
+    }
+    (headers, payload)
+}
+
+fn sanitize_header_value(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(MAX_ERROR_HEADER_LEN)
+        .collect()
+}
+
+async fn publish_reply(
+    client: &async_nats::Client,
+    reply: Subject,
+    headers: HeaderMap,
+    payload: Bytes,
+) {
+    trace!(reply = reply.as_str(), "publishing reply");
+    if let Err(err) = client
+        .publish_with_headers(reply.clone(), headers, payload)
+        .await
+    {
+        warn!(error = ?err, reply = reply.as_str(), "failed to publish reply");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(code: u16) -> StatusCode {
+        StatusCode::from_u16(code).expect("valid status code")
+    }
+
+    #[test]
+    fn successful_replies_have_no_error_headers() {
+        let (headers, payload) = reply_parts(status(200), Bytes::from_static(b"{\"ok\":true}"));
+
+        assert!(headers.get(NATS_SERVICE_ERROR_CODE).is_none());
+        assert!(headers.get(NATS_SERVICE_ERROR).is_none());
+        assert_eq!(Bytes::from_static(b"{\"ok\":true}"), payload);
+    }
+
+    #[test]
+    fn failed_replies_carry_status_and_body_as_error_headers() {
+        let (headers, payload) = reply_parts(status(422), Bytes::from_static(b"bad field"));
+
+        assert_eq!(
+            Some("422"),
+            headers
+                .get(NATS_SERVICE_ERROR_CODE)
+                .map(|value| value.as_str())
+        );
+        assert_eq!(
+            Some("bad field"),
+            headers.get(NATS_SERVICE_ERROR).map(|value| value.as_str())
+        );
+        assert_eq!(Bytes::from_static(b"bad field"), payload);
+    }
+
+    #[test]
+    fn error_headers_are_single_line_and_truncated() {
+        let body = format!(
+            "failed to run\r\ncaused by: {}",
+            "x".repeat(2 * MAX_ERROR_HEADER_LEN)
+        );
+        let (headers, payload) = reply_parts(status(500), Bytes::from(body.clone()));
+
+        let description = headers
+            .get(NATS_SERVICE_ERROR)
+            .map(|value| value.as_str())
+            .expect("error header is set");
+        assert!(description.starts_with("failed to run  caused by: x"));
+        assert!(!description.contains(['\r', '\n']));
+        assert_eq!(MAX_ERROR_HEADER_LEN, description.chars().count());
+        // The payload keeps the full error
+        assert_eq!(Bytes::from(body), payload);
+    }
+
+    #[test]
+    fn failed_replies_without_body_describe_the_status() {
+        let (headers, payload) = reply_parts(status(500), Bytes::new());
+
+        assert_eq!(
+            Some("500"),
+            headers.get(NATS_SERVICE_ERROR).map(|value| value.as_str())
+        );
+        assert!(payload.is_empty());
+    }
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+const DEFAULT_INITIAL_DELAY: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(5 * 60);
+const DEFAULT_MULTIPLIER: u32 = 2;
+
+/// An exponential backoff used to compute how long to delay redelivery of a failed message.
+///
+/// The delay for the `n`th delivery attempt is `initial_delay * multiplier^(n - 1)`, capped at
+/// `max_delay`.
+#[derive(Clone, Copy, Debug)]
+pub struct Backoff {
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: DEFAULT_INITIAL_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            multiplier: DEFAULT_MULTIPLIER,
+        }
+    }
+}
+
+impl Backoff {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn initial_delay(self, initial_delay: Duration) -> Self {
+        Self {
+            initial_delay,
+            ..self
+        }
+    }
+
+    pub fn max_delay(self, max_delay: Duration) -> Self {
+        Self { max_delay, ..self }
+    }
+
+    pub fn multiplier(self, multiplier: u32) -> Self {
+        Self { multiplier, ..self }
+    }
+
+    /// Returns the redelivery delay after the given (1-based) delivery attempt failed.
+    pub fn delay_for_attempt(&self, attempt: u64) -> Duration {
+        let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        let factor = self.multiplier.checked_pow(exponent).unwrap_or(u32::MAX);
+
+        self.initial_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
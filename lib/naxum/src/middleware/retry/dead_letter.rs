@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use async_nats::StatusCode;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use tracing::warn;
+
+use crate::{middleware::post_process::Info, BoxError, Head};
+
+/// A message which has failed processing and will not be retried.
+#[derive(Clone, Debug)]
+pub struct DeadLetter {
+    /// The head of the original message
+    pub head: Arc<Head>,
+    /// The payload of the original message
+    pub payload: Bytes,
+    /// JetStream metadata for the final delivery of the original message
+    pub info: Arc<Info>,
+    /// The status of the final failed response
+    pub status: StatusCode,
+    /// A description of the final failure, if one could be determined
+    pub error: Option<String>,
+}
+
+/// Hands off a [`DeadLetter`], typically by publishing it to a dead letter stream.
+///
+/// If the returned future resolves to an `Err`, the original message is negatively acknowledged
+/// rather than terminated so that it isn't lost.
+pub trait OnDeadLetter {
+    fn call(&mut self, dead_letter: DeadLetter) -> BoxFuture<'static, Result<(), BoxError>>;
+}
+
+/// The default [`OnDeadLetter`] which logs and discards the message.
+#[derive(Clone, Debug, Default)]
+pub struct DefaultOnDeadLetter {}
+
+impl DefaultOnDeadLetter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OnDeadLetter for DefaultOnDeadLetter {
+    fn call(&mut self, dead_letter: DeadLetter) -> BoxFuture<'static, Result<(), BoxError>> {
+        Box::pin(async move {
+            warn!(
+                subject = dead_letter.head.subject.as_str(),
+                delivered = dead_letter.info.delivered,
+                status = dead_letter.status.as_u16(),
+                error = dead_letter.error.as_deref(),
+                "discarding message after exhausting retries",
+            );
+            Ok(())
+        })
+    }
+}
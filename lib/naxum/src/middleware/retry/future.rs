@@ -0,0 +1,367 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_nats::{
+    jetstream::{self, message::Acker},
+    StatusCode,
+};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use pin_project_lite::pin_project;
+use tokio_util::sync::DropGuard;
+use tower::Service;
+use tracing::{debug, trace, warn};
+
+use crate::{message::Message, middleware::post_process::Info, response::Response, Head};
+
+use super::{
+    dead_letter::{DeadLetter, OnDeadLetter},
+    throttled::ThrottledDeliveries,
+    Backoff,
+};
+
+pin_project! {
+    pub struct ResponseFuture<S, OnDeadLetter>
+    where
+        S: Service<Message<async_nats::Message>>,
+    {
+        #[pin]
+        pub(crate) inner: S::Future,
+        pub(crate) disposition: Option<Disposition<OnDeadLetter>>,
+        pub(crate) disposing: Option<BoxFuture<'static, ()>>,
+        pub(crate) result: Option<Result<S::Response, S::Error>>,
+        pub(crate) shutdown_guard: Option<DropGuard>,
+    }
+}
+
+impl<S, OnDeadLetterT> Future for ResponseFuture<S, OnDeadLetterT>
+where
+    S: Service<Message<async_nats::Message>, Response = Response>,
+    S::Error: fmt::Display,
+    OnDeadLetterT: OnDeadLetter + Send + 'static,
+{
+    type Output = Result<S::Response, S::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            // Poll the disposition future and when ready return the inner service's result
+            if let Some(disposing) = this.disposing.as_mut() {
+                futures::ready!(disposing.as_mut().poll(cx));
+                return Poll::Ready(
+                    this.result
+                        .take()
+                        .expect("extracting owned value only happens once"),
+                );
+            }
+
+            // Poll the nested service to yield our result
+            let result = futures::ready!(this.inner.as_mut().poll(cx));
+
+            // Cancel the associated `MaintainProgressTask`
+            this.shutdown_guard
+                .take()
+                .expect("extracting shutdown guard value only happens once")
+                .disarm()
+                .cancel();
+
+            let failure = match &result {
+                Ok(response) if response.status().is_success() => None,
+                Ok(response) => Some(Failure {
+                    status: response.status(),
+                    error: Some(String::from_utf8_lossy(response.body().as_bytes()))
+                        .filter(|body| !body.is_empty())
+                        .map(|body| body.into_owned()),
+                }),
+                Err(err) => Some(Failure {
+                    status: StatusCode::from_u16(500).expect("status code is in valid range"),
+                    error: Some(err.to_string()),
+                }),
+            };
+
+            // Transition the state to dispose of the message
+            *this.disposing = Some(
+                this.disposition
+                    .take()
+                    .expect("extracting disposition only happens once")
+                    .dispose(failure),
+            );
+            *this.result = Some(result);
+        }
+    }
+}
+
+struct Failure {
+    status: StatusCode,
+    error: Option<String>,
+}
+
+pub(crate) struct Disposition<OnDeadLetter> {
+    pub(crate) acker: Arc<Acker>,
+    pub(crate) head: Arc<Head>,
+    pub(crate) payload: Bytes,
+    pub(crate) info: Option<Arc<Info>>,
+    pub(crate) backoff: Backoff,
+    pub(crate) max_attempts: u64,
+    pub(crate) on_dead_letter: OnDeadLetter,
+    pub(crate) throttled_deliveries: ThrottledDeliveries,
+}
+
+impl<OnDeadLetterT> Disposition<OnDeadLetterT>
+where
+    OnDeadLetterT: OnDeadLetter + Send + 'static,
+{
+    fn dispose(self, failure: Option<Failure>) -> BoxFuture<'static, ()> {
+        let Self {
+            acker,
+            head,
+            payload,
+            info,
+            backoff,
+            max_attempts,
+            mut on_dead_letter,
+            throttled_deliveries,
+        } = self;
+
+        Box::pin(async move {
+            let subject = head.subject.as_str();
+            let status = failure.as_ref().map(|failure| failure.status);
+            // Throttled deliveries are counted by the server but are not failed attempts
+            let attempt = info.as_ref().map(|info| {
+                u64::try_from(info.delivered)
+                    .unwrap_or_default()
+                    .saturating_sub(throttled_deliveries.count(info.stream_sequence))
+            });
+
+            let action = next_action(status, attempt, max_attempts, backoff);
+            if let Some(info) = &info {
+                match action {
+                    Action::Nak(_) if status.is_some_and(is_throttled) => {
+                        throttled_deliveries.record(info.stream_sequence)
+                    }
+                    Action::Nak(_) => {}
+                    Action::Ack | Action::DeadLetter => {
+                        throttled_deliveries.forget(info.stream_sequence)
+                    }
+                }
+            }
+
+            match action {
+                Action::Ack => {
+                    trace!("double acking message");
+                    if let Err(err) = acker.double_ack().await {
+                        warn!(error = ?err, subject, "failed to double ack the message");
+                    }
+                }
+                Action::Nak(delay) => {
+                    if info.is_none() {
+                        warn!(
+                            subject,
+                            "message has no jetstream metadata, cannot retry with backoff"
+                        );
+                    } else {
+                        debug!(subject, ?attempt, max_attempts, ?delay, "retrying message");
+                    }
+                    nak(&acker, subject, delay).await;
+                }
+                Action::DeadLetter => {
+                    let (Some(info), Some(Failure { status, error })) = (info, failure) else {
+                        unreachable!(
+                            "dead lettering requires a failure and jetstream metadata, this is a bug!"
+                        );
+                    };
+                    let delay = backoff.delay_for_attempt(attempt.unwrap_or_default());
+                    let dead_letter = DeadLetter {
+                        head: head.clone(),
+                        payload,
+                        info,
+                        status,
+                        error,
+                    };
+                    match on_dead_letter.call(dead_letter).await {
+                        Ok(()) => {
+                            trace!("terminating dead lettered message");
+                            if let Err(err) = acker.ack_with(jetstream::AckKind::Term).await {
+                                warn!(error = ?err, subject, "failed to terminate the message");
+                            }
+                        }
+                        Err(err) => {
+                            warn!(error = ?err, subject, "failed to dead letter the message");
+                            nak(&acker, subject, Some(delay)).await;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// How a processed message is acknowledged.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Action {
+    /// Processing succeeded, so acknowledge the message.
+    Ack,
+    /// Redeliver the message, optionally after a delay.
+    Nak(Option<Duration>),
+    /// Hand the message off as a dead letter and terminate it.
+    DeadLetter,
+}
+
+/// Determines how to acknowledge a message given the status of a failed response (`None` on
+/// success) and the delivery attempt from the message's jetstream metadata, if any.
+fn next_action(
+    failure: Option<StatusCode>,
+    attempt: Option<u64>,
+    max_attempts: u64,
+    backoff: Backoff,
+) -> Action {
+    let Some(status) = failure else {
+        return Action::Ack;
+    };
+
+    // A service unavailable response signals that processing was interrupted rather than
+    // failed (i.e. on shutdown), so redeliver as soon as possible
+    if status.as_u16() == 503 {
+        return Action::Nak(None);
+    }
+
+    // Without jetstream metadata the message can't participate in backoff or dead lettering
+    let Some(attempt) = attempt else {
+        return Action::Nak(None);
+    };
+
+    // A too many requests response signals that the message was throttled rather than failed,
+    // so redeliver later. The server still counts the delivery, so the caller subtracts
+    // throttled deliveries from the attempt, but they do count towards the consumer's
+    // `max_deliver`.
+    if is_throttled(status) {
+        return Action::Nak(Some(backoff.delay_for_attempt(attempt)));
+    }
+
+    // Client errors, such as a malformed message, will fail the same way on every attempt
+    let retryable = !(400..500).contains(&status.as_u16());
+
+    if retryable && attempt < max_attempts {
+        Action::Nak(Some(backoff.delay_for_attempt(attempt)))
+    } else {
+        Action::DeadLetter
+    }
+}
+
+fn is_throttled(status: StatusCode) -> bool {
+    status.as_u16() == 429
+}
+
+async fn nak(acker: &Acker, subject: &str, delay: Option<Duration>) {
+    trace!(?delay, "nacking message");
+    if let Err(err) = acker.ack_with(jetstream::AckKind::Nak(delay)).await {
+        warn!(error = ?err, subject, "failed to nack the message");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(code: u16) -> Option<StatusCode> {
+        Some(StatusCode::from_u16(code).expect("valid status code"))
+    }
+
+    fn backoff() -> Backoff {
+        Backoff::new()
+            .initial_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(60))
+            .multiplier(2)
+    }
+
+    #[test]
+    fn success_is_acked() {
+        assert_eq!(Action::Ack, next_action(None, Some(1), 3, backoff()));
+        assert_eq!(Action::Ack, next_action(None, None, 3, backoff()));
+    }
+
+    #[test]
+    fn server_errors_are_retried_with_backoff_then_dead_lettered() {
+        assert_eq!(
+            Action::Nak(Some(Duration::from_secs(1))),
+            next_action(status(500), Some(1), 3, backoff())
+        );
+        assert_eq!(
+            Action::Nak(Some(Duration::from_secs(2))),
+            next_action(status(500), Some(2), 3, backoff())
+        );
+        assert_eq!(
+            Action::DeadLetter,
+            next_action(status(500), Some(3), 3, backoff())
+        );
+        assert_eq!(
+            Action::DeadLetter,
+            next_action(status(500), Some(9), 3, backoff())
+        );
+    }
+
+    #[test]
+    fn client_errors_fail_fast() {
+        for code in [400, 404, 422] {
+            assert_eq!(
+                Action::DeadLetter,
+                next_action(status(code), Some(1), 3, backoff()),
+                "status {code} should not be retried"
+            );
+        }
+    }
+
+    #[test]
+    fn throttled_messages_do_not_count_as_failures() {
+        assert_eq!(
+            Action::Nak(Some(Duration::from_secs(4))),
+            next_action(status(429), Some(3), 3, backoff())
+        );
+        assert_eq!(
+            Action::Nak(Some(Duration::from_secs(60))),
+            next_action(status(429), Some(50), 3, backoff())
+        );
+    }
+
+    #[test]
+    fn service_unavailable_is_redelivered_immediately() {
+        assert_eq!(
+            Action::Nak(None),
+            next_action(status(503), Some(3), 3, backoff())
+        );
+        assert_eq!(
+            Action::Nak(None),
+            next_action(status(503), None, 3, backoff())
+        );
+    }
+
+    #[test]
+    fn missing_metadata_is_redelivered_without_dead_lettering() {
+        assert_eq!(
+            Action::Nak(None),
+            next_action(status(500), None, 1, backoff())
+        );
+        assert_eq!(
+            Action::Nak(None),
+            next_action(status(400), None, 1, backoff())
+        );
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let backoff = backoff();
+
+        assert_eq!(Duration::from_secs(1), backoff.delay_for_attempt(0));
+        assert_eq!(Duration::from_secs(1), backoff.delay_for_attempt(1));
+        assert_eq!(Duration::from_secs(8), backoff.delay_for_attempt(4));
+        assert_eq!(Duration::from_secs(60), backoff.delay_for_attempt(7));
+        assert_eq!(Duration::from_secs(60), backoff.delay_for_attempt(u64::MAX));
+    }
+}
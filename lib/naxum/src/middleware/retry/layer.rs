@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use tower::Layer;
+
+use super::{
+    dead_letter::DefaultOnDeadLetter, service::Retry, throttled::ThrottledDeliveries, Backoff,
+};
+
+// Default `ack_wait` period when unset is 30 seconds (a NATS server default)
+const DEFAULT_PROGRESS_PERIOD: Duration = Duration::from_secs(20);
+const DEFAULT_MAX_ATTEMPTS: u64 = 5;
+
+pub struct RetryLayer<OnDeadLetter = DefaultOnDeadLetter> {
+    pub(crate) backoff: Backoff,
+    pub(crate) max_attempts: u64,
+    pub(crate) on_dead_letter: OnDeadLetter,
+    pub(crate) progress_period: Duration,
+}
+
+impl Default for RetryLayer {
+    fn default() -> Self {
+        Self {
+            backoff: Backoff::default(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            on_dead_letter: Default::default(),
+            progress_period: DEFAULT_PROGRESS_PERIOD,
+        }
+    }
+}
+
+impl RetryLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<OnDeadLetter> RetryLayer<OnDeadLetter> {
+    pub fn backoff(self, backoff: Backoff) -> Self {
+        Self { backoff, ..self }
+    }
+
+    /// Sets the number of delivery attempts after which a failing message becomes a dead letter.
+    ///
+    /// Deliveries this process throttled (answered with `429 Too Many Requests`) are not counted
+    /// as attempts, but the server counts every delivery towards the consumer's `max_deliver`.
+    /// `max_attempts` must be lower than `max_deliver`, with enough room left for throttled
+    /// deliveries, otherwise the server stops redelivering the message before it can be dead
+    /// lettered and it is dropped.
+    pub fn max_attempts(self, max_attempts: u64) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..self
+        }
+    }
+
+    pub fn on_dead_letter<NewOnDeadLetter>(
+        self,
+        new_on_dead_letter: NewOnDeadLetter,
+    ) -> RetryLayer<NewOnDeadLetter> {
+        let Self {
+            backoff,
+            max_attempts,
+            on_dead_letter: _,
+            progress_period,
+        } = self;
+        RetryLayer {
+            backoff,
+            max_attempts,
+            on_dead_letter: new_on_dead_letter,
+            progress_period,
+        }
+    }
+
+    pub fn progress_period(self, progress_period: Duration) -> Self {
+        Self {
+            progress_period,
+            ..self
+        }
+    }
+}
+
+impl<S, OnDeadLetter> Layer<S> for RetryLayer<OnDeadLetter>
+where
+    OnDeadLetter: Clone,
+{
+    type Service = Retry<S, OnDeadLetter>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Retry {
+            inner,
+            backoff: self.backoff,
+            max_attempts: self.max_attempts,
+            on_dead_letter: self.on_dead_letter.clone(),
+            progress_period: self.progress_period,
+            throttled_deliveries: ThrottledDeliveries::default(),
+        }
+    }
+}
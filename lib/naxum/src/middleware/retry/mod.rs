@@ -0,0 +1,16 @@
+//! Middleware which acks successfully processed JetStream messages and retries failed messages
+//! with an exponential backoff, handing them off as dead letters once retries are exhausted.
+
+mod backoff;
+mod dead_letter;
+mod future;
+mod layer;
+mod service;
+mod throttled;
+
+pub use self::{
+    backoff::Backoff,
+    dead_letter::{DeadLetter, DefaultOnDeadLetter, OnDeadLetter},
+    layer::RetryLayer,
+    service::Retry,
+};
@@ -0,0 +1,117 @@
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_nats::jetstream;
+use tokio_util::sync::CancellationToken;
+use tower::Service;
+
+use crate::{
+    message::{Message, MessageHead},
+    middleware::{ack::maintain_progress::MaintainProgressTask, post_process::Info},
+    response::Response,
+};
+
+use super::{
+    dead_letter::{DefaultOnDeadLetter, OnDeadLetter},
+    future::{Disposition, ResponseFuture},
+    layer::RetryLayer,
+    throttled::ThrottledDeliveries,
+    Backoff,
+};
+
+#[derive(Clone, Debug)]
+pub struct Retry<S, OnDeadLetter = DefaultOnDeadLetter> {
+    pub(crate) inner: S,
+    pub(crate) backoff: Backoff,
+    pub(crate) max_attempts: u64,
+    pub(crate) on_dead_letter: OnDeadLetter,
+    pub(crate) progress_period: Duration,
+    pub(crate) throttled_deliveries: ThrottledDeliveries,
+}
+
+impl<S> Retry<S> {
+    pub fn layer() -> RetryLayer {
+        RetryLayer::new()
+    }
+}
+
+impl<S, OnDeadLetterT> Service<Message<jetstream::Message>> for Retry<S, OnDeadLetterT>
+where
+    S: Service<Message<async_nats::Message>, Response = Response>,
+    S::Error: fmt::Display,
+    OnDeadLetterT: OnDeadLetter + Clone + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = ResponseFuture<S, OnDeadLetterT>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Message<jetstream::Message>) -> Self::Future {
+        // Split into jetstream message & extensions
+        let (jetstream_message, extensions) = req.split();
+
+        // Parse the delivery metadata before the message is decomposed. If this fails the message
+        // can still be processed, but can't participate in backoff or dead lettering.
+        let info = jetstream_message.info().ok().map(Info::from).map(Arc::new);
+
+        // Split off acker from jetstream message which is now a core message
+        let (core_message, acker) = jetstream_message.split();
+        let acker = Arc::new(acker);
+
+        // Decompose the core message into head and payload
+        let mut parts = core_message.into_head_and_payload();
+
+        // Append remaining extensions into head and save copies of head and payload
+        parts.0.extensions.extend(extensions);
+        let head = Arc::new(parts.0.clone());
+        let payload = parts.1.clone();
+
+        // Reconstruct a core message from head and payload
+        let (core_message, extensions) =
+            match <async_nats::Message as MessageHead>::from_head_and_payload(parts.0, parts.1) {
+                Ok(msg_and_exts) => msg_and_exts,
+                Err(err) => unreachable!(
+                    "NATS core message from parts is infallible, this is a bug!; error={:?}",
+                    err
+                ),
+            };
+
+        // Create final message from core message and remaining extensions
+        let message = Message::new_with_extensions(core_message, extensions);
+
+        let task_shutdown = CancellationToken::new();
+
+        let task =
+            MaintainProgressTask::new(acker.clone(), self.progress_period, task_shutdown.clone());
+        tokio::spawn(task.run());
+        // The drop guard will trigger a `cancel` on the token to ensure the task is shutdown even
+        // if the response future has issues
+        let shutdown_guard = task_shutdown.drop_guard();
+
+        let response = self.inner.call(message);
+
+        ResponseFuture {
+            inner: response,
+            disposition: Some(Disposition {
+                acker,
+                head,
+                payload,
+                info,
+                backoff: self.backoff,
+                max_attempts: self.max_attempts,
+                on_dead_letter: self.on_dead_letter.clone(),
+                throttled_deliveries: self.throttled_deliveries.clone(),
+            }),
+            disposing: None,
+            result: None,
+            shutdown_guard: Some(shutdown_guard),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// The most messages whose throttled deliveries are tracked at once. Past this the ledger is
+/// cleared, which only makes retries more conservative until it fills up again.
+const MAX_TRACKED_MESSAGES: usize = 10_000;
+
+/// Counts the deliveries of each message that were throttled (answered with `429 Too Many
+/// Requests`) by this process, keyed by stream sequence.
+///
+/// JetStream counts every delivery in a message's `delivered` metadata, throttled or not, so
+/// these are subtracted from it to find out how many times the message actually failed. Counts
+/// only cover this process: deliveries throttled by another consumer instance still count as
+/// attempts.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ThrottledDeliveries {
+    counts: Arc<Mutex<HashMap<u64, u64>>>,
+}
+
+impl ThrottledDeliveries {
+    /// Returns how many deliveries of the message were throttled.
+    pub(crate) fn count(&self, stream_sequence: u64) -> u64 {
+        self.lock()
+            .get(&stream_sequence)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Records that a delivery of the message was throttled.
+    pub(crate) fn record(&self, stream_sequence: u64) {
+        let mut counts = self.lock();
+        if counts.len() >= MAX_TRACKED_MESSAGES && !counts.contains_key(&stream_sequence) {
+            counts.clear();
+        }
+        *counts.entry(stream_sequence).or_default() += 1;
+    }
+
+    /// Forgets the message once it will not be redelivered.
+    pub(crate) fn forget(&self, stream_sequence: u64) {
+        self.lock().remove(&stream_sequence);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, u64>> {
+        self.counts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_are_per_message_until_forgotten() {
+        let throttled = ThrottledDeliveries::default();
+        throttled.record(1);
+        throttled.record(1);
+        throttled.record(2);
+
+        assert_eq!(2, throttled.count(1));
+        assert_eq!(1, throttled.count(2));
+        assert_eq!(0, throttled.count(3));
+
+        throttled.forget(1);
+        assert_eq!(0, throttled.count(1));
+        assert_eq!(1, throttled.count(2));
+    }
+}
@@ -107,6 +107,18 @@ impl<T> Response<T> {
         }
     }
 
+    pub fn default_not_found() -> Self
+    where
+        T: Default,
+    {
+        Self {
+            head: Parts {
+                status: StatusCode::from_u16(404).expect("status code is in valid range"),
+            },
+            body: T::default(),
+        }
+    }
+
     pub fn default_service_unavailable() -> Self
     where
         T: Default,
@@ -118,6 +130,18 @@ impl<T> Response<T> {
             body: T::default(),
         }
     }
+
+    pub fn default_too_many_requests() -> Self
+    where
+        T: Default,
+    {
+        Self {
+            head: Parts {
+                status: StatusCode::from_u16(429).expect("status code is in valid range"),
+            },
+            body: T::default(),
+        }
+    }
 }
 
 impl<T: Default> Default for Response<T> {
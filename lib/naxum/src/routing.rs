@@ -0,0 +1,614 @@
+//! Routing between [`Service`]s and handlers based on NATS subjects.
+
+use std::{
+    convert::Infallible,
+    fmt,
+    task::{Context, Poll},
+};
+
+use tower::{Layer, Service};
+
+use crate::{
+    extract::{MatchedSubject, RawSubjectTokens},
+    handler::Handler,
+    make_service::IntoMakeService,
+    message::{Message, MessageHead},
+    response::{IntoResponse, Response},
+};
+
+mod endpoint;
+pub mod future;
+mod route;
+mod subject_pattern;
+
+pub use self::route::Route;
+pub use self::subject_pattern::InvalidSubjectPattern;
+
+use self::{endpoint::Endpoint, future::RouteFuture, subject_pattern::SubjectPattern};
+
+/// An error returned when a route cannot be added to a [`Router`], see [`Router::try_route`].
+#[derive(Debug)]
+pub enum RouteError {
+    /// The subject pattern is invalid.
+    InvalidPattern(InvalidSubjectPattern),
+    /// A route with the same subject pattern was already added.
+    Overlapping(String),
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPattern(err) => err.fmt(f),
+            Self::Overlapping(pattern) => {
+                write!(f, "overlapping route for subject pattern `{pattern}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+impl From<InvalidSubjectPattern> for RouteError {
+    fn from(err: InvalidSubjectPattern) -> Self {
+        Self::InvalidPattern(err)
+    }
+}
+
+/// The router type for composing handlers and services by NATS subject.
+///
+/// Routes are declared with subject patterns made up of `.` separated tokens, where a token is
+/// either a literal, `*` (matches any single token), `:name` (matches any single token and
+/// captures it as `name`, see [`SubjectTokens`](crate::extract::SubjectTokens)), or `>` (matches
+/// one or more trailing tokens, only allowed as the last token).
+///
+/// Routes are tried in the order they were added and the first match wins. Messages which match
+/// no route are sent to the [fallback](Router::fallback), which responds with a "not found"
+/// response by default.
+///
+/// ```ignore
+/// let app = Router::new()
+///     .route("rebaser.requests.:workspace_id.:change_set_id", process_request)
+///     .nest("rebaser.admin", admin_router)
+///     .fallback(unknown_subject)
+///     .with_state(state);
+/// ```
+#[must_use]
+pub struct Router<S = (), R = async_nats::Message> {
+    routes: Vec<(SubjectPattern, Endpoint<S, R>)>,
+    fallback: Endpoint<S, R>,
+    default_fallback: bool,
+}
+
+impl<S, R> Clone for Router<S, R> {
+    fn clone(&self) -> Self {
+        Self {
+            routes: self.routes.clone(),
+            fallback: self.fallback.clone(),
+            default_fallback: self.default_fallback,
+        }
+    }
+}
+
+impl<S, R> fmt::Debug for Router<S, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Router")
+            .field(
+                "routes",
+                &self
+                    .routes
+                    .iter()
+                    .map(|(pattern, _)| pattern.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .field("default_fallback", &self.default_fallback)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, R> Default for Router<S, R>
+where
+    S: Clone + Send + Sync + 'static,
+    R: MessageHead + Send + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, R> Router<S, R>
+where
+    S: Clone + Send + Sync + 'static,
+    R: MessageHead + Send + 'static,
+{
+    /// Creates a new `Router` with no routes.
+    pub fn new() -> Self {
+        Self {
+            routes: Vec::new(),
+            fallback: Endpoint::Route(Route::new(tower::service_fn(not_found::<R>))),
+            default_fallback: true,
+        }
+    }
+
+    /// Adds a handler for messages with subjects matching the given pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is invalid or if a route with the same pattern was already added.
+    /// See [`Router::try_route`] for a fallible version.
+    #[track_caller]
+    pub fn route<H, T>(self, pattern: &str, handler: H) -> Self
+    where
+        H: Handler<T, S, R>,
+        T: 'static,
+    {
+        match self.try_route(pattern, handler) {
+            Ok(router) => router,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Adds a handler for messages with subjects matching the given pattern, returning an error
+    /// if the pattern is invalid or if a route with the same pattern was already added.
+    pub fn try_route<H, T>(self, pattern: &str, handler: H) -> Result<Self, RouteError>
+    where
+        H: Handler<T, S, R>,
+        T: 'static,
+    {
+        self.try_push_route(
+            SubjectPattern::parse(pattern)?,
+            Endpoint::from_handler(handler),
+        )
+    }
+
+    /// Adds a [`Service`] for messages with subjects matching the given pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is invalid or if a route with the same pattern was already added.
+    /// See [`Router::try_route_service`] for a fallible version.
+    #[track_caller]
+    pub fn route_service<T>(self, pattern: &str, service: T) -> Self
+    where
+        T: Service<Message<R>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse + 'static,
+        T::Future: Send + 'static,
+    {
+        match self.try_route_service(pattern, service) {
+            Ok(router) => router,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Adds a [`Service`] for messages with subjects matching the given pattern, returning an
+    /// error if the pattern is invalid or if a route with the same pattern was already added.
+    pub fn try_route_service<T>(self, pattern: &str, service: T) -> Result<Self, RouteError>
+    where
+        T: Service<Message<R>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse + 'static,
+        T::Future: Send + 'static,
+    {
+        self.try_push_route(
+            SubjectPattern::parse(pattern)?,
+            Endpoint::Route(Route::new(service)),
+        )
+    }
+
+    /// Nests the routes of another router under a subject prefix.
+    ///
+    /// Each nested route's pattern is prefixed with `prefix`, so a route of `"create"` nested
+    /// under `"pinga.jobs"` matches the subject `"pinga.jobs.create"`. If the nested router has a
+    /// custom fallback, it handles all unmatched subjects under the prefix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix is invalid, ends in a `>` wildcard, or if any resulting route
+    /// pattern was already added.
+    #[track_caller]
+    pub fn nest(mut self, prefix: &str, router: Router<S, R>) -> Self {
+        let prefix = parse_pattern(prefix);
+        if prefix.has_tail() {
+            panic!("nesting prefix `{prefix}` cannot end with a `>` wildcard");
+        }
+
+        let Router {
+            routes,
+            fallback,
+            default_fallback,
+        } = router;
+
+        for (pattern, endpoint) in routes {
+            let pattern = prefix.join(&pattern).unwrap_or_else(|err| panic!("{err}"));
+            self = self.push_route(pattern, endpoint);
+        }
+        if !default_fallback {
+            let pattern = prefix
+                .join(&parse_pattern(">"))
+                .unwrap_or_else(|err| panic!("{err}"));
+            self = self.push_route(pattern, fallback);
+        }
+
+        self
+    }
+
+    /// Adds a handler for messages which don't match any route.
+    pub fn fallback<H, T>(mut self, handler: H) -> Self
+    where
+        H: Handler<T, S, R>,
+        T: 'static,
+    {
+        self.fallback = Endpoint::from_handler(handler);
+        self.default_fallback = false;
+        self
+    }
+
+    /// Adds a [`Service`] for messages which don't match any route.
+    pub fn fallback_service<T>(mut self, service: T) -> Self
+    where
+        T: Service<Message<R>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse + 'static,
+        T::Future: Send + 'static,
+    {
+        self.fallback = Endpoint::Route(Route::new(service));
+        self.default_fallback = false;
+        self
+    }
+
+    /// Applies a [`Layer`] to all routes and the fallback of the router.
+    ///
+    /// Only routes added before calling this method are wrapped.
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route<R>> + Clone + Send + Sync + 'static,
+        L::Service: Service<Message<R>> + Clone + Send + 'static,
+        <L::Service as Service<Message<R>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Message<R>>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Message<R>>>::Future: Send + 'static,
+    {
+        self.fallback = self.fallback.layer(layer.clone());
+        self.route_layer_inner(layer)
+    }
+
+    /// Applies a [`Layer`] to all routes of the router, but not to the fallback.
+    ///
+    /// Only routes added before calling this method are wrapped.
+    pub fn route_layer<L>(self, layer: L) -> Self
+    where
+        L: Layer<Route<R>> + Clone + Send + Sync + 'static,
+        L::Service: Service<Message<R>> + Clone + Send + 'static,
+        <L::Service as Service<Message<R>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Message<R>>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Message<R>>>::Future: Send + 'static,
+    {
+        self.route_layer_inner(layer)
+    }
+
+    /// Provides the state for the router, returning a router which no longer requires it.
+    pub fn with_state<S2>(self, state: S) -> Router<S2, R> {
+        Router {
+            routes: self
+                .routes
+                .into_iter()
+                .map(|(pattern, endpoint)| {
+                    (pattern, Endpoint::Route(endpoint.into_route(state.clone())))
+                })
+                .collect(),
+            fallback: Endpoint::Route(self.fallback.into_route(state)),
+            default_fallback: self.default_fallback,
+        }
+    }
+
+    fn route_layer_inner<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route<R>> + Clone + Send + Sync + 'static,
+        L::Service: Service<Message<R>> + Clone + Send + 'static,
+        <L::Service as Service<Message<R>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Message<R>>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Message<R>>>::Future: Send + 'static,
+    {
+        self.routes = self
+            .routes
+            .into_iter()
+            .map(|(pattern, endpoint)| (pattern, endpoint.layer(layer.clone())))
+            .collect();
+        self
+    }
+
+    #[track_caller]
+    fn push_route(self, pattern: SubjectPattern, endpoint: Endpoint<S, R>) -> Self {
+        match self.try_push_route(pattern, endpoint) {
+            Ok(router) => router,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    fn try_push_route(
+        mut self,
+        pattern: SubjectPattern,
+        endpoint: Endpoint<S, R>,
+    ) -> Result<Self, RouteError> {
+        if self.routes.iter().any(|(existing, _)| existing == &pattern) {
+            return Err(RouteError::Overlapping(pattern.to_string()));
+        }
+        self.routes.push((pattern, endpoint));
+        Ok(self)
+    }
+}
+
+impl<R> Router<(), R>
+where
+    R: MessageHead + Send + 'static,
+{
+    /// Converts this router into a [`MakeService`](tower::MakeService), suitable for
+    /// [`serve`](crate::serve).
+    pub fn into_make_service(self) -> IntoMakeService<Self> {
+        // Resolve all handlers into routes up front rather than once per message
+        IntoMakeService::new(self.with_state(()))
+    }
+}
+
+impl<R> Service<Message<R>> for Router<(), R>
+where
+    R: MessageHead + Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = RouteFuture<R>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut msg: Message<R>) -> Self::Future {
+        let matched = self.routes.iter().find_map(|(pattern, endpoint)| {
+            pattern
+                .matches(msg.subject().as_str())
+                .map(|tokens| (pattern, endpoint, tokens))
+        });
+
+        let mut route = match matched {
+            Some((pattern, endpoint, tokens)) => {
+                msg.extensions_mut()
+                    .insert(MatchedSubject::from(pattern.as_str()));
+                msg.extensions_mut().insert(RawSubjectTokens::new(tokens));
+                endpoint.clone().into_route(())
+            }
+            None => self.fallback.clone().into_route(()),
+        };
+
+        route.call(msg)
+    }
+}
+
+async fn not_found<R>(_msg: Message<R>) -> Result<Response, Infallible> {
+    Ok(Response::default_not_found())
+}
+
+#[track_caller]
+fn parse_pattern(pattern: &str) -> SubjectPattern {
+    SubjectPattern::parse(pattern).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use tower::ServiceExt;
+
+    use crate::{
+        extract::SubjectTokens,
+        middleware::limit::{KeyLimit, KeyedLimitLayer, KeyedLimits, SubjectTokenKey},
+        StatusCode,
+    };
+
+    use super::*;
+
+    async fn call(router: &Router, subject: &str) -> Response {
+        router
+            .clone()
+            .oneshot(Message::for_test(subject, None, Bytes::new()))
+            .await
+            .expect("router is infallible")
+    }
+
+    fn body(response: &Response) -> &str {
+        std::str::from_utf8(response.body().as_bytes()).expect("utf-8 body")
+    }
+
+    #[test]
+    fn try_route_rejects_invalid_patterns() {
+        let result = Router::<(), async_nats::Message>::new().try_route("a.>.b", || async {});
+
+        assert!(matches!(result, Err(RouteError::InvalidPattern(_))));
+    }
+
+    #[test]
+    fn try_route_rejects_duplicate_patterns() {
+        let result = Router::<(), async_nats::Message>::new()
+            .route("a.:id", || async {})
+            .try_route("a.:id", || async {});
+
+        assert!(matches!(result, Err(RouteError::Overlapping(pattern)) if pattern == "a.:id"));
+    }
+
+    #[test]
+    #[should_panic(expected = "overlapping route")]
+    fn route_panics_on_duplicate_patterns() {
+        let _ = Router::<(), async_nats::Message>::new()
+            .route("a.b", || async {})
+            .route("a.b", || async {});
+    }
+
+    #[tokio::test]
+    async fn routes_by_subject_in_order() {
+        let router = Router::new()
+            .route(
+                "jobs.:kind.:id",
+                |SubjectTokens((kind, id)): SubjectTokens<(String, u32)>| async move {
+                    format!("{kind}/{id}")
+                },
+            )
+            .route("jobs.>", |matched: MatchedSubject| async move {
+                matched.as_str().to_owned()
+            })
+            .with_state(());
+
+        assert_eq!("create/7", body(&call(&router, "jobs.create.7").await));
+        assert_eq!("jobs.>", body(&call(&router, "jobs.create.7.extra").await));
+
+        let response = call(&router, "other.subject").await;
+        assert_eq!(404, response.status().as_u16());
+    }
+
+    #[tokio::test]
+    async fn invalid_tokens_are_rejected() {
+        let router = Router::new()
+            .route(
+                "jobs.:id",
+                |SubjectTokens(id): SubjectTokens<u32>| async move { id.to_string() },
+            )
+            .with_state(());
+
+        assert_eq!("7", body(&call(&router, "jobs.7").await));
+        assert!(call(&router, "jobs.seven").await.status().is_client_error());
+    }
+
+    #[tokio::test]
+    async fn nested_routes_and_fallbacks() {
+        let admin = Router::new()
+            .route(
+                "reset.:id",
+                |SubjectTokens(id): SubjectTokens<String>| async move { format!("reset {id}") },
+            )
+            .fallback(|| async { "admin fallback" });
+        let router = Router::new()
+            .nest("svc.admin", admin)
+            .fallback(|| async { (StatusCode::from_u16(400).expect("valid"), "fallback") })
+            .with_state(());
+
+        assert_eq!("reset 1", body(&call(&router, "svc.admin.reset.1").await));
+        assert_eq!(
+            "admin fallback",
+            body(&call(&router, "svc.admin.other").await)
+        );
+
+        let response = call(&router, "svc.other").await;
+        assert_eq!(400, response.status().as_u16());
+        assert_eq!("fallback", body(&response));
+    }
+
+    #[tokio::test]
+    async fn keyed_limits_use_captured_tokens() {
+        let limits = KeyedLimits::new(KeyLimit::unlimited().max_concurrency(1).max_queued(0));
+        let (started_tx, mut started_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+        let release_rx = std::sync::Arc::new(std::sync::Mutex::new(Some(release_rx)));
+
+        let router = Router::new()
+            .route("jobs.:workspace.:id", move || {
+                let started_tx = started_tx.clone();
+                let release_rx = release_rx.clone();
+                async move {
+                    // Only the first message blocks, until it is released
+                    let release_rx = release_rx.lock().expect("lock is not poisoned").take();
+                    if let Some(release_rx) = release_rx {
+                        let _ = started_tx.send(());
+                        let _ = release_rx.await;
+                    }
+                }
+            })
+            .layer(KeyedLimitLayer::new(
+                SubjectTokenKey::named("workspace"),
+                limits.clone(),
+            ))
+            .with_state(());
+
+        // Hold the only permit for workspace `a`
+        let held = tokio::spawn(router.clone().oneshot(Message::for_test(
+            "jobs.a.1",
+            None,
+            Bytes::new(),
+        )));
+        started_rx.recv().await.expect("first message started");
+
+        assert_eq!(429, call(&router, "jobs.a.2").await.status().as_u16());
+        assert!(call(&router, "jobs.b.1").await.status().is_success());
+
+        release_tx.send(()).expect("handler is waiting");
+        assert!(held
+            .await
+            .expect("task should not panic")
+            .expect("router is infallible")
+            .status()
+            .is_success());
+        assert!(call(&router, "jobs.a.3").await.status().is_success());
+    }
+
+    #[tokio::test]
+    async fn saturated_key_does_not_block_other_keys_under_a_global_limit() {
+        // Like the server's concurrency limit, every message holds a global slot until it is
+        // processed, including while it waits on its key's limits
+        const GLOBAL_LIMIT: usize = 3;
+        let limits = KeyedLimits::new(KeyLimit::unlimited().max_concurrency(1).max_queued(1));
+        let gate = std::sync::Arc::new(tokio::sync::Semaphore::new(0));
+
+        let router = Router::new()
+            .route("jobs.:workspace.:id", {
+                let gate = gate.clone();
+                move |SubjectTokens((workspace, _)): SubjectTokens<(String, u32)>| {
+                    let gate = gate.clone();
+                    async move {
+                        // Messages for workspace `a` block until the gate is opened
+                        if workspace == "a" {
+                            let _permit = gate.acquire().await;
+                        }
+                    }
+                }
+            })
+            .layer(KeyedLimitLayer::new(
+                SubjectTokenKey::named("workspace"),
+                limits.clone(),
+            ))
+            .with_state(());
+        let service = tower::limit::ConcurrencyLimit::new(router, GLOBAL_LIMIT);
+
+        // Flood workspace `a` with more messages than there are global slots
+        let flood: Vec<_> = (0..GLOBAL_LIMIT * 2)
+            .map(|id| {
+                tokio::spawn(service.clone().oneshot(Message::for_test(
+                    &format!("jobs.a.{id}"),
+                    None,
+                    Bytes::new(),
+                )))
+            })
+            .collect();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(1, limits.queued("a"));
+
+        let response = tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            service
+                .clone()
+                .oneshot(Message::for_test("jobs.b.1", None, Bytes::new())),
+        )
+        .await
+        .expect("other key should not be blocked")
+        .expect("router is infallible");
+        assert!(response.status().is_success());
+
+        gate.add_permits(GLOBAL_LIMIT * 2);
+        let mut rejected = 0;
+        for handle in flood {
+            let response = handle
+                .await
+                .expect("task should not panic")
+                .expect("router is infallible");
+            if response.status().as_u16() == 429 {
+                rejected += 1;
+            } else {
+                assert!(response.status().is_success());
+            }
+        }
+        // One message was processed and one queued, the rest were rejected
+        assert_eq!(GLOBAL_LIMIT * 2 - 2, rejected);
+    }
+}
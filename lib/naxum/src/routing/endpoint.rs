@@ -0,0 +1,132 @@
+use std::{convert::Infallible, sync::Arc};
+
+use tower::{Layer, Service};
+
+use crate::{
+    handler::Handler,
+    message::{Message, MessageHead},
+    response::IntoResponse,
+};
+
+use super::Route;
+
+/// A routing target which may still be waiting on its state.
+pub(crate) enum Endpoint<S, R> {
+    Route(Route<R>),
+    Handler(BoxedIntoRoute<S, R>),
+}
+
+impl<S, R> Endpoint<S, R>
+where
+    S: Clone + Send + 'static,
+    R: MessageHead + Send + 'static,
+{
+    pub(crate) fn from_handler<H, T>(handler: H) -> Self
+    where
+        H: Handler<T, S, R>,
+        T: 'static,
+        S: Sync,
+    {
+        Self::Handler(BoxedIntoRoute(Box::new(MakeErasedHandler {
+            handler,
+            into_route: |handler, state| {
+                Route::new(<H as Handler<T, S, R>>::with_state(handler, state))
+            },
+        })))
+    }
+
+    pub(crate) fn layer<L>(self, layer: L) -> Self
+    where
+        L: Layer<Route<R>> + Clone + Send + Sync + 'static,
+        L::Service: Service<Message<R>> + Clone + Send + 'static,
+        <L::Service as Service<Message<R>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Message<R>>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Message<R>>>::Future: Send + 'static,
+    {
+        match self {
+            Self::Route(route) => Self::Route(route.layer(layer)),
+            Self::Handler(handler) => Self::Handler(BoxedIntoRoute(Box::new(Layered {
+                inner: handler,
+                layer: Arc::new(move |route: Route<R>| route.layer(layer.clone())),
+            }))),
+        }
+    }
+
+    pub(crate) fn into_route(self, state: S) -> Route<R> {
+        match self {
+            Self::Route(route) => route,
+            Self::Handler(handler) => handler.0.into_route(state),
+        }
+    }
+}
+
+impl<S, R> Clone for Endpoint<S, R> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Route(route) => Self::Route(route.clone()),
+            Self::Handler(handler) => Self::Handler(handler.clone()),
+        }
+    }
+}
+
+/// A handler which has been type-erased and can be turned into a [`Route`] once given its state.
+pub(crate) struct BoxedIntoRoute<S, R>(Box<dyn ErasedIntoRoute<S, R>>);
+
+impl<S, R> Clone for BoxedIntoRoute<S, R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_box())
+    }
+}
+
+trait ErasedIntoRoute<S, R>: Send {
+    fn clone_box(&self) -> Box<dyn ErasedIntoRoute<S, R>>;
+
+    fn into_route(self: Box<Self>, state: S) -> Route<R>;
+}
+
+struct MakeErasedHandler<H, S, R> {
+    handler: H,
+    into_route: fn(H, S) -> Route<R>,
+}
+
+impl<H, S, R> ErasedIntoRoute<S, R> for MakeErasedHandler<H, S, R>
+where
+    H: Clone + Send + 'static,
+    S: 'static,
+    R: 'static,
+{
+    fn clone_box(&self) -> Box<dyn ErasedIntoRoute<S, R>> {
+        Box::new(Self {
+            handler: self.handler.clone(),
+            into_route: self.into_route,
+        })
+    }
+
+    fn into_route(self: Box<Self>, state: S) -> Route<R> {
+        (self.into_route)(self.handler, state)
+    }
+}
+
+type LayerFn<R> = Arc<dyn Fn(Route<R>) -> Route<R> + Send + Sync>;
+
+struct Layered<S, R> {
+    inner: BoxedIntoRoute<S, R>,
+    layer: LayerFn<R>,
+}
+
+impl<S, R> ErasedIntoRoute<S, R> for Layered<S, R>
+where
+    S: 'static,
+    R: 'static,
+{
+    fn clone_box(&self) -> Box<dyn ErasedIntoRoute<S, R>> {
+        Box::new(Self {
+            inner: self.inner.clone(),
+            layer: self.layer.clone(),
+        })
+    }
+
+    fn into_route(self: Box<Self>, state: S) -> Route<R> {
+        (self.layer)(self.inner.0.into_route(state))
+    }
+}
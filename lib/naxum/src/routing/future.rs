@@ -0,0 +1,45 @@
+//! Router future types.
+
+use std::{
+    convert::Infallible,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+use tower::util::{BoxCloneService, Oneshot};
+
+use crate::{message::Message, response::Response};
+
+pin_project! {
+    /// Response future for [`Route`](super::Route) and [`Router`](super::Router).
+    pub struct RouteFuture<R> {
+        #[pin]
+        inner: Oneshot<BoxCloneService<Message<R>, Response, Infallible>, Message<R>>,
+    }
+}
+
+impl<R> RouteFuture<R> {
+    pub(super) fn new(
+        inner: Oneshot<BoxCloneService<Message<R>, Response, Infallible>, Message<R>>,
+    ) -> Self {
+        Self { inner }
+    }
+}
+
+impl<R> fmt::Debug for RouteFuture<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouteFuture").finish_non_exhaustive()
+    }
+}
+
+impl<R> Future for RouteFuture<R> {
+    type Output = Result<Response, Infallible>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    convert::Infallible,
+    fmt,
+    task::{Context, Poll},
+};
+
+use tower::{util::BoxCloneService, Layer, Service, ServiceExt};
+
+use crate::{
+    message::{Message, MessageHead},
+    response::{IntoResponse, Response},
+};
+
+use super::future::RouteFuture;
+
+/// A type-erased service which a [`Router`](super::Router) dispatches matched messages to.
+pub struct Route<R>(BoxCloneService<Message<R>, Response, Infallible>);
+
+impl<R> Route<R>
+where
+    R: MessageHead + Send + 'static,
+{
+    pub(crate) fn new<T>(svc: T) -> Self
+    where
+        T: Service<Message<R>, Error = Infallible> + Clone + Send + 'static,
+        T::Response: IntoResponse + 'static,
+        T::Future: Send + 'static,
+    {
+        Self(BoxCloneService::new(
+            svc.map_response(IntoResponse::into_response),
+        ))
+    }
+
+    pub(crate) fn layer<L>(self, layer: L) -> Self
+    where
+        L: Layer<Self>,
+        L::Service: Service<Message<R>> + Clone + Send + 'static,
+        <L::Service as Service<Message<R>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Message<R>>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Message<R>>>::Future: Send + 'static,
+    {
+        Self::new(layer.layer(self).map_err(Into::into))
+    }
+}
+
+impl<R> Clone for Route<R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<R> fmt::Debug for Route<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Route").finish_non_exhaustive()
+    }
+}
+
+impl<R> Service<Message<R>> for Route<R>
+where
+    R: MessageHead + Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = RouteFuture<R>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Each call drives a clone of the inner service to readiness in a `Oneshot`
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn call(&mut self, msg: Message<R>) -> Self::Future {
+        RouteFuture::new(self.0.clone().oneshot(msg))
+    }
+}
@@ -0,0 +1,249 @@
+use std::{fmt, sync::Arc};
+
+const TOKEN_SEPARATOR: char = '.';
+const SINGLE_WILDCARD: &str = "*";
+const TAIL_WILDCARD: &str = ">";
+const NAMED_PREFIX: char = ':';
+
+/// A NATS subject pattern that a route is matched against.
+///
+/// Patterns are made up of `.` separated tokens, each of which is one of:
+///
+/// - a literal token, which only matches itself
+/// - `*`, which matches any single token
+/// - `:name`, which matches any single token and captures it under `name`
+/// - `>`, which matches one or more trailing tokens and must be the last token
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct SubjectPattern {
+    pattern: Arc<str>,
+    tokens: Vec<PatternToken>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum PatternToken {
+    Literal(Box<str>),
+    Named(Arc<str>),
+    Tail,
+    Wildcard,
+}
+
+impl SubjectPattern {
+    pub(crate) fn parse(pattern: &str) -> Result<Self, InvalidSubjectPattern> {
+        if pattern.is_empty() {
+            return Err(InvalidSubjectPattern::new(pattern, "pattern is empty"));
+        }
+
+        let mut tokens = Vec::new();
+        let mut parts = pattern.split(TOKEN_SEPARATOR).peekable();
+        while let Some(part) = parts.next() {
+            let token = match part {
+                "" => {
+                    return Err(InvalidSubjectPattern::new(
+                        pattern,
+                        "pattern has empty token",
+                    ))
+                }
+                SINGLE_WILDCARD => PatternToken::Wildcard,
+                TAIL_WILDCARD => {
+                    if parts.peek().is_some() {
+                        return Err(InvalidSubjectPattern::new(
+                            pattern,
+                            "`>` is only allowed as the last token",
+                        ));
+                    }
+                    PatternToken::Tail
+                }
+                named if named.starts_with(NAMED_PREFIX) => {
+                    let name = &named[NAMED_PREFIX.len_utf8()..];
+                    if name.is_empty() {
+                        return Err(InvalidSubjectPattern::new(pattern, "token name is empty"));
+                    }
+                    if tokens
+                        .iter()
+                        .any(|token| matches!(token, PatternToken::Named(existing) if &**existing == name))
+                    {
+                        return Err(InvalidSubjectPattern::new(
+                            pattern,
+                            "token name is used more than once",
+                        ));
+                    }
+                    PatternToken::Named(name.into())
+                }
+                literal => PatternToken::Literal(literal.into()),
+            };
+            tokens.push(token);
+        }
+
+        Ok(Self {
+            pattern: pattern.into(),
+            tokens,
+        })
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns a new pattern with `self` as a prefix of `other`.
+    pub(crate) fn join(&self, other: &Self) -> Result<Self, InvalidSubjectPattern> {
+        Self::parse(&format!(
+            "{}{TOKEN_SEPARATOR}{}",
+            self.pattern, other.pattern
+        ))
+    }
+
+    pub(crate) fn has_tail(&self) -> bool {
+        matches!(self.tokens.last(), Some(PatternToken::Tail))
+    }
+
+    /// Matches a subject against the pattern, returning the named tokens on a match.
+    pub(crate) fn matches(&self, subject: &str) -> Option<Vec<(Arc<str>, Arc<str>)>> {
+        let mut named = Vec::new();
+        let mut parts = subject.split(TOKEN_SEPARATOR);
+
+        for token in &self.tokens {
+            match token {
+                // A tail wildcard needs at least one more token to match
+                PatternToken::Tail => return parts.next().map(|_| named),
+                PatternToken::Wildcard => {
+                    parts.next()?;
+                }
+                PatternToken::Named(name) => {
+                    let part = parts.next()?;
+                    named.push((name.clone(), part.into()));
+                }
+                PatternToken::Literal(literal) => {
+                    if parts.next()? != &**literal {
+                        return None;
+                    }
+                }
+            }
+        }
+
+        match parts.next() {
+            Some(_) => None,
+            None => Some(named),
+        }
+    }
+}
+
+impl fmt::Display for SubjectPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+/// An error returned when a subject pattern cannot be parsed.
+#[derive(Debug)]
+pub struct InvalidSubjectPattern {
+    pattern: String,
+    reason: &'static str,
+}
+
+impl InvalidSubjectPattern {
+    fn new(pattern: &str, reason: &'static str) -> Self {
+        Self {
+            pattern: pattern.to_owned(),
+            reason,
+        }
+    }
+}
+
+impl std::error::Error for InvalidSubjectPattern {}
+
+impl fmt::Display for InvalidSubjectPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid subject pattern `{}`: {}",
+            self.pattern, self.reason
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(tokens: Vec<(Arc<str>, Arc<str>)>) -> Vec<(String, String)> {
+        tokens
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parse_rejects_invalid_patterns() {
+        for pattern in ["", "a..b", ".a", "a.", "a.>.b", ">.a", "a.:", "a.:id.:id"] {
+            assert!(
+                SubjectPattern::parse(pattern).is_err(),
+                "expected `{pattern}` to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn literal_matches_only_itself() {
+        let pattern = SubjectPattern::parse("a.b").expect("valid pattern");
+
+        assert_eq!(Some(vec![]), pattern.matches("a.b"));
+        assert_eq!(None, pattern.matches("a"));
+        assert_eq!(None, pattern.matches("a.c"));
+        assert_eq!(None, pattern.matches("a.b.c"));
+    }
+
+    #[test]
+    fn single_wildcard_matches_exactly_one_token() {
+        let pattern = SubjectPattern::parse("a.*.c").expect("valid pattern");
+
+        assert_eq!(Some(vec![]), pattern.matches("a.b.c"));
+        assert_eq!(None, pattern.matches("a.c"));
+        assert_eq!(None, pattern.matches("a.b.b.c"));
+        assert!(!pattern.has_tail());
+    }
+
+    #[test]
+    fn tail_wildcard_matches_one_or_more_tokens() {
+        let pattern = SubjectPattern::parse("a.>").expect("valid pattern");
+
+        assert!(pattern.has_tail());
+        assert_eq!(None, pattern.matches("a"));
+        assert_eq!(Some(vec![]), pattern.matches("a.b"));
+        assert_eq!(Some(vec![]), pattern.matches("a.b.c.d"));
+        assert_eq!(None, pattern.matches("b.c"));
+    }
+
+    #[test]
+    fn named_tokens_are_captured_in_order() {
+        let pattern = SubjectPattern::parse("jobs.:workspace.*.:change_set.>").expect("valid");
+
+        let captured = pattern
+            .matches("jobs.w1.x.cs1.more.tokens")
+            .expect("subject should match");
+
+        assert_eq!(
+            vec![
+                ("workspace".to_owned(), "w1".to_owned()),
+                ("change_set".to_owned(), "cs1".to_owned()),
+            ],
+            named(captured)
+        );
+        assert_eq!(None, pattern.matches("jobs.w1.x.cs1"));
+    }
+
+    #[test]
+    fn join_prefixes_and_revalidates() {
+        let prefix = SubjectPattern::parse("svc.:id").expect("valid");
+        let suffix = SubjectPattern::parse("events.>").expect("valid");
+
+        let joined = prefix.join(&suffix).expect("valid join");
+        assert_eq!("svc.:id.events.>", joined.as_str());
+        assert!(joined.matches("svc.1.events.created").is_some());
+
+        let tail = SubjectPattern::parse("svc.>").expect("valid");
+        assert!(tail.join(&suffix).is_err());
+
+        let duplicate = SubjectPattern::parse(":id").expect("valid");
+        assert!(prefix.join(&duplicate).is_err());
+    }
+}
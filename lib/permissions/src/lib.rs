@@ -1,13 +1,16 @@
+use serde::Deserialize;
 use si_data_spicedb::{
     Relationship, Relationships, SpiceDBObject, SpiceDbClient, SpiceDbError, ZedToken,
 };
-use si_events::{UserPk, WorkspacePk};
+use si_events::{ChangeSetId, SchemaId, UserPk, ViewId, WorkspacePk};
 use std::result;
 use thiserror::Error;
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum Error {
+    #[error("{0} cannot be granted to restricted members")]
+    NotGrantable(Permission),
     #[error("Builder must contain object, permission, and subject.")]
     PermissionBuilder,
     #[error(
@@ -15,6 +18,8 @@ pub enum Error {
         required_fields
     )]
     RelationBuilder { required_fields: Vec<String> },
+    #[error("{0} cannot be granted on a change set, view or schema")]
+    ScopeNotSupported(Permission),
     #[error("spicedb client error: {0}")]
     SpiceDb(#[from] SpiceDbError),
 }
@@ -24,22 +29,273 @@ type Result<T> = result::Result<T, Error>;
 #[derive(Clone, Copy, strum::Display, Debug)]
 #[strum(serialize_all = "snake_case")]
 pub enum ObjectType {
+    ChangeSet,
+    Schema,
     User,
+    View,
     Workspace,
 }
 
-#[derive(Clone, Copy, strum::Display)]
+/// Permissions are checked on workspaces. [`Permission::Edit`] can also be checked on change
+/// sets, views and schemas, see [`Scope`].
+#[derive(Clone, Copy, strum::Display, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 #[strum(serialize_all = "snake_case")]
 pub enum Permission {
     Approve,
+    AuthorFuncs,
+    Edit,
     Manage,
+    ReadSecrets,
+    /// Whether the user is a restricted member of the workspace, see [`is_allowed`].
+    Restricted,
+    RunActions,
+    /// Whether the user is a restricted member of the workspace who can only create components
+    /// of the schemas they are granted [`Permission::Edit`] on, see [`can_use_schema`].
+    SchemaRestricted,
+}
+
+impl Permission {
+    /// The workspace relation that grants the permission to restricted members.
+    fn relation(&self) -> Option<Relation> {
+        match self {
+            Self::AuthorFuncs => Some(Relation::FuncAuthor),
+            Self::Edit => Some(Relation::Editor),
+            Self::ReadSecrets => Some(Relation::SecretReader),
+            Self::RunActions => Some(Relation::ActionRunner),
+            Self::Approve | Self::Manage | Self::Restricted | Self::SchemaRestricted => None,
+        }
+    }
 }
 
 #[derive(Clone, Copy, strum::Display, Debug)]
 #[strum(serialize_all = "snake_case")]
 pub enum Relation {
+    ActionRunner,
     Approver,
+    Editor,
+    FuncAuthor,
     Owner,
+    RestrictedMember,
+    SchemaRestrictedMember,
+    SecretReader,
+    /// Links a change set, view or schema to the workspace it belongs to.
+    Workspace,
+}
+
+/// An object within a workspace that [`Permission::Edit`] can be granted on.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", content = "id", rename_all = "camelCase")]
+pub enum Scope {
+    ChangeSet(ChangeSetId),
+    Schema(SchemaId),
+    View(ViewId),
+}
+
+impl Scope {
+    fn object(&self) -> SpiceDBObject {
+        match self {
+            Self::ChangeSet(id) => SpiceDBObject::new(ObjectType::ChangeSet, id),
+            Self::Schema(id) => SpiceDBObject::new(ObjectType::Schema, id),
+            Self::View(id) => SpiceDBObject::new(ObjectType::View, id),
+        }
+    }
+}
+
+/// Checks whether a user can do something that requires the given [`Permission`] in a
+/// workspace.
+///
+/// Users who aren't restricted members of the workspace can do everything but approve and
+/// manage, which are checked with [`PermissionBuilder`] directly. Restricted members need to be
+/// granted the permission on the workspace or, for [`Permission::Edit`], on any of the given
+/// scopes. Everything is checked in a single request.
+///
+/// Grants only ever add up: a grant on a broader scope is not narrowed by a more specific one.
+/// For example, a member granted [`Permission::Edit`] on a change set can edit every view in it,
+/// whether or not they were granted anything on those views. Restrict a member to some views by
+/// granting them the views alone.
+pub async fn is_allowed(
+    client: &mut SpiceDbClient,
+    workspace_pk: WorkspacePk,
+    user_pk: UserPk,
+    permission: Permission,
+    scopes: &[Scope],
+) -> Result<bool> {
+    let mut checks = vec![
+        PermissionBuilder::new()
+            .workspace_object(workspace_pk)
+            .permission(Permission::Restricted)
+            .user_subject(user_pk),
+        PermissionBuilder::new()
+            .workspace_object(workspace_pk)
+            .permission(permission)
+            .user_subject(user_pk),
+    ];
+    if permission == Permission::Edit {
+        checks.extend(scopes.iter().map(|scope| {
+            PermissionBuilder::new()
+                .scope_object(*scope)
+                .permission(permission)
+                .user_subject(user_pk)
+        }));
+    }
+
+    match PermissionBuilder::has_permissions(&checks, client)
+        .await?
+        .split_first()
+    {
+        Some((is_restricted, granted)) => Ok(!is_restricted || granted.contains(&true)),
+        None => Ok(false),
+    }
+}
+
+/// Checks whether a user can create components of a schema.
+///
+/// Limiting restricted members to some schemas is opt-in: only those who are also
+/// [`Relation::SchemaRestrictedMember`]s of the workspace need to be granted
+/// [`Permission::Edit`] on the schema (or the workspace).
+pub async fn can_use_schema(
+    client: &mut SpiceDbClient,
+    workspace_pk: WorkspacePk,
+    user_pk: UserPk,
+    schema_id: SchemaId,
+) -> Result<bool> {
+    let checks = [
+        PermissionBuilder::new()
+            .workspace_object(workspace_pk)
+            .permission(Permission::SchemaRestricted)
+            .user_subject(user_pk),
+        PermissionBuilder::new()
+            .scope_object(Scope::Schema(schema_id))
+            .permission(Permission::Edit)
+            .user_subject(user_pk),
+        PermissionBuilder::new()
+            .workspace_object(workspace_pk)
+            .permission(Permission::Edit)
+            .user_subject(user_pk),
+    ];
+
+    match PermissionBuilder::has_permissions(&checks, client).await?[..] {
+        [is_schema_restricted, schema_allowed, workspace_allowed] => {
+            Ok(!is_schema_restricted || schema_allowed || workspace_allowed)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Makes the user a restricted member of the workspace, who can only do what they are granted
+/// with [`grant`]. With `limit_to_schemas`, they can also only create components of the schemas
+/// they are granted [`Permission::Edit`] on.
+pub async fn restrict(
+    client: &mut SpiceDbClient,
+    workspace_pk: WorkspacePk,
+    user_pk: UserPk,
+    limit_to_schemas: bool,
+) -> Result<Option<ZedToken>> {
+    let mut relationships = vec![RelationBuilder::new()
+        .workspace_object(workspace_pk)
+        .relation(Relation::RestrictedMember)
+        .user_subject(user_pk)
+        .check()?];
+    let schema_restriction = RelationBuilder::new()
+        .workspace_object(workspace_pk)
+        .relation(Relation::SchemaRestrictedMember)
+        .user_subject(user_pk)
+        .check()?;
+
+    if limit_to_schemas {
+        relationships.push(schema_restriction);
+    } else {
+        client
+            .delete_relationships(vec![schema_restriction])
+            .await?;
+    }
+
+    Ok(client.touch_relationships(relationships).await?)
+}
+
+/// Lifts every restriction on the user in the workspace. What they were granted is kept, so
+/// that restricting them again restores it.
+pub async fn unrestrict(
+    client: &mut SpiceDbClient,
+    workspace_pk: WorkspacePk,
+    user_pk: UserPk,
+) -> Result<Option<ZedToken>> {
+    let relationships = [Relation::RestrictedMember, Relation::SchemaRestrictedMember]
+        .into_iter()
+        .map(|relation| {
+            RelationBuilder::new()
+                .workspace_object(workspace_pk)
+                .relation(relation)
+                .user_subject(user_pk)
+                .check()
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(client.delete_relationships(relationships).await?)
+}
+
+/// Grants a [`Permission`] to a restricted member of the workspace, on the whole workspace or,
+/// for [`Permission::Edit`], on a single [`Scope`]. Granting on a scope also records which
+/// workspace the scope belongs to, so that grants on the workspace apply to it.
+pub async fn grant(
+    client: &mut SpiceDbClient,
+    workspace_pk: WorkspacePk,
+    user_pk: UserPk,
+    permission: Permission,
+    scope: Option<Scope>,
+) -> Result<Option<ZedToken>> {
+    let relationships = grant_relationships(workspace_pk, user_pk, permission, scope)?;
+
+    Ok(client.touch_relationships(relationships).await?)
+}
+
+/// Revokes what [`grant`] granted. The scope stays linked to its workspace.
+pub async fn revoke(
+    client: &mut SpiceDbClient,
+    workspace_pk: WorkspacePk,
+    user_pk: UserPk,
+    permission: Permission,
+    scope: Option<Scope>,
+) -> Result<Option<ZedToken>> {
+    let mut relationships = grant_relationships(workspace_pk, user_pk, permission, scope)?;
+    relationships.truncate(1);
+
+    Ok(client.delete_relationships(relationships).await?)
+}
+
+/// The relationship granting the permission, followed by the one linking the scope to its
+/// workspace, if any.
+fn grant_relationships(
+    workspace_pk: WorkspacePk,
+    user_pk: UserPk,
+    permission: Permission,
+    scope: Option<Scope>,
+) -> Result<Relationships> {
+    let relation = permission
+        .relation()
+        .ok_or(Error::NotGrantable(permission))?;
+
+    match scope {
+        None => Ok(vec![RelationBuilder::new()
+            .workspace_object(workspace_pk)
+            .relation(relation)
+            .user_subject(user_pk)
+            .check()?]),
+        Some(scope) if permission == Permission::Edit => Ok(vec![
+            RelationBuilder::new()
+                .scope_object(scope)
+                .relation(relation)
+                .user_subject(user_pk)
+                .check()?,
+            RelationBuilder::new()
+                .scope_object(scope)
+                .relation(Relation::Workspace)
+                .subject(ObjectType::Workspace, workspace_pk)
+                .check()?,
+        ]),
+        Some(_) => Err(Error::ScopeNotSupported(permission)),
+    }
 }
 
 /// RelationBuilder allows defining a relationship in SpiceDb.
@@ -82,6 +338,11 @@ impl RelationBuilder {
         self.object(ObjectType::Workspace, id)
     }
 
+    pub fn scope_object(mut self, scope: Scope) -> Self {
+        self.object = Some(scope.object());
+        self
+    }
+
     pub fn relation(mut self, relation: Relation) -> Self {
         self.relation = Some(relation);
         self
@@ -101,6 +362,17 @@ impl RelationBuilder {
         self
     }
 
+    /// Creates a new relationship in SpiceDb, or leaves it be if it already exists
+    pub async fn touch(&self, client: &mut SpiceDbClient) -> Result<Option<ZedToken>> {
+        match self.check() {
+            Ok(relationship) => client
+                .touch_relationships(vec![relationship])
+                .await
+                .map_err(Error::SpiceDb),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Creates a new relationship in SpiceDb
     pub async fn create(&self, client: &mut SpiceDbClient) -> Result<Option<ZedToken>> {
         match self.check() {
@@ -206,6 +478,11 @@ impl PermissionBuilder {
         self.object(ObjectType::Workspace, id)
     }
 
+    pub fn scope_object(mut self, scope: Scope) -> Self {
+        self.object = Some(scope.object());
+        self
+    }
+
     pub fn permission(mut self, permission: Permission) -> Self {
         self.permission = Some(permission);
         self
@@ -236,6 +513,23 @@ impl PermissionBuilder {
         }
     }
 
+    /// Checks several permissions in a single request, returning whether each is granted, in
+    /// order
+    pub async fn has_permissions(
+        builders: &[PermissionBuilder],
+        client: &mut SpiceDbClient,
+    ) -> Result<Vec<bool>> {
+        let perms = builders
+            .iter()
+            .map(|builder| builder.check_has_permission())
+            .collect::<Result<_>>()?;
+
+        client
+            .check_bulk_permissions(perms)
+            .await
+            .map_err(Error::SpiceDb)
+    }
+
     fn check_has_permission(&self) -> Result<si_data_spicedb::Permission> {
         match (self.object.clone(), self.permission, self.subject.clone()) {
             (Some(object), Some(permission), Some(subject)) => {
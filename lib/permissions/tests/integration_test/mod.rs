@@ -1,10 +1,11 @@
 use std::env;
 
 use indoc::indoc;
-use permissions::{ObjectType, Permission, PermissionBuilder, Relation, RelationBuilder};
+use permissions::{ObjectType, Permission, PermissionBuilder, Relation, RelationBuilder, Scope};
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
 use si_data_spicedb::{Client, SpiceDbClient, SpiceDbConfig};
+use si_events::{SchemaId, UserPk, ViewId, WorkspacePk};
 
 const ENV_VAR_SPICEDB_URL: &str = "SI_TEST_SPICEDB_URL";
 
@@ -30,7 +31,28 @@ async fn write_schema(mut client: SpiceDbClient) {
 
         definition workspace {
             relation approver: user
+            relation owner: user
+            relation restricted_member: user
+            relation schema_restricted_member: user
+            relation editor: user
+            relation action_runner: user
             permission approve = approver
+            permission restricted = restricted_member - owner
+            permission schema_restricted = schema_restricted_member - owner
+            permission edit = editor+owner
+            permission run_actions = action_runner+owner
+        }
+
+        definition view {
+            relation workspace: workspace
+            relation editor: user
+            permission edit = editor+workspace->edit
+        }
+
+        definition schema {
+            relation workspace: workspace
+            relation editor: user
+            permission edit = editor+workspace->edit
         }
     "};
 
@@ -87,3 +109,275 @@ async fn add_remove_approver_from_workspace() {
         .await
         .expect("could not check permission"));
 }
+
+#[tokio::test]
+async fn restricted_member_is_limited_to_granted_scopes() {
+    let config = spicedb_config();
+
+    let mut client = Client::new(&config)
+        .await
+        .expect("failed to connect to spicedb");
+
+    write_schema(client.clone()).await;
+
+    let workspace_pk = WorkspacePk::generate();
+    let member = UserPk::generate();
+    let contractor = UserPk::generate();
+    let granted_view = ViewId::generate();
+    let other_view = ViewId::generate();
+
+    RelationBuilder::new()
+        .workspace_object(workspace_pk)
+        .relation(Relation::RestrictedMember)
+        .user_subject(contractor)
+        .create(&mut client)
+        .await
+        .expect("could not create relationship");
+    RelationBuilder::new()
+        .scope_object(Scope::View(granted_view))
+        .relation(Relation::Editor)
+        .user_subject(contractor)
+        .create(&mut client)
+        .await
+        .expect("could not create relationship");
+
+    // Members who aren't restricted keep full access.
+    assert!(permissions::is_allowed(
+        &mut client,
+        workspace_pk,
+        member,
+        Permission::RunActions,
+        &[],
+    )
+    .await
+    .expect("could not check permission"));
+
+    assert!(permissions::is_allowed(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::Edit,
+        &[Scope::View(granted_view)],
+    )
+    .await
+    .expect("could not check permission"));
+    assert!(!permissions::is_allowed(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::Edit,
+        &[Scope::View(other_view)],
+    )
+    .await
+    .expect("could not check permission"));
+    assert!(!permissions::is_allowed(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::RunActions,
+        &[],
+    )
+    .await
+    .expect("could not check permission"));
+
+    RelationBuilder::new()
+        .workspace_object(workspace_pk)
+        .relation(Relation::ActionRunner)
+        .user_subject(contractor)
+        .create(&mut client)
+        .await
+        .expect("could not create relationship");
+    assert!(permissions::is_allowed(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::RunActions,
+        &[],
+    )
+    .await
+    .expect("could not check permission"));
+}
+
+#[tokio::test]
+async fn grant_and_revoke_scoped_permissions() {
+    let config = spicedb_config();
+
+    let mut client = Client::new(&config)
+        .await
+        .expect("failed to connect to spicedb");
+
+    write_schema(client.clone()).await;
+
+    let workspace_pk = WorkspacePk::generate();
+    let contractor = UserPk::generate();
+    let view = ViewId::generate();
+
+    permissions::restrict(&mut client, workspace_pk, contractor, false)
+        .await
+        .expect("could not restrict user");
+    assert!(!permissions::is_allowed(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::Edit,
+        &[Scope::View(view)],
+    )
+    .await
+    .expect("could not check permission"));
+
+    // Granting twice is fine.
+    for _ in 0..2 {
+        permissions::grant(
+            &mut client,
+            workspace_pk,
+            contractor,
+            Permission::Edit,
+            Some(Scope::View(view)),
+        )
+        .await
+        .expect("could not grant permission");
+    }
+    assert!(permissions::is_allowed(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::Edit,
+        &[Scope::View(view)],
+    )
+    .await
+    .expect("could not check permission"));
+
+    // The view is now linked to the workspace, so a grant on the workspace covers it too.
+    permissions::revoke(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::Edit,
+        Some(Scope::View(view)),
+    )
+    .await
+    .expect("could not revoke permission");
+    assert!(!permissions::is_allowed(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::Edit,
+        &[Scope::View(view)],
+    )
+    .await
+    .expect("could not check permission"));
+    permissions::grant(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::Edit,
+        None,
+    )
+    .await
+    .expect("could not grant permission");
+    assert!(PermissionBuilder::new()
+        .scope_object(Scope::View(view))
+        .permission(Permission::Edit)
+        .user_subject(contractor)
+        .has_permission(&mut client)
+        .await
+        .expect("could not check permission"));
+
+    assert!(matches!(
+        permissions::grant(
+            &mut client,
+            workspace_pk,
+            contractor,
+            Permission::RunActions,
+            Some(Scope::View(view)),
+        )
+        .await,
+        Err(permissions::Error::ScopeNotSupported(
+            Permission::RunActions
+        ))
+    ));
+    assert!(matches!(
+        permissions::grant(
+            &mut client,
+            workspace_pk,
+            contractor,
+            Permission::Manage,
+            None
+        )
+        .await,
+        Err(permissions::Error::NotGrantable(Permission::Manage))
+    ));
+
+    permissions::unrestrict(&mut client, workspace_pk, contractor)
+        .await
+        .expect("could not unrestrict user");
+    assert!(permissions::is_allowed(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::RunActions,
+        &[],
+    )
+    .await
+    .expect("could not check permission"));
+}
+
+#[tokio::test]
+async fn schema_restrictions_are_opt_in() {
+    let config = spicedb_config();
+
+    let mut client = Client::new(&config)
+        .await
+        .expect("failed to connect to spicedb");
+
+    write_schema(client.clone()).await;
+
+    let workspace_pk = WorkspacePk::generate();
+    let contractor = UserPk::generate();
+    let view = ViewId::generate();
+    let granted_schema = SchemaId::generate();
+    let other_schema = SchemaId::generate();
+
+    permissions::restrict(&mut client, workspace_pk, contractor, false)
+        .await
+        .expect("could not restrict user");
+    permissions::grant(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::Edit,
+        Some(Scope::View(view)),
+    )
+    .await
+    .expect("could not grant permission");
+
+    // A view-scoped editor can use any schema unless limited to some.
+    assert!(
+        permissions::can_use_schema(&mut client, workspace_pk, contractor, other_schema)
+            .await
+            .expect("could not check permission")
+    );
+
+    permissions::restrict(&mut client, workspace_pk, contractor, true)
+        .await
+        .expect("could not restrict user");
+    permissions::grant(
+        &mut client,
+        workspace_pk,
+        contractor,
+        Permission::Edit,
+        Some(Scope::Schema(granted_schema)),
+    )
+    .await
+    .expect("could not grant permission");
+    assert!(
+        permissions::can_use_schema(&mut client, workspace_pk, contractor, granted_schema)
+            .await
+            .expect("could not check permission")
+    );
+    assert!(
+        !permissions::can_use_schema(&mut client, workspace_pk, contractor, other_schema)
+            .await
+            .expect("could not check permission")
+    );
+}
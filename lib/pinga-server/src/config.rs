@@ -55,6 +55,9 @@ pub struct Config {
     #[builder(default = "default_concurrency_limit()")]
     concurrency_limit: usize,
 
+    #[builder(default)]
+    workspace_concurrency_limit: Option<usize>,
+
     #[builder(default = "random_instance_id()")]
     instance_id: String,
 
@@ -102,6 +105,11 @@ impl Config {
         self.concurrency_limit
     }
 
+    /// Gets the config's per-workspace concurrency limit, if any.
+    pub fn workspace_concurrency_limit(&self) -> Option<usize> {
+        self.workspace_concurrency_limit
+    }
+
     /// Gets the config's instance ID.
     pub fn instance_id(&self) -> &str {
         self.instance_id.as_ref()
@@ -123,6 +131,8 @@ pub struct ConfigFile {
     crypto: VeritechCryptoConfig,
     #[serde(default = "default_concurrency_limit")]
     concurrency_limit: usize,
+    #[serde(default)]
+    workspace_concurrency_limit: Option<usize>,
     #[serde(default = "random_instance_id")]
     instance_id: String,
     #[serde(default = "default_layer_db_config")]
@@ -137,6 +147,7 @@ impl Default for ConfigFile {
             pg: Default::default(),
             nats: Default::default(),
             concurrency_limit: default_concurrency_limit(),
+            workspace_concurrency_limit: None,
             crypto: Default::default(),
             instance_id: random_instance_id(),
             layer_db_config: default_layer_db_config(),
@@ -160,6 +171,7 @@ impl TryFrom<ConfigFile> for Config {
         config.nats(value.nats);
         config.crypto(value.crypto);
         config.concurrency_limit(value.concurrency_limit);
+        config.workspace_concurrency_limit(value.workspace_concurrency_limit);
         config.instance_id(value.instance_id);
         config.symmetric_crypto_service(value.symmetric_crypto_service.try_into()?);
         config.layer_db_config(value.layer_db_config);
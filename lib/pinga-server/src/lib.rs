@@ -1,6 +1,7 @@
 mod app_state;
 mod config;
 mod handlers;
+mod refresh_scheduler;
 pub mod server;
 
 use std::io;
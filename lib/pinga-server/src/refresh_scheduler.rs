@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use dal::{action::refresh_schedule::RefreshSchedule, DalContextBuilder};
+use telemetry::prelude::*;
+use tokio::time::{self, MissedTickBehavior};
+use tokio_util::sync::CancellationToken;
+
+/// Periodically enqueues the `Refresh` actions of due
+/// [`RefreshSchedules`](dal::action::refresh_schedule::RefreshSchedule). The actions are then
+/// dispatched back to pinga as regular action jobs.
+pub(crate) struct RefreshScheduler {
+    ctx_builder: DalContextBuilder,
+    tick: Duration,
+    shutdown_token: CancellationToken,
+}
+
+impl RefreshScheduler {
+    pub(crate) fn new(
+        ctx_builder: DalContextBuilder,
+        tick: Duration,
+        shutdown_token: CancellationToken,
+    ) -> Self {
+        Self {
+            ctx_builder,
+            tick,
+            shutdown_token,
+        }
+    }
+
+    pub(crate) async fn run(self) {
+        let mut interval = time::interval(self.tick);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown_token.cancelled() => {
+                    debug!("refresh scheduler shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    match RefreshSchedule::run_due(&self.ctx_builder).await {
+                        Ok(0) => {}
+                        Ok(count) => debug!(count, "ran due refresh schedules"),
+                        Err(err) => {
+                            error!(si.error.message = ?err, "unable to run due refresh schedules");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::{
     future::{Future, IntoFuture as _},
     io,
     sync::Arc,
+    time::Duration,
 };
 
 use dal::{
@@ -14,6 +15,7 @@ use naxum::{
     handler::Handler as _,
     middleware::{
         ack::AckLayer,
+        limit::{KeyLimit, KeyedLimitLayer, KeyedLimits, SubjectTokenKey},
         matched_subject::{ForSubject, MatchedSubjectLayer},
         trace::TraceLayer,
     },
@@ -34,10 +36,17 @@ use telemetry_utils::metric;
 use tokio_util::{sync::CancellationToken, task::TaskTracker};
 use veritech_client::Client as VeritechClient;
 
-use crate::{app_state::AppState, handlers, Config, ServerError, ServerResult};
+use crate::{
+    app_state::AppState, handlers, refresh_scheduler::RefreshScheduler, Config, ServerError,
+    ServerResult,
+};
 
 const CONSUMER_NAME: &str = "pinga-server";
 
+/// How often pinga checks for due refresh schedules. This is the finest granularity a schedule
+/// can have.
+const REFRESH_SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
 /// Server metadata, used with telemetry.
 #[derive(Clone, Debug)]
 pub struct ServerMetadata {
@@ -60,13 +69,16 @@ impl ServerMetadata {
 pub struct Server {
     metadata: Arc<ServerMetadata>,
     inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send>,
+    keyed_limits: KeyedLimits,
     shutdown_token: CancellationToken,
+    server_tracker: TaskTracker,
 }
 
 impl fmt::Debug for Server {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Server")
             .field("metadata", &self.metadata)
+            .field("keyed_limits", &self.keyed_limits)
             .field("shutdown_token", &self.shutdown_token)
             .finish()
     }
@@ -117,13 +129,25 @@ impl Server {
             compute_executor,
         );
 
-        Self::from_services(
+        let server = Self::from_services(
             config.instance_id().to_string(),
             config.concurrency_limit(),
             services_context,
             token,
         )
-        .await
+        .await?;
+
+        if let Some(limit) = config.workspace_concurrency_limit() {
+            // Messages waiting on a workspace's limit hold one of the server's concurrency
+            // slots, so the queue is bounded to keep a busy workspace from taking up every slot
+            server.keyed_limits().set_default_limit(
+                KeyLimit::unlimited()
+                    .max_concurrency(limit)
+                    .max_queued(limit),
+            );
+        }
+
+        Ok(server)
     }
 
     #[instrument(name = "pinga.init.from_services", level = "info", skip_all)]
@@ -158,8 +182,21 @@ impl Server {
 
         let ctx_builder = DalContext::builder(services_context, false);
 
+        let server_tracker = TaskTracker::new();
+        server_tracker.spawn(
+            RefreshScheduler::new(
+                ctx_builder.clone(),
+                REFRESH_SCHEDULER_TICK,
+                shutdown_token.clone(),
+            )
+            .run(),
+        );
+
         let state = AppState::new(metadata.clone(), concurrency_limit, ctx_builder);
 
+        // Limits are unset by default and can be changed at runtime via `keyed_limits()`
+        let keyed_limits = KeyedLimits::default();
+
         let app = ServiceBuilder::new()
             .layer(
                 MatchedSubjectLayer::new()
@@ -173,6 +210,10 @@ impl Server {
                     .on_response(telemetry_nats::NatsOnResponse::new()),
             )
             .layer(AckLayer::new())
+            .layer(KeyedLimitLayer::new(
+                SubjectTokenKey::named("workspace_id"),
+                keyed_limits.clone(),
+            ))
             .service(handlers::process_request.with_state(state))
             .map_response(Response::into_response);
 
@@ -184,10 +225,17 @@ impl Server {
         Ok(Self {
             metadata,
             inner: Box::new(inner.into_future()),
+            keyed_limits,
             shutdown_token,
+            server_tracker,
         })
     }
 
+    /// Returns a handle to the per-workspace limits, which can be changed while running.
+    pub fn keyed_limits(&self) -> &KeyedLimits {
+        &self.keyed_limits
+    }
+
     #[inline]
     pub async fn run(self) {
         if let Err(err) = self.try_run().await {
@@ -197,6 +245,9 @@ impl Server {
 
     pub async fn try_run(self) -> ServerResult<()> {
         self.inner.await.map_err(ServerError::Naxum)?;
+        info!("pinga inner loop exited, now shutting down the server tracker's tasks");
+        self.server_tracker.close();
+        self.server_tracker.wait().await;
         info!("pinga main loop shutdown complete");
         Ok(())
     }
@@ -21,6 +21,7 @@ use si_data_nats::{
 };
 use si_events::{
     rebase_batch_address::RebaseBatchAddress, ChangeSetId, EventSessionId, WorkspacePk,
+    WorkspaceSnapshotAddress,
 };
 use telemetry::prelude::*;
 use telemetry_nats::propagation;
@@ -109,6 +110,7 @@ impl Client {
             updates_address,
             None,
             None,
+            None,
             event_session_id,
         )
         .await
@@ -139,6 +141,42 @@ impl Client {
             updates_address,
             Some(from_change_set_id),
             None,
+            None,
+            event_session_id,
+        )
+        .await
+    }
+
+    /// Asynchronously enqueues graph updates that were produced by another Change Set's snapshot &
+    /// return a [`RequestId`].
+    ///
+    /// The Rebaser records `from_snapshot_address` as the merge base of the Change Set once the
+    /// updates have been replayed onto it.
+    #[instrument(
+        name = "rebaser_client.replay_updates_from_change_set",
+        level = "info",
+        skip_all,
+        fields(
+            si.change_set.id = %change_set_id,
+            si.workspace.id = %workspace_id,
+        ),
+    )]
+    pub async fn replay_updates_from_change_set(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+        updates_address: RebaseBatchAddress,
+        from_change_set_id: ChangeSetId,
+        from_snapshot_address: WorkspaceSnapshotAddress,
+        event_session_id: EventSessionId,
+    ) -> Result<RequestId> {
+        self.call_async(
+            workspace_id,
+            change_set_id,
+            updates_address,
+            Some(from_change_set_id),
+            Some(from_snapshot_address),
+            None,
             event_session_id,
         )
         .await
@@ -213,6 +251,7 @@ impl Client {
         change_set_id: ChangeSetId,
         updates_address: RebaseBatchAddress,
         from_change_set_id: Option<ChangeSetId>,
+        from_snapshot_address: Option<WorkspaceSnapshotAddress>,
         maybe_reply_inbox: Option<&Subject>,
         event_session_id: EventSessionId,
     ) -> Result<RequestId> {
@@ -230,6 +269,7 @@ impl Client {
             change_set_id,
             updates_address,
             from_change_set_id,
+            from_snapshot_address,
             event_session_id: Some(event_session_id),
         });
 
@@ -306,6 +346,7 @@ impl Client {
                 change_set_id,
                 updates_address,
                 from_change_set_id,
+                None,
                 Some(&reply_inbox),
                 event_session_id,
             )
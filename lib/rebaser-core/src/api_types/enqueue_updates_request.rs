@@ -9,17 +9,19 @@ use strum::{AsRefStr, EnumDiscriminants, EnumIs, EnumString, VariantNames};
 
 mod v1;
 mod v2;
+mod v3;
 
 pub use self::v1::EnqueueUpdatesRequestV1;
 pub use self::v2::EnqueueUpdatesRequestV2;
+pub use self::v3::EnqueueUpdatesRequestV3;
 
-pub type EnqueueUpdatesRequestVCurrent = EnqueueUpdatesRequestV2;
+pub type EnqueueUpdatesRequestVCurrent = EnqueueUpdatesRequestV3;
 
 #[derive(Clone, Eq, Serialize, PartialEq, VariantNames)]
 #[serde(rename_all = "camelCase")]
 #[strum(serialize_all = "camelCase")]
 pub enum EnqueueUpdatesRequest {
-    V2(EnqueueUpdatesRequestV2),
+    V3(EnqueueUpdatesRequestV3),
 }
 
 impl ApiWrapper for EnqueueUpdatesRequest {
@@ -30,19 +32,19 @@ impl ApiWrapper for EnqueueUpdatesRequest {
 
     fn id(&self) -> RequestId {
         match self {
-            Self::V2(EnqueueUpdatesRequestVCurrent { id, .. }) => *id,
+            Self::V3(EnqueueUpdatesRequestVCurrent { id, .. }) => *id,
         }
     }
 
     fn new_current(current: Self::Current) -> Self {
-        Self::V2(current)
+        Self::V3(current)
     }
 }
 
 impl fmt::Debug for EnqueueUpdatesRequest {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::V2(inner) => inner.fmt(f),
+            Self::V3(inner) => inner.fmt(f),
         }
     }
 }
@@ -52,7 +54,7 @@ impl Deref for EnqueueUpdatesRequest {
 
     fn deref(&self) -> &Self::Target {
         match self {
-            Self::V2(inner) => inner,
+            Self::V3(inner) => inner,
         }
     }
 }
@@ -60,7 +62,7 @@ impl Deref for EnqueueUpdatesRequest {
 impl DerefMut for EnqueueUpdatesRequest {
     fn deref_mut(&mut self) -> &mut Self::Target {
         match self {
-            Self::V2(inner) => inner,
+            Self::V3(inner) => inner,
         }
     }
 }
@@ -73,6 +75,7 @@ impl DerefMut for EnqueueUpdatesRequest {
 pub enum EnqueueUpdatesRequestVersions {
     V1(EnqueueUpdatesRequestV1),
     V2(EnqueueUpdatesRequestV2),
+    V3(EnqueueUpdatesRequestV3),
 }
 
 impl ApiVersionsWrapper for EnqueueUpdatesRequestVersions {
@@ -82,20 +85,31 @@ impl ApiVersionsWrapper for EnqueueUpdatesRequestVersions {
         match self {
             Self::V1(EnqueueUpdatesRequestV1 { id, .. }) => *id,
             Self::V2(EnqueueUpdatesRequestV2 { id, .. }) => *id,
+            Self::V3(EnqueueUpdatesRequestV3 { id, .. }) => *id,
         }
     }
 
     fn into_current_version(self) -> Result<Self::Target, UpgradeError> {
         match self {
-            Self::V1(inner) => Ok(Self::Target::V2(EnqueueUpdatesRequestVCurrent {
+            Self::V1(inner) => Ok(Self::Target::V3(EnqueueUpdatesRequestVCurrent {
                 id: inner.id,
                 workspace_id: inner.workspace_id,
                 change_set_id: inner.change_set_id,
                 updates_address: inner.updates_address,
                 from_change_set_id: inner.from_change_set_id,
+                from_snapshot_address: None,
                 event_session_id: None,
             })),
-            Self::V2(inner) => Ok(Self::Target::V2(inner)),
+            Self::V2(inner) => Ok(Self::Target::V3(EnqueueUpdatesRequestVCurrent {
+                id: inner.id,
+                workspace_id: inner.workspace_id,
+                change_set_id: inner.change_set_id,
+                updates_address: inner.updates_address,
+                from_change_set_id: inner.from_change_set_id,
+                from_snapshot_address: None,
+                event_session_id: inner.event_session_id,
+            })),
+            Self::V3(inner) => Ok(Self::Target::V3(inner)),
         }
     }
 }
@@ -0,0 +1,19 @@
+use naxum_api_types::RequestId;
+use serde::{Deserialize, Serialize};
+use si_events::{
+    rebase_batch_address::RebaseBatchAddress, ChangeSetId, EventSessionId, WorkspacePk,
+    WorkspaceSnapshotAddress,
+};
+
+#[derive(Clone, Debug, Deserialize, Eq, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EnqueueUpdatesRequestV3 {
+    pub id: RequestId,
+    pub workspace_id: WorkspacePk,
+    pub change_set_id: ChangeSetId,
+    pub updates_address: RebaseBatchAddress,
+    pub from_change_set_id: Option<ChangeSetId>,
+    /// The snapshot of the "from" change set that produced the updates, when they are replayed.
+    pub from_snapshot_address: Option<WorkspaceSnapshotAddress>,
+    pub event_session_id: Option<EventSessionId>,
+}
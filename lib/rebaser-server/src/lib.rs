@@ -63,6 +63,9 @@ pub enum ServerError {
     /// When a database pool error occurs
     #[error("dal pg pool error: {0}")]
     DalPgPool(#[source] Box<si_data_pg::PgPoolError>),
+    /// When failing to create the dead letter queue stream
+    #[error("dead letter queue error: {0}")]
+    DeadLetterQueue(#[from] nats_dead_letter_queue::NatsDeadLetterQueueError),
     /// When failing to create or fetch a Jetstream consumer
     #[error("jetstream consumer error: {0}")]
     JsConsumer(#[from] si_data_nats::async_nats::jetstream::stream::ConsumerError),
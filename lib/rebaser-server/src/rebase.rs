@@ -125,6 +125,24 @@ pub async fn perform_rebase(
 
         ctx.set_workspace_snapshot(to_rebase_workspace_snapshot);
     }
+    // A batch replayed from HEAD brings the change set up to date with the HEAD snapshot that
+    // produced it, which becomes the merge base. Anything HEAD did since is still on its way.
+    // This holds even when the change set already had every update, so nothing was applied.
+    if !updating_head {
+        if let (Some(from_change_set_id), Some(from_snapshot_address)) =
+            (request.from_change_set_id, request.from_snapshot_address)
+        {
+            if from_change_set_id == workspace.default_change_set_id().into()
+                && to_rebase_change_set.base_change_set_id
+                    == Some(workspace.default_change_set_id())
+            {
+                to_rebase_change_set
+                    .update_merge_base(ctx, from_snapshot_address)
+                    .await?;
+            }
+        }
+    }
+
     let updates_count = rebase_batch.updates().len();
     span.record("si.updates.count", updates_count.to_string());
 
@@ -157,6 +175,7 @@ pub async fn perform_rebase(
         }) {
             let workspace_pk = *workspace.pk();
             let updates_address = request.updates_address;
+            let head_snapshot_address = to_rebase_change_set.workspace_snapshot_address;
             {
                 let ctx_clone = ctx.clone();
                 server_tracker.spawn(async move {
@@ -171,6 +190,7 @@ pub async fn perform_rebase(
                         target_change_set.id,
                         updates_address,
                         to_rebase_change_set.id,
+                        head_snapshot_address,
                     )
                     .await
                     {
@@ -241,12 +261,14 @@ async fn replay_changes(
     change_set_id: ChangeSetId,
     updates_address: RebaseBatchAddress,
     from_change_set_id: ChangeSetId,
+    from_snapshot_address: WorkspaceSnapshotAddress,
 ) -> RebaseResult<()> {
-    ctx.run_async_rebase_from_change_set(
+    ctx.run_async_replay_from_change_set(
         workspace_pk,
         change_set_id,
         updates_address,
         from_change_set_id,
+        from_snapshot_address,
     )
     .await?;
 
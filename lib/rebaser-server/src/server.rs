@@ -10,12 +10,13 @@ use dal::{
     feature_flags::FeatureFlagService, DalContext, DalLayerDb, DedicatedExecutor, JetstreamStreams,
     JobQueueProcessor, NatsProcessor, ServicesContext,
 };
+use nats_dead_letter_queue::PublishOnDeadLetter;
 use naxum::{
     extract::MatchedSubject,
     handler::Handler as _,
     middleware::{
-        ack::AckLayer,
         matched_subject::{ForSubject, MatchedSubjectLayer},
+        retry::RetryLayer,
         trace::{OnRequest, TraceLayer},
     },
     response::{IntoResponse, Response},
@@ -158,6 +159,9 @@ impl Server {
 
         let requests_stream = nats::rebaser_requests_jetstream_stream(&context).await?;
 
+        // Tasks which fail past their retries are republished to the dead letter queue
+        nats_dead_letter_queue::create_stream(&context).await?;
+
         let ctx_builder = DalContext::builder(services_context, false);
 
         let server_tracker = TaskTracker::new();
@@ -184,7 +188,7 @@ impl Server {
                     .on_request(RebaserOnRequest)
                     .on_response(telemetry_nats::NatsOnResponse::new()),
             )
-            .layer(AckLayer::new())
+            .layer(RetryLayer::new().on_dead_letter(PublishOnDeadLetter::new(context.clone())))
             .service(handlers::default.with_state(state))
             .map_response(Response::into_response);
 
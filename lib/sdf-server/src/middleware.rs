@@ -1,3 +1,5 @@
+mod scoped_permission;
 mod workspace_permission;
 
+pub use self::scoped_permission::{ScopedPermission, ScopedPermissionLayer};
 pub use self::workspace_permission::{WorkspacePermission, WorkspacePermissionLayer};
@@ -0,0 +1,171 @@
+use std::{
+    collections::HashMap,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, Path, Query},
+    http::{header, Request, StatusCode},
+    response::{IntoResponse, Response},
+};
+use futures::future::BoxFuture;
+use permissions::{Permission, Scope};
+use tower::{Layer, Service};
+
+use crate::{
+    extract::{self, Authorization},
+    AppState,
+};
+
+/// The v1 routes carry the change set in their query string or JSON body, rather than the path.
+const VISIBILITY_CHANGE_SET_PARAM: &str = "visibility_change_set_pk";
+
+/// Matches the default body limit of axum, which the v1 routes' JSON bodies are held to.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Checks a fine-grained [`Permission`] for restricted members of the workspace (see
+/// [`permissions::is_allowed`]), scoped to the view and change set of the request, if any.
+///
+/// A grant on any of those scopes is enough, so handlers behind a view scope must still make sure
+/// they only touch that view.
+#[derive(Clone)]
+pub struct ScopedPermissionLayer {
+    state: AppState,
+    permission: Permission,
+}
+
+impl ScopedPermissionLayer {
+    pub fn new(state: AppState, permission: Permission) -> Self {
+        Self { state, permission }
+    }
+}
+
+impl<S> Layer<S> for ScopedPermissionLayer {
+    type Service = ScopedPermission<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ScopedPermission {
+            inner,
+            state: self.state.clone(),
+            permission: self.permission,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ScopedPermission<S> {
+    inner: S,
+    state: AppState,
+    permission: Permission,
+}
+
+impl<S> Service<Request<Body>> for ScopedPermission<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let mut me = self.clone();
+
+        Box::pin(async move {
+            let (mut parts, mut body) = req.into_parts();
+
+            let Authorization(claim) =
+                match Authorization::from_request_parts(&mut parts, &me.state).await {
+                    Ok(claim) => claim,
+                    Err(err) => return Ok(err.into_response()),
+                };
+
+            // The most specific scope comes first.
+            let mut scopes = Vec::new();
+            if let Ok(Path(params)) =
+                Path::<HashMap<String, String>>::from_request_parts(&mut parts, &me.state).await
+            {
+                // Named views, such as "default", are only scoped to the change set.
+                if let Some(Ok(view_id)) = params
+                    .get("view_id")
+                    .or_else(|| params.get("viewId"))
+                    .map(|id| id.parse())
+                {
+                    scopes.push(Scope::View(view_id));
+                }
+                if let Some(Ok(change_set_id)) = params.get("change_set_id").map(|id| id.parse()) {
+                    scopes.push(Scope::ChangeSet(change_set_id));
+                }
+            }
+
+            if scopes.is_empty() {
+                if let Ok(Query(params)) =
+                    Query::<HashMap<String, String>>::from_request_parts(&mut parts, &me.state)
+                        .await
+                {
+                    if let Some(Ok(change_set_id)) =
+                        params.get(VISIBILITY_CHANGE_SET_PARAM).map(|id| id.parse())
+                    {
+                        scopes.push(Scope::ChangeSet(change_set_id));
+                    }
+                }
+            }
+
+            // Only small JSON bodies are buffered; anything else is checked against the workspace.
+            let is_small_json = parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with("application/json"))
+                && parts
+                    .headers
+                    .get(header::CONTENT_LENGTH)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse::<usize>().ok())
+                    .is_some_and(|length| length <= MAX_BODY_BYTES);
+            if scopes.is_empty() && is_small_json {
+                let bytes = match hyper::body::to_bytes(body).await {
+                    Ok(bytes) => bytes,
+                    Err(err) => {
+                        return Ok((StatusCode::BAD_REQUEST, err.to_string()).into_response())
+                    }
+                };
+                if let Some(Ok(change_set_id)) = serde_json::from_slice::<serde_json::Value>(&bytes)
+                    .ok()
+                    .as_ref()
+                    .and_then(|value| value.get(VISIBILITY_CHANGE_SET_PARAM))
+                    .and_then(|id| id.as_str())
+                    .map(|id| id.parse())
+                {
+                    scopes.push(Scope::ChangeSet(change_set_id));
+                }
+                body = Body::from(bytes);
+            }
+
+            if let Some(client) = me.state.spicedb_client() {
+                match permissions::is_allowed(
+                    client,
+                    claim.workspace_pk.into(),
+                    claim.user_pk.into(),
+                    me.permission,
+                    &scopes,
+                )
+                .await
+                {
+                    Ok(true) => {}
+                    Ok(false) | Err(_) => return Ok(extract::unauthorized_error().into_response()),
+                }
+            }
+
+            let req = Request::from_parts(parts, body);
+
+            let response = me.inner.call(req).await?;
+            Ok(response)
+        })
+    }
+}
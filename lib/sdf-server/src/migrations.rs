@@ -4,8 +4,9 @@ use audit_logs::database::{
     AuditDatabaseContext, AuditDatabaseContextError, AuditDatabaseMigrationError,
 };
 use dal::{
-    cached_module::CachedModuleError, slow_rt::SlowRuntimeError,
-    workspace_snapshot::migrator::SnapshotGraphMigrator, ServicesContext,
+    builtins::func::install_missing_intrinsics, cached_module::CachedModuleError,
+    slow_rt::SlowRuntimeError, workspace_snapshot::migrator::SnapshotGraphMigrator, BuiltinsError,
+    ServicesContext,
 };
 use telemetry::prelude::*;
 use thiserror::Error;
@@ -27,6 +28,8 @@ pub enum MigratorError {
     MigrateAuditDatabase(#[source] AuditDatabaseMigrationError),
     #[error("error while migrating dal database: {0}")]
     MigrateDalDatabase(#[source] dal::ModelError),
+    #[error("error while installing missing intrinsics: {0}")]
+    MigrateIntrinsics(#[source] BuiltinsError),
     #[error("error while migrating layer db database: {0}")]
     MigrateLayerDbDatabase(#[source] si_layer_cache::LayerDbError),
     #[error("error while migrating snapshots: {0}")]
@@ -116,6 +119,10 @@ impl Migrator {
             .await
             .map_err(|err| span.record_err(err))?;
 
+        self.migrate_intrinsics()
+            .await
+            .map_err(|err| span.record_err(err))?;
+
         span.record_ok();
         Ok(())
     }
@@ -165,4 +172,13 @@ impl Migrator {
             .map_err(MigratorError::migrate_snapshots)?;
         Ok(())
     }
+
+    #[instrument(name = "sdf.migrator.migrate_intrinsics", level = "info", skip_all)]
+    async fn migrate_intrinsics(&self) -> MigratorResult<()> {
+        // The rebaser applies the installs to each HEAD, there's no need to wait for it
+        let dal_context = self.services_context.clone().into_builder(false);
+        install_missing_intrinsics(&dal_context)
+            .await
+            .map_err(MigratorError::MigrateIntrinsics)
+    }
 }
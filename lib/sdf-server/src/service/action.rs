@@ -3,6 +3,7 @@ use axum::response::{IntoResponse, Response};
 use axum::routing::post;
 use axum::{routing::get, Router};
 use dal::{FuncError as DalFuncError, WsEventError};
+use permissions::Permission;
 use si_layer_cache::LayerDbError;
 use thiserror::Error;
 
@@ -13,7 +14,7 @@ use dal::{
 use dal::{ComponentError, ComponentId, StandardModelError, TransactionsError, UserError, UserPk};
 
 use super::ApiError;
-use crate::AppState;
+use crate::{middleware::ScopedPermissionLayer, AppState};
 
 mod cancel;
 mod history;
@@ -75,11 +76,15 @@ impl IntoResponse for ActionError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
+pub fn routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route("/list", get(list_actions::list_actions))
-        .route("/put_on_hold", post(put_on_hold::put_on_hold))
-        .route("/cancel", post(cancel::cancel))
-        .route("/retry", post(retry::retry))
         .route("/history", get(history::history))
+        .merge(
+            Router::new()
+                .route("/put_on_hold", post(put_on_hold::put_on_hold))
+                .route("/cancel", post(cancel::cancel))
+                .route("/retry", post(retry::retry))
+                .route_layer(ScopedPermissionLayer::new(state, Permission::RunActions)),
+        )
 }
@@ -12,10 +12,11 @@ use dal::{
     WorkspaceSnapshotError, WsEventError,
 };
 
+use permissions::Permission;
 use telemetry::prelude::*;
 use thiserror::Error;
 
-use crate::AppState;
+use crate::{middleware::ScopedPermissionLayer, AppState};
 
 use super::ApiError;
 
@@ -56,6 +57,8 @@ pub enum ChangeSetError {
     Func(#[from] FuncError),
     #[error("invalid header name {0}")]
     Hyper(#[from] hyper::http::Error),
+    #[error("merge preview error: {0}")]
+    MergePreview(#[from] dal::change_set::merge_preview::MergePreviewError),
     #[error("schema error: {0}")]
     Schema(#[from] SchemaError),
     #[error("schema variant error: {0}")]
@@ -64,6 +67,8 @@ pub enum ChangeSetError {
     StandardModel(#[from] StandardModelError),
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
+    #[error("applying change set {0} would overwrite {1} concurrent change(s) to its base")]
+    UnresolvedOverwrites(ChangeSetId, usize),
     #[error("workspace error: {0}")]
     Workspace(#[from] WorkspaceError),
     #[error("workspace snapshot error: {0}")]
@@ -81,7 +86,12 @@ impl IntoResponse for ChangeSetError {
                 (StatusCode::NOT_MODIFIED, self.to_string())
             }
             ChangeSetError::ChangeSetNotFound => (StatusCode::NOT_FOUND, self.to_string()),
-            ChangeSetError::DalChangeSetApply(_) => (StatusCode::CONFLICT, self.to_string()),
+            ChangeSetError::DalChangeSet(DalChangeSetError::ApprovalPolicyNotSatisfied(_)) => {
+                (StatusCode::PRECONDITION_FAILED, self.to_string())
+            }
+            ChangeSetError::DalChangeSetApply(_) | ChangeSetError::UnresolvedOverwrites(_, _) => {
+                (StatusCode::CONFLICT, self.to_string())
+            }
             ChangeSetError::DvuRootsNotEmpty(_) => (
                 StatusCode::PRECONDITION_REQUIRED,
                 "There are dependent values that still need to be calculated. Please retry!"
@@ -94,46 +104,54 @@ impl IntoResponse for ChangeSetError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
+pub fn routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route(
             "/list_open_change_sets",
             get(list_open_change_sets::list_open_change_sets),
         )
-        .route("/add_action", post(add_action::add_action))
-        .route(
-            "/create_change_set",
-            post(create_change_set::create_change_set),
-        )
-        .route(
-            "/apply_change_set",
-            post(apply_change_set::apply_change_set),
-        )
-        .route(
-            "/abandon_change_set",
-            post(abandon_change_set::abandon_change_set),
-        )
-        .route(
-            "/begin_approval_process",
-            post(begin_approval_process::begin_approval_process),
-        )
-        .route(
-            "/cancel_approval_process",
-            post(begin_approval_process::cancel_approval_process),
-        )
-        .route("/merge_vote", post(merge_vote::merge_vote))
-        .route(
-            "/begin_abandon_approval_process",
-            post(begin_abandon_approval_process::begin_abandon_approval_process),
-        )
-        .route(
-            "/cancel_abandon_approval_process",
-            post(begin_abandon_approval_process::cancel_abandon_approval_process),
-        )
-        .route("/abandon_vote", post(abandon_vote::abandon_vote))
-        .route("/rebase_on_base", post(rebase_on_base::rebase_on_base))
         .route(
             "/status_with_base",
             post(status_with_base::status_with_base),
         )
+        .merge(
+            Router::new()
+                .route(
+                    "/create_change_set",
+                    post(create_change_set::create_change_set),
+                )
+                .route(
+                    "/apply_change_set",
+                    post(apply_change_set::apply_change_set),
+                )
+                .route(
+                    "/abandon_change_set",
+                    post(abandon_change_set::abandon_change_set),
+                )
+                .route(
+                    "/begin_approval_process",
+                    post(begin_approval_process::begin_approval_process),
+                )
+                .route(
+                    "/cancel_approval_process",
+                    post(begin_approval_process::cancel_approval_process),
+                )
+                .route("/merge_vote", post(merge_vote::merge_vote))
+                .route(
+                    "/begin_abandon_approval_process",
+                    post(begin_abandon_approval_process::begin_abandon_approval_process),
+                )
+                .route(
+                    "/cancel_abandon_approval_process",
+                    post(begin_abandon_approval_process::cancel_abandon_approval_process),
+                )
+                .route("/abandon_vote", post(abandon_vote::abandon_vote))
+                .route("/rebase_on_base", post(rebase_on_base::rebase_on_base))
+                .route_layer(ScopedPermissionLayer::new(state.clone(), Permission::Edit)),
+        )
+        .merge(
+            Router::new()
+                .route("/add_action", post(add_action::add_action))
+                .route_layer(ScopedPermissionLayer::new(state, Permission::RunActions)),
+        )
 }
@@ -2,7 +2,10 @@ use axum::{
     extract::{Host, OriginalUri},
     Json,
 };
-use dal::{change_set::ChangeSet, Func, Schema, SchemaVariant, Visibility};
+use dal::{
+    change_set::{merge_preview::MergePreview, ChangeSet},
+    Func, Schema, SchemaVariant, Visibility,
+};
 use serde::{Deserialize, Serialize};
 use si_events::audit_log::AuditLogKind;
 
@@ -18,6 +21,10 @@ use super::{ChangeSetError, ChangeSetResult};
 pub struct ApplyChangeSetRequest {
     #[serde(flatten)]
     pub visibility: Visibility,
+    /// Refuse to apply if doing so would overwrite changes made to the base change set since the
+    /// change set last caught up with it.
+    #[serde(default)]
+    pub refuse_overwrites: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -51,6 +58,20 @@ pub async fn apply_change_set(
         return Err(ChangeSetError::DvuRootsNotEmpty(ctx.change_set_id()));
     }
 
+    if request.refuse_overwrites {
+        let preview = MergePreview::for_change_set(&ctx, ctx.change_set_id()).await?;
+        if preview.has_unresolved_overwrites() {
+            return Err(ChangeSetError::UnresolvedOverwrites(
+                ctx.change_set_id(),
+                preview.overwrites,
+            ));
+        }
+    }
+
+    // This route doesn't require the change set to be approved, but it still has to satisfy the
+    // approval policy of the workspace, if there is one.
+    ChangeSet::ensure_approval_policy_satisfied(&ctx, ctx.change_set_id()).await?;
+
     // Lock all unlocked variants
     for schema_id in Schema::list_ids(&ctx).await? {
         let schema = Schema::get_by_id_or_error(&ctx, schema_id).await?;
@@ -36,7 +36,7 @@ pub async fn rebase_on_base(
 ) -> ChangeSetResult<Json<RebaseOnBaseResponse>> {
     let ctx: dal::DalContext = builder.build(request_ctx.build(request.visibility)).await?;
 
-    let change_set = ChangeSet::find(&ctx, request.visibility.change_set_id)
+    let mut change_set = ChangeSet::find(&ctx, request.visibility.change_set_id)
         .await?
         .ok_or(dal::ChangeSetError::ChangeSetNotFound(
             request.visibility.change_set_id,
@@ -62,9 +62,15 @@ pub async fn rebase_on_base(
             .await?;
     }
 
+    // The change set has now caught up with its base.
+    change_set
+        .update_merge_base(&ctx, base_change_set.workspace_snapshot_address)
+        .await?;
+    ctx.commit_no_rebase().await?;
+
     let user = ChangeSet::extract_userid_from_context(&ctx).await;
-    // There is no commit, and the rebase request has already gone through & succeeded, so send out
-    // the WsEvent immediately.
+    // The rebase request has already gone through & succeeded, so send out the WsEvent
+    // immediately.
     WsEvent::change_set_applied(&ctx, base_change_set.id, change_set.id, user)
         .await?
         .publish_immediately(&ctx)
@@ -1,7 +1,10 @@
 use std::num::ParseIntError;
 
 use super::ApiError;
-use crate::{service::component::conflicts_for_component::conflicts_for_component, AppState};
+use crate::{
+    middleware::ScopedPermissionLayer,
+    service::component::conflicts_for_component::conflicts_for_component, AppState,
+};
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
@@ -23,6 +26,7 @@ use dal::{
 };
 use dal::{attribute::value::AttributeValueError, component::debug::ComponentDebugViewError};
 use dal::{ChangeSetError, TransactionsError};
+use permissions::Permission;
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::task::JoinError;
@@ -155,7 +159,7 @@ impl IntoResponse for ComponentError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
+pub fn routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route("/get_actions", get(get_actions::get_actions))
         .route(
@@ -173,30 +177,38 @@ pub fn routes() -> Router<AppState> {
         .route("/get_code", get(get_code::get_code))
         .route("/get_diff", get(get_diff::get_diff))
         .route("/get_resource", get(get_resource::get_resource))
-        .route(
-            "/update_property_editor_value",
-            post(update_property_editor_value::update_property_editor_value),
-        )
-        .route(
-            "/insert_property_editor_value",
-            post(insert_property_editor_value::insert_property_editor_value),
-        )
-        .route(
-            "/delete_property_editor_value",
-            post(delete_property_editor_value::delete_property_editor_value),
-        )
-        .route(
-            "/restore_default_function",
-            post(restore_default_function::restore_default_function),
-        )
-        .route("/set_type", post(set_type::set_type))
-        .route("/set_name", post(set_name::set_name))
-        .route("/set_resource_id", post(set_resource_id::set_resource_id))
-        .route("/refresh", post(refresh::refresh))
         .route("/debug", get(debug::debug_component))
         .route("/json", get(json::json))
-        .route("/upgrade_component", post(upgrade::upgrade))
         .route("/conflicts", get(conflicts_for_component))
-        .route("/manage", post(manage::manage))
-        .route("/unmanage", post(unmanage::unmanage))
+        .merge(
+            Router::new()
+                .route(
+                    "/update_property_editor_value",
+                    post(update_property_editor_value::update_property_editor_value),
+                )
+                .route(
+                    "/insert_property_editor_value",
+                    post(insert_property_editor_value::insert_property_editor_value),
+                )
+                .route(
+                    "/delete_property_editor_value",
+                    post(delete_property_editor_value::delete_property_editor_value),
+                )
+                .route(
+                    "/restore_default_function",
+                    post(restore_default_function::restore_default_function),
+                )
+                .route("/set_type", post(set_type::set_type))
+                .route("/set_name", post(set_name::set_name))
+                .route("/set_resource_id", post(set_resource_id::set_resource_id))
+                .route("/upgrade_component", post(upgrade::upgrade))
+                .route("/manage", post(manage::manage))
+                .route("/unmanage", post(unmanage::unmanage))
+                .route_layer(ScopedPermissionLayer::new(state.clone(), Permission::Edit)),
+        )
+        .merge(
+            Router::new()
+                .route("/refresh", post(refresh::refresh))
+                .route_layer(ScopedPermissionLayer::new(state, Permission::RunActions)),
+        )
 }
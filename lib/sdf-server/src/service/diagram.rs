@@ -15,13 +15,14 @@ use dal::socket::output::OutputSocketError;
 use dal::workspace_snapshot::WorkspaceSnapshotError;
 use dal::{ChangeSetError, SchemaError, SchemaVariantId, StandardModelError, TransactionsError};
 use dal::{SchemaId, WsEventError};
+use permissions::Permission;
 use std::num::{ParseFloatError, ParseIntError};
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::task::JoinError;
 
 use super::ApiError;
-use crate::AppState;
+use crate::{middleware::ScopedPermissionLayer, AppState};
 
 pub mod create_component;
 pub mod create_connection;
@@ -152,36 +153,8 @@ impl IntoResponse for DiagramError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
+pub fn routes(state: AppState) -> Router<AppState> {
     Router::new()
-        .route(
-            "/add_components_to_view",
-            post(add_components_to_view::add_components_to_view),
-        )
-        .route(
-            "/delete_connection",
-            post(delete_connection::delete_connection),
-        )
-        .route(
-            "/delete_components",
-            post(delete_component::delete_components),
-        )
-        .route(
-            "/remove_delete_intent",
-            post(remove_delete_intent::remove_delete_intent),
-        )
-        .route(
-            "/create_connection",
-            post(create_connection::create_connection),
-        )
-        .route(
-            "/create_component",
-            post(create_component::create_component),
-        )
-        .route(
-            "/set_component_position",
-            post(set_component_position::set_component_position),
-        )
         // Gets diagram for default view TODO: Delete this
         .route("/get_diagram", get(get_diagram::get_diagram))
         .route(
@@ -190,4 +163,36 @@ pub fn routes() -> Router<AppState> {
         )
         .route("/list_schemas", get(list_schemas::list_schemas))
         .route("/dvu_roots", get(dvu_roots::dvu_roots))
+        .merge(
+            Router::new()
+                .route(
+                    "/add_components_to_view",
+                    post(add_components_to_view::add_components_to_view),
+                )
+                .route(
+                    "/delete_connection",
+                    post(delete_connection::delete_connection),
+                )
+                .route(
+                    "/delete_components",
+                    post(delete_component::delete_components),
+                )
+                .route(
+                    "/remove_delete_intent",
+                    post(remove_delete_intent::remove_delete_intent),
+                )
+                .route(
+                    "/create_connection",
+                    post(create_connection::create_connection),
+                )
+                .route(
+                    "/create_component",
+                    post(create_component::create_component),
+                )
+                .route(
+                    "/set_component_position",
+                    post(set_component_position::set_component_position),
+                )
+                .route_layer(ScopedPermissionLayer::new(state, Permission::Edit)),
+        )
 }
@@ -13,6 +13,7 @@ use dal::{
     TransactionsError, UserError, UserPk, WorkspaceError, WorkspacePk, WorkspaceSnapshotError,
     WsEventError,
 };
+use permissions::Permission;
 use serde::{Deserialize, Serialize};
 use si_layer_cache::LayerDbError;
 use si_pkg::{SiPkg, SiPkgError};
@@ -23,7 +24,7 @@ use thiserror::Error;
 use tokio::fs::read_dir;
 use ulid::Ulid;
 
-use crate::AppState;
+use crate::{middleware::ScopedPermissionLayer, AppState};
 
 use super::ApiError;
 
@@ -242,38 +243,46 @@ pub async fn pkg_open(builder: &DalContextBuilder, file_name: &str) -> ModuleRes
     Ok(SiPkg::load_from_file(&real_pkg_path).await?)
 }
 
-pub fn routes() -> Router<AppState> {
+pub fn routes(state: AppState) -> Router<AppState> {
     Router::new()
-        .route(
-            "/export_workspace",
-            post(export_workspace::export_workspace),
-        )
         .route("/get_module_by_hash", get(get_module::get_module_by_hash))
-        .route("/install_module", post(install_module::install_module))
-        .route(
-            "/install_workspace",
-            post(install_workspace::install_workspace),
-        )
         .route("/list_modules", get(list_modules::list_modules))
         .route(
             "/remote_module_spec",
             get(remote_module_spec::remote_module_spec),
         )
-        .route(
-            "/set_as_builtin",
-            post(builtin_module_spec::promote_to_builtin),
-        )
-        .route("/reject_module", post(reject_module::reject_module))
-        .route(
-            "/begin_approval_process",
-            post(approval_process::begin_approval_process),
+        .merge(
+            Router::new()
+                .route(
+                    "/export_workspace",
+                    post(export_workspace::export_workspace),
+                )
+                .route("/install_module", post(install_module::install_module))
+                .route(
+                    "/install_workspace",
+                    post(install_workspace::install_workspace),
+                )
+                .route(
+                    "/begin_approval_process",
+                    post(approval_process::begin_approval_process),
+                )
+                .route(
+                    "/cancel_approval_process",
+                    post(approval_process::cancel_approval_process),
+                )
+                .route(
+                    "/import_workspace_vote",
+                    post(import_workspace_vote::import_workspace_vote),
+                )
+                .route_layer(ScopedPermissionLayer::new(state.clone(), Permission::Edit)),
         )
-        .route(
-            "/cancel_approval_process",
-            post(approval_process::cancel_approval_process),
-        )
-        .route(
-            "/import_workspace_vote",
-            post(import_workspace_vote::import_workspace_vote),
+        .merge(
+            Router::new()
+                .route(
+                    "/set_as_builtin",
+                    post(builtin_module_spec::promote_to_builtin),
+                )
+                .route("/reject_module", post(reject_module::reject_module))
+                .route_layer(ScopedPermissionLayer::new(state, Permission::AuthorFuncs)),
         )
 }
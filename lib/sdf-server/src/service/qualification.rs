@@ -17,6 +17,7 @@ use crate::AppState;
 
 use super::ApiError;
 
+pub mod get_drift_summary;
 pub mod get_summary;
 
 // code endpoints here are deprecated, removing them from the module tree
@@ -85,5 +86,10 @@ impl IntoResponse for QualificationError {
 }
 
 pub fn routes() -> Router<AppState> {
-    Router::new().route("/get_summary", get(get_summary::get_summary))
+    Router::new()
+        .route(
+            "/get_drift_summary",
+            get(get_drift_summary::get_drift_summary),
+        )
+        .route("/get_summary", get(get_summary::get_summary))
 }
@@ -0,0 +1,28 @@
+use axum::{extract::Query, Json};
+use dal::component::drift::DriftSummary;
+use dal::Visibility;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{AccessBuilder, HandlerContext},
+    service::qualification::QualificationResult,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDriftSummaryRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn get_drift_summary(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetDriftSummaryRequest>,
+) -> QualificationResult<Json<DriftSummary>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let drift_summary = DriftSummary::get_summary(&ctx).await?;
+
+    Ok(Json(drift_summary))
+}
@@ -6,11 +6,12 @@ use dal::{
     ChangeSetError, KeyPairError, SecretId, StandardModelError, TransactionsError, UserError,
     WorkspacePk, WsEventError,
 };
+use permissions::Permission;
 use telemetry::prelude::*;
 use thiserror::Error;
 
 use super::impl_default_error_into_response;
-use crate::AppState;
+use crate::{middleware::ScopedPermissionLayer, AppState};
 
 pub mod create_secret;
 pub mod delete_secret;
@@ -59,11 +60,15 @@ pub type SecretResult<T> = Result<T, SecretError>;
 
 impl_default_error_into_response!(SecretError);
 
-pub fn routes() -> Router<AppState> {
+pub fn routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route("/get_public_key", get(get_public_key::get_public_key))
-        .route("/", post(create_secret::create_secret))
-        .route("/", get(list_secrets::list_secrets))
-        .route("/", patch(update_secret::update_secret))
-        .route("/", delete(delete_secret::delete_secret))
+        .merge(
+            Router::new()
+                .route("/", post(create_secret::create_secret))
+                .route("/", get(list_secrets::list_secrets))
+                .route("/", patch(update_secret::update_secret))
+                .route("/", delete(delete_secret::delete_secret))
+                .route_layer(ScopedPermissionLayer::new(state, Permission::ReadSecrets)),
+        )
 }
@@ -5,9 +5,12 @@ use crate::AppState;
 pub mod admin;
 pub mod audit_log;
 pub mod change_set;
+pub mod component;
 pub mod func;
 pub mod management;
 pub mod module;
+pub mod refresh_schedule;
+pub mod restricted_member;
 pub mod variant;
 pub mod view;
 
@@ -21,9 +24,30 @@ pub fn routes(state: AppState) -> Router<AppState> {
         .nest("/admin", admin::v2_routes(state.clone()))
         .nest(&format!("{PREFIX}/audit-logs"), audit_log::v2_routes())
         .nest(CHANGE_SET_PREFIX, change_set::v2_routes(state.clone()))
-        .nest(&format!("{PREFIX}/funcs"), func::v2_routes())
-        .nest(&format!("{PREFIX}/modules"), module::v2_routes())
-        .nest(&format!("{PREFIX}/schema-variants"), variant::v2_routes())
-        .nest(&format!("{PREFIX}/management"), management::v2_routes())
-        .nest(&format!("{PREFIX}/views"), view::v2_routes())
+        .nest(
+            &format!("{PREFIX}/components"),
+            component::v2_routes(state.clone()),
+        )
+        .nest(&format!("{PREFIX}/funcs"), func::v2_routes(state.clone()))
+        .nest(
+            &format!("{PREFIX}/modules"),
+            module::v2_routes(state.clone()),
+        )
+        .nest(
+            "/workspaces/:workspace_id/refresh-schedules",
+            refresh_schedule::v2_routes(state.clone()),
+        )
+        .nest(
+            "/workspaces/:workspace_id/restricted-members",
+            restricted_member::v2_routes(state.clone()),
+        )
+        .nest(
+            &format!("{PREFIX}/schema-variants"),
+            variant::v2_routes(state.clone()),
+        )
+        .nest(
+            &format!("{PREFIX}/management"),
+            management::v2_routes(state.clone()),
+        )
+        .nest(&format!("{PREFIX}/views"), view::v2_routes(state))
 }
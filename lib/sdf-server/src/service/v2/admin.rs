@@ -17,6 +17,7 @@ use thiserror::Error;
 
 use crate::{extract::AdminAccessBuilder, service::ApiError, AppState};
 
+mod garbage_collect;
 mod get_snapshot;
 mod kill_execution;
 mod list_change_sets;
@@ -25,6 +26,7 @@ mod prompts;
 mod search_workspaces;
 mod set_concurrency_limit;
 mod set_snapshot;
+mod set_value_concurrency_limit;
 mod update_module_cache;
 
 // 1GB
@@ -43,6 +45,8 @@ pub enum AdminAPIError {
     ChangeSetNotFound(ChangeSetId),
     #[error("func runner error: {0}")]
     FuncRunner(#[from] FuncRunnerError),
+    #[error("garbage collection error: {0}")]
+    GarbageCollection(#[from] dal::garbage_collection::GarbageCollectionError),
     #[error("layer db error: {0}")]
     LayerDb(#[from] si_layer_cache::LayerDbError),
     #[error("multipart error: {0}")]
@@ -75,6 +79,7 @@ pub struct AdminWorkspace {
     pub timestamp: dal::Timestamp,
     pub snapshot_version: WorkspaceSnapshotGraphDiscriminants,
     pub component_concurrency_limit: Option<i32>,
+    pub value_concurrency_limit: Option<i32>,
 }
 
 impl From<Workspace> for AdminWorkspace {
@@ -86,6 +91,7 @@ impl From<Workspace> for AdminWorkspace {
             timestamp: value.timestamp().to_owned(),
             snapshot_version: value.snapshot_version(),
             component_concurrency_limit: value.raw_component_concurrency_limit(),
+            value_concurrency_limit: value.raw_value_concurrency_limit(),
         }
     }
 }
@@ -167,6 +173,7 @@ pub fn v2_routes(state: AppState) -> Router<AppState> {
             "/func/runs/:func_run_id/kill_execution",
             put(kill_execution::kill_execution),
         )
+        .route("/garbage_collect", post(garbage_collect::garbage_collect))
         .route("/workspaces", get(search_workspaces::search_workspaces))
         .route(
             "/workspaces/:workspace_pk/users",
@@ -176,6 +183,10 @@ pub fn v2_routes(state: AppState) -> Router<AppState> {
             "/workspaces/:workspace_pk/set_concurrency_limit",
             post(set_concurrency_limit::set_concurrency_limit),
         )
+        .route(
+            "/workspaces/:workspace_pk/set_value_concurrency_limit",
+            post(set_value_concurrency_limit::set_value_concurrency_limit),
+        )
         .route(
             "/workspaces/:workspace_pk/change_sets",
             get(list_change_sets::list_change_sets),
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{Host, OriginalUri},
+    response::Json,
+};
+use dal::garbage_collection;
+use serde::{Deserialize, Serialize};
+use si_layer_cache::db::gc::{GcOptions, GcReport};
+use telemetry::prelude::*;
+
+use super::AdminAPIResult;
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    track_no_ctx,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GarbageCollectRequest {
+    /// Rows written within this many seconds are never collected. Defaults to seven days.
+    pub retention_seconds: Option<u64>,
+    /// Defaults to `true`: nothing is deleted unless explicitly requested.
+    pub dry_run: Option<bool>,
+}
+
+#[instrument(
+    name = "admin.garbage_collect",
+    level = "info",
+    skip_all,
+    fields(
+        si.layer_db.gc.dry_run = Empty,
+    ),
+)]
+pub async fn garbage_collect(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Json(request): Json<GarbageCollectRequest>,
+) -> AdminAPIResult<Json<GcReport>> {
+    let span = current_span_for_instrument_at!("info");
+
+    let mut options = GcOptions::default();
+    if let Some(retention_seconds) = request.retention_seconds {
+        options = options.with_retention(Duration::from_secs(retention_seconds));
+    }
+    if let Some(dry_run) = request.dry_run {
+        options = options.with_dry_run(dry_run);
+    }
+    span.record("si.layer_db.gc.dry_run", options.dry_run);
+
+    let ctx = builder.build_head(access_builder).await?;
+
+    let report = garbage_collection::collect(&ctx, options).await?;
+
+    track_no_ctx(
+        &posthog_client,
+        &original_uri,
+        &host_name,
+        ctx.history_actor().distinct_id(),
+        None,
+        None,
+        "admin.garbage_collect",
+        serde_json::json!({
+            "dry_run": report.dry_run,
+            "retention_seconds": options.retention.as_secs(),
+            "workspace_snapshots_collected": report.workspace_snapshots.collected.len(),
+            "workspace_snapshots_collected_bytes": report.workspace_snapshots.collected_bytes,
+            "cas_collected": report.cas.collected.len(),
+            "cas_collected_bytes": report.cas.collected_bytes,
+        }),
+    );
+
+    Ok(Json(report))
+}
@@ -57,11 +57,21 @@ pub async fn set_snapshot(
     );
 
     let data_clone = snapshot_data.clone();
+    let zstd_dictionaries = ctx.layer_db().zstd_dictionaries().clone();
     let (workspace_snapshot_address, _) = tokio::task::spawn_blocking(move || {
-        let uploaded_address = WorkspaceSnapshotAddress::new(&data_clone);
+        // Snapshots are addressed by the hash of their uncompressed bytes, the same as when the
+        // layer db writes them
+        let uncompressed = si_layer_cache::db::serialize::decompress_to_vec_with_dictionaries(
+            &data_clone,
+            &zstd_dictionaries,
+        )?;
+        let uploaded_address = WorkspaceSnapshotAddress::new(&uncompressed);
         // We do this to make sure the uploaded snapshot is valid
         let graph: Arc<WorkspaceSnapshotGraph> =
-            si_layer_cache::db::serialize::from_bytes(&data_clone)?;
+            si_layer_cache::db::serialize::from_bytes_with_dictionaries(
+                &data_clone,
+                &zstd_dictionaries,
+            )?;
         Ok::<(WorkspaceSnapshotAddress, Arc<WorkspaceSnapshotGraph>), AdminAPIError>((
             uploaded_address,
             graph,
@@ -74,10 +84,6 @@ pub async fn set_snapshot(
         workspace_snapshot_address.to_string(),
     );
 
-    // Our compression algorithm appears to be non-deterministic, so we write
-    // exactly the bytes we received, so that the snapshot address matches the
-    // blake3 hash seen locally. Otherwise it makes it seem like we're writing a
-    // different snapshot than the one we uploaded
     ctx.layer_db()
         .workspace_snapshot()
         .write_bytes_to_durable_storage(&workspace_snapshot_address, &snapshot_data)
@@ -3,27 +3,42 @@ use std::result;
 use axum::{
     http::StatusCode,
     response::IntoResponse,
-    routing::{get, post},
+    routing::{get, post, put},
     Router,
 };
 use dal::{ChangeSetId, ChangeSetStatus, WsEventError};
 use si_data_spicedb::SpiceDbError;
+use si_events::WorkspaceSnapshotAddress;
 use thiserror::Error;
 
-use crate::{middleware::WorkspacePermissionLayer, service::ApiError, AppState};
+use crate::{
+    middleware::{ScopedPermissionLayer, WorkspacePermissionLayer},
+    service::ApiError,
+    AppState,
+};
 
 mod apply;
+mod approval_policy;
+mod approval_status;
 mod approve;
 mod cancel_approval_request;
+mod cherry_pick;
+mod diff;
 mod force_apply;
+mod fork;
 mod list;
+mod merge_preview;
 mod reject;
 mod reopen;
 mod request_approval;
+mod restore;
+mod rollback_on_failure;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum Error {
+    #[error("approval policy error: {0}")]
+    ApprovalPolicy(#[from] dal::change_set::approval_policy::ApprovalPolicyError),
     #[error("change set error: {0}")]
     ChangeSet(#[from] dal::ChangeSetError),
     #[error("change set apply error: {0}")]
@@ -32,10 +47,16 @@ pub enum Error {
     ChangeSetNotApprovedForApply(ChangeSetStatus),
     #[error("change set not found: {0}")]
     ChangeSetNotFound(ChangeSetId),
+    #[error("cherry-pick error: {0}")]
+    CherryPick(#[from] dal::change_set::cherry_pick::CherryPickError),
+    #[error("snapshot diff error: {0}")]
+    Diff(#[from] dal::change_set::diff::SnapshotDiffError),
     #[error("dvu roots are not empty for change set: {0}")]
     DvuRootsNotEmpty(ChangeSetId),
     #[error("func error: {0}")]
     Func(#[from] dal::FuncError),
+    #[error("merge preview error: {0}")]
+    MergePreview(#[from] dal::change_set::merge_preview::MergePreviewError),
     #[error("permissions error: {0}")]
     Permissions(#[from] permissions::Error),
     #[error("schema error: {0}")]
@@ -48,10 +69,14 @@ pub enum Error {
     SpiceDBNotFound,
     #[error("transactions error: {0}")]
     Transactions(#[from] dal::TransactionsError),
+    #[error("applying change set {0} would overwrite {1} concurrent change(s) to its base")]
+    UnresolvedOverwrites(ChangeSetId, usize),
     #[error("found an unexpected number of open change sets matching default change set (should be one, found {0:?})")]
     UnexpectedNumberOfOpenChangeSetsMatchingDefaultChangeSet(Vec<ChangeSetId>),
     #[error("workspace snapshot error: {0}")]
     WorkspaceSnapshot(#[from] dal::WorkspaceSnapshotError),
+    #[error("workspace snapshot not found in workspace: {0}")]
+    WorkspaceSnapshotNotFound(WorkspaceSnapshotAddress),
     #[error("ws event error: {0}")]
     WsEvent(#[from] WsEventError),
 }
@@ -59,8 +84,28 @@ pub enum Error {
 impl IntoResponse for Error {
     fn into_response(self) -> axum::response::Response {
         let status_code = match &self {
+            Self::ApprovalPolicy(
+                dal::change_set::approval_policy::ApprovalPolicyError::SelfApprovalNotAllowed(_, _),
+            ) => StatusCode::FORBIDDEN,
+            Self::ApprovalPolicy(
+                dal::change_set::approval_policy::ApprovalPolicyError::UnknownApprovalGroup(_),
+            ) => StatusCode::BAD_REQUEST,
+            Self::ChangeSet(dal::ChangeSetError::ApprovalPolicyNotSatisfied(_)) => {
+                StatusCode::PRECONDITION_FAILED
+            }
+            Self::ChangeSet(dal::ChangeSetError::InvalidRestorePoint(_)) => StatusCode::NOT_FOUND,
+            Self::ChangeSet(
+                dal::ChangeSetError::RestoreSnapshotMissing(_)
+                | dal::ChangeSetError::RestoreSnapshotNeedsMigration(_, _)
+                | dal::ChangeSetError::RestoreSnapshotNotRecorded(_),
+            ) => StatusCode::GONE,
             Self::ChangeSetApply(_) => StatusCode::CONFLICT,
+            Self::CherryPick(
+                dal::change_set::cherry_pick::CherryPickError::CherryPickOntoSelf(_),
+            ) => StatusCode::BAD_REQUEST,
             Self::DvuRootsNotEmpty(_) => StatusCode::PRECONDITION_FAILED,
+            Self::UnresolvedOverwrites(_, _) => StatusCode::CONFLICT,
+            Self::WorkspaceSnapshotNotFound(_) => StatusCode::NOT_FOUND,
             Self::Transactions(dal::TransactionsError::BadWorkspaceAndChangeSet) => {
                 StatusCode::FORBIDDEN
             }
@@ -80,11 +125,9 @@ pub fn v2_routes(state: AppState) -> Router<AppState> {
         .nest(
             "/:change_set_id",
             Router::new()
-                .route("/apply", post(apply::apply))
-                .route(
-                    "/request_approval",
-                    post(request_approval::request_approval),
-                )
+                .route("/approval_status", get(approval_status::approval_status))
+                .route("/diff", get(diff::diff))
+                .route("/merge_preview", get(merge_preview::merge_preview))
                 .route(
                     "/approve",
                     post(approve::approve).layer(WorkspacePermissionLayer::new(
@@ -99,19 +142,55 @@ pub fn v2_routes(state: AppState) -> Router<AppState> {
                         permissions::Permission::Approve,
                     )),
                 )
-                .route(
-                    "/cancel_approval_request",
-                    post(cancel_approval_request::cancel_approval_request),
-                )
-                // Consider how we make it editable again after it's been rejected
-                .route("/reopen", post(reopen::reopen))
                 .route(
                     "/force_apply",
                     post(force_apply::force_apply).layer(WorkspacePermissionLayer::new(
                         state.clone(),
                         permissions::Permission::Approve,
                     )),
+                )
+                .merge(
+                    Router::new()
+                        .route("/apply", post(apply::apply))
+                        .route(
+                            "/request_approval",
+                            post(request_approval::request_approval),
+                        )
+                        .route(
+                            "/cancel_approval_request",
+                            post(cancel_approval_request::cancel_approval_request),
+                        )
+                        .route("/cherry_pick", post(cherry_pick::cherry_pick))
+                        .route("/fork", post(fork::fork))
+                        // Consider how we make it editable again after it's been rejected
+                        .route("/reopen", post(reopen::reopen))
+                        .route(
+                            "/rollback_on_failure",
+                            post(rollback_on_failure::rollback_on_failure),
+                        )
+                        .route_layer(ScopedPermissionLayer::new(
+                            state.clone(),
+                            permissions::Permission::Edit,
+                        )),
                 ),
         )
         .route("/", get(list::list_actionable))
+        .route(
+            "/approval_policy",
+            get(approval_policy::get_approval_policy),
+        )
+        .route(
+            "/approval_policy",
+            put(approval_policy::set_approval_policy).layer(WorkspacePermissionLayer::new(
+                state.clone(),
+                permissions::Permission::Manage,
+            )),
+        )
+        .route(
+            "/restore",
+            post(restore::restore).layer(WorkspacePermissionLayer::new(
+                state,
+                permissions::Permission::Approve,
+            )),
+        )
 }
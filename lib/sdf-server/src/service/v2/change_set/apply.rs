@@ -1,5 +1,8 @@
-use axum::extract::{Host, OriginalUri, Path};
-use dal::{ChangeSet, ChangeSetId, WorkspacePk};
+use axum::extract::{Host, OriginalUri, Path, Query};
+use dal::{
+    change_set::merge_preview::MergePreview, ChangeSet, ChangeSetId, DalContext, WorkspacePk,
+};
+use serde::Deserialize;
 use si_events::audit_log::AuditLogKind;
 
 use super::{Error, Result};
@@ -8,6 +11,34 @@ use crate::{
     track,
 };
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyRequest {
+    /// Refuse to apply if doing so would overwrite changes made to the base change set since the
+    /// change set last caught up with it.
+    #[serde(default)]
+    pub refuse_overwrites: bool,
+}
+
+/// Returns an error if the request asks to refuse overwrites and the merge preview has some.
+pub async fn ensure_no_unresolved_overwrites(
+    ctx: &DalContext,
+    change_set_id: ChangeSetId,
+    request: &ApplyRequest,
+) -> Result<()> {
+    if request.refuse_overwrites {
+        let preview = MergePreview::for_change_set(ctx, change_set_id).await?;
+        if preview.has_unresolved_overwrites() {
+            return Err(Error::UnresolvedOverwrites(
+                change_set_id,
+                preview.overwrites,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 pub async fn apply(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
@@ -15,6 +46,7 @@ pub async fn apply(
     OriginalUri(original_uri): OriginalUri,
     Host(host_name): Host,
     Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Query(request): Query<ApplyRequest>,
 ) -> Result<()> {
     let mut ctx = builder
         .build(request_ctx.build(change_set_id.into()))
@@ -22,6 +54,7 @@ pub async fn apply(
     let change_set = ChangeSet::find(&ctx, change_set_id)
         .await?
         .ok_or(Error::ChangeSetNotFound(ctx.change_set_id()))?;
+    ensure_no_unresolved_overwrites(&ctx, change_set_id, &request).await?;
     ChangeSet::prepare_for_apply(&ctx).await?;
 
     // We need to run a commit before apply so changes get saved
@@ -0,0 +1,51 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{change_set::approval_policy::ApprovalPolicy, WorkspacePk};
+
+use super::Result;
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    track,
+};
+
+pub async fn get_approval_policy(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path(_workspace_pk): Path<WorkspacePk>,
+) -> Result<Json<Option<ApprovalPolicy>>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    Ok(Json(ApprovalPolicy::get(&ctx).await?))
+}
+
+/// Sets the approval policy of the workspace, or removes it if the body is `null`.
+pub async fn set_approval_policy(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path(_workspace_pk): Path<WorkspacePk>,
+    Json(policy): Json<Option<ApprovalPolicy>>,
+) -> Result<Json<Option<ApprovalPolicy>>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    ApprovalPolicy::set(&ctx, policy.clone()).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "set_change_set_approval_policy",
+        serde_json::json!({
+            "policy": policy,
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(policy))
+}
@@ -0,0 +1,20 @@
+use axum::{extract::Path, Json};
+use dal::{change_set::approval_policy::ApprovalPolicyEvaluation, ChangeSetId, WorkspacePk};
+
+use super::Result;
+use crate::extract::{AccessBuilder, HandlerContext};
+
+/// Evaluates the approvals of the change set against the approval policy of the workspace.
+pub async fn approval_status(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+) -> Result<Json<ApprovalPolicyEvaluation>> {
+    let ctx = builder
+        .build(request_ctx.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(
+        ApprovalPolicyEvaluation::for_change_set(&ctx, change_set_id).await?,
+    ))
+}
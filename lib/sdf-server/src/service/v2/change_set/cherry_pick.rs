@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{change_set::cherry_pick::CherryPickReport, ChangeSet, ChangeSetId, WorkspacePk};
+use serde::Deserialize;
+
+use super::Result;
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    service::force_change_set_response::ForceChangeSetResponse,
+    track,
+};
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CherryPickRequest {
+    source_change_set_id: ChangeSetId,
+}
+
+/// Performs the changes made in the source change set on this change set, reporting the ones
+/// that could not be applied.
+pub async fn cherry_pick(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(request): Json<CherryPickRequest>,
+) -> Result<ForceChangeSetResponse<CherryPickReport>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let report = ChangeSet::cherry_pick(&ctx, request.source_change_set_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "cherry_pick_change_set",
+        serde_json::json!({
+            "source_change_set_id": request.source_change_set_id,
+            "applied": report.applied,
+            "unapplied": report.unapplied.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, report))
+}
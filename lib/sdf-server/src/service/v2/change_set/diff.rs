@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Path, Query},
+    Json,
+};
+use dal::{change_set::diff::SnapshotDiff, ChangeSet, ChangeSetId, WorkspacePk};
+use serde::Deserialize;
+use si_events::WorkspaceSnapshotAddress;
+
+use super::{Error, Result};
+use crate::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffRequest {
+    /// Defaults to the current snapshot of the change set's base change set.
+    before: Option<WorkspaceSnapshotAddress>,
+    /// Defaults to the current snapshot of the change set.
+    after: Option<WorkspaceSnapshotAddress>,
+}
+
+/// Returns a structured diff of the change set against its base change set. Either side can be
+/// replaced with the snapshot of any change set (including applied ones) in the workspace.
+pub async fn diff(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Path((workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Query(request): Query<DiffRequest>,
+) -> Result<Json<SnapshotDiff>> {
+    let ctx = builder
+        .build(request_ctx.build(change_set_id.into()))
+        .await?;
+
+    let change_set = ChangeSet::find(&ctx, change_set_id)
+        .await?
+        .ok_or(Error::ChangeSetNotFound(change_set_id))?;
+
+    // Only allow diffing snapshots that belong to this workspace.
+    if request.before.is_some() || request.after.is_some() {
+        let known_addresses: Vec<WorkspaceSnapshotAddress> =
+            ChangeSet::list_all_for_workspace(&ctx, workspace_pk)
+                .await?
+                .into_iter()
+                .map(|change_set| change_set.workspace_snapshot_address)
+                .collect();
+        for address in [request.before, request.after].into_iter().flatten() {
+            if !known_addresses.contains(&address) {
+                return Err(Error::WorkspaceSnapshotNotFound(address));
+            }
+        }
+    }
+
+    let before = match request.before {
+        Some(before) => before,
+        None => SnapshotDiff::base_workspace_snapshot_address(&ctx, &change_set).await?,
+    };
+    let after = request
+        .after
+        .unwrap_or(change_set.workspace_snapshot_address);
+
+    Ok(Json(SnapshotDiff::between(&ctx, before, after).await?))
+}
@@ -1,8 +1,11 @@
-use axum::extract::{Host, OriginalUri, Path};
+use axum::extract::{Host, OriginalUri, Path, Query};
 use dal::{ChangeSet, ChangeSetId, WorkspacePk};
 use si_events::audit_log::AuditLogKind;
 
-use super::{Error, Result};
+use super::{
+    apply::{ensure_no_unresolved_overwrites, ApplyRequest},
+    Error, Result,
+};
 use crate::{
     extract::{AccessBuilder, HandlerContext, PosthogClient},
     track,
@@ -15,6 +18,7 @@ pub async fn force_apply(
     OriginalUri(original_uri): OriginalUri,
     Host(host_name): Host,
     Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Query(request): Query<ApplyRequest>,
 ) -> Result<()> {
     let mut ctx = builder
         .build(request_ctx.build(change_set_id.into()))
@@ -22,6 +26,7 @@ pub async fn force_apply(
     let change_set = ChangeSet::find(&ctx, change_set_id)
         .await?
         .ok_or(Error::ChangeSetNotFound(ctx.change_set_id()))?;
+    ensure_no_unresolved_overwrites(&ctx, change_set_id, &request).await?;
     let old_status = change_set.status;
     ChangeSet::prepare_for_force_apply(&ctx).await?;
     ctx.write_audit_log(
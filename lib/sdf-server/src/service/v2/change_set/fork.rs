@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{change_set::fork::ForkBase, ChangeSet, ChangeSetId, WorkspacePk};
+use serde::Deserialize;
+use si_events::audit_log::AuditLogKind;
+
+use super::{Error, Result};
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    track,
+};
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkRequest {
+    name: Option<String>,
+    #[serde(default)]
+    base: ForkBase,
+}
+
+/// Creates a new change set from the current state of an open change set.
+pub async fn fork(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(request): Json<ForkRequest>,
+) -> Result<Json<si_frontend_types::ChangeSet>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let source_change_set = ChangeSet::find(&ctx, change_set_id)
+        .await?
+        .ok_or(Error::ChangeSetNotFound(change_set_id))?;
+    let name = request
+        .name
+        .unwrap_or_else(|| format!("Fork of {}", source_change_set.name));
+
+    let change_set = ChangeSet::fork(&ctx, &name, change_set_id, request.base).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "fork_change_set",
+        serde_json::json!({
+            "change_set_name": name,
+            "source_change_set_id": change_set_id,
+            "base": request.base,
+        }),
+    );
+
+    ctx.write_audit_log(AuditLogKind::CreateChangeSet, name)
+        .await?;
+
+    let change_set_view = change_set.into_frontend_type(&ctx).await?;
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(change_set_view))
+}
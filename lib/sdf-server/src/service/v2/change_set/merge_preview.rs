@@ -0,0 +1,22 @@
+use axum::{extract::Path, Json};
+use dal::{change_set::merge_preview::MergePreview, ChangeSetId, WorkspacePk};
+
+use super::Result;
+use crate::extract::{AccessBuilder, HandlerContext};
+
+/// Previews applying the change set to the current state of its base change set, grouped by
+/// component, with every update classified as clean, an overwrite of a concurrent change or
+/// dropped.
+pub async fn merge_preview(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+) -> Result<Json<MergePreview>> {
+    let ctx = builder
+        .build(request_ctx.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(
+        MergePreview::for_change_set(&ctx, change_set_id).await?,
+    ))
+}
@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{change_set::restore::RestorePoint, ChangeSet, WorkspacePk};
+use serde::Deserialize;
+use si_events::audit_log::AuditLogKind;
+
+use super::Result;
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    track,
+};
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreRequest {
+    name: Option<String>,
+    restore_point: RestorePoint,
+}
+
+/// Creates a new change set that, once approved and applied, reverts HEAD to the given
+/// restore point.
+pub async fn restore(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path(_workspace_pk): Path<WorkspacePk>,
+    Json(request): Json<RestoreRequest>,
+) -> Result<Json<si_frontend_types::ChangeSet>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let change_set = ChangeSet::fork_head_as_of(&ctx, request.name, request.restore_point).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "restore_change_set",
+        serde_json::json!({
+            "change_set_name": change_set.name,
+            "restore_point": request.restore_point,
+        }),
+    );
+
+    ctx.write_audit_log(AuditLogKind::CreateChangeSet, change_set.name.to_owned())
+        .await?;
+
+    let change_set_view = change_set.into_frontend_type(&ctx).await?;
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(change_set_view))
+}
@@ -0,0 +1,58 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{ChangeSet, ChangeSetId, WorkspacePk};
+use serde::Deserialize;
+
+use super::{Error, Result};
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    track,
+};
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RollbackOnFailureRequest {
+    enabled: bool,
+}
+
+/// Opts the actions enqueued by applying the change set in or out of rollback-on-failure.
+pub async fn rollback_on_failure(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(request): Json<RollbackOnFailureRequest>,
+) -> Result<Json<si_frontend_types::ChangeSet>> {
+    let ctx = builder
+        .build(request_ctx.build(change_set_id.into()))
+        .await?;
+
+    let mut change_set = ChangeSet::find(&ctx, change_set_id)
+        .await?
+        .ok_or(Error::ChangeSetNotFound(change_set_id))?;
+    change_set
+        .set_rollback_on_failure(&ctx, request.enabled)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "set_change_set_rollback_on_failure",
+        serde_json::json!({
+            "change_set": change_set_id,
+            "enabled": request.enabled,
+        }),
+    );
+
+    let change_set_view = change_set.into_frontend_type(&ctx).await?;
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(change_set_view))
+}
@@ -0,0 +1,76 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Router,
+};
+use dal::{
+    attribute::value::AttributeValueError, component::bulk_update::BulkUpdateFailure,
+    AttributeValueId, ChangeSetError, ComponentError, ComponentId, TransactionsError, WsEventError,
+};
+use permissions::Permission;
+use thiserror::Error;
+
+use crate::{middleware::ScopedPermissionLayer, service::ApiError, AppState};
+
+mod bulk_update_attributes;
+mod get_provenance;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ComponentApiError {
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] AttributeValueError),
+    #[error("attribute value {0} is not for component {1}")]
+    AttributeValueNotForComponent(AttributeValueId, ComponentId),
+    #[error("bulk update not applied, {} value(s) failed: {}", .0.len(), format_failures(.0))]
+    BulkUpdateFailed(Vec<BulkUpdateFailure>),
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("ws event error: {0}")]
+    WsEvent(#[from] WsEventError),
+}
+
+pub type ComponentApiResult<T> = Result<T, ComponentApiError>;
+
+impl IntoResponse for ComponentApiError {
+    fn into_response(self) -> Response {
+        let status_code = match &self {
+            Self::AttributeValueNotForComponent(_, _) => StatusCode::NOT_FOUND,
+            Self::BulkUpdateFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => ApiError::DEFAULT_ERROR_STATUS_CODE,
+        };
+
+        ApiError::new(status_code, self).into_response()
+    }
+}
+
+fn format_failures(failures: &[BulkUpdateFailure]) -> String {
+    failures
+        .iter()
+        .map(|failure| {
+            format!(
+                "{} on component {}: {}",
+                failure.path, failure.component_id, failure.message
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub fn v2_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/bulk_update_attributes",
+            post(bulk_update_attributes::bulk_update_attributes)
+                .layer(ScopedPermissionLayer::new(state, Permission::Edit)),
+        )
+        .route(
+            "/:component_id/attribute_values/:attribute_value_id/provenance",
+            get(get_provenance::get_provenance),
+        )
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    component::bulk_update::{AttributePatch, BulkUpdateChange, BulkUpdateTargets},
+    AttributeValue, ChangeSet, ChangeSetId, Component, WorkspacePk, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+use si_events::audit_log::AuditLogKind;
+
+use super::{ComponentApiError, ComponentApiResult};
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    service::force_change_set_response::ForceChangeSetResponse,
+    track,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub targets: BulkUpdateTargets,
+    pub patches: Vec<AttributePatch>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub changes: Vec<BulkUpdateChange>,
+}
+
+pub async fn bulk_update_attributes(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(Request { targets, patches }): Json<Request>,
+) -> ComponentApiResult<ForceChangeSetResponse<Response>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let report = Component::bulk_update_attributes(&ctx, targets, patches).await?;
+    if !report.applied() {
+        // Nothing has been committed, so dropping the context discards the forced change set too.
+        return Err(ComponentApiError::BulkUpdateFailed(report.failures));
+    }
+
+    for change in &report.changes {
+        let component = Component::get_by_id(&ctx, change.component_id).await?;
+        let schema_variant = component.schema_variant(&ctx).await?;
+        let prop = AttributeValue::prop(&ctx, change.attribute_value_id).await?;
+
+        ctx.write_audit_log(
+            AuditLogKind::UpdatePropertyEditorValue {
+                component_id: change.component_id.into(),
+                component_name: component.name(&ctx).await?,
+                schema_variant_id: schema_variant.id().into(),
+                schema_variant_display_name: schema_variant.display_name().to_string(),
+                prop_id: prop.id.into(),
+                prop_name: prop.name.to_owned(),
+                attribute_value_id: change.attribute_value_id.into(),
+                before_value: change.before_value.clone(),
+                after_value: change.after_value.clone(),
+            },
+            prop.name,
+        )
+        .await?;
+    }
+
+    let component_ids = report.component_ids();
+    let mut socket_map = HashMap::new();
+    for &component_id in &component_ids {
+        let component = Component::get_by_id(&ctx, component_id).await?;
+        let payload = component
+            .into_frontend_type(
+                &ctx,
+                None,
+                component.change_status(&ctx).await?,
+                &mut socket_map,
+            )
+            .await?;
+        WsEvent::component_updated(&ctx, payload)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    }
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "bulk_update_attributes",
+        serde_json::json!({
+            "how": "/component/bulk_update_attributes",
+            "component_count": component_ids.len(),
+            "change_count": report.changes.len(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(
+        force_change_set_id,
+        Response {
+            changes: report.changes,
+        },
+    ))
+}
@@ -0,0 +1,34 @@
+use axum::{extract::Path, Json};
+use dal::{
+    attribute::value::provenance::ProvenanceNode, AttributeValue, AttributeValueId, ChangeSetId,
+    ComponentId, WorkspacePk,
+};
+
+use super::{ComponentApiError, ComponentApiResult};
+use crate::extract::{AccessBuilder, HandlerContext};
+
+pub async fn get_provenance(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id, component_id, attribute_value_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        ComponentId,
+        AttributeValueId,
+    )>,
+) -> ComponentApiResult<Json<ProvenanceNode>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    if AttributeValue::component_id(&ctx, attribute_value_id).await? != component_id {
+        return Err(ComponentApiError::AttributeValueNotForComponent(
+            attribute_value_id,
+            component_id,
+        ));
+    }
+
+    Ok(Json(
+        AttributeValue::provenance(&ctx, attribute_value_id).await?,
+    ))
+}
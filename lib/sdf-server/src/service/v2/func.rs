@@ -9,22 +9,24 @@ use dal::{
     attribute::{prototype::argument::AttributePrototypeArgumentError, value::AttributeValueError},
     func::{
         argument::FuncArgumentError, authoring::FuncAuthoringError, binding::FuncBindingError,
-        runner::FuncRunnerError,
+        runner::FuncRunnerError, test_case::FuncTestCaseError, version::FuncVersionError,
     },
     workspace_snapshot::graph::WorkspaceSnapshotGraphError,
     ChangeSetError, DalContext, Func, FuncError, FuncId, SchemaVariantError,
     WorkspaceSnapshotError, WsEventError,
 };
+use permissions::Permission;
 use si_frontend_types::FuncCode;
 use si_layer_cache::LayerDbError;
 use telemetry::prelude::*;
 use thiserror::Error;
 use veritech_client::FunctionResultFailureErrorKind;
 
-use crate::{service::ApiError, AppState};
+use crate::{middleware::ScopedPermissionLayer, service::ApiError, AppState};
 
 pub mod argument;
 pub mod binding;
+pub mod cancel_func_run;
 pub mod create_func;
 pub mod create_unlocked_copy;
 pub mod delete_func;
@@ -35,8 +37,12 @@ pub mod get_func_run;
 pub mod list_all_funcs;
 pub mod list_funcs;
 pub mod save_code;
+pub mod search_func_run_logs;
+pub mod test_case;
 pub mod test_execute;
 pub mod update_func;
+pub mod update_func_timeout;
+pub mod version;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -65,6 +71,12 @@ pub enum FuncAPIError {
     FuncNameReserved(String),
     #[error("The function does not exist")]
     FuncNotFound(FuncId),
+    #[error("func runner error: {0}")]
+    FuncRunner(#[from] FuncRunnerError),
+    #[error("func test case error: {0}")]
+    FuncTestCase(#[from] FuncTestCaseError),
+    #[error("func version error: {0}")]
+    FuncVersion(#[from] FuncVersionError),
     #[error("hyper error: {0}")]
     Http(#[from] axum::http::Error),
     #[error("layer db error: {0}")]
@@ -143,12 +155,23 @@ impl IntoResponse for FuncAPIError {
             | Self::MissingPrototypeId
             | Self::MissingSchemaVariantAndFunc
             | Self::Func(FuncError::FuncLocked(_))
+            | Self::FuncTestCase(FuncTestCaseError::InvalidFixtureArgs)
+            | Self::FuncVersion(FuncVersionError::FuncLocked(_))
+            | Self::FuncVersion(FuncVersionError::InvalidVersion(_))
+            | Self::FuncAuthoring(FuncAuthoringError::InvalidFuncTimeout(_))
             | Self::SchemaVariant(dal::SchemaVariantError::SchemaVariantLocked(_)) => {
                 (StatusCode::BAD_REQUEST, None)
             }
+            Self::FuncTestCase(FuncTestCaseError::NameInUse(_, _))
+            | Self::FuncRunner(FuncRunnerError::FuncRunNotCancellable(_, _)) => {
+                (StatusCode::CONFLICT, None)
+            }
 
             // Return 404 when the func is not found
             Self::FuncNotFound(_) |
+            Self::FuncRunner(FuncRunnerError::FuncRunNotFound(_)) |
+            Self::FuncVersion(FuncVersionError::VersionNotFound(_, _)) |
+            Self::FuncAuthoring(FuncAuthoringError::FuncVersion(FuncVersionError::VersionNotFound(_, _))) |
             // When a graph node cannot be found for a schema variant, it is not found
             Self::SchemaVariant(dal::SchemaVariantError::NotFound(_)) => (StatusCode::NOT_FOUND, None),
 
@@ -174,6 +197,7 @@ impl IntoResponse for FuncAPIError {
             Self::FuncAuthoring(FuncAuthoringError::AttributeValue(AttributeValueError::FuncRunner(err))) =>
                 func_runner_err_to_status_and_message(*err),
             Self::FuncAuthoring(FuncAuthoringError::FuncRunner(err)) => func_runner_err_to_status_and_message(err),
+            Self::FuncRunner(err) => func_runner_err_to_status_and_message(err),
 
 
             _ => (ApiError::DEFAULT_ERROR_STATUS_CODE, None)
@@ -183,57 +207,118 @@ impl IntoResponse for FuncAPIError {
     }
 }
 
-pub fn v2_routes() -> Router<AppState> {
+pub fn v2_routes(state: AppState) -> Router<AppState> {
     Router::new()
         // Func Stuff
         .route("/", get(list_funcs::list_funcs))
         .route("/including_pruned", get(list_all_funcs::list_all_funcs))
         .route("/code", get(get_code::get_code)) // accepts a list of func_ids
-        .route("/runs/:func_run_id", get(get_func_run::get_func_run)) // accepts a list of func_ids
-        .route("/", post(create_func::create_func))
-        .route("/:func_id", put(update_func::update_func)) // only save the func's metadata
-        .route("/:func_id/code", put(save_code::save_code)) // only saves func code
-        .route("/:func_id/test_execute", post(test_execute::test_execute))
-        .route("/:func_id/execute", post(execute_func::execute_func))
-        .route(
-            "/:func_id",
-            post(create_unlocked_copy::create_unlocked_copy),
-        )
-        .route("/:func_id", delete(delete_func::delete_func))
-        // Func Bindings
         .route(
-            "/:func_id/bindings",
-            post(binding::create_binding::create_binding),
-        )
-        .route(
-            "/:func_id/bindings",
-            delete(binding::delete_binding::delete_binding),
+            "/runs/logs/search",
+            get(search_func_run_logs::search_func_run_logs),
         )
+        .route("/runs/:func_run_id", get(get_func_run::get_func_run)) // accepts a list of func_ids
         .route(
-            "/:func_id/bindings",
-            put(binding::update_binding::update_binding),
+            "/:func_id/generate_aws_function",
+            get(generate_aws_function::generate_aws_function),
         )
-        // Reset Attribute Bindings
         .route(
-            "/:func_id/reset_attribute_binding",
-            post(binding::attribute::reset_attribute_binding::reset_attribute_binding),
+            "/:func_id/test_cases",
+            get(test_case::list_test_cases::list_test_cases),
         )
-        // Func Arguments
         .route(
-            "/:func_id/arguments",
-            post(argument::create_argument::create_func_argument),
+            "/:func_id/versions",
+            get(version::list_versions::list_versions),
         )
         .route(
-            "/:func_id/arguments/:func_argument_id",
-            put(argument::update_argument::update_func_argument),
+            "/:func_id/versions/diff",
+            get(version::diff_versions::diff_versions),
         )
-        .route(
-            "/:func_id/arguments/:func_argument_id",
-            delete(argument::delete_argument::delete_func_argument),
+        .merge(
+            Router::new()
+                .route("/", post(create_func::create_func))
+                .route("/:func_id", put(update_func::update_func)) // only save the func's metadata
+                .route("/:func_id/code", put(save_code::save_code)) // only saves func code
+                .route(
+                    "/:func_id/timeout",
+                    put(update_func_timeout::update_func_timeout),
+                )
+                .route("/:func_id/test_execute", post(test_execute::test_execute))
+                .route("/:func_id/execute", post(execute_func::execute_func))
+                .route(
+                    "/:func_id",
+                    post(create_unlocked_copy::create_unlocked_copy),
+                )
+                .route("/:func_id", delete(delete_func::delete_func))
+                // Func Bindings
+                .route(
+                    "/:func_id/bindings",
+                    post(binding::create_binding::create_binding),
+                )
+                .route(
+                    "/:func_id/bindings",
+                    delete(binding::delete_binding::delete_binding),
+                )
+                .route(
+                    "/:func_id/bindings",
+                    put(binding::update_binding::update_binding),
+                )
+                // Reset Attribute Bindings
+                .route(
+                    "/:func_id/reset_attribute_binding",
+                    post(binding::attribute::reset_attribute_binding::reset_attribute_binding),
+                )
+                // Func Arguments
+                .route(
+                    "/:func_id/arguments",
+                    post(argument::create_argument::create_func_argument),
+                )
+                .route(
+                    "/:func_id/arguments/:func_argument_id",
+                    put(argument::update_argument::update_func_argument),
+                )
+                .route(
+                    "/:func_id/arguments/:func_argument_id",
+                    delete(argument::delete_argument::delete_func_argument),
+                )
+                // Func Test Cases
+                .route(
+                    "/:func_id/test_cases",
+                    post(test_case::create_test_case::create_test_case),
+                )
+                .route(
+                    "/:func_id/test_cases/run",
+                    post(test_case::run_test_cases::run_test_cases),
+                )
+                .route(
+                    "/:func_id/test_cases/:test_case_id",
+                    put(test_case::update_test_case::update_test_case),
+                )
+                .route(
+                    "/:func_id/test_cases/:test_case_id",
+                    delete(test_case::delete_test_case::delete_test_case),
+                )
+                // Func Versions
+                .route(
+                    "/:func_id/versions/draft",
+                    put(version::update_draft::update_draft),
+                )
+                .route(
+                    "/:func_id/versions/:version/restore",
+                    post(version::restore_version::restore_version),
+                )
+                .route_layer(ScopedPermissionLayer::new(
+                    state.clone(),
+                    Permission::AuthorFuncs,
+                )),
         )
-        .route(
-            "/:func_id/generate_aws_function",
-            get(generate_aws_function::generate_aws_function),
+        .merge(
+            Router::new()
+                .route(
+                    "/runs/:func_run_id/cancel",
+                    post(cancel_func_run::cancel_func_run),
+                )
+                .route_layer(ScopedPermissionLayer::new(state, Permission::RunActions)),
         )
 }
 
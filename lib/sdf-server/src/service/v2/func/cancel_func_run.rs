@@ -0,0 +1,41 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{func::runner::FuncRunner, ChangeSetId, WorkspacePk};
+use si_events::FuncRunId;
+
+use super::FuncAPIResult;
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    track,
+};
+
+pub async fn cancel_func_run(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_run_id)): Path<(WorkspacePk, ChangeSetId, FuncRunId)>,
+) -> FuncAPIResult<()> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    FuncRunner::cancel_execution(&ctx, func_run_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "cancel_func_run",
+        serde_json::json!({
+            "how": "/func/runs/cancel",
+            "func_run_id": func_run_id,
+        }),
+    );
+
+    // We commit without a rebase here because only the func run table has changed.
+    ctx.commit_no_rebase().await?;
+
+    Ok(())
+}
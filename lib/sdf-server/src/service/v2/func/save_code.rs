@@ -3,8 +3,9 @@ use axum::{
     Json,
 };
 use dal::{
-    func::authoring::FuncAuthoringClient, ChangeSet, ChangeSetId, Func, FuncId, WorkspacePk,
-    WsEvent,
+    func::{authoring::FuncAuthoringClient, test_case::FuncTestCase},
+    job::definition::FuncTestCasesJob,
+    ChangeSet, ChangeSetId, Func, FuncId, WorkspacePk, WsEvent,
 };
 use serde::{Deserialize, Serialize};
 
@@ -57,6 +58,16 @@ pub async fn save_code(
         }),
     );
 
+    // Test cases can take a while to run, so run them in the background and report their results
+    // over the websocket rather than holding up the save.
+    if !FuncTestCase::list_ids_for_func(&ctx, func_id)
+        .await?
+        .is_empty()
+    {
+        ctx.enqueue_func_test_cases(FuncTestCasesJob::new(&ctx, func_id))
+            .await?;
+    }
+
     ctx.commit().await?;
 
     Ok(ForceChangeSetResponse::empty(force_change_set_id))
@@ -0,0 +1,83 @@
+use axum::{
+    extract::{Path, Query},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use dal::{ChangeSetId, WorkspacePk};
+use serde::{Deserialize, Serialize};
+use si_events::{FuncRunId, FuncRunLogId, OutputLine, OutputLineFilter};
+
+use super::get_func_run::OutputLineView;
+use crate::{
+    extract::{AccessBuilder, HandlerContext},
+    service::v2::func::FuncAPIResult,
+};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFuncRunLogsRequest {
+    pub query: String,
+    pub level: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunLogSearchResult {
+    pub func_run_id: FuncRunId,
+    pub func_run_log_id: FuncRunLogId,
+    pub change_set_id: si_events::ChangeSetId,
+    pub updated_at: DateTime<Utc>,
+    /// The lines containing any of the query terms.
+    pub lines: Vec<OutputLineView>,
+}
+
+/// Searches the logs of every func run in the workspace, most recently updated first.
+pub async fn search_func_run_logs(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Query(request): Query<SearchFuncRunLogsRequest>,
+) -> FuncAPIResult<Json<Vec<FuncRunLogSearchResult>>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let func_run_logs = ctx
+        .layer_db()
+        .func_run_log()
+        .search_for_workspace(ctx.events_tenancy().workspace_pk, &request.query, limit)
+        .await?;
+
+    let term_filters: Vec<OutputLineFilter> = request
+        .query
+        .split_whitespace()
+        .map(|term| OutputLineFilter {
+            level: request.level.clone(),
+            text: Some(term.to_owned()),
+        })
+        .collect();
+    let matches = |line: &OutputLine| term_filters.iter().any(|filter| filter.matches(line));
+
+    Ok(Json(
+        func_run_logs
+            .into_iter()
+            .map(|func_run_log| FuncRunLogSearchResult {
+                func_run_id: func_run_log.func_run_id(),
+                func_run_log_id: func_run_log.id(),
+                change_set_id: func_run_log.tenancy().change_set_id,
+                updated_at: func_run_log.updated_at(),
+                lines: func_run_log
+                    .logs()
+                    .iter()
+                    .filter(|line| matches(line))
+                    .map(Into::into)
+                    .collect(),
+            })
+            .collect(),
+    ))
+}
@@ -0,0 +1,5 @@
+pub mod create_test_case;
+pub mod delete_test_case;
+pub mod list_test_cases;
+pub mod run_test_cases;
+pub mod update_test_case;
@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::test_case::{FuncTestCase, FuncTestCaseExpectation, FuncTestCaseFixture},
+    ChangeSet, ChangeSetId, Func, FuncId, WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::func::FuncAPIResult},
+    track,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTestCaseRequest {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub fixture: FuncTestCaseFixture,
+    pub expectation: FuncTestCaseExpectation,
+}
+
+pub async fn create_test_case(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id)): Path<(WorkspacePk, ChangeSetId, FuncId)>,
+    Json(request): Json<CreateTestCaseRequest>,
+) -> FuncAPIResult<ForceChangeSetResponse<FuncTestCase>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let func = Func::get_by_id_or_error(&ctx, func_id).await?;
+    let test_case = FuncTestCase::new(
+        &ctx,
+        func_id,
+        request.name,
+        request.description,
+        request.fixture,
+        request.expectation,
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "create_func_test_case",
+        serde_json::json!({
+            "how": "/func/create_test_case",
+            "func_id": func_id,
+            "func_name": func.name.clone(),
+            "test_case_name": test_case.name.clone(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, test_case))
+}
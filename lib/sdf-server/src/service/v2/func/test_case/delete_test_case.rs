@@ -0,0 +1,49 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    func::test_case::{FuncTestCase, FuncTestCaseId},
+    ChangeSet, ChangeSetId, FuncId, WorkspacePk,
+};
+
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::func::FuncAPIResult},
+    track,
+};
+
+pub async fn delete_test_case(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id, test_case_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        FuncId,
+        FuncTestCaseId,
+    )>,
+) -> FuncAPIResult<ForceChangeSetResponse<()>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    FuncTestCase::remove(&ctx, test_case_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "delete_func_test_case",
+        serde_json::json!({
+            "how": "/func/delete_test_case",
+            "func_id": func_id,
+            "test_case_id": test_case_id,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::empty(force_change_set_id))
+}
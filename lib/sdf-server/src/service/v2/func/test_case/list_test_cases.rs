@@ -0,0 +1,19 @@
+use axum::{extract::Path, Json};
+use dal::{func::test_case::FuncTestCase, ChangeSetId, FuncId, WorkspacePk};
+
+use crate::{
+    extract::{AccessBuilder, HandlerContext},
+    service::v2::func::FuncAPIResult,
+};
+
+pub async fn list_test_cases(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id, func_id)): Path<(WorkspacePk, ChangeSetId, FuncId)>,
+) -> FuncAPIResult<Json<Vec<FuncTestCase>>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(FuncTestCase::list_for_func(&ctx, func_id).await?))
+}
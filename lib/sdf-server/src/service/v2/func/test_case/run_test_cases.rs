@@ -0,0 +1,54 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::test_case::{FuncTestCase, FuncTestCaseRunResult},
+    ChangeSetId, Func, FuncId, WorkspacePk, WsEvent,
+};
+
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    service::v2::func::FuncAPIResult,
+    track,
+};
+
+pub async fn run_test_cases(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id)): Path<(WorkspacePk, ChangeSetId, FuncId)>,
+) -> FuncAPIResult<Json<Vec<FuncTestCaseRunResult>>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let func = Func::get_by_id_or_error(&ctx, func_id).await?;
+    let results = FuncTestCase::run_all_for_func(&ctx, func_id).await?;
+
+    WsEvent::func_test_cases_run(&ctx, func_id, results.clone())
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "run_func_test_cases",
+        serde_json::json!({
+            "how": "/func/run_test_cases",
+            "func_id": func_id,
+            "func_name": func.name.clone(),
+            "test_case_count": results.len(),
+            "failed_count": results.iter().filter(|result| !result.passed).count(),
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(results))
+}
@@ -0,0 +1,70 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::test_case::{FuncTestCase, FuncTestCaseExpectation, FuncTestCaseFixture, FuncTestCaseId},
+    ChangeSet, ChangeSetId, FuncId, WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::func::FuncAPIResult},
+    track,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTestCaseRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub fixture: FuncTestCaseFixture,
+    pub expectation: FuncTestCaseExpectation,
+}
+
+pub async fn update_test_case(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id, test_case_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        FuncId,
+        FuncTestCaseId,
+    )>,
+    Json(request): Json<UpdateTestCaseRequest>,
+) -> FuncAPIResult<ForceChangeSetResponse<FuncTestCase>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let test_case = FuncTestCase::modify_by_id(&ctx, test_case_id, |test_case| {
+        test_case.name = request.name;
+        test_case.description = request.description;
+        test_case.fixture = request.fixture;
+        test_case.expectation = request.expectation;
+        Ok(())
+    })
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "update_func_test_case",
+        serde_json::json!({
+            "how": "/func/update_test_case",
+            "func_id": func_id,
+            "test_case_id": test_case_id,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, test_case))
+}
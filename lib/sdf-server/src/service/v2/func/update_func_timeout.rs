@@ -0,0 +1,68 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::authoring::FuncAuthoringClient, ChangeSet, ChangeSetId, FuncId, WorkspacePk, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+use si_frontend_types::FuncSummary;
+
+use super::FuncAPIResult;
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    service::force_change_set_response::ForceChangeSetResponse,
+    track,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFuncTimeoutRequest {
+    /// Leaving this unset falls back to the default timeout of the function executor.
+    pub timeout_secs: Option<u64>,
+}
+
+pub async fn update_func_timeout(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id)): Path<(WorkspacePk, ChangeSetId, FuncId)>,
+    Json(request): Json<UpdateFuncTimeoutRequest>,
+) -> FuncAPIResult<ForceChangeSetResponse<FuncSummary>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let updated_func =
+        FuncAuthoringClient::update_func_timeout(&ctx, func_id, request.timeout_secs)
+            .await?
+            .into_frontend_type(&ctx)
+            .await?;
+
+    WsEvent::func_updated(&ctx, updated_func.clone(), None)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "update_func_timeout",
+        serde_json::json!({
+            "how": "/func/update_func_timeout",
+            "func_id": func_id,
+            "func_name": updated_func.name.clone(),
+            "timeout_secs": request.timeout_secs,
+        }),
+    );
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(
+        force_change_set_id,
+        updated_func,
+    ))
+}
@@ -0,0 +1,4 @@
+pub mod diff_versions;
+pub mod list_versions;
+pub mod restore_version;
+pub mod update_draft;
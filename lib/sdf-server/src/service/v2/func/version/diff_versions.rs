@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Path, Query},
+    Json,
+};
+use dal::{
+    func::version::{FuncSemVer, FuncVersion, FuncVersionDiff},
+    ChangeSetId, FuncId, WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{AccessBuilder, HandlerContext},
+    service::v2::func::FuncAPIResult,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffVersionsRequest {
+    pub from: FuncSemVer,
+    /// Diffs against the func's current code when omitted.
+    pub to: Option<FuncSemVer>,
+}
+
+pub async fn diff_versions(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id, func_id)): Path<(WorkspacePk, ChangeSetId, FuncId)>,
+    Query(request): Query<DiffVersionsRequest>,
+) -> FuncAPIResult<Json<FuncVersionDiff>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(
+        FuncVersion::diff(&ctx, func_id, request.from, request.to).await?,
+    ))
+}
@@ -0,0 +1,33 @@
+use axum::{extract::Path, Json};
+use dal::{
+    func::version::{FuncVersion, FuncVersionDraft},
+    ChangeSetId, FuncId, WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{AccessBuilder, HandlerContext},
+    service::v2::func::FuncAPIResult,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListVersionsResponse {
+    pub versions: Vec<FuncVersion>,
+    pub draft: Option<FuncVersionDraft>,
+}
+
+pub async fn list_versions(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id, func_id)): Path<(WorkspacePk, ChangeSetId, FuncId)>,
+) -> FuncAPIResult<Json<ListVersionsResponse>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(ListVersionsResponse {
+        versions: FuncVersion::list_for_func(&ctx, func_id).await?,
+        draft: FuncVersion::get_draft(&ctx, func_id).await?,
+    }))
+}
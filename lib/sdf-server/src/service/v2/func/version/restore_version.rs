@@ -0,0 +1,84 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    func::{authoring::FuncAuthoringClient, version::FuncSemVer},
+    ChangeSet, ChangeSetId, Func, FuncId, WorkspacePk, WsEvent,
+};
+
+use serde::{Deserialize, Serialize};
+use si_frontend_types::{FuncCode, FuncSummary};
+
+use super::super::{get_code_response, FuncAPIResult};
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    service::force_change_set_response::ForceChangeSetResponse,
+    track,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreVersionResponse {
+    summary: FuncSummary,
+    code: FuncCode,
+}
+
+pub async fn restore_version(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id, version)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        FuncId,
+        FuncSemVer,
+    )>,
+) -> FuncAPIResult<ForceChangeSetResponse<RestoreVersionResponse>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let was_locked = Func::get_by_id_or_error(&ctx, func_id).await?.is_locked;
+    let func = FuncAuthoringClient::restore_func_version(&ctx, func_id, version).await?;
+    let code = get_code_response(&ctx, func.id).await?;
+    let summary = func.into_frontend_type(&ctx).await?;
+
+    // Restoring a locked func creates a new unlocked copy, otherwise the code is replaced in place
+    if was_locked {
+        WsEvent::func_created(&ctx, summary.clone())
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    } else {
+        WsEvent::func_updated(&ctx, summary.clone(), None)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+        WsEvent::func_code_saved(&ctx, code.clone(), false)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    }
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "restore_func_version",
+        serde_json::json!({
+            "how": "/func/restore_version",
+            "func_id": summary.func_id,
+            "func_name": summary.name.to_owned(),
+            "version": version.to_string(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(
+        force_change_set_id,
+        RestoreVersionResponse { summary, code },
+    ))
+}
@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::version::{FuncVersion, FuncVersionBump, FuncVersionDraft},
+    ChangeSet, ChangeSetId, FuncId, WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::func::FuncAPIResult},
+    track,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDraftRequest {
+    #[serde(default)]
+    pub bump: FuncVersionBump,
+    pub message: Option<String>,
+}
+
+pub async fn update_draft(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id)): Path<(WorkspacePk, ChangeSetId, FuncId)>,
+    Json(request): Json<UpdateDraftRequest>,
+) -> FuncAPIResult<ForceChangeSetResponse<FuncVersionDraft>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let draft = FuncVersion::set_draft(&ctx, func_id, request.bump, request.message).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "update_func_version_draft",
+        serde_json::json!({
+            "how": "/func/update_version_draft",
+            "func_id": func_id,
+            "bump": draft.bump,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, draft))
+}
@@ -1,4 +1,5 @@
 use crate::{
+    middleware::ScopedPermissionLayer,
     service::{force_change_set_response::ForceChangeSetResponse, ApiError},
     AppState,
 };
@@ -17,6 +18,7 @@ use dal::{
     },
     ChangeSet, ChangeSetError, ChangeSetId, ComponentId, TransactionsError, WorkspacePk,
 };
+use permissions::Permission;
 use serde::{Deserialize, Serialize};
 use si_layer_cache::LayerDbError;
 use telemetry::prelude::*;
@@ -30,6 +32,7 @@ use crate::{
 
 use super::func::FuncAPIError;
 
+mod dry_run;
 mod history;
 mod latest;
 
@@ -145,15 +148,23 @@ pub async fn run_prototype(
     ))
 }
 
-pub fn v2_routes() -> Router<AppState> {
+pub fn v2_routes(state: AppState) -> Router<AppState> {
     Router::new()
-        .route(
-            "/prototype/:prototypeId/:componentId/:viewId",
-            post(run_prototype),
-        )
         .route(
             "/prototype/:prototypeId/:componentId/latest",
             get(latest::latest),
         )
         .route("/history", get(history::history))
+        .merge(
+            Router::new()
+                .route(
+                    "/prototype/:prototypeId/:componentId/:viewId",
+                    post(run_prototype),
+                )
+                .route(
+                    "/prototype/:prototypeId/:componentId/:viewId/dry_run",
+                    post(dry_run::dry_run_prototype),
+                )
+                .route_layer(ScopedPermissionLayer::new(state, Permission::Edit)),
+        )
 }
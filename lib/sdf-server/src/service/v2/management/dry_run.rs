@@ -0,0 +1,102 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    diagram::view::ViewId,
+    management::{
+        plan::ManagementPlan,
+        prototype::{ManagementPrototype, ManagementPrototypeId},
+        ManagementFuncReturn, ManagementOperator,
+    },
+    ChangeSetId, ComponentId, WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+use si_events::FuncRunId;
+use veritech_client::ManagementFuncStatus;
+
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    track,
+};
+
+use super::{ManagementApiError, ManagementApiResult};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunPrototypeResponse {
+    func_run_id: FuncRunId,
+    status: ManagementFuncStatus,
+    message: Option<String>,
+    plan: Option<ManagementPlan>,
+}
+
+/// Runs the management func and returns the plan of what running it would do, without changing
+/// the change set.
+pub async fn dry_run_prototype(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, prototype_id, component_id, view_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        ManagementPrototypeId,
+        ComponentId,
+        ViewId,
+    )>,
+) -> ManagementApiResult<Json<DryRunPrototypeResponse>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let mut execution_result =
+        ManagementPrototype::execute_by_id(&ctx, prototype_id, component_id, view_id.into())
+            .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "dry_run_prototype",
+        serde_json::json!({
+            "how": "/management/dry_run_prototype",
+            "view_id": view_id,
+            "prototype_id": prototype_id,
+            "component_id": component_id,
+        }),
+    );
+
+    let func_run_id = execution_result.func_run_id;
+    let Some(result) = execution_result.result.take() else {
+        return Err(ManagementApiError::ManagementPrototypeExecutionFailure(
+            prototype_id,
+        ));
+    };
+    let result: ManagementFuncReturn = result.try_into()?;
+
+    let plan = match (result.status, result.operations) {
+        (ManagementFuncStatus::Ok, Some(operations)) => Some(
+            ManagementOperator::new(
+                &ctx,
+                component_id,
+                operations,
+                execution_result,
+                Some(view_id),
+            )
+            .await?
+            .plan()
+            .await?,
+        ),
+        _ => None,
+    };
+
+    Ok(Json(DryRunPrototypeResponse {
+        func_run_id,
+        status: result.status,
+        message: result.message,
+        plan,
+    }))
+}
@@ -4,11 +4,12 @@ use axum::{
     routing::{get, post},
     Router,
 };
+use permissions::Permission;
 use si_frontend_types as frontend_types;
 use telemetry::prelude::*;
 use thiserror::Error;
 
-use crate::{service::ApiError, AppState};
+use crate::{middleware::ScopedPermissionLayer, service::ApiError, AppState};
 
 mod contribute;
 mod sync;
@@ -53,8 +54,10 @@ impl IntoResponse for ModulesAPIError {
     }
 }
 
-pub fn v2_routes() -> Router<AppState> {
-    Router::new()
-        .route("/contribute", post(contribute::contribute))
-        .route("/sync", get(sync::sync))
+pub fn v2_routes(state: AppState) -> Router<AppState> {
+    Router::new().route("/sync", get(sync::sync)).merge(
+        Router::new()
+            .route("/contribute", post(contribute::contribute))
+            .route_layer(ScopedPermissionLayer::new(state, Permission::AuthorFuncs)),
+    )
 }
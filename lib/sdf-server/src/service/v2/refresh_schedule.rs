@@ -0,0 +1,57 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Router,
+};
+use dal::action::refresh_schedule::{RefreshScheduleError, RefreshScheduleId};
+use permissions::Permission;
+use thiserror::Error;
+
+use crate::{middleware::ScopedPermissionLayer, service::ApiError, AppState};
+
+mod create;
+mod list;
+mod remove;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum RefreshScheduleApiError {
+    #[error("refresh schedule error: {0}")]
+    RefreshSchedule(#[from] RefreshScheduleError),
+    #[error("refresh schedule not found: {0}")]
+    RefreshScheduleNotFound(RefreshScheduleId),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] dal::TransactionsError),
+}
+
+pub type RefreshScheduleApiResult<T> = Result<T, RefreshScheduleApiError>;
+
+impl IntoResponse for RefreshScheduleApiError {
+    fn into_response(self) -> Response {
+        let status_code = match &self {
+            Self::RefreshSchedule(
+                RefreshScheduleError::CronNeverMatches(_)
+                | RefreshScheduleError::CronParse(..)
+                | RefreshScheduleError::IntervalTooShort(_),
+            ) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::RefreshSchedule(
+                RefreshScheduleError::ComponentNotFound(_)
+                | RefreshScheduleError::SchemaVariantNotFound(_),
+            )
+            | Self::RefreshScheduleNotFound(_) => StatusCode::NOT_FOUND,
+            _ => ApiError::DEFAULT_ERROR_STATUS_CODE,
+        };
+
+        ApiError::new(status_code, self).into_response()
+    }
+}
+
+pub fn v2_routes(state: AppState) -> Router<AppState> {
+    Router::new().route("/", get(list::list)).merge(
+        Router::new()
+            .route("/", post(create::create))
+            .route("/:refresh_schedule_id", delete(remove::remove))
+            .route_layer(ScopedPermissionLayer::new(state, Permission::RunActions)),
+    )
+}
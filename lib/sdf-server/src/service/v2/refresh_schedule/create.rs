@@ -0,0 +1,53 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    action::refresh_schedule::{RefreshCadence, RefreshSchedule, RefreshScheduleTarget},
+    WorkspacePk,
+};
+use serde::Deserialize;
+
+use super::RefreshScheduleApiResult;
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    track,
+};
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRefreshScheduleRequest {
+    target: RefreshScheduleTarget,
+    cadence: RefreshCadence,
+}
+
+pub async fn create(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path(_workspace_pk): Path<WorkspacePk>,
+    Json(request): Json<CreateRefreshScheduleRequest>,
+) -> RefreshScheduleApiResult<Json<RefreshSchedule>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let schedule = RefreshSchedule::new(&ctx, request.target, request.cadence).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "create_refresh_schedule",
+        serde_json::json!({
+            "refresh_schedule_id": schedule.id,
+            "target": schedule.target,
+            "cadence": schedule.cadence,
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(schedule))
+}
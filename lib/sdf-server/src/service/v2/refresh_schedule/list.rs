@@ -0,0 +1,15 @@
+use axum::{extract::Path, Json};
+use dal::{action::refresh_schedule::RefreshSchedule, WorkspacePk};
+
+use super::RefreshScheduleApiResult;
+use crate::extract::{AccessBuilder, HandlerContext};
+
+pub async fn list(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path(_workspace_pk): Path<WorkspacePk>,
+) -> RefreshScheduleApiResult<Json<Vec<RefreshSchedule>>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    Ok(Json(RefreshSchedule::list(&ctx).await?))
+}
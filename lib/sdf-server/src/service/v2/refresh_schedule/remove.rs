@@ -0,0 +1,43 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    action::refresh_schedule::{RefreshSchedule, RefreshScheduleId},
+    WorkspacePk,
+};
+
+use super::{RefreshScheduleApiError, RefreshScheduleApiResult};
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    track,
+};
+
+pub async fn remove(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, refresh_schedule_id)): Path<(WorkspacePk, RefreshScheduleId)>,
+) -> RefreshScheduleApiResult<()> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    if !RefreshSchedule::remove(&ctx, refresh_schedule_id).await? {
+        return Err(RefreshScheduleApiError::RefreshScheduleNotFound(
+            refresh_schedule_id,
+        ));
+    }
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "remove_refresh_schedule",
+        serde_json::json!({
+            "refresh_schedule_id": refresh_schedule_id,
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(())
+}
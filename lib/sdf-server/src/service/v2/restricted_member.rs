@@ -0,0 +1,48 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{post, put},
+    Router,
+};
+use permissions::Permission;
+use thiserror::Error;
+
+use crate::{middleware::WorkspacePermissionLayer, service::ApiError, AppState};
+
+mod grant;
+mod restrict;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum RestrictedMemberApiError {
+    #[error("permissions error: {0}")]
+    Permissions(#[from] permissions::Error),
+    #[error("spicedb not found")]
+    SpiceDBNotFound,
+}
+
+pub type RestrictedMemberApiResult<T> = Result<T, RestrictedMemberApiError>;
+
+impl IntoResponse for RestrictedMemberApiError {
+    fn into_response(self) -> Response {
+        let status_code = match &self {
+            Self::Permissions(permissions::Error::NotGrantable(_)) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            Self::Permissions(permissions::Error::ScopeNotSupported(_)) => StatusCode::BAD_REQUEST,
+            _ => ApiError::DEFAULT_ERROR_STATUS_CODE,
+        };
+
+        ApiError::new(status_code, self).into_response()
+    }
+}
+
+pub fn v2_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/:user_id",
+            put(restrict::restrict).delete(restrict::unrestrict),
+        )
+        .route("/:user_id/grants", post(grant::grant).delete(grant::revoke))
+        .route_layer(WorkspacePermissionLayer::new(state, Permission::Manage))
+}
@@ -0,0 +1,59 @@
+use axum::extract::{Json, Path, State};
+use dal::{UserPk, WorkspacePk};
+use permissions::{Permission, Scope};
+use serde::Deserialize;
+
+use super::{RestrictedMemberApiError, RestrictedMemberApiResult};
+use crate::AppState;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GrantRequest {
+    permission: Permission,
+    /// Limits an edit grant to a change set, view or schema. Without one, the permission applies
+    /// to the whole workspace.
+    #[serde(default)]
+    scope: Option<Scope>,
+}
+
+pub async fn grant(
+    State(mut state): State<AppState>,
+    Path((workspace_pk, user_pk)): Path<(WorkspacePk, UserPk)>,
+    Json(request): Json<GrantRequest>,
+) -> RestrictedMemberApiResult<()> {
+    let client = state
+        .spicedb_client()
+        .ok_or(RestrictedMemberApiError::SpiceDBNotFound)?;
+
+    permissions::grant(
+        client,
+        workspace_pk.into(),
+        user_pk.into(),
+        request.permission,
+        request.scope,
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn revoke(
+    State(mut state): State<AppState>,
+    Path((workspace_pk, user_pk)): Path<(WorkspacePk, UserPk)>,
+    Json(request): Json<GrantRequest>,
+) -> RestrictedMemberApiResult<()> {
+    let client = state
+        .spicedb_client()
+        .ok_or(RestrictedMemberApiError::SpiceDBNotFound)?;
+
+    permissions::revoke(
+        client,
+        workspace_pk.into(),
+        user_pk.into(),
+        request.permission,
+        request.scope,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use axum::extract::{Json, Path, State};
+use dal::{UserPk, WorkspacePk};
+use serde::Deserialize;
+
+use super::{RestrictedMemberApiError, RestrictedMemberApiResult};
+use crate::AppState;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RestrictRequest {
+    /// Whether the member can only create components of the schemas they are granted edit on.
+    #[serde(default)]
+    limit_to_schemas: bool,
+}
+
+pub async fn restrict(
+    State(mut state): State<AppState>,
+    Path((workspace_pk, user_pk)): Path<(WorkspacePk, UserPk)>,
+    Json(request): Json<RestrictRequest>,
+) -> RestrictedMemberApiResult<()> {
+    let client = state
+        .spicedb_client()
+        .ok_or(RestrictedMemberApiError::SpiceDBNotFound)?;
+
+    permissions::restrict(
+        client,
+        workspace_pk.into(),
+        user_pk.into(),
+        request.limit_to_schemas,
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn unrestrict(
+    State(mut state): State<AppState>,
+    Path((workspace_pk, user_pk)): Path<(WorkspacePk, UserPk)>,
+) -> RestrictedMemberApiResult<()> {
+    let client = state
+        .spicedb_client()
+        .ok_or(RestrictedMemberApiError::SpiceDBNotFound)?;
+
+    permissions::unrestrict(client, workspace_pk.into(), user_pk.into()).await?;
+
+    Ok(())
+}
@@ -8,10 +8,11 @@ use dal::{
     cached_module::CachedModuleError, module::ModuleError, ChangeSetError, SchemaVariantId,
     WsEventError,
 };
+use permissions::Permission;
 use telemetry::prelude::*;
 use thiserror::Error;
 
-use crate::{service::ApiError, AppState};
+use crate::{middleware::ScopedPermissionLayer, service::ApiError, AppState};
 
 pub mod create_unlocked_copy;
 mod delete_unlocked_variant;
@@ -65,16 +66,20 @@ impl IntoResponse for SchemaVariantsAPIError {
     }
 }
 
-pub fn v2_routes() -> Router<AppState> {
+pub fn v2_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route("/", get(list_variants::list_variants))
         .route("/:schema_variant_id", get(get_variant::get_variant))
-        .route(
-            "/:schema_variant_id",
-            post(create_unlocked_copy::create_unlocked_copy),
-        )
-        .route(
-            "/:schema_variant_id",
-            delete(delete_unlocked_variant::delete_unlocked_variant),
+        .merge(
+            Router::new()
+                .route(
+                    "/:schema_variant_id",
+                    post(create_unlocked_copy::create_unlocked_copy),
+                )
+                .route(
+                    "/:schema_variant_id",
+                    delete(delete_unlocked_variant::delete_unlocked_variant),
+                )
+                .route_layer(ScopedPermissionLayer::new(state, Permission::AuthorFuncs)),
         )
 }
@@ -1,6 +1,7 @@
 use std::num::ParseIntError;
 
 use crate::app_state::AppState;
+use crate::middleware::ScopedPermissionLayer;
 use crate::service::ApiError;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
@@ -9,12 +10,15 @@ use axum::Router;
 use dal::cached_module::CachedModuleError;
 use dal::component::frame::FrameError;
 use dal::component::inferred_connection_graph::InferredConnectionGraphError;
+use dal::diagram::geometry::Geometry;
+use dal::diagram::view::ViewId;
 use dal::pkg::PkgError;
 use dal::slow_rt::SlowRuntimeError;
 use dal::{
-    ChangeSetError, ComponentError, SchemaError, SchemaId, SchemaVariantError, TransactionsError,
-    WorkspaceSnapshotError, WsEventError,
+    ChangeSetError, ComponentError, ComponentId, DalContext, SchemaError, SchemaId,
+    SchemaVariantError, TransactionsError, WorkspaceSnapshotError, WsEventError,
 };
+use permissions::Permission;
 use thiserror::Error;
 use tokio::task::JoinError;
 
@@ -39,6 +43,8 @@ pub enum ViewError {
     ChangeSet(#[from] ChangeSetError),
     #[error("component error: {0}")]
     Component(#[from] ComponentError),
+    #[error("component {0} is not in view {1}")]
+    ComponentNotInView(ComponentId, ViewId),
     #[error("dal diagram error: {0}")]
     DalDiagram(#[from] dal::diagram::DiagramError),
     #[error("frame error: {0}")]
@@ -55,12 +61,16 @@ pub enum ViewError {
     ParseInt(#[from] ParseIntError),
     #[error("paste error")]
     Paste,
+    #[error("permissions error: {0}")]
+    Permissions(#[from] permissions::Error),
     #[error("pkg error: {0}")]
     Pkg(#[from] PkgError),
     #[error("schema error: {0}")]
     Schema(#[from] SchemaError),
     #[error("No schema installed after successful package import for {0}")]
     SchemaNotInstalledAfterImport(SchemaId),
+    #[error("not permitted to create components of schema {0}")]
+    SchemaNotPermitted(SchemaId),
     #[error("schema variant error: {0}")]
     SchemaVariant(#[from] SchemaVariantError),
     #[error("serrde error: {0}")]
@@ -86,9 +96,9 @@ impl IntoResponse for ViewError {
             ViewError::DalDiagram(
                 dal::diagram::DiagramError::DeletingLastGeometryForComponent(_, _),
             )
-            | ViewError::Component(ComponentError::ComponentAlreadyInView(_, _)) => {
-                (StatusCode::FORBIDDEN, self.to_string())
-            }
+            | ViewError::Component(ComponentError::ComponentAlreadyInView(_, _))
+            | ViewError::ComponentNotInView(_, _)
+            | ViewError::SchemaNotPermitted(_) => (StatusCode::FORBIDDEN, self.to_string()),
             ViewError::DalDiagram(dal::diagram::DiagramError::ViewNotFound(_)) => {
                 (StatusCode::NOT_FOUND, self.to_string())
             }
@@ -99,48 +109,69 @@ impl IntoResponse for ViewError {
     }
 }
 
-pub fn v2_routes() -> Router<AppState> {
+/// Returns an error unless the [`Component`](dal::Component) has a geometry in the view. Edit
+/// permissions for the routes below are checked against the view in the path, so they must not
+/// change components outside of it.
+async fn ensure_component_in_view(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    view_id: ViewId,
+) -> ViewResult<()> {
+    if Geometry::try_get_by_component_and_view(ctx, component_id, view_id)
+        .await?
+        .is_none()
+    {
+        return Err(ViewError::ComponentNotInView(component_id, view_id));
+    }
+    Ok(())
+}
+
+pub fn v2_routes(state: AppState) -> Router<AppState> {
     Router::new()
         // Func Stuff
         .route("/", get(list_views::list_views))
-        .route("/", post(create_view::create_view))
-        .route("/:view_id", put(update_view::update_view))
         .route("/:view_id/get_diagram", get(get_diagram::get_diagram))
         .route("/:view_id/get_geometry", get(get_diagram::get_geometry))
         .route(
             "/default/get_diagram",
             get(get_diagram::get_default_diagram),
         )
-        .route(
-            "/:view_id/component",
-            post(create_component::create_component),
-        )
-        .route(
-            "/:view_id/paste_components",
-            post(paste_component::paste_component),
-        )
-        .route(
-            "/:view_id/erase_components",
-            delete(erase_components::erase_components),
-        )
-        .route(
-            "/:view_id/component/set_geometry",
-            put(set_geometry::set_component_geometry),
-        )
-        .route(
-            "/:view_id/component/set_parent",
-            put(set_component_parent::set_component_parent),
-        )
-        .route(
-            "/:view_id/view_object",
-            post(create_view_object::create_view_object),
-        )
-        .route(
-            "/:view_id/view_object",
-            delete(erase_view_object::erase_view_object),
-        )
-        .route(
-            "/:view_id/view_object/set_geometry",
-            put(set_geometry::set_view_object_geometry),
+        .merge(
+            Router::new()
+                .route("/", post(create_view::create_view))
+                .route("/:view_id", put(update_view::update_view))
+                .route(
+                    "/:view_id/component",
+                    post(create_component::create_component),
+                )
+                .route(
+                    "/:view_id/paste_components",
+                    post(paste_component::paste_component),
+                )
+                .route(
+                    "/:view_id/erase_components",
+                    delete(erase_components::erase_components),
+                )
+                .route(
+                    "/:view_id/component/set_geometry",
+                    put(set_geometry::set_component_geometry),
+                )
+                .route(
+                    "/:view_id/component/set_parent",
+                    put(set_component_parent::set_component_parent),
+                )
+                .route(
+                    "/:view_id/view_object",
+                    post(create_view_object::create_view_object),
+                )
+                .route(
+                    "/:view_id/view_object",
+                    delete(erase_view_object::erase_view_object),
+                )
+                .route(
+                    "/:view_id/view_object/set_geometry",
+                    put(set_geometry::set_view_object_geometry),
+                )
+                .route_layer(ScopedPermissionLayer::new(state, Permission::Edit)),
         )
 }
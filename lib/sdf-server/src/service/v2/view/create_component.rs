@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use axum::extract::Path;
+use axum::extract::{Path, State};
 use axum::{
     extract::{Host, OriginalUri},
     Json,
@@ -22,9 +22,9 @@ use si_events::audit_log::AuditLogKind;
 use si_frontend_types::SchemaVariant as FrontendVariant;
 
 use crate::{
-    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    extract::{AccessBuilder, Authorization, HandlerContext, PosthogClient},
     service::force_change_set_response::ForceChangeSetResponse,
-    track,
+    track, AppState,
 };
 
 use super::{ViewError, ViewResult};
@@ -56,9 +56,12 @@ pub struct CreateComponentResponse {
     pub installed_variant: Option<FrontendVariant>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn create_component(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(access_builder): AccessBuilder,
+    Authorization(claim): Authorization,
+    State(mut state): State<AppState>,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Host(host_name): Host,
@@ -129,6 +132,22 @@ pub async fn create_component(
     };
 
     let variant = SchemaVariant::get_by_id_or_error(&ctx, schema_variant_id).await?;
+
+    // Restricted members can be limited to creating components of some schemas only.
+    if let Some(client) = state.spicedb_client() {
+        let schema_id = variant.schema_id(&ctx).await?;
+        if !permissions::can_use_schema(
+            client,
+            claim.workspace_pk.into(),
+            claim.user_pk.into(),
+            schema_id.into(),
+        )
+        .await?
+        {
+            return Err(ViewError::SchemaNotPermitted(schema_id));
+        }
+    }
+
     let mut component = Component::new(&ctx, &name, variant.id(), view_id).await?;
     let initial_geometry = component.geometry(&ctx, view_id).await?;
     ctx.write_audit_log(
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use telemetry::prelude::*;
 
-use super::{ViewError, ViewResult};
+use super::{ensure_component_in_view, ViewError, ViewResult};
 use crate::{
     extract::{AccessBuilder, HandlerContext, PosthogClient},
     service::force_change_set_response::ForceChangeSetResponse,
@@ -50,6 +50,11 @@ pub async fn paste_component(
 
     let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
 
+    // The originals are only read, but the new parent gains a child.
+    if let Some(parent_id) = request.new_parent_node_id {
+        ensure_component_in_view(&ctx, parent_id, view_id).await?;
+    }
+
     let mut pasted_components_by_original = HashMap::new();
     for component_payload in &request.components {
         let component_id = component_payload.id;
@@ -1,4 +1,4 @@
-use super::ViewResult;
+use super::{ensure_component_in_view, ViewResult};
 use crate::{
     extract::{AccessBuilder, HandlerContext},
     service::force_change_set_response::ForceChangeSetResponse,
@@ -31,7 +31,7 @@ pub struct SetComponentParentResponse {
 pub async fn set_component_parent(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(access_builder): AccessBuilder,
-    Path((_workspace_pk, change_set_id, _view_id)): Path<(WorkspacePk, ChangeSetId, ViewId)>,
+    Path((_workspace_pk, change_set_id, view_id)): Path<(WorkspacePk, ChangeSetId, ViewId)>,
     Json(request): Json<SetComponentParentRequest>,
 ) -> ViewResult<ForceChangeSetResponse<SetComponentParentResponse>> {
     let mut ctx = builder
@@ -40,6 +40,13 @@ pub async fn set_component_parent(
 
     let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
 
+    for (&id, maybe_new_parent) in &request.parent_id_by_component_id {
+        ensure_component_in_view(&ctx, id, view_id).await?;
+        if let Some(new_parent) = *maybe_new_parent {
+            ensure_component_in_view(&ctx, new_parent, view_id).await?;
+        }
+    }
+
     let mut socket_map = HashMap::new();
     for (id, maybe_new_parent) in request.parent_id_by_component_id {
         let component = Component::get_by_id(&ctx, id).await?;
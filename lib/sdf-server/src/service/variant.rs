@@ -8,11 +8,12 @@ use dal::{
     pkg::PkgError, schema::variant::authoring::VariantAuthoringError, ChangeSetError, FuncError,
     FuncId, SchemaError, SchemaId, SchemaVariantId, TransactionsError, WsEventError,
 };
+use permissions::Permission;
 use si_pkg::{SiPkgError, SpecError};
 use telemetry::prelude::*;
 use thiserror::Error;
 
-use crate::AppState;
+use crate::{middleware::ScopedPermissionLayer, AppState};
 
 use super::ApiError;
 
@@ -104,7 +105,7 @@ impl IntoResponse for SchemaVariantError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
+pub fn routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route("/create_variant", post(create_variant::create_variant))
         .route(
@@ -113,4 +114,5 @@ pub fn routes() -> Router<AppState> {
         )
         .route("/clone_variant", post(clone_variant::clone_variant))
         .route("/save_variant", post(save_variant::save_variant))
+        .route_layer(ScopedPermissionLayer::new(state, Permission::AuthorFuncs))
 }
@@ -3,6 +3,8 @@ use crdt::CrdtError;
 use dal::{TransactionsError, WsEventError};
 use nats_multiplexer_client::MultiplexerClientError;
 use si_data_pg::{PgError, PgPoolError};
+use si_events::FuncRunId;
+use si_layer_cache::LayerDbError;
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::sync::TryLockError;
@@ -16,6 +18,10 @@ use super::ApiError;
 pub enum WsError {
     #[error("crdt error: {0}")]
     Crdt(#[from] CrdtError),
+    #[error("func run not found: {0}")]
+    FuncRunNotFound(FuncRunId),
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
     #[error("nats multiplexer client error: {0}")]
     MultiplexerClient(#[from] MultiplexerClientError),
     #[error("nats error: {0}")]
@@ -33,11 +39,16 @@ pub enum WsError {
 }
 
 pub mod crdt;
+pub mod func_run_logs;
 pub mod workspace_updates;
 
 impl IntoResponse for WsError {
     fn into_response(self) -> Response {
-        let (status_code, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+        let status_code = match self {
+            Self::FuncRunNotFound(_) => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        let error_message = self.to_string();
 
         ApiError::new(status_code, error_message).into_response()
     }
@@ -50,4 +61,5 @@ pub fn routes() -> Router<AppState> {
             get(workspace_updates::workspace_updates),
         )
         .route("/crdt", get(crdt::crdt))
+        .route("/func_run_logs", get(func_run_logs::func_run_logs))
 }
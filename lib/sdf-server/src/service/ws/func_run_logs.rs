@@ -0,0 +1,240 @@
+use axum::extract::{
+    ws::{self, WebSocket},
+    Query, State, WebSocketUpgrade,
+};
+use axum::response::IntoResponse;
+use dal::DalLayerDb;
+use nats_multiplexer_client::MultiplexerClientError;
+use serde::{Deserialize, Serialize};
+use si_data_nats::Subject;
+use si_events::{FuncRunId, OutputLineFilter};
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_util::sync::CancellationToken;
+
+use super::WsError;
+use crate::{
+    app_state::ServicesContext, extract::WsAuthorization, nats_multiplexer::NatsMultiplexerClients,
+    service::v2::func::get_func_run::OutputLineView,
+};
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum FuncRunLogsError {
+    #[error("axum error: {0}")]
+    Axum(#[from] axum::Error),
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] si_layer_cache::LayerDbError),
+    #[error("nats multiplexer client error: {0}")]
+    MultiplexerClient(#[from] MultiplexerClientError),
+    #[error("serde json error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+type Result<T> = std::result::Result<T, FuncRunLogsError>;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunLogsRequest {
+    pub func_run_id: FuncRunId,
+    /// The number of log lines the client has already seen, used to resume a dropped stream.
+    #[serde(default)]
+    pub offset: usize,
+    pub level: Option<String>,
+    pub text: Option<String>,
+}
+
+/// Messages sent down the socket. Every message carries the offset to resume from, which counts
+/// all lines of the log, including those skipped by the filter.
+#[remain::sorted]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum FuncRunLogsMessage {
+    /// The log is finalized and no further lines will be sent; the socket is closed afterwards.
+    Finished { offset: usize },
+    Lines {
+        lines: Vec<OutputLineView>,
+        offset: usize,
+    },
+}
+
+/// Just enough of a [`WsEvent`](dal::WsEvent) to pick out updates to the streamed log.
+#[derive(Debug, Deserialize)]
+struct WsEventEnvelope {
+    payload: WsEventEnvelopePayload,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", content = "data")]
+enum WsEventEnvelopePayload {
+    #[serde(rename_all = "camelCase")]
+    FuncRunLogUpdated { func_run_id: FuncRunId },
+    #[serde(other)]
+    Other,
+}
+
+/// Streams the logs of a single func run as they are produced.
+///
+/// Log lines are never buffered per client: update events only wake the stream up, and every
+/// wake up sends whatever lines were written after the client's offset. A slow client therefore
+/// receives fewer, larger batches rather than growing a queue on the server.
+///
+/// The log is always read from the database, which is written before the update event is sent.
+/// The memory cache is only updated once the layer db's own update event arrives, which may be
+/// after ours.
+pub async fn func_run_logs(
+    wsu: WebSocketUpgrade,
+    WsAuthorization(claim): WsAuthorization,
+    Query(request): Query<FuncRunLogsRequest>,
+    State(services_context): State<ServicesContext>,
+    State(shutdown_token): State<CancellationToken>,
+    State(nats_multiplexer_clients): State<NatsMultiplexerClients>,
+) -> std::result::Result<impl IntoResponse, WsError> {
+    let workspace_pk = claim.workspace_pk;
+    let layer_db = services_context.layer_db().clone();
+
+    // Subscribe before reading the log for the first time so that no update can be missed
+    let receiver = nats_multiplexer_clients
+        .ws
+        .try_lock()?
+        .receiver(Subject::from(format!("si.workspace_pk.{workspace_pk}.>")))
+        .await?;
+
+    let func_run = layer_db.func_run().read(request.func_run_id).await?;
+    if func_run.map(|func_run| func_run.workspace_pk()) != Some(workspace_pk.into()) {
+        return Err(WsError::FuncRunNotFound(request.func_run_id));
+    }
+
+    Ok(wsu.on_upgrade(move |mut socket| async move {
+        let stream = FuncRunLogStream {
+            layer_db,
+            func_run_id: request.func_run_id,
+            offset: request.offset,
+            filter: OutputLineFilter {
+                level: request.level,
+                text: request.text,
+            },
+        };
+        match stream.run(&mut socket, receiver, shutdown_token).await {
+            Ok(()) => {
+                if let Err(err) = socket.close().await {
+                    trace!(error = ?err, "failed to close func run log stream");
+                }
+            }
+            // An error is most likely returned when the client side terminates the websocket
+            // session, so this is our "normal" behavior
+            Err(err) => trace!(error = ?err, "func run log stream ended"),
+        }
+    }))
+}
+
+struct FuncRunLogStream {
+    layer_db: DalLayerDb,
+    func_run_id: FuncRunId,
+    offset: usize,
+    filter: OutputLineFilter,
+}
+
+impl FuncRunLogStream {
+    async fn run(
+        mut self,
+        ws: &mut WebSocket,
+        mut receiver: broadcast::Receiver<si_data_nats::Message>,
+        token: CancellationToken,
+    ) -> Result<()> {
+        if self.send_new_lines(ws).await? {
+            return Ok(());
+        }
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => {
+                    trace!("func run log stream has received cancellation");
+                    return Ok(());
+                }
+                msg = ws.recv() => match msg {
+                    Some(Ok(ws::Message::Close(_))) | None => return Ok(()),
+                    Some(Err(err)) => return Err(err.into()),
+                    // The stream is one way, anything else the client sends is ignored
+                    Some(Ok(_)) => {}
+                },
+                recv_result = receiver.recv() => {
+                    match recv_result {
+                        Ok(nats_msg) => {
+                            let Ok(event) = serde_json::from_slice::<WsEventEnvelope>(nats_msg.payload()) else {
+                                continue;
+                            };
+                            match event.payload {
+                                WsEventEnvelopePayload::FuncRunLogUpdated { func_run_id }
+                                    if func_run_id == self.func_run_id => {}
+                                _ => continue,
+                            }
+                        }
+                        // Missed events are fine, the log itself is the source of truth
+                        Err(RecvError::Lagged(skipped)) => {
+                            trace!(skipped, "func run log stream lagged behind workspace events");
+                        }
+                        Err(RecvError::Closed) => return Ok(()),
+                    }
+
+                    if self.send_new_lines(ws).await? {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends the lines written since the last offset, returning `true` once the log is finalized.
+    async fn send_new_lines(&mut self, ws: &mut WebSocket) -> Result<bool> {
+        let Some(func_run_log) = self
+            .layer_db
+            .func_run_log()
+            .get_for_func_run_id(self.func_run_id)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        let logs = func_run_log.logs();
+        let lines: Vec<OutputLineView> = logs
+            .get(self.offset..)
+            .unwrap_or_default()
+            .iter()
+            .filter(|line| self.filter.matches(line))
+            .map(Into::into)
+            .collect();
+        self.offset = self.offset.max(logs.len());
+
+        if !lines.is_empty() {
+            Self::send(
+                ws,
+                &FuncRunLogsMessage::Lines {
+                    lines,
+                    offset: self.offset,
+                },
+            )
+            .await?;
+        }
+
+        if func_run_log.is_finalized() {
+            Self::send(
+                ws,
+                &FuncRunLogsMessage::Finished {
+                    offset: self.offset,
+                },
+            )
+            .await?;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    async fn send(ws: &mut WebSocket, message: &FuncRunLogsMessage) -> Result<()> {
+        ws.send(ws::Message::Text(serde_json::to_string(message)?))
+            .await?;
+        Ok(())
+    }
+}
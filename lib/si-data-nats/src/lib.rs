@@ -11,7 +11,7 @@ use std::{fmt::Debug, hash, io, ops, sync::Arc, time::Duration};
 
 use async_nats::{subject::ToSubject, ToServerAddrs};
 use bytes::Bytes;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::sync::Mutex;
@@ -36,6 +36,8 @@ pub type NatsError = Error;
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum Error {
+    #[error("error deserializing object: {0}")]
+    Deserialize(#[source] serde_json::Error),
     #[error("io error: {0}")]
     Io(#[from] io::Error),
     #[error("nats connect error: {0}")]
@@ -52,6 +54,8 @@ pub enum Error {
     NatsUnsubscribe(#[from] async_nats::UnsubscribeError),
     #[error("error serializing object: {0}")]
     Serialize(#[source] serde_json::Error),
+    #[error("service error reply ({code}): {description}")]
+    ServiceError { code: u16, description: String },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -581,6 +585,71 @@ impl Client {
         Ok(Message::new(msg, self.metadata.clone()))
     }
 
+    /// Sends a request with a JSON serialized payload and deserializes the JSON reply.
+    ///
+    /// Replies carrying the standard NATS service error headers, such as those published by a
+    /// naxum service using the reply middleware, are returned as [`Error::ServiceError`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), si_data_nats::Error> {
+    /// let client = si_data_nats::Client::connect_with_options(
+    ///     "demo.nats.io",
+    ///     None,
+    ///     Default::default(),
+    /// ).await?;
+    /// let response: serde_json::Value = client
+    ///     .request_json("service", &serde_json::json!({ "key": "value" }))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn request_json<T, R>(&self, subject: impl ToSubject, request: &T) -> Result<R>
+    where
+        T: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        self.request_json_with_headers(subject, HeaderMap::new(), request)
+            .await
+    }
+
+    /// Sends a request with headers and a JSON serialized payload and deserializes the JSON
+    /// reply.
+    ///
+    /// See [`Client::request_json`] for how error replies are handled.
+    pub async fn request_json_with_headers<T, R>(
+        &self,
+        subject: impl ToSubject,
+        headers: HeaderMap,
+        request: &T,
+    ) -> Result<R>
+    where
+        T: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let payload = serde_json::to_vec(request).map_err(Error::Serialize)?;
+        let reply = self
+            .request_with_headers(subject, headers, payload.into())
+            .await?;
+
+        if let Some(code) = reply
+            .headers()
+            .and_then(|headers| headers.get(service::NATS_SERVICE_ERROR_CODE))
+        {
+            return Err(Error::ServiceError {
+                code: code.as_str().parse().unwrap_or_default(),
+                description: reply
+                    .headers()
+                    .and_then(|headers| headers.get(service::NATS_SERVICE_ERROR))
+                    .map(|description| description.to_string())
+                    .unwrap_or_default(),
+            });
+        }
+
+        serde_json::from_slice(reply.payload()).map_err(Error::Deserialize)
+    }
+
     /// Sends the request created by the [Request].
     ///
     /// # Examples
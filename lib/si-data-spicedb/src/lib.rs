@@ -16,8 +16,8 @@ use spicedb_client::{
     builder::WriteRelationshipsRequestBuilder, types::ConsistencyRequirement, SpicedbClient,
 };
 use spicedb_grpc::authzed::api::v1::{
-    relationship_update::Operation, Consistency, LookupSubjectsRequest, ObjectReference,
-    WriteRelationshipsRequest,
+    check_bulk_permissions_pair, relationship_update::Operation, Consistency,
+    LookupSubjectsRequest, ObjectReference, WriteRelationshipsRequest,
 };
 use telemetry::prelude::*;
 use thiserror::Error;
@@ -32,6 +32,8 @@ pub use types::{
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum Error {
+    #[error("bulk permission check failed: {0}")]
+    BulkCheck(String),
     #[error("error connecting to spicedb at {1}: {0}")]
     Connection(#[source] spicedb_client::result::Error, Url),
     #[error("spicedb endpoint has no host part: {0}")]
@@ -324,6 +326,32 @@ impl Client {
             .await
     }
 
+    /// Creates the relationships, or leaves them be if they already exist.
+    #[instrument(
+        name = "spicedb_client.touch_relationships",
+        level = "debug",
+        skip_all,
+        fields(
+            db.connection_string = %self.metadata.db_connection_string(),
+            db.system = %self.metadata.db_system(),
+            network.peer.address = self.metadata.network_peer_address(),
+            network.protocol.name = self.metadata.network_protocol_name(),
+            network.transport = self.metadata.network_transport(),
+            otel.kind = SpanKind::Client.as_str(),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+            server.address = self.metadata.server_address(),
+            server.port = self.metadata.server_port(),
+        ),
+    )]
+    pub async fn touch_relationships(
+        &mut self,
+        relationships: Relationships,
+    ) -> Result<Option<ZedToken>> {
+        self.update_relationships(relationships, Operation::Touch)
+            .await
+    }
+
     async fn update_relationships(
         &mut self,
         relationships: Relationships,
@@ -380,6 +408,55 @@ impl Client {
         Ok(Permission::has_permission(resp))
     }
 
+    /// Checks several permissions in a single request. The results are in the same order as the
+    /// permissions.
+    #[instrument(
+        name = "spicedb_client.check_bulk_permissions",
+        level = "debug",
+        skip_all,
+        fields(
+            db.connection_string = %self.metadata.db_connection_string(),
+            db.system = %self.metadata.db_system(),
+            network.peer.address = self.metadata.network_peer_address(),
+            network.protocol.name = self.metadata.network_protocol_name(),
+            network.transport = self.metadata.network_transport(),
+            otel.kind = SpanKind::Client.as_str(),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+            server.address = self.metadata.server_address(),
+            server.port = self.metadata.server_port(),
+        ),
+    )]
+    pub async fn check_bulk_permissions(
+        &mut self,
+        permissions: Vec<Permission>,
+    ) -> Result<Vec<bool>> {
+        let span = current_span_for_instrument_at!("debug");
+
+        let pairs = self
+            .inner
+            .check_bulk_permissions(Permission::into_bulk_request(permissions))
+            .await
+            .map_err(|err| span.record_err(Error::SpiceDb(err)))?
+            .pairs;
+
+        let mut results = Vec::with_capacity(pairs.len());
+        for pair in pairs {
+            match pair.response {
+                Some(check_bulk_permissions_pair::Response::Item(item)) => {
+                    results.push(Permission::has_permission(item.permissionship))
+                }
+                Some(check_bulk_permissions_pair::Response::Error(status)) => {
+                    return Err(span.record_err(Error::BulkCheck(status.message)));
+                }
+                None => return Err(span.record_err(Error::BulkCheck("no response".to_string()))),
+            }
+        }
+
+        span.record_ok();
+        Ok(results)
+    }
+
     #[instrument(
         name = "spicedb_client.lookup_subjects",
         level = "debug",
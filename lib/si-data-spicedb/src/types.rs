@@ -244,6 +244,42 @@ impl Permission {
         }
     }
 
+    /// Builds a single request for all of the permissions. Zed tokens are opaque, so the request
+    /// is only as fresh as the first one given.
+    pub(crate) fn into_bulk_request(permissions: Vec<Self>) -> v1::CheckBulkPermissionsRequest {
+        let requirement = match permissions
+            .iter()
+            .find_map(|permission| permission.zed_token.clone())
+        {
+            Some(z) => ConsistencyRequirement::AtLeastAsFresh(v1::ZedToken { token: z.0 }),
+            None => ConsistencyRequirement::MinimizeLatency(true),
+        };
+        v1::CheckBulkPermissionsRequest {
+            consistency: Some(v1::Consistency {
+                requirement: Some(requirement),
+            }),
+            items: permissions
+                .into_iter()
+                .map(|permission| v1::CheckBulkPermissionsRequestItem {
+                    resource: Some(ObjectReference {
+                        object_type: permission.resource.r#type,
+                        object_id: permission.resource.id,
+                    }),
+                    permission: permission.permission,
+                    subject: Some(SubjectReference {
+                        object: Some(ObjectReference {
+                            object_type: permission.subject.r#type,
+                            object_id: permission.subject.id,
+                        }),
+                        optional_relation: "".to_string(),
+                    }),
+                    context: None,
+                })
+                .collect(),
+            with_tracing: false,
+        }
+    }
+
     pub(crate) fn has_permission(permissionship: i32) -> bool {
         i32::from(v1::check_permission_response::Permissionship::HasPermission) == permissionship
     }
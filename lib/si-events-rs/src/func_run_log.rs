@@ -66,6 +66,29 @@ pub struct OutputLine {
     pub timestamp: u64,
 }
 
+/// Selects the [`OutputLines`](OutputLine) of a [`FuncRunLog`] that match a level and/or contain
+/// some text. Both comparisons are case-insensitive and an empty filter matches every line.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputLineFilter {
+    pub level: Option<String>,
+    pub text: Option<String>,
+}
+
+impl OutputLineFilter {
+    pub fn matches(&self, line: &OutputLine) -> bool {
+        let level_matches = self
+            .level
+            .as_deref()
+            .map_or(true, |level| line.level.eq_ignore_ascii_case(level));
+        let text_matches = self.text.as_deref().map_or(true, |text| {
+            line.message.to_lowercase().contains(&text.to_lowercase())
+        });
+
+        level_matches && text_matches
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct FuncRunLog {
     id: FuncRunLogId,
@@ -41,7 +41,7 @@ pub use crate::{
         FuncBackendResponseType, FuncKind, FuncRun, FuncRunBuilder, FuncRunBuilderError, FuncRunId,
         FuncRunState, FuncRunValue, ManagementPrototypeId, ViewId,
     },
-    func_run_log::{FuncRunLog, FuncRunLogId, OutputLine},
+    func_run_log::{FuncRunLog, FuncRunLogId, OutputLine, OutputLineFilter},
     resource_metadata::{ResourceMetadata, ResourceStatus},
     schema::SchemaId,
     schema_variant::{PropId, SchemaVariantId},
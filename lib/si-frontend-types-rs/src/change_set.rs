@@ -18,4 +18,6 @@ pub struct ChangeSet {
     pub reviewed_by_user_id: Option<String>,
     pub reviewed_by_user: Option<String>,
     pub reviewed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub rollback_on_failure: bool,
 }
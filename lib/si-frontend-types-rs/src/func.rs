@@ -12,6 +12,7 @@ pub enum IntrinsicFuncKind {
     Identity,
     SetArray,
     SetBoolean,
+    SetFloat,
     SetInteger,
     SetJson,
     SetMap,
@@ -30,6 +31,7 @@ pub struct FuncSummary {
     pub display_name: Option<String>,
     pub description: Option<String>,
     pub is_locked: bool,
+    pub timeout_secs: Option<u64>,
     pub arguments: Vec<FuncArgument>,
     pub bindings: Vec<FuncBinding>,
     pub types: Option<String>,
@@ -225,6 +227,7 @@ pub enum FuncArgumentKind {
     Any,
     Array,
     Boolean,
+    Float,
     Integer,
     Json,
     Map,
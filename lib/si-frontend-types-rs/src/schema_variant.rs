@@ -101,6 +101,7 @@ pub enum PropKind {
     Any,
     Array,
     Boolean,
+    Float,
     Integer,
     Json,
     Map,
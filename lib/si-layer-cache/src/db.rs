@@ -1,7 +1,7 @@
 use serde::Deserialize;
 use si_data_pg::PgPoolConfig;
 use si_runtime::DedicatedExecutor;
-use std::{future::IntoFuture, io, sync::Arc};
+use std::{future::IntoFuture, io, sync::Arc, time::Duration};
 
 use serde::{de::DeserializeOwned, Serialize};
 use si_data_nats::{NatsClient, NatsConfig};
@@ -15,6 +15,7 @@ use ulid::Ulid;
 use crate::db::encrypted_secret::EncryptedSecretDb;
 use crate::db::func_run::FuncRunDb;
 use crate::db::func_run_log::FuncRunLogDb;
+use crate::db::serialize::{CompressionConfig, ZstdDictionaries};
 use crate::hybrid_cache::CacheConfig;
 use crate::{
     activity_client::ActivityClient,
@@ -33,6 +34,7 @@ pub mod cas;
 pub mod encrypted_secret;
 pub mod func_run;
 pub mod func_run_log;
+pub mod gc;
 pub mod rebase_batch;
 pub mod serialize;
 pub mod workspace_snapshot;
@@ -56,6 +58,7 @@ where
     persister_client: PersisterClient,
     activity: ActivityClient,
     instance_id: Ulid,
+    zstd_dictionaries: ZstdDictionaries,
 }
 
 impl<CasValue, EncryptedSecretValue, WorkspaceSnapshotValue, RebaseBatchValue>
@@ -75,11 +78,12 @@ where
         let pg_pool = PgPool::new(&config.pg_pool_config).await?;
         let nats_client = NatsClient::new(&config.nats_config).await?;
 
-        Self::from_services(
+        Self::from_services_with_compression_config(
             pg_pool,
             nats_client,
             compute_executor,
             config.cache_config,
+            config.compression_config,
             token.clone(),
         )
         .await
@@ -92,9 +96,35 @@ where
         compute_executor: DedicatedExecutor,
         cache_config: CacheConfig,
         token: CancellationToken,
+    ) -> LayerDbResult<(Self, LayerDbGracefulShutdown)> {
+        Self::from_services_with_compression_config(
+            pg_pool,
+            nats_client,
+            compute_executor,
+            cache_config,
+            CompressionConfig::default(),
+            token,
+        )
+        .await
+    }
+
+    #[instrument(
+        name = "layer_db.init.from_services_with_compression_config",
+        level = "info",
+        skip_all
+    )]
+    pub async fn from_services_with_compression_config(
+        pg_pool: PgPool,
+        nats_client: NatsClient,
+        compute_executor: DedicatedExecutor,
+        cache_config: CacheConfig,
+        compression_config: CompressionConfig,
+        token: CancellationToken,
     ) -> LayerDbResult<(Self, LayerDbGracefulShutdown)> {
         let instance_id = Ulid::new();
 
+        let zstd_dictionaries = compression_config.load_zstd_dictionaries()?;
+
         let tracker = TaskTracker::new();
 
         let (tx, rx) = mpsc::unbounded_channel();
@@ -109,6 +139,8 @@ where
                 .memory_usable_max_percent(30)
                 .disk_usable_max_percent(30)
                 .with_path_join(cas::CACHE_NAME),
+            compression_config.codec_for(cas::DBNAME),
+            zstd_dictionaries.clone(),
             compute_executor.clone(),
             tracker.clone(),
             token.clone(),
@@ -124,6 +156,8 @@ where
                 .memory_usable_max_percent(5)
                 .disk_usable_max_percent(5)
                 .with_path_join(encrypted_secret::CACHE_NAME),
+            compression_config.codec_for(encrypted_secret::DBNAME),
+            zstd_dictionaries.clone(),
             compute_executor.clone(),
             tracker.clone(),
             token.clone(),
@@ -139,6 +173,8 @@ where
                 .memory_usable_max_percent(5)
                 .disk_usable_max_percent(5)
                 .with_path_join(func_run::CACHE_NAME),
+            compression_config.codec_for(func_run::DBNAME),
+            zstd_dictionaries.clone(),
             compute_executor.clone(),
             tracker.clone(),
             token.clone(),
@@ -154,6 +190,8 @@ where
                 .memory_usable_max_percent(5)
                 .disk_usable_max_percent(5)
                 .with_path_join(func_run_log::CACHE_NAME),
+            compression_config.codec_for(func_run_log::DBNAME),
+            zstd_dictionaries.clone(),
             compute_executor.clone(),
             tracker.clone(),
             token.clone(),
@@ -169,6 +207,8 @@ where
                 .memory_usable_max_percent(5)
                 .disk_usable_max_percent(5)
                 .with_path_join(rebase_batch::CACHE_NAME),
+            compression_config.codec_for(rebase_batch::DBNAME),
+            zstd_dictionaries.clone(),
             compute_executor.clone(),
             tracker.clone(),
             token.clone(),
@@ -184,6 +224,8 @@ where
                 .memory_usable_max_percent(50)
                 .disk_usable_max_percent(50)
                 .with_path_join(workspace_snapshot::CACHE_NAME),
+            compression_config.codec_for(workspace_snapshot::DBNAME),
+            zstd_dictionaries.clone(),
             compute_executor.clone(),
             tracker.clone(),
             token.clone(),
@@ -209,6 +251,7 @@ where
             pg_pool.clone(),
             &nats_client,
             instance_id,
+            zstd_dictionaries.clone(),
             token.clone(),
         )
         .await?;
@@ -219,6 +262,10 @@ where
             EncryptedSecretDb::new(encrypted_secret_cache, persister_client.clone());
         let func_run = FuncRunDb::new(func_run_cache, persister_client.clone());
         let func_run_log = FuncRunLogDb::new(func_run_log_cache, persister_client.clone());
+        tracker.spawn(backfill_func_run_log_search(
+            func_run_log.clone(),
+            token.clone(),
+        ));
         let workspace_snapshot = WorkspaceSnapshotDb::new(snapshot_cache, persister_client.clone());
         let rebase_batch = RebaseBatchDb::new(rebase_batch_cache, persister_client.clone());
 
@@ -237,6 +284,7 @@ where
             nats_client,
             instance_id,
             rebase_batch,
+            zstd_dictionaries,
         };
 
         Ok((layerdb, graceful_shutdown))
@@ -286,6 +334,11 @@ where
         &self.activity
     }
 
+    /// The zstd dictionaries loaded for this layer db, needed to read values compressed with one.
+    pub fn zstd_dictionaries(&self) -> &ZstdDictionaries {
+        &self.zstd_dictionaries
+    }
+
     /// Run all migrations
     pub async fn pg_migrate(&self) -> LayerDbResult<()> {
         // This will do all migrations, not just "cas" migrations. We might want
@@ -296,6 +349,26 @@ where
     }
 }
 
+/// Retries until the backfill completes, since the search column only exists once the layer db
+/// has been migrated, which may happen after this instance starts.
+async fn backfill_func_run_log_search(func_run_log: FuncRunLogDb, token: CancellationToken) {
+    const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+    loop {
+        match func_run_log.backfill_search(token.clone()).await {
+            Ok(()) => return,
+            Err(err) => debug!(
+                si.error.message = ?err,
+                "func run log search backfill failed, retrying later",
+            ),
+        }
+        tokio::select! {
+            _ = token.cancelled() => return,
+            _ = tokio::time::sleep(RETRY_INTERVAL) => {}
+        }
+    }
+}
+
 #[must_use = "graceful shutdown must be spawned on runtime"]
 #[derive(Debug, Clone)]
 pub struct LayerDbGracefulShutdown {
@@ -359,4 +432,6 @@ pub struct LayerDbConfig {
     pub pg_pool_config: PgPoolConfig,
     pub nats_config: NatsConfig,
     pub cache_config: CacheConfig,
+    #[serde(default)]
+    pub compression_config: CompressionConfig,
 }
@@ -162,6 +162,9 @@ where
 
     async fn process_message(&self, event: LayeredEvent) -> LayerDbResult<()> {
         match event.event_kind {
+            crate::event::LayeredEventKind::CasEvict => {
+                self.cas_cache.evict_from_cache_updates(event.key);
+            }
             crate::event::LayeredEventKind::CasInsertion => {
                 if !self.cas_cache.contains(&event.key) {
                     let serialized_value =
@@ -45,8 +45,13 @@ where
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<(ContentHash, PersisterStatusReader)> {
-        let (postcard_value, size_hint) = serialize::to_vec(&value)?;
-        let key = ContentHash::new(&postcard_value);
+        let (postcard_value, uncompressed_value) = serialize::to_vec_and_uncompressed(
+            &value,
+            self.cache.codec(),
+            self.cache.zstd_dictionaries(),
+        )?;
+        let size_hint = uncompressed_value.len();
+        let key = ContentHash::new(&uncompressed_value);
         let cache_key: Arc<str> = key.to_string().into();
 
         self.cache
@@ -67,6 +72,35 @@ where
         Ok((key, reader))
     }
 
+    /// Removes the value for the given key from every layer: the local foyer cache (memory and
+    /// disk), the caches of remote instances and Postgres.
+    ///
+    /// This should only be used for content that is no longer referenced by anything, such as
+    /// during garbage collection.
+    pub fn evict(
+        &self,
+        key: &ContentHash,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        let cache_key = key.to_string();
+        self.cache.remove_from_memory(&cache_key);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::CasEvict,
+            Arc::new(DBNAME.to_string()),
+            cache_key.into(),
+            Arc::new(Vec::new()),
+            Arc::new("cas".to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.evict_event(event)?;
+
+        Ok(reader)
+    }
+
     pub async fn read(&self, key: &ContentHash) -> LayerDbResult<Option<Arc<V>>> {
         self.cache.get(key.to_string().into()).await
     }
@@ -50,7 +50,11 @@ where
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<PersisterStatusReader> {
-        let (postcard_value, size_hint) = serialize::to_vec(&value)?;
+        let (postcard_value, size_hint) = serialize::to_vec_with_codec(
+            &value,
+            self.cache.codec(),
+            self.cache.zstd_dictionaries(),
+        )?;
 
         let cache_key: Arc<str> = key.to_string().into();
 
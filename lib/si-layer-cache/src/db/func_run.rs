@@ -17,7 +17,7 @@ use crate::{
     persister::PersisterClient,
 };
 
-use super::serialize;
+use super::serialize::{self, ZstdDictionaries};
 
 pub const DBNAME: &str = "func_runs";
 pub const CACHE_NAME: &str = DBNAME;
@@ -29,6 +29,7 @@ pub struct FuncRunDb {
     persister_client: PersisterClient,
     ready_many_for_workspace_id_query: String,
     get_last_qualification_for_attribute_value_id: String,
+    get_last_run_for_attribute_value_id: String,
     list_action_history: String,
     get_last_action_by_action_id: String,
     list_management_history: String,
@@ -49,6 +50,12 @@ impl FuncRunDb {
                    ORDER BY updated_at DESC
                    LIMIT 1",
             ),
+            get_last_run_for_attribute_value_id: format!(
+                "SELECT value FROM {DBNAME}
+                   WHERE workspace_id = $1 AND attribute_value_id = $2
+                   ORDER BY updated_at DESC
+                   LIMIT 1",
+            ),
             list_action_history: format!(
                 "SELECT value FROM {DBNAME}
                    WHERE function_kind = 'Action' AND workspace_id = $1
@@ -93,7 +100,10 @@ impl FuncRunDb {
                 let mut result_rows = Vec::with_capacity(rows.len());
                 for row in rows.into_iter() {
                     let postcard_bytes: Vec<u8> = row.get("value");
-                    let func_run: FuncRun = serialize::from_bytes(&postcard_bytes[..])?;
+                    let func_run: FuncRun = serialize::from_bytes_with_dictionaries(
+                        &postcard_bytes[..],
+                        self.cache.zstd_dictionaries(),
+                    )?;
                     result_rows.push(func_run);
                 }
                 Some(result_rows)
@@ -119,7 +129,40 @@ impl FuncRunDb {
             .await?;
 
         let maybe_func = if let Some(row) = maybe_row {
-            Some(serialize::from_bytes(row.get("value"))?)
+            Some(serialize::from_bytes_with_dictionaries(
+                row.get("value"),
+                self.cache.zstd_dictionaries(),
+            )?)
+        } else {
+            None
+        };
+
+        Ok(maybe_func)
+    }
+
+    /// Returns the most recent run of any function for the attribute value. Unlike
+    /// [`Self::get_last_qualification_for_attribute_value_id`], this does not wait for a run to
+    /// appear.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_last_run_for_attribute_value_id(
+        &self,
+        workspace_pk: WorkspacePk,
+        attribute_value_id: AttributeValueId,
+    ) -> LayerDbResult<Option<FuncRun>> {
+        let maybe_row = self
+            .cache
+            .pg()
+            .query_opt(
+                &self.get_last_run_for_attribute_value_id,
+                &[&workspace_pk, &attribute_value_id],
+            )
+            .await?;
+
+        let maybe_func = if let Some(row) = maybe_row {
+            Some(serialize::from_bytes_with_dictionaries(
+                row.get("value"),
+                self.cache.zstd_dictionaries(),
+            )?)
         } else {
             None
         };
@@ -145,7 +188,10 @@ impl FuncRunDb {
                 let mut result_rows = Vec::with_capacity(rows.len());
                 for row in rows.into_iter() {
                     let postcard_bytes: Vec<u8> = row.get("value");
-                    let func_run: FuncRun = serialize::from_bytes(&postcard_bytes[..])?;
+                    let func_run: FuncRun = serialize::from_bytes_with_dictionaries(
+                        &postcard_bytes[..],
+                        self.cache.zstd_dictionaries(),
+                    )?;
                     result_rows.push(func_run);
                 }
                 Some(result_rows)
@@ -172,7 +218,10 @@ impl FuncRunDb {
             .await?;
 
         let maybe_func = if let Some(row) = maybe_row {
-            Some(serialize::from_bytes(row.get("value"))?)
+            Some(serialize::from_bytes_with_dictionaries(
+                row.get("value"),
+                self.cache.zstd_dictionaries(),
+            )?)
         } else {
             None
         };
@@ -198,7 +247,10 @@ impl FuncRunDb {
             let result = match maybe_row {
                 Some(row) => {
                     let postcard_bytes: Vec<u8> = row.get("value");
-                    let func_run: FuncRun = serialize::from_bytes(&postcard_bytes[..])?;
+                    let func_run: FuncRun = serialize::from_bytes_with_dictionaries(
+                        &postcard_bytes[..],
+                        self.cache.zstd_dictionaries(),
+                    )?;
                     Some(func_run)
                 }
                 None => None,
@@ -220,7 +272,11 @@ impl FuncRunDb {
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<()> {
-        let (postcard_value, size_hint) = serialize::to_vec(&value)?;
+        let (postcard_value, size_hint) = serialize::to_vec_with_codec(
+            &value,
+            self.cache.codec(),
+            self.cache.zstd_dictionaries(),
+        )?;
         let cache_key: Arc<str> = value.id().to_string().into();
         let sort_key: Arc<str> = value.tenancy().workspace_pk.to_string().into();
 
@@ -350,7 +406,10 @@ impl FuncRunDb {
             )
             .await?
             .ok_or_else(|| LayerDbError::ActionIdNotFound(action_id))?;
-        let mut func_run: FuncRun = serialize::from_bytes(maybe_row.get("value"))?;
+        let mut func_run: FuncRun = serialize::from_bytes_with_dictionaries(
+            maybe_row.get("value"),
+            self.cache.zstd_dictionaries(),
+        )?;
         func_run.set_action_result_state(Some(action_result_state));
 
         self.write(Arc::new(func_run), None, tenancy, actor).await?;
@@ -386,7 +445,10 @@ impl FuncRunDb {
                 let mut func_runs = Vec::new();
                 for row in rows {
                     // NOTE(nick): higher order functions... yeah I want those errors, sorry.
-                    func_runs.push(serialize::from_bytes(row.get("value"))?)
+                    func_runs.push(serialize::from_bytes_with_dictionaries(
+                        row.get("value"),
+                        self.cache.zstd_dictionaries(),
+                    )?)
                 }
                 Ok(Some(func_runs))
             }
@@ -397,8 +459,10 @@ impl FuncRunDb {
     pub async fn insert_to_pg(
         pg: &PgLayer,
         event_payload: &LayeredEventPayload,
+        zstd_dictionaries: &ZstdDictionaries,
     ) -> LayerDbResult<()> {
-        let func_run: FuncRun = serialize::from_bytes(&event_payload.value[..])?;
+        let func_run: FuncRun =
+            serialize::from_bytes_with_dictionaries(&event_payload.value[..], zstd_dictionaries)?;
         let json: serde_json::Value = serde_json::to_value(func_run.clone())?;
         pg.insert_raw(
             &format!(
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use si_events::{Actor, FuncRunId, FuncRunLog, Tenancy, WebEvent};
+use si_events::{Actor, FuncRunId, FuncRunLog, FuncRunLogId, Tenancy, WebEvent, WorkspacePk};
+use telemetry::prelude::*;
+use tokio_util::sync::CancellationToken;
 
 use crate::{
     error::LayerDbResult,
@@ -15,11 +17,18 @@ pub const DBNAME: &str = "func_run_logs";
 pub const CACHE_NAME: &str = DBNAME;
 pub const PARTITION_KEY: &str = "workspace_id";
 
+/// Held while backfilling search vectors, so that only one instance does it at a time.
+const SEARCH_BACKFILL_LOCK: i64 = 0x66_72_6c_73; // "frls"
+const SEARCH_BACKFILL_BATCH_SIZE: i64 = 500;
+
 #[derive(Debug, Clone)]
 pub struct FuncRunLogDb {
     pub cache: Arc<LayerCache<Arc<FuncRunLog>>>,
     persister_client: PersisterClient,
     get_for_func_run_id_query: String,
+    search_for_workspace_query: String,
+    search_backfill_select_query: String,
+    search_backfill_update_query: String,
 }
 
 impl FuncRunLogDb {
@@ -28,6 +37,22 @@ impl FuncRunLogDb {
             cache,
             persister_client,
             get_for_func_run_id_query: format!("SELECT value FROM {DBNAME} WHERE func_run_id = $1"),
+            search_for_workspace_query: format!(
+                "SELECT value FROM {DBNAME}
+                 WHERE workspace_id = $1 AND search_vector @@ plainto_tsquery('simple', $2)
+                 ORDER BY updated_at DESC
+                 LIMIT $3"
+            ),
+            search_backfill_select_query: format!(
+                "SELECT key, value FROM {DBNAME}
+                 WHERE search_vector IS NULL AND key > $1
+                 ORDER BY key
+                 LIMIT $2"
+            ),
+            search_backfill_update_query: format!(
+                "UPDATE {DBNAME} SET search_vector = to_tsvector('simple', $2)
+                 WHERE key = $1 AND search_vector IS NULL"
+            ),
         }
     }
 
@@ -38,7 +63,11 @@ impl FuncRunLogDb {
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<()> {
-        let (postcard_value, size_hint) = serialize::to_vec(&value)?;
+        let (postcard_value, size_hint) = serialize::to_vec_with_codec(
+            &value,
+            self.cache.codec(),
+            self.cache.zstd_dictionaries(),
+        )?;
         let cache_key: Arc<str> = value.id().to_string().into();
         let sort_key: Arc<str> = value.tenancy().workspace_pk.to_string().into();
 
@@ -64,6 +93,38 @@ impl FuncRunLogDb {
         Ok(())
     }
 
+    pub async fn read(&self, key: FuncRunLogId) -> LayerDbResult<Option<Arc<FuncRunLog>>> {
+        self.cache.get(key.to_string().into()).await
+    }
+
+    /// Full-text search over the log messages of every func run in a workspace, most recently
+    /// updated first.
+    pub async fn search_for_workspace(
+        &self,
+        workspace_id: WorkspacePk,
+        query: &str,
+        limit: i64,
+    ) -> LayerDbResult<Vec<Arc<FuncRunLog>>> {
+        let maybe_rows = self
+            .cache
+            .pg()
+            .query(
+                &self.search_for_workspace_query,
+                &[&workspace_id.to_string(), &query, &limit],
+            )
+            .await?;
+
+        let mut func_run_logs = Vec::new();
+        for row in maybe_rows.unwrap_or_default() {
+            func_run_logs.push(serialize::from_bytes_with_dictionaries(
+                row.get("value"),
+                self.cache.zstd_dictionaries(),
+            )?);
+        }
+
+        Ok(func_run_logs)
+    }
+
     pub async fn get_for_func_run_id(
         &self,
         func_run_id: FuncRunId,
@@ -74,12 +135,108 @@ impl FuncRunLogDb {
             .query_opt(&self.get_for_func_run_id_query, &[&func_run_id])
             .await?;
         if let Some(row) = maybe_row {
-            Ok(Some(serialize::from_bytes(row.get("value"))?))
+            Ok(Some(serialize::from_bytes_with_dictionaries(
+                row.get("value"),
+                self.cache.zstd_dictionaries(),
+            )?))
         } else {
             Ok(None)
         }
     }
 
+    /// Fills in the search vector of logs written before search existed, a batch at a time, then
+    /// builds the search indexes concurrently so that writes are never blocked.
+    ///
+    /// Only one instance backfills at a time; any other returns straight away.
+    #[instrument(name = "func_run_log.backfill_search", level = "info", skip_all)]
+    pub async fn backfill_search(&self, token: CancellationToken) -> LayerDbResult<()> {
+        let client = self.cache.pg().client().await?;
+        let locked: bool = client
+            .query_one(
+                "SELECT pg_try_advisory_lock($1) AS locked",
+                &[&SEARCH_BACKFILL_LOCK],
+            )
+            .await?
+            .get("locked");
+        if !locked {
+            debug!("func run log search backfill is running elsewhere");
+            return Ok(());
+        }
+
+        let result = self.backfill_search_locked(&client, &token).await;
+
+        client
+            .query_one("SELECT pg_advisory_unlock($1)", &[&SEARCH_BACKFILL_LOCK])
+            .await?;
+        result
+    }
+
+    async fn backfill_search_locked(
+        &self,
+        client: &si_data_pg::InstrumentedClient,
+        token: &CancellationToken,
+    ) -> LayerDbResult<()> {
+        let mut last_key = String::new();
+        let mut backfilled = 0;
+        loop {
+            if token.is_cancelled() {
+                return Ok(());
+            }
+
+            let rows = client
+                .query(
+                    &self.search_backfill_select_query,
+                    &[&last_key, &SEARCH_BACKFILL_BATCH_SIZE],
+                )
+                .await?;
+            let Some(last_row) = rows.last() else {
+                break;
+            };
+            last_key = last_row.get("key");
+
+            for row in &rows {
+                let key: String = row.get("key");
+                // An unreadable log is left searchable by nothing, rather than retried forever
+                let text = match serialize::from_bytes_with_dictionaries::<FuncRunLog>(
+                    row.get("value"),
+                    self.cache.zstd_dictionaries(),
+                ) {
+                    Ok(func_run_log) => search_text(&func_run_log),
+                    Err(err) => {
+                        warn!(
+                            si.error.message = ?err,
+                            key,
+                            "could not read func run log to backfill",
+                        );
+                        String::new()
+                    }
+                };
+                client
+                    .execute(&self.search_backfill_update_query, &[&key, &text])
+                    .await?;
+            }
+            backfilled += rows.len();
+            debug!(backfilled, "backfilled func run log search vectors");
+        }
+
+        // Each on its own, since concurrent index builds cannot run inside a transaction block
+        client
+            .batch_execute(&format!(
+                "CREATE INDEX CONCURRENTLY IF NOT EXISTS func_run_logs_search_vector
+                 ON {DBNAME} USING GIN (search_vector)"
+            ))
+            .await?;
+        client
+            .batch_execute(&format!(
+                "CREATE INDEX CONCURRENTLY IF NOT EXISTS func_run_logs_workspace_id_updated_at
+                 ON {DBNAME} (workspace_id, updated_at DESC)"
+            ))
+            .await?;
+
+        info!(backfilled, "func run log search backfill complete");
+        Ok(())
+    }
+
     pub async fn insert_to_pg(&self, func_run_log: Arc<FuncRunLog>) -> LayerDbResult<()> {
         self.cache
             .pg()
@@ -93,6 +250,7 @@ impl FuncRunLogDb {
                     workspace_id,
                     change_set_id,
                     func_run_id,
+                    search_vector,
                     value
                 ) VALUES (
                     $1,
@@ -102,9 +260,11 @@ impl FuncRunLogDb {
                     $5,
                     $6,
                     $7,
-                    $8
+                    to_tsvector('simple', $8),
+                    $9
                 ) ON CONFLICT (key) DO UPDATE SET
                     updated_at = EXCLUDED.updated_at,
+                    search_vector = EXCLUDED.search_vector,
                     value = EXCLUDED.value;"
                 ),
                 &[
@@ -115,10 +275,26 @@ impl FuncRunLogDb {
                     &func_run_log.tenancy().workspace_pk.to_string(),
                     &func_run_log.tenancy().change_set_id.to_string(),
                     &func_run_log.func_run_id().to_string(),
-                    &serialize::to_vec(&func_run_log)?.0,
+                    &search_text(&func_run_log),
+                    &serialize::to_vec_with_codec(
+                        &func_run_log,
+                        self.cache.codec(),
+                        self.cache.zstd_dictionaries(),
+                    )?
+                    .0,
                 ],
             )
             .await?;
         Ok(())
     }
 }
+
+/// The text a func run log is searched by: every message, one per line.
+fn search_text(func_run_log: &FuncRunLog) -> String {
+    func_run_log
+        .logs()
+        .iter()
+        .map(|line| line.message.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
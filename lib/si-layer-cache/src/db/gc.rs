@@ -0,0 +1,320 @@
+//! Mark-and-sweep garbage collection for workspace snapshots and CAS content.
+//!
+//! The layer db has no notion of change sets, so it cannot decide on its own what is reachable.
+//! Collection is split into two phases:
+//!
+//! * **Mark**: the caller (the dal) walks every change set pointer it still cares about, loads
+//!   the snapshot at that address and collects every content hash referenced by the graph. It
+//!   must also treat every snapshot returned by
+//!   [`LayerDb::recent_workspace_snapshot_addresses`] as a root, since those may have been
+//!   written but not yet pointed to by a change set.
+//! * **Sweep**: [`LayerDb::sweep`] deletes every snapshot and CAS row created before the
+//!   retention window that was not marked. Content referenced by func runs is always kept so
+//!   that func run history can still be displayed.
+//!
+//! Deletion goes through the regular eviction path, so values are removed from Postgres, the
+//! local foyer cache (memory and disk) and the caches of every other running instance.
+
+use std::{collections::HashSet, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use si_events::{Actor, ContentHash, Tenancy, WorkspaceSnapshotAddress};
+use telemetry::prelude::*;
+
+use crate::{
+    error::LayerDbResult,
+    persister::{PersistStatus, PersisterStatusReader},
+    pg::PgLayer,
+    LayerDb,
+};
+
+use super::func_run;
+
+/// The default retention window: anything written within this window is never collected.
+pub const DEFAULT_RETENTION: Duration = Duration::from_secs(60 * 60 * 24 * 7);
+
+/// How many keys are read from a table at a time while sweeping.
+const SWEEP_PAGE_SIZE: i64 = 1000;
+
+const FUNC_RUN_CONTENT_HASHES_QUERY: &str = "
+    SELECT DISTINCT key FROM (
+        SELECT unnest(ARRAY[
+            json_value->>'function_args_cas_address',
+            json_value->>'function_code_cas_address',
+            json_value->>'result_value_cas_address',
+            json_value->>'result_unprocessed_value_cas_address'
+        ]) AS key
+        FROM func_runs
+    ) AS func_run_keys
+    WHERE key IS NOT NULL
+";
+
+/// Options controlling a garbage collection run.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GcOptions {
+    /// Rows created within this window are never collected, even when unreachable.
+    pub retention: Duration,
+    /// When `true`, nothing is deleted and the report only describes what would be collected.
+    pub dry_run: bool,
+}
+
+impl Default for GcOptions {
+    fn default() -> Self {
+        Self {
+            retention: DEFAULT_RETENTION,
+            dry_run: true,
+        }
+    }
+}
+
+impl GcOptions {
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+/// The set of reachable snapshot addresses and content hashes, produced by the mark phase.
+#[derive(Clone, Debug, Default)]
+pub struct GcRoots {
+    pub workspace_snapshot_addresses: HashSet<WorkspaceSnapshotAddress>,
+    pub content_hashes: HashSet<ContentHash>,
+}
+
+impl GcRoots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mark_workspace_snapshot_address(&mut self, address: WorkspaceSnapshotAddress) -> bool {
+        self.workspace_snapshot_addresses.insert(address)
+    }
+
+    pub fn mark_content_hashes(&mut self, hashes: impl IntoIterator<Item = ContentHash>) {
+        self.content_hashes.extend(hashes);
+    }
+}
+
+/// Per-table results of a sweep.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GcTableReport {
+    /// Number of rows older than the retention window that were considered.
+    pub scanned: usize,
+    /// Number of considered rows that were reachable and kept.
+    pub retained: usize,
+    /// Keys of the unreachable rows (deleted, or to be deleted on a dry run).
+    pub collected: Vec<String>,
+    /// Total stored size of the unreachable rows, in bytes.
+    pub collected_bytes: u64,
+}
+
+/// The result of a garbage collection run.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub workspace_snapshots: GcTableReport,
+    pub cas: GcTableReport,
+}
+
+impl<CasValue, EncryptedSecretValue, WorkspaceSnapshotValue, RebaseBatchValue>
+    LayerDb<CasValue, EncryptedSecretValue, WorkspaceSnapshotValue, RebaseBatchValue>
+where
+    CasValue: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    EncryptedSecretValue: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    WorkspaceSnapshotValue: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    RebaseBatchValue: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// Returns the addresses of every workspace snapshot written within the retention window.
+    ///
+    /// These must be marked as roots by the caller, as they may belong to a rebase that has not
+    /// yet updated its change set pointer.
+    #[instrument(
+        name = "layer_db.gc.recent_workspace_snapshot_addresses",
+        level = "info",
+        skip_all
+    )]
+    pub async fn recent_workspace_snapshot_addresses(
+        &self,
+        retention: Duration,
+    ) -> LayerDbResult<Vec<WorkspaceSnapshotAddress>> {
+        let cutoff = Utc::now() - chrono::Duration::from_std(retention)?;
+
+        self.workspace_snapshot
+            .cache
+            .pg()
+            .get_keys_created_since(cutoff)
+            .await?
+            .into_iter()
+            .map(|key| Ok(key.parse()?))
+            .collect()
+    }
+
+    /// Deletes every workspace snapshot and CAS row older than the retention window that is not
+    /// reachable from the given roots.
+    ///
+    /// With [`GcOptions::dry_run`] set, nothing is deleted and the report describes what would
+    /// have been collected.
+    #[instrument(
+        name = "layer_db.gc.sweep",
+        level = "info",
+        skip_all,
+        fields(
+            si.layer_db.gc.dry_run = options.dry_run,
+            si.layer_db.gc.cas.collected = Empty,
+            si.layer_db.gc.workspace_snapshots.collected = Empty,
+        ),
+    )]
+    pub async fn sweep(
+        &self,
+        roots: &GcRoots,
+        options: GcOptions,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<GcReport> {
+        let span = current_span_for_instrument_at!("info");
+
+        let cutoff = Utc::now() - chrono::Duration::from_std(options.retention)?;
+
+        // Snapshots go first so that a crash part way through never leaves a snapshot behind
+        // whose content is gone.
+        let reachable_snapshots: HashSet<String> = roots
+            .workspace_snapshot_addresses
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let workspace_snapshots = Self::sweep_table(
+            self.workspace_snapshot.cache.pg(),
+            cutoff,
+            &reachable_snapshots,
+            options.dry_run,
+            |key| {
+                let address: WorkspaceSnapshotAddress = key.parse()?;
+                self.workspace_snapshot.evict(&address, tenancy, actor)
+            },
+        )
+        .await?;
+
+        let mut reachable_content: HashSet<String> = roots
+            .content_hashes
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        reachable_content.extend(self.func_run_content_hashes().await?);
+        let cas = Self::sweep_table(
+            self.cas.cache.pg(),
+            cutoff,
+            &reachable_content,
+            options.dry_run,
+            |key| {
+                let hash: ContentHash = key.parse()?;
+                self.cas.evict(&hash, tenancy, actor)
+            },
+        )
+        .await?;
+
+        span.record(
+            "si.layer_db.gc.workspace_snapshots.collected",
+            workspace_snapshots.collected.len(),
+        );
+        span.record("si.layer_db.gc.cas.collected", cas.collected.len());
+
+        info!(
+            dry_run = options.dry_run,
+            workspace_snapshots.scanned = workspace_snapshots.scanned,
+            workspace_snapshots.collected = workspace_snapshots.collected.len(),
+            workspace_snapshots.collected_bytes = workspace_snapshots.collected_bytes,
+            cas.scanned = cas.scanned,
+            cas.collected = cas.collected.len(),
+            cas.collected_bytes = cas.collected_bytes,
+            "layer db garbage collection complete",
+        );
+
+        Ok(GcReport {
+            dry_run: options.dry_run,
+            workspace_snapshots,
+            cas,
+        })
+    }
+
+    async fn func_run_content_hashes(&self) -> LayerDbResult<HashSet<String>> {
+        let rows = self
+            .func_run
+            .cache
+            .pg()
+            .query(FUNC_RUN_CONTENT_HASHES_QUERY, &[])
+            .await?
+            .unwrap_or_default();
+        debug!(
+            count = rows.len(),
+            "marked content hashes referenced by {}",
+            func_run::DBNAME,
+        );
+
+        Ok(rows.into_iter().map(|row| row.get("key")).collect())
+    }
+
+    /// Pages through the rows of a table created before the cutoff, evicting the unreachable
+    /// ones page by page unless this is a dry run.
+    async fn sweep_table(
+        pg: PgLayer,
+        cutoff: DateTime<Utc>,
+        reachable: &HashSet<String>,
+        dry_run: bool,
+        evict: impl Fn(&str) -> LayerDbResult<PersisterStatusReader>,
+    ) -> LayerDbResult<GcTableReport> {
+        let mut report = GcTableReport::default();
+        let mut after_key = String::new();
+
+        loop {
+            let page = pg
+                .get_keys_created_before(cutoff, &after_key, SWEEP_PAGE_SIZE)
+                .await?;
+            let Some((last_key, _)) = page.last() else {
+                break;
+            };
+            after_key.clone_from(last_key);
+            let page_len = page.len();
+
+            let mut readers = Vec::new();
+            for (key, size) in page {
+                report.scanned += 1;
+                if reachable.contains(&key) {
+                    report.retained += 1;
+                    continue;
+                }
+
+                report.collected_bytes += u64::try_from(size).unwrap_or_default();
+                if !dry_run {
+                    readers.push(evict(&key)?);
+                }
+                report.collected.push(key);
+            }
+            Self::wait_for_evictions(readers).await?;
+
+            if page_len < SWEEP_PAGE_SIZE as usize {
+                break;
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn wait_for_evictions(readers: Vec<PersisterStatusReader>) -> LayerDbResult<()> {
+        for reader in readers {
+            if let PersistStatus::Error(err) = reader.get_status().await? {
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+}
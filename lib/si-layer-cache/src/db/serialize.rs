@@ -1,8 +1,219 @@
-use serde::{de::DeserializeOwned, Serialize};
+//! Serialization of values stored in the layer db.
+//!
+//! Values are serialized with postcard and then compressed with a [`Codec`]. Deflate, the default,
+//! is written as a bare deflate stream so that older binaries can still read it. Every other codec
+//! prefixes the compressed bytes with a small header, so that the codec used to write a value can
+//! always be recovered when reading it, regardless of how the layer db is currently configured:
+//!
+//! ```text
+//! +-------------+----------------+----------+---------------------------------+
+//! | magic (0xFF) | format version | codec id | codec parameters (codec defined) |
+//! +-------------+----------------+----------+---------------------------------+
+//! ```
+//!
+//! A deflate stream can never start with `0xFF` (its first block would have the reserved block type
+//! `0b11`), so any value without the magic byte is read as headerless deflate.
+//!
+//! Content addresses are computed over the uncompressed postcard bytes (see
+//! [`to_vec_and_uncompressed`]), so the same value gets the same address whichever codec wrote it.
+
+use std::{collections::HashMap, io::Read, path::PathBuf, sync::Arc};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use telemetry::prelude::*;
 
 use crate::{error::LayerDbResult, LayerDbError};
 
+/// The first byte of every value written with a header.
+const HEADER_MAGIC: u8 = 0xFF;
+/// The current version of the header format.
+pub const FORMAT_VERSION: u8 = 1;
+
+const CODEC_ID_DEFLATE: u8 = 1;
+const CODEC_ID_ZSTD: u8 = 2;
+const CODEC_ID_ZSTD_DICTIONARY: u8 = 3;
+
+const DEFAULT_DEFLATE_LEVEL: u8 = 1;
+const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// The compression codec used when writing values.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Codec {
+    /// Deflate (via miniz). 1 is the best speed, 6 is default, 9 is best compression but may be
+    /// too slow.
+    Deflate {
+        #[serde(default = "default_deflate_level")]
+        level: u8,
+    },
+    /// Zstandard, optionally with a trained dictionary from the layer db's [`ZstdDictionaries`].
+    Zstd {
+        #[serde(default = "default_zstd_level")]
+        level: i32,
+        #[serde(default)]
+        dictionary_id: Option<u32>,
+    },
+}
+
+impl Default for Codec {
+    fn default() -> Self {
+        Self::Deflate {
+            level: DEFAULT_DEFLATE_LEVEL,
+        }
+    }
+}
+
+fn default_deflate_level() -> u8 {
+    DEFAULT_DEFLATE_LEVEL
+}
+
+fn default_zstd_level() -> i32 {
+    DEFAULT_ZSTD_LEVEL
+}
+
+impl Codec {
+    fn compress(
+        &self,
+        uncompressed: &[u8],
+        dictionaries: &ZstdDictionaries,
+    ) -> LayerDbResult<Vec<u8>> {
+        match *self {
+            // Written without a header, so that binaries predating the header can read it
+            Self::Deflate { level } => {
+                Ok(miniz_oxide::deflate::compress_to_vec(uncompressed, level))
+            }
+            Self::Zstd {
+                level,
+                dictionary_id: None,
+            } => {
+                let mut compressed = vec![HEADER_MAGIC, FORMAT_VERSION, CODEC_ID_ZSTD];
+                compressed.extend(
+                    zstd::bulk::compress(uncompressed, level)
+                        .map_err(|e| LayerDbError::Compress(e.to_string()))?,
+                );
+                Ok(compressed)
+            }
+            Self::Zstd {
+                level,
+                dictionary_id: Some(dictionary_id),
+            } => {
+                let dictionary = dictionaries.get(dictionary_id)?;
+                let mut compressed = vec![HEADER_MAGIC, FORMAT_VERSION, CODEC_ID_ZSTD_DICTIONARY];
+                compressed.extend(dictionary_id.to_le_bytes());
+                compressed.extend(
+                    zstd::bulk::Compressor::with_dictionary(level, dictionary)
+                        .and_then(|mut compressor| compressor.compress(uncompressed))
+                        .map_err(|e| LayerDbError::Compress(e.to_string()))?,
+                );
+                Ok(compressed)
+            }
+        }
+    }
+}
+
+/// Per-table codec selection for the layer db.
+///
+/// Only affects how new values are written; every codec can always be read.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CompressionConfig {
+    /// Codec used for any table without an entry in `tables`.
+    #[serde(default)]
+    pub default_codec: Codec,
+    /// Codecs keyed by table name (for example, `workspace_snapshots` or `cas`).
+    #[serde(default)]
+    pub tables: HashMap<String, Codec>,
+    /// Trained zstd dictionaries to load at startup.
+    #[serde(default)]
+    pub zstd_dictionaries: Vec<ZstdDictionaryConfig>,
+}
+
+/// A trained zstd dictionary, loaded from disk and registered under `id`.
+///
+/// The id is written into the header of every value compressed with the dictionary, so a
+/// dictionary must stay registered under the same id for as long as values using it exist.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ZstdDictionaryConfig {
+    pub id: u32,
+    pub path: PathBuf,
+}
+
+impl CompressionConfig {
+    /// Returns the codec to use when writing to the given table.
+    pub fn codec_for(&self, table_name: &str) -> Codec {
+        self.tables
+            .get(table_name)
+            .copied()
+            .unwrap_or(self.default_codec)
+    }
+
+    /// Reads every configured zstd dictionary from disk.
+    pub fn load_zstd_dictionaries(&self) -> LayerDbResult<ZstdDictionaries> {
+        let mut dictionaries = HashMap::new();
+        for dictionary in &self.zstd_dictionaries {
+            dictionaries.insert(dictionary.id, std::fs::read(&dictionary.path)?);
+        }
+        Ok(ZstdDictionaries::new(dictionaries))
+    }
+}
+
+/// The trained zstd dictionaries available to a layer db, keyed by id.
+///
+/// Each layer db owns its own set, loaded from its [`CompressionConfig`], and hands it to every
+/// cache so that values compressed with a dictionary can be written and read back.
+#[derive(Clone, Debug, Default)]
+pub struct ZstdDictionaries(Arc<HashMap<u32, Vec<u8>>>);
+
+impl ZstdDictionaries {
+    pub fn new(dictionaries: impl IntoIterator<Item = (u32, Vec<u8>)>) -> Self {
+        Self(Arc::new(dictionaries.into_iter().collect()))
+    }
+
+    fn get(&self, id: u32) -> LayerDbResult<&[u8]> {
+        self.0
+            .get(&id)
+            .map(Vec::as_slice)
+            .ok_or(LayerDbError::MissingZstdDictionary(id))
+    }
+}
+
+/// Trains a zstd dictionary from a set of sample values.
+///
+/// Samples should be representative values of the type the dictionary will be used for (for
+/// example, workspace snapshots), serialized but *not* compressed.
+pub fn train_zstd_dictionary<S: AsRef<[u8]>>(
+    samples: &[S],
+    max_size: usize,
+) -> LayerDbResult<Vec<u8>> {
+    zstd::dict::from_samples(samples, max_size).map_err(|e| LayerDbError::Compress(e.to_string()))
+}
+
+#[inline]
+pub fn to_vec<T>(value: &T) -> LayerDbResult<(Vec<u8>, usize)>
+where
+    T: Serialize + ?Sized,
+{
+    to_vec_with_codec(value, Codec::default(), &ZstdDictionaries::default())
+}
+
+#[inline]
+pub fn to_vec_with_codec<T>(
+    value: &T,
+    codec: Codec,
+    dictionaries: &ZstdDictionaries,
+) -> LayerDbResult<(Vec<u8>, usize)>
+where
+    T: Serialize + ?Sized,
+{
+    let (compressed, uncompressed) = to_vec_and_uncompressed(value, codec, dictionaries)?;
+
+    Ok((compressed, uncompressed.len()))
+}
+
+/// Serializes and compresses a value, returning both the compressed bytes and the uncompressed
+/// postcard bytes.
+///
+/// Content addresses must be computed over the uncompressed bytes, since the compressed ones
+/// depend on the codec.
 #[inline]
 #[instrument(
     name = "serialize.to_vec",
@@ -11,23 +222,26 @@ use crate::{error::LayerDbResult, LayerDbError};
     fields(
         bytes.size.compressed = Empty,
         bytes.size.uncompressed = Empty,
+        serialize.codec = ?codec,
     )
 )]
-pub fn to_vec<T>(value: &T) -> LayerDbResult<(Vec<u8>, usize)>
+pub fn to_vec_and_uncompressed<T>(
+    value: &T,
+    codec: Codec,
+    dictionaries: &ZstdDictionaries,
+) -> LayerDbResult<(Vec<u8>, Vec<u8>)>
 where
     T: Serialize + ?Sized,
 {
     let span = current_span_for_instrument_at!("debug");
 
     let serialized = postcard::to_stdvec(value)?;
-    let uncompressed_size = serialized.len();
-    // 1 is the best speed, 6 is default, 9 is best compression but may be too slow
-    let compressed = miniz_oxide::deflate::compress_to_vec(&serialized, 1);
+    let compressed = codec.compress(&serialized, dictionaries)?;
 
     span.record("bytes.size.compressed", compressed.len());
-    span.record("bytes.size.uncompressed", uncompressed_size);
+    span.record("bytes.size.uncompressed", serialized.len());
 
-    Ok((compressed, uncompressed_size))
+    Ok((compressed, serialized))
 }
 
 #[inline]
@@ -43,8 +257,19 @@ pub fn from_bytes<T>(bytes: &[u8]) -> LayerDbResult<T>
 where
     T: DeserializeOwned,
 {
-    let uncompressed = miniz_oxide::inflate::decompress_to_vec(bytes)
-        .map_err(|e| LayerDbError::Decompress(e.to_string()))?;
+    from_bytes_with_dictionaries(bytes, &ZstdDictionaries::default())
+}
+
+/// Like [`from_bytes`], but can also read values compressed with one of the given dictionaries.
+#[inline]
+pub fn from_bytes_with_dictionaries<T>(
+    bytes: &[u8],
+    dictionaries: &ZstdDictionaries,
+) -> LayerDbResult<T>
+where
+    T: DeserializeOwned,
+{
+    let uncompressed = decompress_to_vec_with_dictionaries(bytes, dictionaries)?;
 
     Ok(postcard::from_bytes(&uncompressed)?)
 }
@@ -58,12 +283,11 @@ where
         bytes.size = bytes.len(),
     )
 )]
-pub async fn from_bytes_async<T>(bytes: &[u8]) -> LayerDbResult<T>
+pub async fn from_bytes_async<T>(bytes: &[u8], dictionaries: &ZstdDictionaries) -> LayerDbResult<T>
 where
     T: DeserializeOwned,
 {
-    let uncompressed = miniz_oxide::inflate::decompress_to_vec(bytes)
-        .map_err(|e| LayerDbError::Decompress(e.to_string()))?;
+    let uncompressed = decompress_to_vec_with_dictionaries(bytes, dictionaries)?;
 
     tokio::task::yield_now().await;
 
@@ -71,7 +295,47 @@ where
 }
 
 pub fn decompress_to_vec(compressed_bytes: &[u8]) -> LayerDbResult<Vec<u8>> {
-    let uncompressed = miniz_oxide::inflate::decompress_to_vec(compressed_bytes)
-        .map_err(|e| LayerDbError::Decompress(e.to_string()))?;
-    Ok(uncompressed)
+    decompress_to_vec_with_dictionaries(compressed_bytes, &ZstdDictionaries::default())
+}
+
+pub fn decompress_to_vec_with_dictionaries(
+    compressed_bytes: &[u8],
+    dictionaries: &ZstdDictionaries,
+) -> LayerDbResult<Vec<u8>> {
+    let (header, body) = match compressed_bytes {
+        [HEADER_MAGIC, rest @ ..] => match rest {
+            [FORMAT_VERSION, codec_id, body @ ..] => (*codec_id, body),
+            [version, ..] => return Err(LayerDbError::UnsupportedFormatVersion(*version)),
+            [] => return Err(LayerDbError::MalformedHeader),
+        },
+        // Legacy values have no header and are always deflate
+        legacy => return inflate(legacy),
+    };
+
+    match header {
+        CODEC_ID_DEFLATE => inflate(body),
+        CODEC_ID_ZSTD => {
+            zstd::stream::decode_all(body).map_err(|e| LayerDbError::Decompress(e.to_string()))
+        }
+        CODEC_ID_ZSTD_DICTIONARY => {
+            let (dictionary_id, body) = match body {
+                [a, b, c, d, body @ ..] => (u32::from_le_bytes([*a, *b, *c, *d]), body),
+                _ => return Err(LayerDbError::MalformedHeader),
+            };
+            let dictionary = dictionaries.get(dictionary_id)?;
+
+            let mut uncompressed = Vec::new();
+            zstd::stream::read::Decoder::with_dictionary(body, dictionary)
+                .and_then(|mut decoder| decoder.read_to_end(&mut uncompressed))
+                .map_err(|e| LayerDbError::Decompress(e.to_string()))?;
+
+            Ok(uncompressed)
+        }
+        unknown => Err(LayerDbError::UnknownCodec(unknown)),
+    }
+}
+
+fn inflate(compressed_bytes: &[u8]) -> LayerDbResult<Vec<u8>> {
+    miniz_oxide::inflate::decompress_to_vec(compressed_bytes)
+        .map_err(|e| LayerDbError::Decompress(e.to_string()))
 }
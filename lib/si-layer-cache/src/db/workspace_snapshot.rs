@@ -45,9 +45,14 @@ where
         actor: Actor,
     ) -> LayerDbResult<(WorkspaceSnapshotAddress, PersisterStatusReader)> {
         let value_clone = value.clone();
-        let (postcard_value, size_hint) = serialize::to_vec(&value)?;
-
-        let key = WorkspaceSnapshotAddress::new(&postcard_value);
+        let (postcard_value, uncompressed_value) = serialize::to_vec_and_uncompressed(
+            &value,
+            self.cache.codec(),
+            self.cache.zstd_dictionaries(),
+        )?;
+        let size_hint = uncompressed_value.len();
+
+        let key = WorkspaceSnapshotAddress::new(&uncompressed_value);
         let cache_key: Arc<str> = key.to_string().into();
 
         self.cache.insert(cache_key.clone(), value_clone, size_hint);
@@ -2,7 +2,10 @@ use std::{error, num::TryFromIntError};
 
 use si_data_nats::async_nats::jetstream;
 use si_data_pg::{PgError, PgPoolError};
-use si_events::{content_hash::ContentHashParseError, ActionId, FuncRunId};
+use si_events::{
+    content_hash::ContentHashParseError,
+    workspace_snapshot_address::WorkspaceSnapshotAddressParseError, ActionId, FuncRunId,
+};
 use si_std::CanonicalFileError;
 use thiserror::Error;
 use tokio_stream::Elapsed;
@@ -38,6 +41,10 @@ pub enum LayerDbError {
     CacheUpdateNoHeaders,
     #[error("canonical file error: {0}")]
     CanonicalFile(#[from] CanonicalFileError),
+    #[error("chrono out of range error: {0}")]
+    ChronoOutOfRange(#[from] chrono::OutOfRangeError),
+    #[error("compression error: {0}")]
+    Compress(String),
     #[error("content conversion error: {0}")]
     ContentConversion(String),
     #[error("could not convert to key from string")]
@@ -60,10 +67,14 @@ pub enum LayerDbError {
     JoinError(#[from] tokio::task::JoinError),
     #[error("Layered Event Server send error: {0}")]
     LayeredEventSend(#[from] Box<tokio::sync::mpsc::error::SendError<LayeredEvent>>),
+    #[error("malformed serialization header")]
+    MalformedHeader,
     #[error("missing func_run when one was expected: {0}")]
     MissingFuncRun(FuncRunId),
     #[error("missing internal buffer entry when expected; this is an internal bug")]
     MissingInternalBuffer,
+    #[error("no zstd dictionary registered with id {0}")]
+    MissingZstdDictionary(u32),
     #[error("ack error: {0}")]
     NatsAck(#[source] si_data_nats::async_nats::Error),
     #[error("raw ack error: {0}")]
@@ -102,6 +113,12 @@ pub enum LayerDbError {
     TokioOneShotRecv(#[from] tokio::sync::oneshot::error::RecvError),
     #[error("unexpected activity variant; expected={0}, actual={1}")]
     UnexpectedActivityVariant(String, String),
+    #[error("unknown compression codec id: {0}")]
+    UnknownCodec(u8),
+    #[error("unsupported serialization format version: {0}")]
+    UnsupportedFormatVersion(u8),
+    #[error("failed to parse workspace snapshot address from str: {0}")]
+    WorkspaceSnapshotAddressParse(#[from] WorkspaceSnapshotAddressParseError),
 }
 
 impl LayerDbError {
@@ -92,6 +92,7 @@ impl std::str::FromStr for LayeredEventId {
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
 pub enum LayeredEventKind {
+    CasEvict,
     CasInsertion,
     EncryptedSecretInsertion,
     FuncRunLogWrite,
@@ -10,7 +10,7 @@ use tokio::fs;
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::db::serialize;
+use crate::db::serialize::{self, ZstdDictionaries};
 use crate::error::LayerDbResult;
 use crate::LayerDbError;
 
@@ -45,13 +45,17 @@ where
     V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
 {
     cache: HybridCache<Arc<str>, MaybeDeserialized<V>>,
+    zstd_dictionaries: ZstdDictionaries,
 }
 
 impl<V> Cache<V>
 where
     V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
 {
-    pub async fn new(config: CacheConfig) -> LayerDbResult<Self> {
+    pub async fn new(
+        config: CacheConfig,
+        zstd_dictionaries: ZstdDictionaries,
+    ) -> LayerDbResult<Self> {
         let total_memory_bytes = *TOTAL_SYSTEM_MEMORY_BYTES;
 
         let memory_cache_capacity_bytes = {
@@ -128,7 +132,10 @@ where
             .await
             .map_err(|e| LayerDbError::Foyer(e.into()))?;
 
-        Ok(Self { cache })
+        Ok(Self {
+            cache,
+            zstd_dictionaries,
+        })
     }
 
     pub async fn get(&self, key: &str) -> Option<V> {
@@ -138,7 +145,7 @@ where
                 MaybeDeserialized::RawBytes(bytes) => {
                     // If we fail to deserialize the raw bytes for some reason, pretend that we never
                     // had the key in the first place, and also remove it from the cache.
-                    match serialize::from_bytes_async::<V>(bytes).await {
+                    match serialize::from_bytes_async::<V>(bytes, &self.zstd_dictionaries).await {
                         Ok(deserialized) => {
                             self.insert(key.into(), deserialized.clone(), bytes.len());
                             Some(deserialized)
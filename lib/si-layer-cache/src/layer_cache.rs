@@ -10,7 +10,7 @@ use telemetry::prelude::*;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::TaskTracker;
 
-use crate::db::serialize;
+use crate::db::serialize::{self, Codec, ZstdDictionaries};
 use crate::error::LayerDbResult;
 use crate::hybrid_cache::{Cache, CacheConfig};
 use crate::pg::PgLayer;
@@ -24,6 +24,8 @@ where
     cache: Cache<V>,
     name: String,
     pg: PgLayer,
+    codec: Codec,
+    zstd_dictionaries: ZstdDictionaries,
     #[allow(dead_code)]
     compute_executor: DedicatedExecutor,
 }
@@ -36,11 +38,13 @@ where
         name: &str,
         pg_pool: PgPool,
         cache_config: CacheConfig,
+        codec: Codec,
+        zstd_dictionaries: ZstdDictionaries,
         #[allow(dead_code)] compute_executor: DedicatedExecutor,
         tracker: TaskTracker,
         token: CancellationToken,
     ) -> LayerDbResult<Arc<Self>> {
-        let cache = Cache::new(cache_config).await?;
+        let cache = Cache::new(cache_config, zstd_dictionaries.clone()).await?;
 
         let pg = PgLayer::new(pg_pool.clone(), name);
 
@@ -48,6 +52,8 @@ where
             cache,
             name: name.to_string(),
             pg,
+            codec,
+            zstd_dictionaries,
             compute_executor,
         }
         .into();
@@ -70,7 +76,8 @@ where
 
             None => match self.pg.get(&key).await? {
                 Some(bytes) => {
-                    let deserialized: V = serialize::from_bytes(&bytes)?;
+                    let deserialized: V =
+                        serialize::from_bytes_with_dictionaries(&bytes, &self.zstd_dictionaries)?;
 
                     self.cache
                         .insert(key.clone(), deserialized.clone(), bytes.len());
@@ -121,7 +128,8 @@ where
         if !not_found.is_empty() {
             if let Some(pg_found) = self.pg.get_many(&not_found).await? {
                 for (k, bytes) in pg_found {
-                    let deserialized: V = serialize::from_bytes(&bytes)?;
+                    let deserialized: V =
+                        serialize::from_bytes_with_dictionaries(&bytes, &self.zstd_dictionaries)?;
                     self.cache
                         .insert(k.clone().into(), deserialized.clone(), bytes.len());
                     found_keys.insert(
@@ -138,7 +146,7 @@ where
     }
 
     pub async fn deserialize_memory_value(&self, bytes: Arc<Vec<u8>>) -> LayerDbResult<V> {
-        serialize::from_bytes_async(&bytes)
+        serialize::from_bytes_async(&bytes, &self.zstd_dictionaries)
             .await
             .map_err(Into::into)
     }
@@ -151,6 +159,16 @@ where
         self.pg.clone()
     }
 
+    /// The codec used to compress values written to this cache's table.
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// The zstd dictionaries used to compress and decompress values in this cache's table.
+    pub fn zstd_dictionaries(&self) -> &ZstdDictionaries {
+        &self.zstd_dictionaries
+    }
+
     pub fn remove_from_memory(&self, key: &str) {
         self.cache.remove(key);
     }
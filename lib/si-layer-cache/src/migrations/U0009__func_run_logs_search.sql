@@ -0,0 +1,3 @@
+-- Nullable and without a default, so that adding it does not rewrite the table. Existing rows are
+-- backfilled, and the search indexes built concurrently, by FuncRunLogDb::backfill_search.
+ALTER TABLE func_run_logs ADD COLUMN IF NOT EXISTS search_vector tsvector;
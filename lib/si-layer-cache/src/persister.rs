@@ -14,6 +14,7 @@ use tokio_util::{sync::CancellationToken, task::TaskTracker};
 use ulid::Ulid;
 
 use crate::db::func_run::FuncRunDb;
+use crate::db::serialize::ZstdDictionaries;
 use crate::event::LayeredEventKind;
 use crate::{
     error::{LayerDbError, LayerDbResult},
@@ -105,6 +106,7 @@ pub struct PersisterTask {
     messages: mpsc::UnboundedReceiver<PersistMessage>,
     pg_pool: PgPool,
     layered_event_client: LayeredEventClient,
+    zstd_dictionaries: ZstdDictionaries,
     tracker: TaskTracker,
     shutdown_token: CancellationToken,
 }
@@ -117,6 +119,7 @@ impl PersisterTask {
         pg_pool: PgPool,
         nats_client: &NatsClient,
         instance_id: Ulid,
+        zstd_dictionaries: ZstdDictionaries,
         shutdown_token: CancellationToken,
     ) -> LayerDbResult<Self> {
         let tracker = TaskTracker::new();
@@ -139,6 +142,7 @@ impl PersisterTask {
             messages,
             pg_pool,
             layered_event_client,
+            zstd_dictionaries,
             tracker,
             shutdown_token,
         })
@@ -177,6 +181,7 @@ impl PersisterTask {
                     let task = PersistEventTask::new(
                         self.pg_pool.clone(),
                         self.layered_event_client.clone(),
+                        self.zstd_dictionaries.clone(),
                     );
                     self.tracker.spawn(task.write_layers(event, status_tx));
                 }
@@ -184,6 +189,7 @@ impl PersisterTask {
                     let task = PersistEventTask::new(
                         self.pg_pool.clone(),
                         self.layered_event_client.clone(),
+                        self.zstd_dictionaries.clone(),
                     );
                     self.tracker.spawn(task.evict_layers(event, status_tx));
                 }
@@ -209,13 +215,19 @@ pub struct PersisterTaskError {
 pub struct PersistEventTask {
     pg_pool: PgPool,
     layered_event_client: LayeredEventClient,
+    zstd_dictionaries: ZstdDictionaries,
 }
 
 impl PersistEventTask {
-    pub fn new(pg_pool: PgPool, layered_event_client: LayeredEventClient) -> Self {
+    pub fn new(
+        pg_pool: PgPool,
+        layered_event_client: LayeredEventClient,
+        zstd_dictionaries: ZstdDictionaries,
+    ) -> Self {
         PersistEventTask {
             pg_pool,
             layered_event_client,
+            zstd_dictionaries,
         }
     }
 
@@ -323,7 +335,8 @@ impl PersistEventTask {
     pub async fn write_to_pg(&self, event: Arc<LayeredEvent>) -> LayerDbResult<()> {
         let pg_layer = PgLayer::new(self.pg_pool.clone(), event.payload.db_name.as_ref());
         match event.event_kind {
-            LayeredEventKind::CasInsertion
+            LayeredEventKind::CasEvict
+            | LayeredEventKind::CasInsertion
             | LayeredEventKind::EncryptedSecretInsertion
             | LayeredEventKind::Raw
             | LayeredEventKind::RebaseBatchEvict
@@ -345,7 +358,7 @@ impl PersistEventTask {
                 // FuncRunLogDb::insert_to_pg(&pg_layer, &event.payload).await?
             }
             LayeredEventKind::FuncRunWrite => {
-                FuncRunDb::insert_to_pg(&pg_layer, &event.payload).await?
+                FuncRunDb::insert_to_pg(&pg_layer, &event.payload, &self.zstd_dictionaries).await?
             }
         }
         Ok(())
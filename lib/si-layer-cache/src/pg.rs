@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use si_data_pg::{postgres_types::ToSql, PgPool, PgPoolConfig, PgRow};
+use chrono::{DateTime, Utc};
+
+use si_data_pg::{postgres_types::ToSql, InstrumentedClient, PgPool, PgPoolConfig, PgRow};
 use telemetry::tracing::info;
 use telemetry_utils::metric;
 
@@ -33,6 +35,8 @@ pub struct PgLayer {
     get_value_by_prefix_query: String,
     get_value_many_query: String,
     get_most_recent_query: String,
+    get_keys_created_before_query: String,
+    get_keys_created_since_query: String,
     insert_value_query: String,
     contains_key_query: String,
     search_query: String,
@@ -48,6 +52,8 @@ impl PgLayer {
             get_value_by_prefix_query: format!("SELECT key, value FROM {table_name} WHERE key like $1"),
             get_value_many_query: format!("SELECT key, value FROM {table_name} WHERE key = any($1)"),
             get_most_recent_query: format!("SELECT key, value FROM {table_name} ORDER BY created_at LIMIT $1"),
+            get_keys_created_before_query: format!("SELECT key, octet_length(value) AS size FROM {table_name} WHERE created_at < $1 AND key > $2 ORDER BY key LIMIT $3"),
+            get_keys_created_since_query: format!("SELECT key FROM {table_name} WHERE created_at >= $1"),
             insert_value_query: format!("INSERT INTO {table_name} (key, sort_key, value) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING"),
             contains_key_query: format!("SELECT key FROM {table_name} WHERE key = $1 LIMIT 1"),
             search_query: format!("SELECT value FROM {table_name} WHERE sort_key LIKE $1"),
@@ -74,6 +80,12 @@ impl PgLayer {
         }
     }
 
+    /// Checks out a connection, for work that has to stay on one session (such as holding an
+    /// advisory lock).
+    pub async fn client(&self) -> LayerDbResult<InstrumentedClient> {
+        Ok(self.pool.get().await?)
+    }
+
     pub async fn get_raw(
         &self,
         query: &str,
@@ -176,6 +188,42 @@ impl PgLayer {
         Ok(Some(result))
     }
 
+    /// Returns the key and stored size (in bytes) of up to `limit` rows created before the given
+    /// time, ordered by key and starting after `after_key`. Pass the last key of a page to get the
+    /// next one.
+    pub async fn get_keys_created_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        after_key: &str,
+        limit: i64,
+    ) -> LayerDbResult<Vec<(String, i32)>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                &self.get_keys_created_before_query,
+                &[&cutoff, &after_key, &limit],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get("key"), row.get("size")))
+            .collect())
+    }
+
+    /// Returns the key of every row created at or after the given time.
+    pub async fn get_keys_created_since(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> LayerDbResult<Vec<String>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(&self.get_keys_created_since_query, &[&cutoff])
+            .await?;
+
+        Ok(rows.into_iter().map(|row| row.get("key")).collect())
+    }
+
     pub async fn search(&self, sort_key_like: impl AsRef<str>) -> LayerDbResult<Vec<Vec<u8>>> {
         let sort_key_like = sort_key_like.as_ref();
         let client = self.pool.get().await?;
@@ -1,7 +1,12 @@
 use std::{sync::Arc, time::Duration};
 
 use si_events::{Actor, CasValue, ChangeSetId, ContentHash, Tenancy, UserPk, WorkspacePk};
-use si_layer_cache::{db::serialize, hybrid_cache::CacheConfig, persister::PersistStatus, LayerDb};
+use si_layer_cache::{
+    db::serialize::{self, Codec, ZstdDictionaries},
+    hybrid_cache::CacheConfig,
+    persister::PersistStatus,
+    LayerDb,
+};
 use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 
@@ -309,9 +314,13 @@ async fn stress_test() {
     for v in values {
         let big_string: Arc<String> = Arc::new(v.repeat(10_000_000));
         let cas_value = Arc::new(CasValue::String(big_string.to_string()));
-        let (postcard_value, _) =
-            serialize::to_vec(&cas_value).expect("cannot deserialize big ass string");
-        let cas_pk_string = ContentHash::new(&postcard_value).to_string();
+        let (_, uncompressed_value) = serialize::to_vec_and_uncompressed(
+            &cas_value,
+            Codec::default(),
+            &ZstdDictionaries::default(),
+        )
+        .expect("cannot deserialize big ass string");
+        let cas_pk_string = ContentHash::new(&uncompressed_value).to_string();
         let ldb_slash_task = ldb_slash.clone();
         let _write_big_string = big_string.clone();
         let write_cas_value = cas_value.clone();
@@ -222,3 +222,147 @@ async fn write_and_get_for_func_run_id() {
 
     assert_eq!(value.id(), read_value.id());
 }
+
+#[tokio::test]
+async fn search_for_workspace() {
+    let token = CancellationToken::new();
+
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        setup_pg_db("func_run_log_search_for_workspace").await,
+        setup_nats_client(Some("func_run_log_search_for_workspace".to_string())).await,
+        setup_compute_executor(),
+        CacheConfig::default(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate ldb");
+
+    let workspace_pk = WorkspacePk::new();
+    let actor = Actor::User(UserPk::new());
+    let line = |message: &str| OutputLine {
+        stream: "stdout".to_string(),
+        execution_id: "execution".to_string(),
+        level: "info".to_string(),
+        group: None,
+        message: message.to_string(),
+        timestamp: 0,
+    };
+
+    let tenancy = Tenancy::new(workspace_pk, ChangeSetId::new());
+    let mut matching = FuncRunLog::new(FuncRunId::new(), tenancy);
+    matching.push_log(line("creating bucket poop-canoe"));
+    matching.push_log(line("bucket created"));
+    let mut not_matching = FuncRunLog::new(FuncRunId::new(), tenancy);
+    not_matching.push_log(line("refreshing instance"));
+
+    // Logs from other workspaces are never returned
+    let other_tenancy = Tenancy::new(WorkspacePk::new(), ChangeSetId::new());
+    let mut other_workspace = FuncRunLog::new(FuncRunId::new(), other_tenancy);
+    other_workspace.push_log(line("creating bucket elsewhere"));
+
+    for (func_run_log, tenancy) in [
+        (matching.clone(), tenancy),
+        (not_matching, tenancy),
+        (other_workspace, other_tenancy),
+    ] {
+        ldb.func_run_log()
+            .write(Arc::new(func_run_log), None, tenancy, actor)
+            .await
+            .expect("failed to write to layerdb");
+    }
+
+    let found = ldb
+        .func_run_log()
+        .search_for_workspace(workspace_pk, "bucket", 10)
+        .await
+        .expect("failed to search func run logs");
+    assert_eq!(
+        vec![matching.func_run_id()],
+        found
+            .iter()
+            .map(|func_run_log| func_run_log.func_run_id())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn backfill_search() {
+    let token = CancellationToken::new();
+
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        setup_pg_db("func_run_log_backfill_search").await,
+        setup_nats_client(Some("func_run_log_backfill_search".to_string())).await,
+        setup_compute_executor(),
+        CacheConfig::default(),
+        token.clone(),
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate ldb");
+
+    let workspace_pk = WorkspacePk::new();
+    let tenancy = Tenancy::new(workspace_pk, ChangeSetId::new());
+    let mut func_run_log = FuncRunLog::new(FuncRunId::new(), tenancy);
+    func_run_log.push_log(OutputLine {
+        stream: "stdout".to_string(),
+        execution_id: "execution".to_string(),
+        level: "info".to_string(),
+        group: None,
+        message: "deleting bucket poop-canoe".to_string(),
+        timestamp: 0,
+    });
+    ldb.func_run_log()
+        .write(
+            Arc::new(func_run_log.clone()),
+            None,
+            tenancy,
+            Actor::User(UserPk::new()),
+        )
+        .await
+        .expect("failed to write to layerdb");
+
+    // Make the log look like it was written before search existed
+    ldb.func_run_log()
+        .cache
+        .pg()
+        .insert_raw(
+            "UPDATE func_run_logs SET search_vector = NULL WHERE key = $1",
+            &[&func_run_log.id().to_string()],
+        )
+        .await
+        .expect("failed to clear search vector");
+    assert!(ldb
+        .func_run_log()
+        .search_for_workspace(workspace_pk, "bucket", 10)
+        .await
+        .expect("failed to search func run logs")
+        .is_empty());
+
+    // The backfill may also be running in the background, in which case this returns straight away
+    let max_check_count = 50;
+    let mut check_count = 0;
+    let found = loop {
+        ldb.func_run_log()
+            .backfill_search(token.clone())
+            .await
+            .expect("failed to backfill search");
+        let found = ldb
+            .func_run_log()
+            .search_for_workspace(workspace_pk, "bucket", 10)
+            .await
+            .expect("failed to search func run logs");
+        if !found.is_empty() || check_count >= max_check_count {
+            break found;
+        }
+        check_count += 1;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+    assert_eq!(
+        vec![func_run_log.func_run_id()],
+        found
+            .iter()
+            .map(|func_run_log| func_run_log.func_run_id())
+            .collect::<Vec<_>>()
+    );
+}
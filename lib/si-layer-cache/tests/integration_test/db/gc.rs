@@ -0,0 +1,148 @@
+use std::{sync::Arc, time::Duration};
+
+use si_events::{Actor, CasValue, ChangeSetId, Tenancy, UserPk, WorkspacePk};
+use si_layer_cache::{
+    db::gc::{GcOptions, GcRoots},
+    hybrid_cache::CacheConfig,
+    persister::PersistStatus,
+    LayerDb,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::integration_test::{setup_compute_executor, setup_nats_client, setup_pg_db};
+
+type TestLayerDb = LayerDb<CasValue, String, String, String>;
+
+#[tokio::test]
+async fn sweep_unreachable() {
+    let token = CancellationToken::new();
+
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        setup_pg_db("gc_sweep_unreachable").await,
+        setup_nats_client(Some("gc_sweep_unreachable".to_string())).await,
+        setup_compute_executor(),
+        CacheConfig::default(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate layer db");
+
+    let tenancy = Tenancy::new(WorkspacePk::new(), ChangeSetId::new());
+    let actor = Actor::User(UserPk::new());
+
+    let mut statuses = Vec::new();
+    let (reachable_hash, status) = ldb
+        .cas()
+        .write(
+            Arc::new(serde_json::json!("reachable").into()),
+            None,
+            tenancy,
+            actor,
+        )
+        .expect("failed to write to layerdb");
+    statuses.push(status);
+    let (unreachable_hash, status) = ldb
+        .cas()
+        .write(
+            Arc::new(serde_json::json!("unreachable").into()),
+            None,
+            tenancy,
+            actor,
+        )
+        .expect("failed to write to layerdb");
+    statuses.push(status);
+    let (reachable_address, status) = ldb
+        .workspace_snapshot()
+        .write(Arc::new("reachable".to_string()), None, tenancy, actor)
+        .expect("failed to write to layerdb");
+    statuses.push(status);
+    let (unreachable_address, status) = ldb
+        .workspace_snapshot()
+        .write(Arc::new("unreachable".to_string()), None, tenancy, actor)
+        .expect("failed to write to layerdb");
+    statuses.push(status);
+
+    for status in statuses {
+        match status.get_status().await.expect("failed to get status") {
+            PersistStatus::Finished => {}
+            PersistStatus::Error(e) => panic!("Write failed; {e}"),
+        }
+    }
+
+    // Everything was just written, so everything is inside the default retention window.
+    let recent = ldb
+        .recent_workspace_snapshot_addresses(GcOptions::default().retention)
+        .await
+        .expect("could not list recent snapshots");
+    assert!(recent.contains(&reachable_address));
+    assert!(recent.contains(&unreachable_address));
+
+    let mut roots = GcRoots::new();
+    roots.mark_workspace_snapshot_address(reachable_address);
+    roots.mark_content_hashes([reachable_hash]);
+
+    let options = GcOptions::default().with_retention(Duration::ZERO);
+
+    // A dry run reports what would be collected, but leaves it in place.
+    let report = ldb
+        .sweep(&roots, options.with_dry_run(true), tenancy, actor)
+        .await
+        .expect("could not sweep");
+    assert!(report.dry_run);
+    assert!(report.cas.collected.contains(&unreachable_hash.to_string()));
+    assert!(!report.cas.collected.contains(&reachable_hash.to_string()));
+    assert!(report
+        .workspace_snapshots
+        .collected
+        .contains(&unreachable_address.to_string()));
+    assert!(!report
+        .workspace_snapshots
+        .collected
+        .contains(&reachable_address.to_string()));
+    assert!(ldb
+        .cas()
+        .cache
+        .pg()
+        .contains_key(&unreachable_hash.to_string())
+        .await
+        .expect("could not check pg"));
+
+    // A real run removes the unreachable values from every layer.
+    let report = ldb
+        .sweep(&roots, options.with_dry_run(false), tenancy, actor)
+        .await
+        .expect("could not sweep");
+    assert!(!report.dry_run);
+
+    assert!(!ldb
+        .cas()
+        .cache
+        .pg()
+        .contains_key(&unreachable_hash.to_string())
+        .await
+        .expect("could not check pg"));
+    assert!(!ldb.cas().cache.contains(&unreachable_hash.to_string()));
+    assert!(ldb
+        .cas()
+        .cache
+        .pg()
+        .contains_key(&reachable_hash.to_string())
+        .await
+        .expect("could not check pg"));
+
+    assert!(!ldb
+        .workspace_snapshot()
+        .cache
+        .pg()
+        .contains_key(&unreachable_address.to_string())
+        .await
+        .expect("could not check pg"));
+    assert!(ldb
+        .workspace_snapshot()
+        .cache
+        .pg()
+        .contains_key(&reachable_address.to_string())
+        .await
+        .expect("could not check pg"));
+}
@@ -1,4 +1,5 @@
 mod cas;
 mod func_run;
 mod func_run_log;
+mod gc;
 mod workspace_snapshot;
@@ -1,6 +1,6 @@
 use rand::seq::SliceRandom;
 use rand::thread_rng;
-use si_layer_cache::db::serialize;
+use si_layer_cache::db::serialize::{self, Codec, ZstdDictionaries};
 use si_layer_cache::hybrid_cache::CacheConfig;
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
@@ -13,6 +13,8 @@ async fn make_layer_cache(db_name: &str) -> Arc<LayerCache<String>> {
         "cas",
         super::setup_pg_db(db_name).await,
         CacheConfig::default(),
+        Codec::default(),
+        ZstdDictionaries::default(),
         super::setup_compute_executor(),
         TaskTracker::new(),
         CancellationToken::new(),
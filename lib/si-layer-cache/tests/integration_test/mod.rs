@@ -9,6 +9,7 @@ use crate::TEST_PG_DBNAME;
 mod activities;
 mod db;
 mod layer_cache;
+mod serialize;
 
 const DEFAULT_TEST_PG_USER: &str = "si_test";
 const DEFAULT_TEST_PG_PORT_STR: &str = "6432";
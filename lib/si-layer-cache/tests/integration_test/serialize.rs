@@ -0,0 +1,117 @@
+use si_layer_cache::db::serialize::{self, Codec, ZstdDictionaries};
+
+const VALUE: &str = "the sound of tomorrow, the sound of today";
+
+#[test]
+fn round_trips_every_codec() {
+    let dictionary_id = 42;
+    let samples: Vec<Vec<u8>> = (0..1000)
+        .map(|i| postcard::to_stdvec(&format!("{VALUE} {i}")).expect("should serialize"))
+        .collect();
+    let dictionary =
+        serialize::train_zstd_dictionary(&samples, 4096).expect("should train dictionary");
+    let dictionaries = ZstdDictionaries::new([(dictionary_id, dictionary)]);
+
+    for codec in [
+        Codec::Deflate { level: 1 },
+        Codec::Deflate { level: 9 },
+        Codec::Zstd {
+            level: 3,
+            dictionary_id: None,
+        },
+        Codec::Zstd {
+            level: 3,
+            dictionary_id: Some(dictionary_id),
+        },
+    ] {
+        let (bytes, _) = serialize::to_vec_with_codec(VALUE, codec, &dictionaries)
+            .expect("should serialize with codec");
+        let value: String = serialize::from_bytes_with_dictionaries(&bytes, &dictionaries)
+            .expect("should deserialize");
+        assert_eq!(VALUE, value, "round trip failed for {codec:?}");
+    }
+}
+
+#[test]
+fn reads_legacy_headerless_deflate() {
+    let serialized = postcard::to_stdvec(VALUE).expect("should serialize");
+    let legacy = miniz_oxide::deflate::compress_to_vec(&serialized, 1);
+
+    let value: String = serialize::from_bytes(&legacy).expect("should deserialize legacy value");
+    assert_eq!(VALUE, value);
+}
+
+#[test]
+fn missing_dictionary_is_an_error() {
+    let serialized = serialize::to_vec_with_codec(
+        VALUE,
+        Codec::Zstd {
+            level: 3,
+            dictionary_id: Some(u32::MAX),
+        },
+        &ZstdDictionaries::default(),
+    );
+    assert!(serialized.is_err());
+}
+
+#[test]
+fn dictionaries_are_scoped_to_their_owner() {
+    let dictionary_id = 7;
+    let samples: Vec<Vec<u8>> = (0..1000)
+        .map(|i| postcard::to_stdvec(&format!("{VALUE} {i}")).expect("should serialize"))
+        .collect();
+    let dictionary =
+        serialize::train_zstd_dictionary(&samples, 4096).expect("should train dictionary");
+    let dictionaries = ZstdDictionaries::new([(dictionary_id, dictionary)]);
+
+    let (bytes, _) = serialize::to_vec_with_codec(
+        VALUE,
+        Codec::Zstd {
+            level: 3,
+            dictionary_id: Some(dictionary_id),
+        },
+        &dictionaries,
+    )
+    .expect("should serialize with dictionary");
+
+    assert!(serialize::from_bytes::<String>(&bytes).is_err());
+    assert!(serialize::from_bytes_with_dictionaries::<String>(
+        &bytes,
+        &ZstdDictionaries::default()
+    )
+    .is_err());
+}
+
+#[test]
+fn default_codec_writes_legacy_headerless_deflate() {
+    let (bytes, _) = serialize::to_vec(VALUE).expect("should serialize");
+    let serialized = postcard::to_stdvec(VALUE).expect("should serialize");
+
+    let inflated = miniz_oxide::inflate::decompress_to_vec(&bytes)
+        .expect("should be readable as bare deflate");
+    assert_eq!(serialized, inflated);
+}
+
+#[test]
+fn uncompressed_bytes_do_not_depend_on_codec() {
+    let dictionaries = ZstdDictionaries::default();
+    let (_, deflate_uncompressed) =
+        serialize::to_vec_and_uncompressed(VALUE, Codec::default(), &dictionaries)
+            .expect("should serialize");
+    let (zstd_compressed, zstd_uncompressed) = serialize::to_vec_and_uncompressed(
+        VALUE,
+        Codec::Zstd {
+            level: 3,
+            dictionary_id: None,
+        },
+        &dictionaries,
+    )
+    .expect("should serialize");
+
+    assert_eq!(deflate_uncompressed, zstd_uncompressed);
+    assert_eq!(
+        postcard::to_stdvec(VALUE).expect("should serialize"),
+        zstd_uncompressed
+    );
+    assert_ne!(zstd_uncompressed, zstd_compressed);
+}
@@ -22,6 +22,7 @@ const KEY_BACKEND_KIND_STR: &str = "backend_kind";
 const KEY_RESPONSE_TYPE_STR: &str = "response_type";
 const KEY_HIDDEN_STR: &str = "hidden";
 const KEY_LINK_STR: &str = "link";
+const KEY_TIMEOUT_SECS_STR: &str = "timeout_secs";
 const KEY_IS_FROM_BUILTIN: &str = "is_from_builtin";
 
 #[derive(Clone, Debug)]
@@ -35,6 +36,7 @@ pub struct FuncData {
     pub response_type: FuncSpecBackendResponseType,
     pub hidden: bool,
     pub link: Option<Url>,
+    pub timeout_secs: Option<u64>,
 }
 
 #[derive(Clone, Debug)]
@@ -76,6 +78,7 @@ impl WriteBytes for FuncNode {
                 KEY_LINK_STR,
                 data.link.as_ref().map(|l| l.as_str()).unwrap_or(""),
             )?;
+            write_key_value_line_opt(writer, KEY_TIMEOUT_SECS_STR, data.timeout_secs)?;
         }
 
         write_common_fields(writer, Some(self.unique_id.as_str()), self.deleted)?;
@@ -121,6 +124,12 @@ impl ReadBytes for FuncNode {
                 } else {
                     Some(Url::parse(&link_str).map_err(GraphError::parse)?)
                 };
+                let timeout_secs = match read_key_value_line_opt(reader, KEY_TIMEOUT_SECS_STR)? {
+                    Some(timeout_secs_str) => {
+                        Some(u64::from_str(&timeout_secs_str).map_err(GraphError::parse)?)
+                    }
+                    None => None,
+                };
 
                 Some(FuncData {
                     name: name.clone(),
@@ -132,6 +141,7 @@ impl ReadBytes for FuncNode {
                     response_type,
                     hidden,
                     link,
+                    timeout_secs,
                 })
             }
         };
@@ -162,6 +172,12 @@ impl NodeChild for FuncSpec {
             .arguments
             .iter()
             .map(|arg| Box::new(arg.clone()) as Box<dyn NodeChild<NodeType = Self::NodeType>>)
+            .chain(self.test_cases.iter().map(|test_case| {
+                Box::new(test_case.clone()) as Box<dyn NodeChild<NodeType = Self::NodeType>>
+            }))
+            .chain(self.versions.iter().map(|version| {
+                Box::new(version.clone()) as Box<dyn NodeChild<NodeType = Self::NodeType>>
+            }))
             .collect();
 
         NodeWithChildren::new(
@@ -178,6 +194,7 @@ impl NodeChild for FuncSpec {
                     response_type: data.response_type,
                     hidden: data.hidden,
                     link: data.link.as_ref().cloned(),
+                    timeout_secs: data.timeout_secs,
                 }),
                 unique_id: self.unique_id.to_owned(),
                 deleted: self.deleted,
@@ -0,0 +1,92 @@
+use super::PkgNode;
+use crate::spec::FuncTestCaseSpec;
+use object_tree::{
+    read_key_value_line, write_key_value_line, GraphError, NameStr, NodeChild, NodeKind,
+    NodeWithChildren, ReadBytes, WriteBytes,
+};
+use std::io::{BufRead, Write};
+
+const KEY_NAME_STR: &str = "name";
+const KEY_DESCRIPTION_STR: &str = "description";
+const KEY_FIXTURE_STR: &str = "fixture";
+const KEY_EXPECTATION_STR: &str = "expectation";
+
+#[derive(Clone, Debug)]
+pub struct FuncTestCaseNode {
+    pub name: String,
+    pub description: Option<String>,
+    pub fixture: serde_json::Value,
+    pub expectation: serde_json::Value,
+}
+
+impl NameStr for FuncTestCaseNode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl WriteBytes for FuncTestCaseNode {
+    fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<(), GraphError> {
+        write_key_value_line(writer, KEY_NAME_STR, &self.name)?;
+        write_key_value_line(
+            writer,
+            KEY_DESCRIPTION_STR,
+            self.description.as_deref().unwrap_or(""),
+        )?;
+        write_key_value_line(
+            writer,
+            KEY_FIXTURE_STR,
+            serde_json::to_string(&self.fixture).map_err(GraphError::parse)?,
+        )?;
+        write_key_value_line(
+            writer,
+            KEY_EXPECTATION_STR,
+            serde_json::to_string(&self.expectation).map_err(GraphError::parse)?,
+        )?;
+
+        Ok(())
+    }
+}
+
+impl ReadBytes for FuncTestCaseNode {
+    fn read_bytes<R: BufRead>(reader: &mut R) -> Result<Option<Self>, GraphError>
+    where
+        Self: std::marker::Sized,
+    {
+        let name = read_key_value_line(reader, KEY_NAME_STR)?;
+        let description_str = read_key_value_line(reader, KEY_DESCRIPTION_STR)?;
+        let description = if description_str.is_empty() {
+            None
+        } else {
+            Some(description_str)
+        };
+        let fixture_str = read_key_value_line(reader, KEY_FIXTURE_STR)?;
+        let fixture = serde_json::from_str(&fixture_str).map_err(GraphError::parse)?;
+        let expectation_str = read_key_value_line(reader, KEY_EXPECTATION_STR)?;
+        let expectation = serde_json::from_str(&expectation_str).map_err(GraphError::parse)?;
+
+        Ok(Some(Self {
+            name,
+            description,
+            fixture,
+            expectation,
+        }))
+    }
+}
+
+impl NodeChild for FuncTestCaseSpec {
+    type NodeType = PkgNode;
+
+    fn as_node_with_children(&self) -> NodeWithChildren<Self::NodeType> {
+        NodeWithChildren::new(
+            NodeKind::Leaf,
+            Self::NodeType::FuncTestCase(FuncTestCaseNode {
+                name: self.name.to_owned(),
+                description: self.description.to_owned(),
+                fixture: self.fixture.to_owned(),
+                expectation: self.expectation.to_owned(),
+            }),
+            vec![],
+        )
+    }
+}
@@ -0,0 +1,88 @@
+use super::PkgNode;
+use crate::spec::FuncVersionSpec;
+use chrono::{DateTime, Utc};
+use object_tree::{
+    read_key_value_line, read_key_value_line_opt, write_key_value_line, write_key_value_line_opt,
+    GraphError, NameStr, NodeChild, NodeKind, NodeWithChildren, ReadBytes, WriteBytes,
+};
+use std::io::{BufRead, Write};
+
+const KEY_VERSION_STR: &str = "version";
+const KEY_CREATED_AT_STR: &str = "created_at";
+const KEY_MESSAGE_STR: &str = "message";
+const KEY_AUTHOR_STR: &str = "author";
+const KEY_HANDLER_STR: &str = "handler";
+const KEY_CODE_STR: &str = "code_base64";
+
+#[derive(Clone, Debug)]
+pub struct FuncVersionNode {
+    pub version: String,
+    pub created_at: DateTime<Utc>,
+    pub message: Option<String>,
+    pub author: Option<String>,
+    pub handler: Option<String>,
+    pub code_base64: Option<String>,
+}
+
+impl NameStr for FuncVersionNode {
+    fn name(&self) -> &str {
+        &self.version
+    }
+}
+
+impl WriteBytes for FuncVersionNode {
+    fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<(), GraphError> {
+        write_key_value_line(writer, KEY_VERSION_STR, &self.version)?;
+        write_key_value_line(writer, KEY_CREATED_AT_STR, self.created_at.to_rfc3339())?;
+        write_key_value_line_opt(writer, KEY_MESSAGE_STR, self.message.as_deref())?;
+        write_key_value_line_opt(writer, KEY_AUTHOR_STR, self.author.as_deref())?;
+        write_key_value_line_opt(writer, KEY_HANDLER_STR, self.handler.as_deref())?;
+        write_key_value_line_opt(writer, KEY_CODE_STR, self.code_base64.as_deref())?;
+
+        Ok(())
+    }
+}
+
+impl ReadBytes for FuncVersionNode {
+    fn read_bytes<R: BufRead>(reader: &mut R) -> Result<Option<Self>, GraphError>
+    where
+        Self: std::marker::Sized,
+    {
+        let version = read_key_value_line(reader, KEY_VERSION_STR)?;
+        let created_at = read_key_value_line(reader, KEY_CREATED_AT_STR)?
+            .parse::<DateTime<Utc>>()
+            .map_err(GraphError::parse)?;
+        let message = read_key_value_line_opt(reader, KEY_MESSAGE_STR)?;
+        let author = read_key_value_line_opt(reader, KEY_AUTHOR_STR)?;
+        let handler = read_key_value_line_opt(reader, KEY_HANDLER_STR)?;
+        let code_base64 = read_key_value_line_opt(reader, KEY_CODE_STR)?;
+
+        Ok(Some(Self {
+            version,
+            created_at,
+            message,
+            author,
+            handler,
+            code_base64,
+        }))
+    }
+}
+
+impl NodeChild for FuncVersionSpec {
+    type NodeType = PkgNode;
+
+    fn as_node_with_children(&self) -> NodeWithChildren<Self::NodeType> {
+        NodeWithChildren::new(
+            NodeKind::Leaf,
+            Self::NodeType::FuncVersion(FuncVersionNode {
+                version: self.version.to_owned(),
+                created_at: self.created_at,
+                message: self.message.to_owned(),
+                author: self.author.to_owned(),
+                handler: self.handler.to_owned(),
+                code_base64: self.code_base64.to_owned(),
+            }),
+            vec![],
+        )
+    }
+}
@@ -29,6 +29,7 @@ const KEY_UNIQUE_ID_STR: &str = "unique_id";
 const PROP_TY_STRING: &str = "string";
 const PROP_TY_JSON: &str = "json";
 const PROP_TY_INTEGER: &str = "integer";
+const PROP_TY_FLOAT: &str = "float";
 const PROP_TY_BOOLEAN: &str = "boolean";
 const PROP_TY_MAP: &str = "map";
 const PROP_TY_ARRAY: &str = "array";
@@ -60,6 +61,11 @@ pub enum PropNode {
         data: Option<PropNodeData>,
         unique_id: Option<String>,
     },
+    Float {
+        name: String,
+        data: Option<PropNodeData>,
+        unique_id: Option<String>,
+    },
     Integer {
         name: String,
         data: Option<PropNodeData>,
@@ -93,6 +99,7 @@ impl PropNode {
             Self::String { .. } => PROP_TY_STRING,
             Self::Json { .. } => PROP_TY_JSON,
             Self::Integer { .. } => PROP_TY_INTEGER,
+            Self::Float { .. } => PROP_TY_FLOAT,
             Self::Boolean { .. } => PROP_TY_BOOLEAN,
             Self::Map { .. } => PROP_TY_MAP,
             Self::Array { .. } => PROP_TY_ARRAY,
@@ -107,6 +114,7 @@ impl NameStr for PropNode {
             Self::String { name, .. }
             | Self::Json { name, .. }
             | Self::Integer { name, .. }
+            | Self::Float { name, .. }
             | Self::Boolean { name, .. }
             | Self::Map { name, .. }
             | Self::Array { name, .. }
@@ -124,6 +132,7 @@ impl WriteBytes for PropNode {
             Self::String { data, .. }
             | Self::Json { data, .. }
             | Self::Integer { data, .. }
+            | Self::Float { data, .. }
             | Self::Boolean { data, .. }
             | Self::Map { data, .. }
             | Self::Array { data, .. }
@@ -177,6 +186,7 @@ impl WriteBytes for PropNode {
         if let Some(unique_id) = match &self {
             Self::String { unique_id, .. }
             | Self::Integer { unique_id, .. }
+            | Self::Float { unique_id, .. }
             | Self::Json { unique_id, .. }
             | Self::Boolean { unique_id, .. }
             | Self::Map { unique_id, .. }
@@ -269,6 +279,11 @@ impl ReadBytes for PropNode {
                 data,
                 unique_id,
             },
+            PROP_TY_FLOAT => Self::Float {
+                name,
+                data,
+                unique_id,
+            },
             PROP_TY_BOOLEAN => Self::Boolean {
                 name,
                 data,
@@ -332,6 +347,11 @@ impl NodeChild for PropSpec {
                 data,
                 unique_id,
             }
+            | Self::Float {
+                name,
+                data,
+                unique_id,
+            }
             | Self::Object {
                 name,
                 data,
@@ -410,6 +430,18 @@ impl NodeChild for PropSpec {
                 ))
                     as Box<dyn NodeChild<NodeType = Self::NodeType>>],
             ),
+            Self::Float { .. } => NodeWithChildren::new(
+                NodeKind::Tree,
+                Self::NodeType::Prop(PropNode::Float {
+                    name,
+                    data,
+                    unique_id,
+                }),
+                vec![Box::new(PropChild::AttrFuncInputs(
+                    inputs.to_owned().unwrap_or(vec![]),
+                ))
+                    as Box<dyn NodeChild<NodeType = Self::NodeType>>],
+            ),
             Self::Boolean { .. } => NodeWithChildren::new(
                 NodeKind::Tree,
                 Self::NodeType::Prop(PropNode::Boolean {
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use object_tree::{Hash, HashedNode};
 use petgraph::prelude::*;
 
@@ -7,7 +8,7 @@ use crate::{
     node::PkgNode,
     spec::{
         FuncArgumentKind, FuncArgumentSpec, FuncSpec, FuncSpecBackendKind,
-        FuncSpecBackendResponseType, FuncSpecData,
+        FuncSpecBackendResponseType, FuncSpecData, FuncTestCaseSpec, FuncVersionSpec,
     },
 };
 
@@ -96,6 +97,172 @@ impl<'a> TryFrom<SiPkgFuncArgument<'a>> for FuncArgumentSpec {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct SiPkgFuncTestCase<'a> {
+    name: String,
+    description: Option<String>,
+    fixture: serde_json::Value,
+    expectation: serde_json::Value,
+
+    hash: Hash,
+    source: Source<'a>,
+}
+
+impl<'a> SiPkgFuncTestCase<'a> {
+    fn from_graph(
+        graph: &'a Graph<HashedNode<PkgNode>, ()>,
+        node_idx: NodeIndex,
+    ) -> PkgResult<Self> {
+        let hashed_node = &graph[node_idx];
+        let node = match hashed_node.inner() {
+            PkgNode::FuncTestCase(node) => node.clone(),
+            unexpected => {
+                return Err(SiPkgError::UnexpectedPkgNodeType(
+                    PkgNode::FUNC_TEST_CASE_KIND_STR,
+                    unexpected.node_kind_str(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            name: node.name,
+            description: node.description,
+            fixture: node.fixture,
+            expectation: node.expectation,
+
+            hash: hashed_node.hash(),
+            source: Source::new(graph, node_idx),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn fixture(&self) -> &serde_json::Value {
+        &self.fixture
+    }
+
+    pub fn expectation(&self) -> &serde_json::Value {
+        &self.expectation
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub fn source(&self) -> &Source<'a> {
+        &self.source
+    }
+}
+
+impl<'a> TryFrom<SiPkgFuncTestCase<'a>> for FuncTestCaseSpec {
+    type Error = SiPkgError;
+
+    fn try_from(value: SiPkgFuncTestCase<'a>) -> Result<Self, Self::Error> {
+        Ok(FuncTestCaseSpec::builder()
+            .name(value.name)
+            .description(value.description)
+            .fixture(value.fixture)
+            .expectation(value.expectation)
+            .build()?)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SiPkgFuncVersion<'a> {
+    version: String,
+    created_at: DateTime<Utc>,
+    message: Option<String>,
+    author: Option<String>,
+    handler: Option<String>,
+    code_base64: Option<String>,
+
+    hash: Hash,
+    source: Source<'a>,
+}
+
+impl<'a> SiPkgFuncVersion<'a> {
+    fn from_graph(
+        graph: &'a Graph<HashedNode<PkgNode>, ()>,
+        node_idx: NodeIndex,
+    ) -> PkgResult<Self> {
+        let hashed_node = &graph[node_idx];
+        let node = match hashed_node.inner() {
+            PkgNode::FuncVersion(node) => node.clone(),
+            unexpected => {
+                return Err(SiPkgError::UnexpectedPkgNodeType(
+                    PkgNode::FUNC_VERSION_KIND_STR,
+                    unexpected.node_kind_str(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            version: node.version,
+            created_at: node.created_at,
+            message: node.message,
+            author: node.author,
+            handler: node.handler,
+            code_base64: node.code_base64,
+
+            hash: hashed_node.hash(),
+            source: Source::new(graph, node_idx),
+        })
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    pub fn handler(&self) -> Option<&str> {
+        self.handler.as_deref()
+    }
+
+    pub fn code_base64(&self) -> Option<&str> {
+        self.code_base64.as_deref()
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub fn source(&self) -> &Source<'a> {
+        &self.source
+    }
+}
+
+impl<'a> TryFrom<SiPkgFuncVersion<'a>> for FuncVersionSpec {
+    type Error = SiPkgError;
+
+    fn try_from(value: SiPkgFuncVersion<'a>) -> Result<Self, Self::Error> {
+        Ok(FuncVersionSpec::builder()
+            .version(value.version)
+            .created_at(value.created_at)
+            .message(value.message)
+            .author(value.author)
+            .handler(value.handler)
+            .code_base64(value.code_base64)
+            .build()?)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SiPkgFuncData {
     name: String,
@@ -107,6 +274,7 @@ pub struct SiPkgFuncData {
     response_type: FuncSpecBackendResponseType,
     hidden: bool,
     link: Option<Url>,
+    timeout_secs: Option<u64>,
 }
 
 impl SiPkgFuncData {
@@ -145,6 +313,10 @@ impl SiPkgFuncData {
     pub fn link(&self) -> Option<&Url> {
         self.link.as_ref()
     }
+
+    pub fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -187,6 +359,7 @@ impl<'a> SiPkgFunc<'a> {
                 response_type: data.response_type,
                 hidden: data.hidden,
                 link: data.link,
+                timeout_secs: data.timeout_secs,
             }),
             hash: func_hashed_node.hash(),
             unique_id: func_node.unique_id,
@@ -203,12 +376,48 @@ impl<'a> SiPkgFunc<'a> {
             .graph
             .neighbors_directed(self.source.node_idx, Outgoing)
         {
+            // Test cases and versions share the func node as their parent
+            if let PkgNode::FuncTestCase(_) | PkgNode::FuncVersion(_) =
+                self.source.graph[idx].inner()
+            {
+                continue;
+            }
             arguments.push(SiPkgFuncArgument::from_graph(self.source.graph, idx)?);
         }
 
         Ok(arguments)
     }
 
+    pub fn test_cases(&self) -> PkgResult<Vec<SiPkgFuncTestCase>> {
+        let mut test_cases = vec![];
+        for idx in self
+            .source
+            .graph
+            .neighbors_directed(self.source.node_idx, Outgoing)
+        {
+            if let PkgNode::FuncTestCase(_) = self.source.graph[idx].inner() {
+                test_cases.push(SiPkgFuncTestCase::from_graph(self.source.graph, idx)?);
+            }
+        }
+
+        Ok(test_cases)
+    }
+
+    pub fn versions(&self) -> PkgResult<Vec<SiPkgFuncVersion>> {
+        let mut versions = vec![];
+        for idx in self
+            .source
+            .graph
+            .neighbors_directed(self.source.node_idx, Outgoing)
+        {
+            if let PkgNode::FuncVersion(_) = self.source.graph[idx].inner() {
+                versions.push(SiPkgFuncVersion::from_graph(self.source.graph, idx)?);
+            }
+        }
+
+        Ok(versions)
+    }
+
     pub fn name(&self) -> &str {
         self.name.as_ref()
     }
@@ -313,6 +522,10 @@ impl<'a> TryFrom<SiPkgFunc<'a>> for FuncSpec {
                 data_builder.link(link.to_owned());
             }
 
+            if let Some(timeout_secs) = data.timeout_secs {
+                data_builder.timeout_secs(timeout_secs);
+            }
+
             builder.data(data_builder.build()?);
         }
 
@@ -320,6 +533,14 @@ impl<'a> TryFrom<SiPkgFunc<'a>> for FuncSpec {
             builder.argument(argument.try_into()?);
         }
 
+        for test_case in value.test_cases()? {
+            builder.test_case(test_case.try_into()?);
+        }
+
+        for version in value.versions()? {
+            builder.version(version.try_into()?);
+        }
+
         Ok(builder.build()?)
     }
 }
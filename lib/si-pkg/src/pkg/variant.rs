@@ -381,7 +381,7 @@ impl<'a> SiPkgSchemaVariant<'a> {
                     }
                     _ => {
                         return Err(SiPkgError::prop_tree_invalid(
-                            "Leaf prop (String, Number, Boolean) cannot have children",
+                            "Leaf prop (String, Number, Float, Boolean) cannot have children",
                         ));
                     }
                 }
@@ -488,7 +488,8 @@ async fn create_prop_stack(
     let default_value = match &spec {
         SiPkgProp::String { data, .. }
         | SiPkgProp::Boolean { data, .. }
-        | SiPkgProp::Number { data, .. } => {
+        | SiPkgProp::Number { data, .. }
+        | SiPkgProp::Float { data, .. } => {
             data.as_ref().and_then(|data| data.default_value.to_owned())
         }
         _ => None,
@@ -519,6 +520,12 @@ async fn create_prop_stack(
                 builder.default_value(dv);
             }
         }
+        SiPkgProp::Float { .. } => {
+            builder.kind(PropSpecKind::Float);
+            if let Some(dv) = default_value {
+                builder.default_value(dv);
+            }
+        }
         SiPkgProp::Object { .. } => {
             builder.kind(PropSpecKind::Object);
         }
@@ -539,6 +546,7 @@ async fn create_prop_stack(
         | SiPkgProp::Map { name, data, .. }
         | SiPkgProp::Array { name, data, .. }
         | SiPkgProp::Number { name, data, .. }
+        | SiPkgProp::Float { name, data, .. }
         | SiPkgProp::Object { name, data, .. }
         | SiPkgProp::Boolean { name, data, .. } => {
             builder.name(name);
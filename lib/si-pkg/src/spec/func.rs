@@ -1,4 +1,5 @@
 use base64::{engine::general_purpose, Engine};
+use chrono::{DateTime, Utc};
 use derive_builder::Builder;
 use object_tree::Hash;
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,7 @@ pub enum FuncArgumentKind {
     Any,
     Array,
     Boolean,
+    Float,
     Integer,
     Json,
     Map,
@@ -57,6 +59,53 @@ impl FuncArgumentSpec {
     }
 }
 
+/// A declarative test case attached to a func. The fixture and expectation are opaque to the
+/// package format and are interpreted by the importer.
+#[derive(Builder, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct FuncTestCaseSpec {
+    #[builder(setter(into))]
+    pub name: String,
+    #[builder(setter(into), default)]
+    pub description: Option<String>,
+    #[builder(setter(into))]
+    pub fixture: serde_json::Value,
+    #[builder(setter(into))]
+    pub expectation: serde_json::Value,
+}
+
+impl FuncTestCaseSpec {
+    pub fn builder() -> FuncTestCaseSpecBuilder {
+        FuncTestCaseSpecBuilder::default()
+    }
+}
+
+/// A released version of a func, carrying the code it had when it was locked.
+#[derive(Builder, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct FuncVersionSpec {
+    #[builder(setter(into))]
+    pub version: String,
+    #[builder(setter(into), default = "Utc::now()")]
+    pub created_at: DateTime<Utc>,
+    #[builder(setter(into), default)]
+    pub message: Option<String>,
+    #[builder(setter(into), default)]
+    pub author: Option<String>,
+    #[builder(setter(into), default)]
+    pub handler: Option<String>,
+    #[builder(setter(into), default)]
+    pub code_base64: Option<String>,
+}
+
+impl FuncVersionSpec {
+    pub fn builder() -> FuncVersionSpecBuilder {
+        FuncVersionSpecBuilder::default()
+    }
+}
+
 #[remain::sorted]
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, AsRefStr, Display, EnumIter, EnumString)]
 #[serde(rename_all = "camelCase")]
@@ -64,6 +113,7 @@ pub enum FuncSpecBackendKind {
     Array,
     Boolean,
     Diff,
+    Float,
     Identity,
     Integer,
     JsAction,
@@ -91,6 +141,7 @@ pub enum FuncSpecBackendResponseType {
     Array,
     Boolean,
     CodeGeneration,
+    Float,
     Identity,
     Integer,
     Json,
@@ -130,6 +181,8 @@ pub struct FuncSpecData {
     pub hidden: bool,
     #[builder(setter(into, strip_option), default)]
     pub link: Option<Url>,
+    #[builder(setter(into, strip_option), default)]
+    pub timeout_secs: Option<u64>,
 }
 
 impl FuncSpecData {
@@ -174,6 +227,12 @@ pub struct FuncSpec {
 
     #[builder(setter(each(name = "argument"), into), default)]
     pub arguments: Vec<FuncArgumentSpec>,
+    #[builder(setter(each(name = "test_case"), into), default)]
+    #[serde(default)]
+    pub test_cases: Vec<FuncTestCaseSpec>,
+    #[builder(setter(each(name = "version"), into), default)]
+    #[serde(default)]
+    pub versions: Vec<FuncVersionSpec>,
 }
 
 impl FuncSpecBuilder {
@@ -44,7 +44,10 @@ impl From<&PropSpec> for PropSpecWidgetKind {
         match node {
             PropSpec::Array { .. } => Self::Array,
             PropSpec::Boolean { .. } => Self::Checkbox,
-            PropSpec::String { .. } | PropSpec::Number { .. } | PropSpec::Json { .. } => Self::Text,
+            PropSpec::String { .. }
+            | PropSpec::Float { .. }
+            | PropSpec::Number { .. }
+            | PropSpec::Json { .. } => Self::Text,
             PropSpec::Object { .. } => Self::Header,
             PropSpec::Map { .. } => Self::Map,
         }
@@ -84,6 +87,12 @@ pub enum PropSpec {
         unique_id: Option<String>,
     },
     #[serde(rename_all = "camelCase")]
+    Float {
+        name: String,
+        data: Option<PropSpecData>,
+        unique_id: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
     Json {
         name: String,
         data: Option<PropSpecData>,
@@ -214,6 +223,7 @@ impl PropSpec {
         match self {
             Self::Array { name, .. }
             | Self::Boolean { name, .. }
+            | Self::Float { name, .. }
             | Self::Map { name, .. }
             | Self::Json { name, .. }
             | Self::Number { name, .. }
@@ -226,6 +236,7 @@ impl PropSpec {
         match self {
             Self::Array { .. } => PropSpecKind::Array,
             Self::Boolean { .. } => PropSpecKind::Boolean,
+            Self::Float { .. } => PropSpecKind::Float,
             Self::Json { .. } => PropSpecKind::Json,
             Self::Map { .. } => PropSpecKind::Map,
             Self::Number { .. } => PropSpecKind::Number,
@@ -238,6 +249,7 @@ impl PropSpec {
         match self {
             Self::Array { data, .. }
             | Self::Boolean { data, .. }
+            | Self::Float { data, .. }
             | Self::Map { data, .. }
             | Self::Number { data, .. }
             | Self::Object { data, .. }
@@ -259,6 +271,7 @@ impl PropSpec {
         match self {
             Self::Json { .. }
             | Self::Boolean { .. }
+            | Self::Float { .. }
             | Self::Number { .. }
             | Self::String { .. } => vec![],
             Self::Object { entries, .. } => entries.iter().collect(),
@@ -475,6 +488,7 @@ impl PropSpec {
 pub enum PropSpecKind {
     Array,
     Boolean,
+    Float,
     Json,
     Map,
     Number,
@@ -696,6 +710,11 @@ impl PropSpecBuilder {
                     unique_id: self.unique_id.to_owned(),
                     data: maybe_data,
                 },
+                PropSpecKind::Float => PropSpec::Float {
+                    name: name.to_owned(),
+                    unique_id: self.unique_id.to_owned(),
+                    data: maybe_data,
+                },
                 PropSpecKind::Boolean => PropSpec::Boolean {
                     name: name.to_owned(),
                     unique_id: self.unique_id.to_owned(),
@@ -45,14 +45,6 @@ pub enum ClientError {
 
 pub type ClientResult<T> = Result<T, ClientError>;
 
-/// This _private_ enum helps dictate what NATS technology should be used in communicating with veritech.
-enum RequestMode {
-    /// Publish messages using core NATS to communicate with veritech.
-    Core,
-    /// Publish messages using NATS Jetstream to communicate with veritech.
-    Jetstream,
-}
-
 #[derive(Clone, Debug)]
 pub struct Client {
     nats: NatsClient,
@@ -179,13 +171,14 @@ impl Client {
         &self,
         request: &KillExecutionRequest,
     ) -> ClientResult<FunctionResult<()>> {
-        self.execute_request(
-            request.nats_subject(self.nats_subject_prefix(), None, None),
-            None,
-            request,
-            RequestMode::Core,
-        )
-        .await
+        self.nats
+            .request_json_with_headers(
+                request.nats_subject(self.nats_subject_prefix(), None, None),
+                propagation::empty_injected_headers(),
+                request,
+            )
+            .await
+            .map_err(Into::into)
     }
 
     async fn execute_jetstream_request<R>(
@@ -207,7 +200,6 @@ impl Client {
             ),
             Some(output_tx),
             request,
-            RequestMode::Jetstream,
         )
         .await
     }
@@ -217,7 +209,6 @@ impl Client {
         subject: Subject,
         output_tx: Option<mpsc::Sender<OutputStream>>,
         request: &R,
-        request_mode: RequestMode,
     ) -> ClientResult<FunctionResult<R::Response>>
     where
         R: Serialize + CycloneRequestable,
@@ -272,34 +263,17 @@ impl Client {
         // Root reply mailbox will receive a reply if nobody is listening to the channel `subject`
         let mut root_subscriber = self.nats.subscribe(reply_mailbox_root.clone()).await?;
 
-        // NOTE(nick,fletcher): based on the provided request mode, we will either communicate user core nats or
-        // jetstream. We neither like nor endorse this behavior. This method should probably be broken up in the
-        // future to cleanly separate core nats and jetstream use.
-        match request_mode {
-            RequestMode::Core => {
-                self.nats
-                    .publish_with_reply_and_headers(
-                        subject,
-                        reply_mailbox_root.clone(),
-                        propagation::empty_injected_headers(),
-                        msg.into(),
-                    )
-                    .await?
-            }
-            RequestMode::Jetstream => {
-                let mut headers = propagation::empty_injected_headers();
-                headers.insert(REPLY_INBOX_HEADER_NAME, reply_mailbox_root.clone());
+        let mut headers = propagation::empty_injected_headers();
+        headers.insert(REPLY_INBOX_HEADER_NAME, reply_mailbox_root.clone());
 
-                self.context
-                    .publish_with_headers(subject, headers, msg.into())
-                    .await
-                    // If `Err` then message failed to publish
-                    .map_err(|err| ClientError::Transport(Box::new(err)))?
-                    .await
-                    // If `Err` then NATS server failed to ack
-                    .map_err(|err| ClientError::Transport(Box::new(err)))?;
-            }
-        }
+        self.context
+            .publish_with_headers(subject, headers, msg.into())
+            .await
+            // If `Err` then message failed to publish
+            .map_err(|err| ClientError::Transport(Box::new(err)))?
+            .await
+            // If `Err` then NATS server failed to ack
+            .map_err(|err| ClientError::Transport(Box::new(err)))?;
 
         let span = Span::current();
 
@@ -119,6 +119,7 @@ async fn executes_simple_management_function() {
              }",
         ),
         before: vec![],
+        timeout_secs: None,
     };
 
     let result = client
@@ -157,6 +158,7 @@ async fn executes_simple_action_run() {
         args: serde_json::json!({ "foo": "bar", "baz": "foo" }),
         code_base64: base64_encode("function numberOfInputs(input) { return { status: 'ok', payload: Object.keys(input)?.length ?? 0 } }"),
         before: vec![],
+        timeout_secs: None,
     };
 
     let result = client
@@ -207,6 +209,7 @@ async fn executes_simple_resolver_function() {
             "function numberOfInputs(input) { return Object.keys(input)?.length ?? 0; }",
         ),
         before: vec![],
+        timeout_secs: None,
     };
 
     let result = client
@@ -276,6 +279,7 @@ async fn type_checks_resolve_function() {
             response_type,
             code_base64: base64_encode("function returnInputValue(input) { return input.value; }"),
             before: vec![],
+            timeout_secs: None,
         };
 
         let result = client
@@ -339,6 +343,7 @@ async fn type_checks_resolve_function() {
             response_type: response_type.clone(),
             code_base64: base64_encode("function returnInputValue(input) { return input.value; }"),
             before: vec![],
+            timeout_secs: None,
         };
 
         let result = client
@@ -384,6 +389,7 @@ async fn executes_simple_validation() {
         validation_format: r#"{"type":"number","flags":{"presence":"required"},"rules":[{"name":"integer"},{"name":"min","args":{"limit":33}},{"name":"max","args":{"limit":33}}]}"#.to_string(),
         code_base64: "".to_string(),
         before: vec![],
+        timeout_secs: None,
     };
 
     let result = client
@@ -428,6 +434,7 @@ async fn executes_simple_schema_variant_definition() {
                     };
                 }",
         ),
+        timeout_secs: None,
     };
 
     let result = client
@@ -74,6 +74,9 @@ pub struct Config {
     #[builder(default = "default_concurrency_limit()")]
     concurrency_limit: usize,
 
+    #[builder(default)]
+    workspace_concurrency_limit: Option<usize>,
+
     #[builder(default = "random_instance_id()")]
     instance_id: String,
 }
@@ -124,6 +127,11 @@ impl Config {
         self.concurrency_limit
     }
 
+    /// Gets the config's per-workspace concurrency limit, if any.
+    pub fn workspace_concurrency_limit(&self) -> Option<usize> {
+        self.workspace_concurrency_limit
+    }
+
     /// Gets the config's instance ID.
     pub fn instance_id(&self) -> &str {
         self.instance_id.as_ref()
@@ -143,6 +151,8 @@ pub struct ConfigFile {
     cyclone_client_execution_timeout_secs: u64,
     #[serde(default = "default_concurrency_limit")]
     concurrency_limit: usize,
+    #[serde(default)]
+    workspace_concurrency_limit: Option<usize>,
     #[serde(default = "random_instance_id")]
     instance_id: String,
 }
@@ -162,6 +172,7 @@ impl ConfigFile {
             healthcheck_pool: default_healthcheck_pool(),
             cyclone_client_execution_timeout_secs: default_cyclone_client_execution_timeout_secs(),
             concurrency_limit: default_concurrency_limit(),
+            workspace_concurrency_limit: None,
             instance_id: random_instance_id(),
         }
     }
@@ -174,6 +185,7 @@ impl ConfigFile {
             healthcheck_pool: default_healthcheck_pool(),
             cyclone_client_execution_timeout_secs: default_cyclone_client_execution_timeout_secs(),
             concurrency_limit: default_concurrency_limit(),
+            workspace_concurrency_limit: None,
             instance_id: random_instance_id(),
         }
     }
@@ -197,6 +209,7 @@ impl TryFrom<ConfigFile> for Config {
             value.cyclone_client_execution_timeout_secs,
         ));
         config.concurrency_limit(value.concurrency_limit);
+        config.workspace_concurrency_limit(value.workspace_concurrency_limit);
         config.instance_id(value.instance_id);
         config.build().map_err(Into::into)
     }
@@ -10,8 +10,10 @@ use si_data_nats::{InnerMessage, Subject};
 // seems strange to get these cyclone_core types from si_pool_noodle?
 use si_pool_noodle::{
     ActionRunResultSuccess, CycloneClient, CycloneRequest, CycloneRequestable, ExecutionError,
-    ManagementResultSuccess, ProgressMessage, ResolverFunctionResultSuccess,
-    SchemaVariantDefinitionResultSuccess, SensitiveStrings, ValidationResultSuccess,
+    FunctionResult, FunctionResultFailure, FunctionResultFailureError,
+    FunctionResultFailureErrorKind, ManagementResultSuccess, ProgressMessage,
+    ResolverFunctionResultSuccess, SchemaVariantDefinitionResultSuccess, SensitiveStrings,
+    ValidationResultSuccess,
 };
 use std::{collections::HashMap, result, str::Utf8Error, sync::Arc, time::Duration};
 use telemetry::prelude::*;
@@ -29,6 +31,9 @@ pub use kill::process_kill_request;
 
 mod kill;
 
+/// How much longer than a per-request function timeout we wait on cyclone before giving up.
+const CYCLONE_CLIENT_EXECUTION_TIMEOUT_GRACE: Duration = Duration::from_secs(90);
+
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum HandlerError {
@@ -186,6 +191,14 @@ where
     let nats_for_publisher = state.nats.clone();
     let publisher = Publisher::new(&nats_for_publisher, &reply_mailbox);
     let execution_id = request.execution_id().to_owned();
+    // Functions may ask for more time than the default, in which case we wait for cyclone to give
+    // up on them first
+    let timeout = match request.timeout_secs() {
+        Some(timeout_secs) => state
+            .cyclone_client_execution_timeout
+            .max(Duration::from_secs(timeout_secs) + CYCLONE_CLIENT_EXECUTION_TIMEOUT_GRACE),
+        None => state.cyclone_client_execution_timeout,
+    };
 
     let cyclone_request = CycloneRequest::from_parts(request.clone(), sensitive_strings);
 
@@ -244,7 +257,6 @@ where
 
     // we do not want to return errors at this point as it will retry functions that may have
     // failed for legitimate reasons and should not be retried
-    let timeout = state.cyclone_client_execution_timeout;
     let result = tokio::select! {
         _ = tokio::time::sleep(timeout) => {
             error!("hit timeout for communicating with cyclone server");
@@ -279,6 +291,21 @@ where
         Err(HandlerError::Killed(execution_id)) => {
             request.dec_run_metric();
             info!(error = ?execution_id, "function killed during execution via signal");
+
+            // Let the caller know that the function will not complete, rather than leaving it
+            // waiting on a result that never comes
+            let function_result: FunctionResult<Request::Response> =
+                FunctionResult::Failure(FunctionResultFailure::new(
+                    execution_id,
+                    FunctionResultFailureError {
+                        kind: FunctionResultFailureErrorKind::KilledExecution,
+                        message: "function execution was killed".to_owned(),
+                    },
+                    timestamp(),
+                ));
+            if let Err(err) = publisher.publish_result(&function_result).await {
+                error!(error = ?err, "failed to publish killed result");
+            }
         }
         Err(err) => {
             request.dec_run_metric();
@@ -1,59 +1,36 @@
-use naxum::{
-    extract::{message_parts::Reply, State},
-    Json,
-};
-use si_data_nats::Subject;
+use naxum::{extract::State, Json};
 use si_pool_noodle::{
     FunctionResult, FunctionResultFailure, FunctionResultFailureError,
     FunctionResultFailureErrorKind, KillExecutionRequest,
 };
 use telemetry::prelude::*;
 
-use crate::{app_state::KillAppState, Publisher};
+use crate::app_state::KillAppState;
 
 use super::{kill_sender_remove_blocking, timestamp, HandlerError, HandlerResult};
 
 pub async fn process_kill_request(
     State(state): State<KillAppState>,
-    Reply(maybe_reply): Reply,
     Json(request): Json<KillExecutionRequest>,
-) -> HandlerResult<()> {
+) -> Json<FunctionResult<()>> {
     info!(execution_id = %request.execution_id, "received request to kill execution");
 
-    let reply_mailbox = match maybe_reply {
-        Some(reply) => reply,
-        None => return Err(HandlerError::NoReplyInbox),
-    };
-
-    kill_execution_request_task(&state, request, reply_mailbox).await;
-
-    Ok(())
-}
-
-async fn kill_execution_request_task(
-    state: &KillAppState,
-    request: KillExecutionRequest,
-    reply_mailbox: Subject,
-) {
-    let publisher = Publisher::new(&state.nats, &reply_mailbox);
-
     let execution_id = request.execution_id;
 
-    let result = match kill_execution_request(state, execution_id.to_owned()).await {
-        Ok(()) => FunctionResult::Success(()),
-        Err(err) => FunctionResult::Failure(FunctionResultFailure::new(
-            execution_id,
-            FunctionResultFailureError {
-                kind: FunctionResultFailureErrorKind::KilledExecution,
-                message: err.to_string(),
-            },
-            timestamp(),
-        )),
-    };
-
-    if let Err(err) = publisher.publish_result(&result).await {
-        error!(?err, "failed to publish result");
-    }
+    // The result is published to the requester by the reply middleware
+    Json(
+        match kill_execution_request(&state, execution_id.to_owned()).await {
+            Ok(()) => FunctionResult::Success(()),
+            Err(err) => FunctionResult::Failure(FunctionResultFailure::new(
+                execution_id,
+                FunctionResultFailureError {
+                    kind: FunctionResultFailureErrorKind::KilledExecution,
+                    message: err.to_string(),
+                },
+                timestamp(),
+            )),
+        },
+    )
 }
 
 #[instrument(name = "veritech.kill_execution_request", level = "info", skip_all)]
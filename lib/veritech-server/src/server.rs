@@ -14,7 +14,9 @@ use naxum::{
     handler::Handler as _,
     middleware::{
         ack::AckLayer,
+        limit::{KeyLimit, KeyedLimitLayer, KeyedLimits, SubjectTokenKey},
         matched_subject::{ForSubject, MatchedSubjectLayer},
+        reply::ReplyLayer,
         trace::TraceLayer,
     },
     response::{IntoResponse, Response},
@@ -60,6 +62,7 @@ pub struct Server {
     metadata: Arc<ServerMetadata>,
     inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send>,
     kill_inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send>,
+    keyed_limits: KeyedLimits,
     shutdown_token: CancellationToken,
 }
 
@@ -67,6 +70,7 @@ impl fmt::Debug for Server {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Server")
             .field("metadata", &self.metadata)
+            .field("keyed_limits", &self.keyed_limits)
             .field("shutdown_token", &self.shutdown_token)
             .finish()
     }
@@ -85,6 +89,17 @@ impl Server {
 
         let kill_senders = Arc::new(Mutex::new(HashMap::new()));
 
+        let keyed_limits = KeyedLimits::default();
+        if let Some(limit) = config.workspace_concurrency_limit() {
+            // Messages waiting on a workspace's limit hold one of the server's concurrency
+            // slots, so the queue is bounded to keep a busy workspace from taking up every slot
+            keyed_limits.set_default_limit(
+                KeyLimit::unlimited()
+                    .max_concurrency(limit)
+                    .max_queued(limit),
+            );
+        }
+
         match config.cyclone_spec() {
             CycloneSpec::LocalHttp(_spec) => {
                 //
@@ -139,6 +154,7 @@ impl Server {
                 let inner_future = Self::build_app(
                     metadata.clone(),
                     config.concurrency_limit(),
+                    keyed_limits.clone(),
                     cyclone_pool,
                     Arc::new(decryption_key),
                     config.cyclone_client_execution_timeout(),
@@ -156,12 +172,18 @@ impl Server {
                     metadata,
                     inner: inner_future,
                     kill_inner: kill_inner_future,
+                    keyed_limits,
                     shutdown_token: token,
                 })
             }
         }
     }
 
+    /// Returns a handle to the per-workspace limits, which can be changed while running.
+    pub fn keyed_limits(&self) -> &KeyedLimits {
+        &self.keyed_limits
+    }
+
     #[inline]
     pub async fn run(self) {
         if let Err(err) = self.try_run().await {
@@ -184,6 +206,7 @@ impl Server {
     async fn build_app(
         metadata: Arc<ServerMetadata>,
         concurrency_limit: usize,
+        keyed_limits: KeyedLimits,
         cyclone_pool: PoolNoodle<LocalUdsInstance, LocalUdsInstanceSpec>,
         decryption_key: Arc<VeritechDecryptionKey>,
         cyclone_client_execution_timeout: Duration,
@@ -228,6 +251,10 @@ impl Server {
                     .on_response(telemetry_nats::NatsOnResponse::new()),
             )
             .layer(AckLayer::new())
+            .layer(KeyedLimitLayer::new(
+                SubjectTokenKey::named("workspace_id"),
+                keyed_limits,
+            ))
             .service(handlers::process_request.with_state(state))
             .map_response(Response::into_response);
 
@@ -256,7 +283,7 @@ impl Server {
                 .map(Ok::<_, Infallible>)
         };
 
-        let state = KillAppState::new(metadata, nats, kill_senders);
+        let state = KillAppState::new(metadata, nats.clone(), kill_senders);
 
         let app = ServiceBuilder::new()
             .layer(
@@ -266,6 +293,7 @@ impl Server {
                     )
                     .on_response(telemetry_nats::NatsOnResponse::new()),
             )
+            .layer(ReplyLayer::new(nats.as_inner().clone()))
             .service(handlers::process_kill_request.with_state(state))
             .map_response(Response::into_response);
 